@@ -468,10 +468,20 @@ pub async fn execute_notebook_cell(
     cell_id: String,
     code: String,
     notebook_path: Option<String>,
+    kernel: Option<String>,
     app_state: State<'_, AppState>,
 ) -> Result<String, String> {
     debug!("[OrchestratorCommands] Executing notebook cell: {}", cell_id);
 
+    // Cells of notebooks attached to a named kernel run in that kernel's session
+    if let Some(kernel) = kernel {
+        use internals::messages::kernel::ExecuteNotebookCellOnKernel;
+        return app_state.actor_system.kernel_manager_actor
+            .send(ExecuteNotebookCellOnKernel { kernel, cell_id, code, notebook_path })
+            .await
+            .map_err(|_| "Actor comm failed".to_string())?;
+    }
+
     use internals::messages::execution::ExecuteNotebookCell;
     use internals::messages::process::SetOutputSuppression;
 
@@ -536,15 +546,78 @@ pub async fn execute_notebook_cells_batch(
     Ok(results)
 }
 
+/// Analyze which globals each notebook cell defines and reads
+#[tauri::command]
+pub async fn get_notebook_dependency_graph(
+    cells: Vec<(String, String)>, // Vec of (cell_id, code) tuples, in notebook order
+    app_state: State<'_, AppState>,
+) -> Result<internals::types::CellDependencyGraph, String> {
+    use internals::messages::execution::{AnalyzeNotebookDependencies, NotebookCellBatchItem};
+
+    let cells: Vec<NotebookCellBatchItem> = cells
+        .into_iter()
+        .map(|(cell_id, code)| NotebookCellBatchItem { cell_id, code, notebook_path: None })
+        .collect();
+
+    app_state.actor_system.execution_actor
+        .send(AnalyzeNotebookDependencies { cells })
+        .await
+        .map_err(|_| "Actor comm failed".to_string())?
+}
+
+/// Re-execute changed notebook cells and all cells depending on them, in dependency order
+#[tauri::command]
+pub async fn execute_notebook_cells_with_dependents(
+    cells: Vec<(String, String)>, // Vec of (cell_id, code) tuples, in notebook order
+    changed_cell_ids: Vec<String>,
+    notebook_path: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<(String, Result<String, String>)>, String> {
+    debug!("[OrchestratorCommands] Executing notebook cells {:?} with dependents", changed_cell_ids);
+
+    use internals::messages::execution::{ExecuteNotebookCellsWithDependents, NotebookCellBatchItem};
+    use internals::messages::process::SetOutputSuppression;
+
+    let _ = app_state.actor_system.process_actor
+        .send(SetOutputSuppression { suppressed: true })
+        .await;
+
+    let cells: Vec<NotebookCellBatchItem> = cells
+        .into_iter()
+        .map(|(cell_id, code)| NotebookCellBatchItem { cell_id, code, notebook_path: notebook_path.clone() })
+        .collect();
+
+    let results = app_state.actor_system.execution_actor
+        .send(ExecuteNotebookCellsWithDependents { cells, changed_cell_ids })
+        .await
+        .map_err(|_| "Actor comm failed".to_string())?;
+
+    // Restore terminal output even when the dependency analysis rejected the run
+    let _ = app_state.actor_system.process_actor
+        .send(SetOutputSuppression { suppressed: false })
+        .await;
+
+    results.map_err(|e| format!("Reactive execution failed: {}", e))
+}
+
 /// Execute Julia file
 #[tauri::command]
 pub async fn execute_julia_file(
     file_path: String,
     _file_content: String,
+    kernel: Option<String>,
     app_state: State<'_, AppState>,
 ) -> Result<String, String> {
     debug!("[OrchestratorCommands] Executing Julia file: {}", file_path);
 
+    if let Some(kernel) = kernel {
+        use internals::messages::kernel::ExecuteFileOnKernel;
+        return app_state.actor_system.kernel_manager_actor
+            .send(ExecuteFileOnKernel { kernel, file_path })
+            .await
+            .map_err(|_| "Actor comm failed".to_string())?;
+    }
+
     app_state.actor_system
         .execution_actor
         .send(ExecuteFile { file_path })
@@ -0,0 +1,83 @@
+use log::debug;
+use tauri::State;
+
+use crate::error::AppError;
+use crate::state::AppState;
+use internals::types::KernelInfo;
+
+#[tauri::command]
+pub async fn start_kernel(
+    app_state: State<'_, AppState>,
+    name: String,
+    project_path: Option<String>,
+) -> Result<KernelInfo, AppError> {
+    debug!("[Kernel] Start kernel '{}' (project: {:?})", name, project_path);
+    use internals::messages::kernel::StartKernel;
+    Ok(
+        app_state
+            .actor_system
+            .kernel_manager_actor
+            .send(StartKernel { name, project_path })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn list_kernels(app_state: State<'_, AppState>) -> Result<Vec<KernelInfo>, AppError> {
+    use internals::messages::kernel::ListKernels;
+    Ok(
+        app_state
+            .actor_system
+            .kernel_manager_actor
+            .send(ListKernels)
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn kill_kernel(app_state: State<'_, AppState>, name: String) -> Result<(), AppError> {
+    debug!("[Kernel] Kill kernel '{}'", name);
+    use internals::messages::kernel::KillKernel;
+    Ok(
+        app_state
+            .actor_system
+            .kernel_manager_actor
+            .send(KillKernel { name })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn restart_kernel(app_state: State<'_, AppState>, name: String) -> Result<KernelInfo, AppError> {
+    debug!("[Kernel] Restart kernel '{}'", name);
+    use internals::messages::kernel::RestartKernel;
+    Ok(
+        app_state
+            .actor_system
+            .kernel_manager_actor
+            .send(RestartKernel { name })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+/// Set how long kernels may stay idle before they are shut down (None disables the timeout)
+#[tauri::command]
+pub async fn set_kernel_idle_timeout(
+    app_state: State<'_, AppState>,
+    timeout_secs: Option<u64>,
+) -> Result<(), AppError> {
+    debug!("[Kernel] Set kernel idle timeout to {:?}s", timeout_secs);
+    use internals::messages::kernel::SetKernelIdleTimeout;
+    Ok(
+        app_state
+            .actor_system
+            .kernel_manager_actor
+            .send(SetKernelIdleTimeout { timeout_secs })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
@@ -1,8 +1,9 @@
 use crate::state::AppState;
 use crate::error::AppError;
 use internals::types::{
-    LspCompletionItem, LspDiagnostic, LspDocumentSymbol, LspHover, LspLocation, LspPosition,
-    LspSignatureHelp,
+    DeadCodeReport, DependencyHealthReport, LspCellDiagnostic, LspCompletionItem, LspDiagnostic,
+    LspDocumentSymbol, LspHover, LspLocation, LspNotebookLocation, LspPosition, LspSignatureHelp,
+    PackageHealthReport,
 };
 use log::{debug, error};
 use tauri::State;
@@ -232,6 +233,173 @@ pub async fn lsp_get_diagnostics(
     }
 }
 
+/// Get the dependency health report for the open project
+///
+/// Returns `None` when no project with a Project.toml is open.
+#[tauri::command]
+pub async fn lsp_get_dependency_health(
+    app_state: State<'_, AppState>,
+) -> Result<Option<DependencyHealthReport>, AppError> {
+    debug!("LSP dependency health request");
+    use internals::messages::lsp::GetDependencyHealth;
+    match app_state
+        .actor_system
+        .lsp_actor
+        .send(GetDependencyHealth)
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+    {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            error!("LSP dependency health error: {}", e);
+            Err(AppError::InternalError(e))
+        }
+    }
+}
+
+/// Get the package health report (type piracy, overwritten methods, ambiguities) for the open project
+///
+/// Returns `None` when no project is open.
+#[tauri::command]
+pub async fn lsp_get_package_health(
+    app_state: State<'_, AppState>,
+) -> Result<Option<PackageHealthReport>, AppError> {
+    debug!("LSP package health request");
+    use internals::messages::lsp::GetPackageHealth;
+    match app_state
+        .actor_system
+        .lsp_actor
+        .send(GetPackageHealth)
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+    {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            error!("LSP package health error: {}", e);
+            Err(AppError::InternalError(e))
+        }
+    }
+}
+
+/// Get the dead code report (unused definitions and imports, undefined exports, unreachable code)
+///
+/// Returns `None` when no project is open.
+#[tauri::command]
+pub async fn lsp_get_dead_code(
+    app_state: State<'_, AppState>,
+) -> Result<Option<DeadCodeReport>, AppError> {
+    debug!("LSP dead code request");
+    use internals::messages::lsp::GetDeadCode;
+    match app_state
+        .actor_system
+        .lsp_actor
+        .send(GetDeadCode)
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+    {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            error!("LSP dead code error: {}", e);
+            Err(AppError::InternalError(e))
+        }
+    }
+}
+
+/// Open or update a notebook from its code cells (`(cell_id, source)` in notebook order)
+///
+/// The cells are analyzed as one document, so each cell sees the definitions of the others.
+#[tauri::command]
+pub async fn lsp_notebook_update(
+    uri: String,
+    cells: Vec<(String, String)>,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    debug!("LSP notebook update: {} ({} cells)", uri, cells.len());
+
+    use internals::messages::lsp::UpdateNotebook;
+    Ok(app_state.actor_system.lsp_actor.send(UpdateNotebook { uri, cells }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??)
+}
+
+/// Close a notebook
+#[tauri::command]
+pub async fn lsp_notebook_close(
+    uri: String,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    debug!("LSP notebook close: {}", uri);
+
+    use internals::messages::lsp::CloseNotebook;
+    Ok(app_state.actor_system.lsp_actor.send(CloseNotebook { uri }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??)
+}
+
+/// Get hover information for a position in a notebook cell
+#[tauri::command]
+pub async fn lsp_notebook_hover(
+    uri: String,
+    cell_id: String,
+    line: u32,
+    character: u32,
+    app_state: State<'_, AppState>,
+) -> Result<Option<LspHover>, AppError> {
+    debug!("LSP notebook hover request for {} cell {} at line {}, character {}", uri, cell_id, line, character);
+
+    let position = LspPosition { line, character };
+    use internals::messages::lsp::GetNotebookHover;
+    Ok(app_state.actor_system.lsp_actor.send(GetNotebookHover { uri, cell_id, position }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??)
+}
+
+/// Get completions for a position in a notebook cell
+#[tauri::command]
+pub async fn lsp_notebook_completions(
+    uri: String,
+    cell_id: String,
+    line: u32,
+    character: u32,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<LspCompletionItem>, AppError> {
+    debug!("LSP notebook completions request for {} cell {} at line {}, character {}", uri, cell_id, line, character);
+
+    let position = LspPosition { line, character };
+    use internals::messages::lsp::GetNotebookCompletions;
+    Ok(app_state.actor_system.lsp_actor.send(GetNotebookCompletions { uri, cell_id, position }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??)
+}
+
+/// Get definition locations for a position in a notebook cell
+///
+/// Locations inside the notebook carry the cell id and a cell-relative range.
+#[tauri::command]
+pub async fn lsp_notebook_definition(
+    uri: String,
+    cell_id: String,
+    line: u32,
+    character: u32,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<LspNotebookLocation>, AppError> {
+    debug!("LSP notebook definition request for {} cell {} at line {}, character {}", uri, cell_id, line, character);
+
+    let position = LspPosition { line, character };
+    use internals::messages::lsp::GetNotebookDefinition;
+    Ok(app_state.actor_system.lsp_actor.send(GetNotebookDefinition { uri, cell_id, position }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??)
+}
+
+/// Get diagnostics for all cells of a notebook, with cell-relative ranges
+#[tauri::command]
+pub async fn lsp_notebook_diagnostics(
+    uri: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<LspCellDiagnostic>, AppError> {
+    debug!("LSP notebook diagnostics request for {}", uri);
+
+    use internals::messages::lsp::GetNotebookDiagnostics;
+    match app_state.actor_system.lsp_actor.send(GetNotebookDiagnostics { uri }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
+        Ok(diagnostics) => Ok(diagnostics),
+        Err(e) => {
+            error!("LSP notebook diagnostics error: {}", e);
+            Err(AppError::InternalError(e))
+        }
+    }
+}
+
 /// Check if LSP is running
 #[tauri::command]
 pub async fn lsp_is_running(app_state: State<'_, AppState>) -> Result<bool, AppError> {
@@ -9,6 +9,7 @@ pub mod files;
 pub mod projects;
 pub mod packages;
 pub mod process;
+pub mod kernel;
 pub mod startup;
 pub mod syntax;
 pub mod notebook;
\ No newline at end of file
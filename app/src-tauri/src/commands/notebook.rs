@@ -1,6 +1,8 @@
 use log::{debug, error};
 use serde_json;
-use shared::frontend::Notebook;
+use shared::frontend::notebook_diff::{self, NotebookDiff, NotebookMergeResult, StripOptions};
+use shared::frontend::notebook_export::{self, NotebookExportFormat};
+use shared::frontend::{percent, pluto, Notebook, NotebookFormat};
 use std::fs;
 use tauri::{command, State};
 use uuid::Uuid;
@@ -9,15 +11,27 @@ use crate::state::AppState;
 use internals::services::base::file_utils::convert_path_for_julia;
 use internals::services::events::event_service::NotebookCellEventPayload;
 
-/// Read and parse a Jupyter notebook file
-#[command]
-pub fn read_notebook(path: String) -> Result<Notebook, String> {
-    debug!("[Notebook] Reading notebook from: {}", path);
-
+/// Read a Jupyter, Pluto or percent-format notebook from disk
+fn load_notebook(path: &str) -> Result<Notebook, String> {
     // Read file content
-    let content = fs::read_to_string(&path)
+    let content = fs::read_to_string(path)
         .map_err(|e| format!("Failed to read notebook file: {}", e))?;
 
+    match NotebookFormat::detect(path, &content) {
+        Some(NotebookFormat::Pluto) => {
+            return pluto::parse_pluto_notebook(&content)
+                .map_err(|e| format!("Failed to parse Pluto notebook: {}", e));
+        }
+        Some(NotebookFormat::Percent) => {
+            return percent::parse_percent_script(&content)
+                .map_err(|e| format!("Failed to parse script notebook: {}", e));
+        }
+        None if path.to_lowercase().ends_with(".jl") => {
+            return Err("Not a notebook: no Pluto header or `# %%` cell markers found".to_string());
+        }
+        _ => {}
+    }
+
     // Parse JSON
     let notebook: Notebook = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse notebook JSON: {}", e))?;
@@ -31,12 +45,21 @@ pub fn read_notebook(path: String) -> Result<Notebook, String> {
     }
 
     // Cell sources are already normalized by the deserializer
+    Ok(notebook)
+}
+
+/// Read and parse a Jupyter notebook file (or a Pluto or percent-format script)
+#[command]
+pub fn read_notebook(path: String) -> Result<Notebook, String> {
+    debug!("[Notebook] Reading notebook from: {}", path);
+
+    let notebook = load_notebook(&path)?;
 
     debug!("[Notebook] Successfully parsed notebook with {} cells", notebook.cells.len());
     Ok(notebook)
 }
 
-/// Write a Jupyter notebook to file
+/// Write a Jupyter notebook to file (Pluto or percent-format script for `.jl` files)
 #[command]
 pub fn write_notebook(path: String, notebook: Notebook) -> Result<(), String> {
     debug!("[Notebook] Writing notebook to: {}", path);
@@ -49,18 +72,116 @@ pub fn write_notebook(path: String, notebook: Notebook) -> Result<(), String> {
         ));
     }
 
-    // Serialize to JSON with pretty printing
-    let json = serde_json::to_string_pretty(&notebook)
-        .map_err(|e| format!("Failed to serialize notebook: {}", e))?;
+    // Julia files keep the Pluto format they were read in; other notebooks become percent scripts
+    let content = if path.to_lowercase().ends_with(".jl") {
+        if notebook.metadata.extra.get("pluto").is_some() {
+            pluto::write_pluto_notebook(&notebook)?
+        } else {
+            percent::write_percent_script(&notebook)
+        }
+    } else {
+        // Serialize to JSON with pretty printing
+        serde_json::to_string_pretty(&notebook)
+            .map_err(|e| format!("Failed to serialize notebook: {}", e))?
+    };
 
     // Write to file
-    fs::write(&path, json)
+    fs::write(&path, content)
         .map_err(|e| format!("Failed to write notebook file: {}", e))?;
 
     debug!("[Notebook] Successfully wrote notebook with {} cells", notebook.cells.len());
     Ok(())
 }
 
+/// Detect whether a file is a notebook and in which format (so `.jl` files can open in the notebook view)
+#[command]
+pub fn detect_notebook_format(path: String) -> Result<Option<NotebookFormat>, String> {
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(NotebookFormat::detect(&path, &content))
+}
+
+/// Export a notebook as a `.jl` script, Markdown or a standalone HTML report
+///
+/// Pass the notebook from the editor to include outputs that are not saved in
+/// the file (Pluto and script notebooks do not store outputs).
+#[command]
+pub fn export_notebook(
+    path: String,
+    format: NotebookExportFormat,
+    output_path: String,
+    notebook: Option<Notebook>,
+) -> Result<(), String> {
+    debug!("[Notebook] Exporting {} as {:?} to {}", path, format, output_path);
+
+    let notebook = match notebook {
+        Some(notebook) => notebook,
+        None => load_notebook(&path)?,
+    };
+    let title = std::path::Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Notebook".to_string());
+
+    let content = notebook_export::export_notebook(&notebook, format, &title);
+    fs::write(&output_path, content)
+        .map_err(|e| format!("Failed to write exported notebook: {}", e))?;
+
+    debug!("[Notebook] Exported notebook with {} cells", notebook.cells.len());
+    Ok(())
+}
+
+/// Strip outputs and execution counts from a notebook file, rewriting it in place
+///
+/// Returns the number of removed outputs.
+#[command]
+pub fn strip_notebook_outputs(path: String, options: Option<StripOptions>) -> Result<usize, String> {
+    debug!("[Notebook] Stripping outputs from {}", path);
+
+    let mut notebook = load_notebook(&path)?;
+    let removed = notebook_diff::strip_outputs(&mut notebook, &options.unwrap_or_default());
+    write_notebook(path, notebook)?;
+
+    debug!("[Notebook] Removed {} outputs", removed);
+    Ok(removed)
+}
+
+/// Cell-level diff between two versions of a notebook
+#[command]
+pub fn diff_notebooks(base_path: String, other_path: String) -> Result<NotebookDiff, String> {
+    debug!("[Notebook] Diffing {} against {}", other_path, base_path);
+
+    let base = load_notebook(&base_path)?;
+    let other = load_notebook(&other_path)?;
+    Ok(notebook_diff::diff_notebooks(&base, &other))
+}
+
+/// Three-way merge of two notebook versions with their common ancestor
+///
+/// The merged notebook is written to `output_path` when given; conflicts are
+/// marked inside the affected cells and listed in the result.
+#[command]
+pub fn merge_notebooks(
+    base_path: String,
+    local_path: String,
+    remote_path: String,
+    output_path: Option<String>,
+) -> Result<NotebookMergeResult, String> {
+    debug!("[Notebook] Merging {} and {} (base {})", local_path, remote_path, base_path);
+
+    let base = load_notebook(&base_path)?;
+    let local = load_notebook(&local_path)?;
+    let remote = load_notebook(&remote_path)?;
+    let result = notebook_diff::merge_notebooks(&base, &local, &remote);
+
+    if let Some(output_path) = output_path {
+        write_notebook(output_path, result.notebook.clone())?;
+    }
+
+    debug!("[Notebook] Merge finished with {} conflicts", result.conflicts.len());
+    Ok(result)
+}
+
 /// Execute an entire notebook sequentially and emit per-cell output events
 #[command]
 pub async fn execute_notebook_file(
@@ -84,10 +205,7 @@ pub async fn execute_notebook_file(
     }
 
     // Read notebook from disk
-    let content = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read notebook file: {}", e))?;
-    let notebook: Notebook = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse notebook JSON: {}", e))?;
+    let notebook = load_notebook(&path)?;
 
     // Determine working directory (notebook folder) for includes
     let dir = std::path::Path::new(&path)
@@ -96,11 +214,15 @@ pub async fn execute_notebook_file(
         .unwrap_or_else(|| ".".to_string());
     let julia_dir = convert_path_for_julia(&dir);
 
-    // Execute code cells sequentially
-    for (idx, cell) in notebook.cells.iter().enumerate() {
+    // Execute code cells sequentially (Pluto notebooks store cells in execution order)
+    for idx in execution_order(&notebook) {
+        let cell = &notebook.cells[idx];
         if cell.cell_type != shared::frontend::CellType::Code {
             continue;
         }
+        if matches!(pluto::cell_metadata(cell), Ok(Some(meta)) if meta.disabled || meta.disabled_by_dependency) {
+            continue;
+        }
 
         // Use index-based cell_id (frontend will match by cell_index to get actual cell IDs)
         let cell_id = format!("cell-{}", idx);
@@ -212,64 +334,13 @@ cd("{}")
                 }));
             }
             
-            // Add plot outputs
-            for (mime_type, data) in buffer.plots {
-                // Clean the data - extract raw SVG/base64, removing any HTML markup
-                let cleaned_data = if mime_type == "image/svg+xml" {
-                    // For SVG, extract raw SVG XML from HTML if present
-                    if data.trim().starts_with("<svg") {
-                        // Already raw SVG, use as-is
-                        data
-                    } else if data.contains("<svg") {
-                        // Extract SVG from HTML (e.g., from <img> tag or other HTML)
-                        // Try to find the SVG content
-                        if let Some(svg_start) = data.find("<svg") {
-                            if let Some(svg_end) = data.rfind("</svg>") {
-                                let svg_content = &data[svg_start..svg_end + 6];
-                                svg_content.to_string()
-                            } else {
-                                data // Fallback to original
-                            }
-                        } else {
-                            data // Fallback to original
-                        }
-                    } else {
-                        // Base64-encoded SVG, use as-is
-                        data
-                    }
-                } else {
-                    // For binary images, ensure we have clean base64 (no data: prefix or HTML)
-                    if data.starts_with("data:") {
-                        // Extract base64 part after comma
-                        if let Some(comma_pos) = data.find(',') {
-                            data[comma_pos + 1..].to_string()
-                        } else {
-                            data
-                        }
-                    } else if data.contains("<img") || data.contains("base64,") {
-                        // Extract base64 from HTML img tag
-                        if let Some(base64_start) = data.find("base64,") {
-                            let base64_part = &data[base64_start + 7..];
-                            // Remove any trailing HTML/attributes
-                            if let Some(end_pos) = base64_part.find('"') {
-                                base64_part[..end_pos].to_string()
-                            } else if let Some(end_pos) = base64_part.find(' ') {
-                                base64_part[..end_pos].to_string()
-                            } else if let Some(end_pos) = base64_part.find('>') {
-                                base64_part[..end_pos].to_string()
-                            } else {
-                                base64_part.to_string()
-                            }
-                        } else {
-                            data
-                        }
-                    } else {
-                        data
-                    }
-                };
-                
+            // Add plot outputs, one display_data output with every representation per plot
+            for bundle in buffer.plots {
                 let mut output_data = serde_json::Map::new();
-                output_data.insert(mime_type.clone(), serde_json::Value::String(cleaned_data));
+                for (mime_type, data) in bundle {
+                    let cleaned_data = clean_plot_data(&mime_type, data);
+                    output_data.insert(mime_type, serde_json::Value::String(cleaned_data));
+                }
                 
                 outputs.push(serde_json::json!({
                     "output_type": "display_data",
@@ -329,3 +400,86 @@ cd("{}")
 
     Ok(())
 }
+
+/// Cell indices in the order cells should run
+fn execution_order(notebook: &Notebook) -> Vec<usize> {
+    let Some(file_order) = pluto::file_order(notebook) else {
+        return (0..notebook.cells.len()).collect();
+    };
+
+    let index_of: std::collections::HashMap<String, usize> = notebook
+        .cells
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, cell)| Some((pluto::cell_metadata(cell).ok()??.cell_id, idx)))
+        .collect();
+    let mut order: Vec<usize> = file_order
+        .iter()
+        .filter_map(|id| index_of.get(id).copied())
+        .collect();
+    // Cells added since the notebook was read run last, in display order
+    let listed: std::collections::HashSet<usize> = order.iter().copied().collect();
+    order.extend((0..notebook.cells.len()).filter(|idx| !listed.contains(idx)));
+    order
+}
+
+/// Extract raw SVG or base64 data from a plot representation, removing any HTML markup
+///
+/// Interactive specs, HTML and plain text are passed through unchanged.
+fn clean_plot_data(mime_type: &str, data: String) -> String {
+    if !mime_type.starts_with("image/") {
+        return data;
+    }
+    if mime_type == "image/svg+xml" {
+        // For SVG, extract raw SVG XML from HTML if present
+        if data.trim().starts_with("<svg") {
+            // Already raw SVG, use as-is
+            data
+        } else if data.contains("<svg") {
+            // Extract SVG from HTML (e.g., from <img> tag or other HTML)
+            // Try to find the SVG content
+            if let Some(svg_start) = data.find("<svg") {
+                if let Some(svg_end) = data.rfind("</svg>") {
+                    let svg_content = &data[svg_start..svg_end + 6];
+                    svg_content.to_string()
+                } else {
+                    data // Fallback to original
+                }
+            } else {
+                data // Fallback to original
+            }
+        } else {
+            // Base64-encoded SVG, use as-is
+            data
+        }
+    } else {
+        // For binary images, ensure we have clean base64 (no data: prefix or HTML)
+        if data.starts_with("data:") {
+            // Extract base64 part after comma
+            if let Some(comma_pos) = data.find(',') {
+                data[comma_pos + 1..].to_string()
+            } else {
+                data
+            }
+        } else if data.contains("<img") || data.contains("base64,") {
+            // Extract base64 from HTML img tag
+            if let Some(base64_start) = data.find("base64,") {
+                let base64_part = &data[base64_start + 7..];
+                // Remove any trailing HTML/attributes
+                if let Some(end_pos) = base64_part.find('"') {
+                    base64_part[..end_pos].to_string()
+                } else if let Some(end_pos) = base64_part.find(' ') {
+                    base64_part[..end_pos].to_string()
+                } else if let Some(end_pos) = base64_part.find('>') {
+                    base64_part[..end_pos].to_string()
+                } else {
+                    base64_part.to_string()
+                }
+            } else {
+                data
+            }
+        } else {
+            data
+        }
+    }
+}
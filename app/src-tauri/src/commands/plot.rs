@@ -3,7 +3,10 @@ use log::{debug, error};
 use tauri::{AppHandle, Emitter, Manager};
 use crate::state::AppState;
 use crate::error::AppError;
-use internals::messages::plot::GetPlots;
+use internals::messages::plot::{
+    ExportPlot, ExportPlots, GetPlot, GetPlots, GetPlotsBySourceFile, PlotExportOptions, PlotRetention,
+    SetPlotRetention,
+};
 
 /// Get all plots from orchestrator's plot server
 #[tauri::command]
@@ -24,17 +27,62 @@ pub async fn get_all_plots(app_handle: AppHandle) -> Result<Vec<PlotData>, AppEr
     }
 }
 
+/// Export a single plot to PNG, SVG or PDF
+#[tauri::command]
+pub async fn export_plot(
+    plot_id: String,
+    file_path: String,
+    options: PlotExportOptions,
+    app_handle: AppHandle,
+) -> Result<(), AppError> {
+    debug!("Exporting plot {} to {}", plot_id, file_path);
+
+    let app_state = app_handle.state::<AppState>();
+    match app_state.actor_system.plot_actor.send(ExportPlot { plot_id, file_path, options }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            error!("Failed to export plot: {}", e);
+            Err(AppError::InternalError(format!("Failed to export plot: {}", e)))
+        }
+    }
+}
+
+/// Export all plots of a source file or session into a directory or zip archive
+#[tauri::command]
+pub async fn export_plots(
+    source_file: Option<String>,
+    session_id: Option<String>,
+    destination: String,
+    zip: bool,
+    options: PlotExportOptions,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, AppError> {
+    debug!("Exporting plots to {} (zip: {})", destination, zip);
+
+    let app_state = app_handle.state::<AppState>();
+    match app_state
+        .actor_system
+        .plot_actor
+        .send(ExportPlots { source_file, session_id, destination, zip, options })
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+    {
+        Ok(files) => Ok(files),
+        Err(e) => {
+            error!("Failed to export plots: {}", e);
+            Err(AppError::InternalError(format!("Failed to export plots: {}", e)))
+        }
+    }
+}
+
 /// Get a specific plot by ID
 #[tauri::command]
 pub async fn get_plot(plot_id: String, app_handle: AppHandle) -> Result<Option<PlotData>, AppError> {
     debug!("Getting plot with ID: {}", plot_id);
 
     let app_state = app_handle.state::<AppState>();
-    match app_state.actor_system.plot_actor.send(GetPlots).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
-        Ok(plots) => {
-            let plot = plots.into_iter().find(|p| p.id == plot_id);
-            Ok(plot)
-        }
+    match app_state.actor_system.plot_actor.send(GetPlot { plot_id }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
+        Ok(plot) => Ok(plot),
         Err(e) => {
             error!("Failed to get plot: {}", e);
             Err(AppError::InternalError(format!("Failed to get plot: {}", e)))
@@ -51,14 +99,8 @@ pub async fn get_plots_by_source_file(
     debug!("Getting plots from source file: {}", source_file);
 
     let app_state = app_handle.state::<AppState>();
-    match app_state.actor_system.plot_actor.send(GetPlots).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
-        Ok(plots) => {
-            let filtered_plots: Vec<PlotData> = plots
-                .into_iter()
-                .filter(|plot| plot.source_file.as_ref() == Some(&source_file))
-                .collect();
-            Ok(filtered_plots)
-        }
+    match app_state.actor_system.plot_actor.send(GetPlotsBySourceFile { source_file }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
+        Ok(plots) => Ok(plots),
         Err(e) => {
             error!("Failed to get plots: {}", e);
             Err(AppError::InternalError(format!("Failed to get plots: {}", e)))
@@ -84,6 +126,21 @@ pub async fn delete_plot(plot_id: String, app_handle: AppHandle) -> Result<bool,
     }
 }
 
+/// Change how many plots, and how many bytes of plot data, the plot history keeps
+#[tauri::command]
+pub async fn set_plot_retention(retention: PlotRetention, app_handle: AppHandle) -> Result<(), AppError> {
+    debug!("Setting plot retention to {:?}", retention);
+
+    let app_state = app_handle.state::<AppState>();
+    match app_state.actor_system.plot_actor.send(SetPlotRetention { retention }).await.map_err(|_| AppError::InternalError("Actor comm failed".to_string()))? {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            error!("Failed to set plot retention: {}", e);
+            Err(AppError::InternalError(format!("Failed to set plot retention: {}", e)))
+        }
+    }
+}
+
 /// Clear all plots
 #[tauri::command]
 pub async fn clear_all_plots(app_handle: AppHandle) -> Result<(), AppError> {
@@ -132,6 +189,7 @@ pub async fn test_plot_system(app_handle: AppHandle) -> Result<String, AppError>
         line_number: Some(1),
         code_context: Some("println(\"Hello World\")".to_string()),
         session_id: Some("test_session".to_string()),
+        alternatives: Default::default(),
     };
 
     match app_state.actor_system
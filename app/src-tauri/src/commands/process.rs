@@ -63,4 +63,159 @@ pub async fn get_backend_busy_status(app_state: State<'_, AppState>) -> Result<b
     )
 }
 
+#[tauri::command]
+pub async fn build_sysimage(app_state: State<'_, AppState>, project_path: String) -> Result<(), AppError> {
+    debug!("[Process] Build sysimage for {}", project_path);
+    use internals::messages::process::BuildSysimage;
+    Ok(
+        app_state
+            .actor_system
+            .process_actor
+            .send(BuildSysimage { project_path })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn get_sysimages(app_state: State<'_, AppState>) -> Result<Vec<internals::types::SysimageInfo>, AppError> {
+    use internals::messages::process::GetSysimages;
+    Ok(
+        app_state
+            .actor_system
+            .process_actor
+            .send(GetSysimages)
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn clear_sysimages(app_state: State<'_, AppState>) -> Result<(), AppError> {
+    debug!("[Process] Clear sysimages");
+    use internals::messages::process::ClearSysimages;
+    Ok(
+        app_state
+            .actor_system
+            .process_actor
+            .send(ClearSysimages)
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn get_launch_profiles(
+    app_state: State<'_, AppState>,
+    project_path: String,
+) -> Result<internals::types::ProjectLaunchProfiles, AppError> {
+    use internals::messages::configuration::GetLaunchProfiles;
+    Ok(
+        app_state
+            .actor_system
+            .config_actor
+            .send(GetLaunchProfiles { project_path })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn save_launch_profile(
+    app_state: State<'_, AppState>,
+    project_path: String,
+    profile: internals::types::JuliaLaunchProfile,
+) -> Result<(), AppError> {
+    debug!("[Process] Save launch profile '{}' for {}", profile.name, project_path);
+    use internals::messages::configuration::SaveLaunchProfile;
+    app_state
+        .actor_system
+        .config_actor
+        .send(SaveLaunchProfile { project_path, profile })
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+        .map_err(AppError::ValidationError)
+}
 
+#[tauri::command]
+pub async fn delete_launch_profile(
+    app_state: State<'_, AppState>,
+    project_path: String,
+    name: String,
+) -> Result<(), AppError> {
+    debug!("[Process] Delete launch profile '{}' for {}", name, project_path);
+    use internals::messages::configuration::DeleteLaunchProfile;
+    Ok(
+        app_state
+            .actor_system
+            .config_actor
+            .send(DeleteLaunchProfile { project_path, name })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+/// Select the launch profile for a project; it takes effect on the next Julia start or restart
+#[tauri::command]
+pub async fn set_active_launch_profile(
+    app_state: State<'_, AppState>,
+    project_path: String,
+    name: Option<String>,
+) -> Result<(), AppError> {
+    debug!("[Process] Set active launch profile {:?} for {}", name, project_path);
+    use internals::messages::configuration::SetActiveLaunchProfile;
+    Ok(
+        app_state
+            .actor_system
+            .config_actor
+            .send(SetActiveLaunchProfile { project_path, name })
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn get_resource_usage(
+    app_state: State<'_, AppState>,
+) -> Result<Option<internals::types::ResourceSample>, AppError> {
+    use internals::messages::process::GetResourceUsage;
+    Ok(
+        app_state
+            .actor_system
+            .process_actor
+            .send(GetResourceUsage)
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn get_resource_monitor_config(
+    app_state: State<'_, AppState>,
+) -> Result<internals::types::ResourceMonitorConfig, AppError> {
+    use internals::messages::process::GetResourceMonitorConfig;
+    Ok(
+        app_state
+            .actor_system
+            .process_actor
+            .send(GetResourceMonitorConfig)
+            .await
+            .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))??,
+    )
+}
+
+#[tauri::command]
+pub async fn set_resource_monitor_config(
+    app_state: State<'_, AppState>,
+    config: internals::types::ResourceMonitorConfig,
+) -> Result<(), AppError> {
+    debug!("[Process] Set resource monitor config: {:?}", config);
+    use internals::messages::process::SetResourceMonitorConfig;
+    app_state
+        .actor_system
+        .process_actor
+        .send(SetResourceMonitorConfig { config })
+        .await
+        .map_err(|_| AppError::InternalError("Actor comm failed".to_string()))?
+        .map_err(AppError::ValidationError)
+}
@@ -118,10 +118,12 @@ fn copy_dir_recursive(src: &std::path::PathBuf, dst: &std::path::PathBuf) -> Res
 use crate::commands::{
     startup::{start_orchestrator, continue_orchestrator_startup},
     lsp::{
-        lsp_get_completions, lsp_get_definition, lsp_get_diagnostics,
-        lsp_get_document_symbols, lsp_get_references,
+        lsp_get_completions, lsp_get_dead_code, lsp_get_definition, lsp_get_diagnostics, lsp_get_dependency_health,
+        lsp_get_document_symbols, lsp_get_package_health, lsp_get_references,
         lsp_get_signature_help, lsp_hover, lsp_initialize, lsp_is_running, lsp_notify_did_change,
         lsp_notify_did_close, lsp_notify_did_open, lsp_notify_did_save,
+        lsp_notebook_close, lsp_notebook_completions, lsp_notebook_definition, lsp_notebook_diagnostics,
+        lsp_notebook_hover, lsp_notebook_update,
         lsp_shutdown, lsp_restart,
     },
     syntax::{
@@ -137,6 +139,8 @@ use crate::commands::{
         execute_julia_code,
         execute_notebook_cell,
         execute_notebook_cells_batch,
+        execute_notebook_cells_with_dependents,
+        get_notebook_dependency_graph,
         execute_julia_file,
         refresh_workspace_variables,
         get_variable_value,
@@ -155,11 +159,16 @@ use crate::commands::{
         stop_file_watcher,
     },
     files::{get_file_tree, read_file_content, write_file_content, create_file_item, create_folder_item, delete_item, rename_item, check_path_exists, load_directory_contents},
-    notebook::{read_notebook, write_notebook, execute_notebook_file},
+    notebook::{
+        read_notebook, write_notebook, execute_notebook_file, detect_notebook_format, export_notebook,
+        strip_notebook_outputs, diff_notebooks, merge_notebooks,
+    },
     projects::{read_project_toml, write_project_toml, generate_uuid},
-    process::{get_session_status, init_terminal_session, is_backend_ready, restart_julia, get_backend_busy_status},
+    kernel::{start_kernel, list_kernels, kill_kernel, restart_kernel, set_kernel_idle_timeout},
+    process::{get_session_status, init_terminal_session, is_backend_ready, restart_julia, get_backend_busy_status, build_sysimage, get_sysimages, clear_sysimages, get_launch_profiles, save_launch_profile, delete_launch_profile, set_active_launch_profile, get_resource_usage, get_resource_monitor_config, set_resource_monitor_config},
     plot::{
-        clear_all_plots, delete_plot, emit_plot_navigator_update, get_all_plots, get_plot,
+        clear_all_plots, delete_plot, emit_plot_navigator_update, export_plot, export_plots,
+        get_all_plots, get_plot, set_plot_retention,
         serve_plot_image, test_plot_system,
     },
     utils::{get_system_info, open_url, set_last_opened_folder, is_subscription_enabled, is_ai_enabled, get_app_settings, set_app_settings, get_available_fonts},
@@ -243,11 +252,18 @@ pub fn run() {
             read_notebook,
             write_notebook,
             execute_notebook_file,
+            detect_notebook_format,
+            export_notebook,
+            strip_notebook_outputs,
+            diff_notebooks,
+            merge_notebooks,
             generate_uuid,
             // Julia operations
             execute_julia_code,
             execute_notebook_cell,
         execute_notebook_cells_batch,
+        execute_notebook_cells_with_dependents,
+        get_notebook_dependency_graph,
             execute_julia_file,
             refresh_workspace_variables,
             get_variable_value,
@@ -259,6 +275,21 @@ pub fn run() {
             init_terminal_session,
             restart_julia,
             restart_julia_orchestrator,
+            build_sysimage,
+            get_sysimages,
+            clear_sysimages,
+            get_launch_profiles,
+            save_launch_profile,
+            delete_launch_profile,
+            set_active_launch_profile,
+            get_resource_usage,
+            get_resource_monitor_config,
+            set_resource_monitor_config,
+            start_kernel,
+            list_kernels,
+            kill_kernel,
+            restart_kernel,
+            set_kernel_idle_timeout,
             // Project management
             get_julia_project_data,
             create_new_julia_project,
@@ -276,8 +307,11 @@ pub fn run() {
             // Plot commands
             get_all_plots,
             get_plot,
+            export_plot,
+            export_plots,
             delete_plot,
             clear_all_plots,
+            set_plot_retention,
             test_plot_system,
             emit_plot_navigator_update,
             serve_plot_image,
@@ -293,6 +327,15 @@ pub fn run() {
             lsp_get_references,
             lsp_get_document_symbols,
             lsp_get_diagnostics,
+            lsp_get_dependency_health,
+            lsp_get_package_health,
+            lsp_get_dead_code,
+            lsp_notebook_update,
+            lsp_notebook_close,
+            lsp_notebook_hover,
+            lsp_notebook_completions,
+            lsp_notebook_definition,
+            lsp_notebook_diagnostics,
             lsp_is_running,
             lsp_initialize,
             lsp_shutdown,
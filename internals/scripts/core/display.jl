@@ -15,6 +15,7 @@ if !@isdefined(JJ_DISPLAYABLE_MIMES)
         "application/vnd.vega.v4+json",
         "application/vnd.vega.v3+json",
         "application/vnd.plotly.v1+json",
+        "application/vnd.webio.node+json",
         "juliavscode/html",
         "image/svg+xml",
         "image/png",
@@ -22,6 +23,12 @@ if !@isdefined(JJ_DISPLAYABLE_MIMES)
     ]
 end
 
+# Extra representations sent alongside the displayed one, so notebooks get a fallback
+# Only cheap text forms: rendering every image and JSON format would slow down each plot
+if !@isdefined(JJ_ALTERNATIVE_MIMES)
+    const JJ_ALTERNATIVE_MIMES = ["text/html", "text/plain"]
+end
+
 # Plot capture system (similar to VS Code)
 struct Compute42Display <: AbstractDisplay
     is_repl::Bool
@@ -88,9 +95,40 @@ function stringmime(m::MIME, x)
     return base64encode(take!(buf))
 end
 
+# Render x as `mime` in the form the backend expects
+function display_payload(mime::String, x)
+    m = MIME(mime)
+    if mime == "image/svg+xml"
+        # SVG is already text, just convert to string directly
+        return String(repr(m, x))
+    elseif startswith(mime, "image/")
+        # Binary images need base64 encoding
+        return stringmime(m, x)
+    else
+        # Other MIME types (JSON, HTML, etc.) are already text
+        return String(repr(m, x))
+    end
+end
+
+# Render the text representations of x that a notebook could show instead
+function collect_alternatives(primary::String, x)
+    alternatives = Dict{String,Any}()
+    for mime in JJ_ALTERNATIVE_MIMES
+        mime == primary && continue
+        try
+            if showable(mime, x)
+                alternatives[mime] = display_payload(mime, x)
+            end
+        catch e
+            println(stderr, "Compute42: Failed to render ", mime, ": ", sprint(showerror, e))
+        end
+    end
+    return alternatives
+end
+
 # Send display message to backend
         # Get plot socket from Main namespace
-function send_display_msg(kind, data)
+function send_display_msg(kind, data, alternatives = Dict{String,Any}())
     try
         # Skip sending empty or meaningless data
         if should_filter_plot_data(data)
@@ -117,7 +155,8 @@ function send_display_msg(kind, data)
                     "source_file" => nothing,
                     "line_number" => nothing,
                     "code_context" => nothing,
-                    "session_id" => nothing
+                    "session_id" => nothing,
+                    "alternatives" => alternatives
                 )
             )
             message_json = JSON.json(msg)
@@ -139,19 +178,8 @@ function Base.display(d::Compute42Display, m::MIME, @nospecialize(x))
     else
         mime = string(m)
         if mime in JJ_DISPLAYABLE_MIMES
-            # Handle binary vs text MIME types
-            # SVG is text, so don't base64-encode it. Only binary images need base64 encoding.
-            if mime == "image/svg+xml"
-                # SVG is already text, just convert to string directly
-                payload = String(repr(m, x))
-            elseif startswith(mime, "image/")
-                # Binary images need base64 encoding
-                payload = stringmime(m, x)
-            else
-                # Other MIME types (JSON, HTML, etc.) are already text
-                payload = String(repr(m, x))
-            end
-            send_display_msg(mime, payload)
+            payload = display_payload(mime, x)
+            send_display_msg(mime, payload, collect_alternatives(mime, x))
         else
             throw(MethodError(display, (d, m, x)))
         end
@@ -1,11 +1,28 @@
 # Compute42 Code Execution Handlers
 
+# GC and allocation stats since `gc_start`, reported in ExecutionComplete metadata
+function execution_gc_stats(gc_start)
+    gc_start === nothing && return nothing
+    try
+        diff = Base.GC_Diff(Base.gc_num(), gc_start)
+        return Dict(
+            "allocated_bytes" => max(diff.allocd, 0),
+            "gc_time_ms" => diff.total_time / 1e6,
+            "gc_count" => diff.pause,
+            "gc_live_bytes" => Base.gc_live_bytes()
+        )
+    catch
+        return nothing
+    end
+end
+
 # Handle new CodeExecution requests
 function handle_code_execution(data)
     # Store execution_type early for use in error handler
     local execution_type = nothing
     local id = nothing
     local code = nothing
+    local gc_start = nothing
     
     try
         # Extract required fields using new JSON utilities
@@ -15,6 +32,7 @@ function handle_code_execution(data)
 
         # Log execution start
         start_time = time()
+        gc_start = Base.gc_num()
         code_length = length(code)
         # println(stderr, "Compute42: [EXEC] Starting $(execution_type) - ID: $(id), Code length: $(code_length) bytes")
         
@@ -177,7 +195,7 @@ function handle_code_execution(data)
                 "success" => true,
                 "duration_ms" => duration_ms,
                 "timestamp" => round(Int, end_time),
-                "metadata" => nothing
+                "metadata" => execution_gc_stats(gc_start)
             )
         )
         send_message_to_backend(response)
@@ -244,7 +262,7 @@ function handle_code_execution(data)
                 "success" => false,
                 "duration_ms" => duration_ms,
                 "timestamp" => round(Int, end_time),
-                "metadata" => nothing
+                "metadata" => execution_gc_stats(gc_start)
             )
         )
         send_message_to_backend(response)
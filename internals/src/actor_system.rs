@@ -9,6 +9,7 @@ use crate::actors::{
     OrchestratorActor, ConfigurationActor, StateActor, ExecutionActor,
     CommunicationActor, ProcessActor, LspActor, PlotActor,
     ProjectActor, FilesystemActor, FileWatcherActor, FileServerActor, InstallationActor,
+    KernelManagerActor,
 };
 use crate::messages::orchestrator::SetActorAddresses;
 use crate::messages::coordination::{ErrorSeverity, ActorError, ActorHealth, DependencyReady, DependencyFailed, ResourceAcquired, ResourceReleased, PerformanceMetric, DebugLog};
@@ -32,6 +33,7 @@ pub struct ActorSystem {
     pub filesystem_actor: Addr<FilesystemActor>,
     pub file_watcher_actor: Addr<FileWatcherActor>,
     pub project_actor: Addr<ProjectActor>,
+    pub kernel_manager_actor: Addr<KernelManagerActor>,
     // pub sysimage_actor: Addr<SysimageActor>,
     
     // Event manager for shared event coordination
@@ -68,11 +70,13 @@ impl ActorSystem {
         let event_manager_for_proc = event_manager.clone();
         let event_emitter_for_proc = event_manager.get_event_emitter().clone();
         let installation_actor_for_proc = installation_actor.clone();
+        let config_actor_for_proc = config_actor.clone();
         let process_actor = Supervisor::start(move |_| {
             ProcessActor::new(
                 event_emitter_for_proc.clone(),
                 event_manager_for_proc.clone(),
                 Some(installation_actor_for_proc.clone()),
+                Some(config_actor_for_proc.clone()),
             )
         });
         
@@ -125,6 +129,18 @@ impl ActorSystem {
         
         // Sysimage actor removed
         
+        // KernelManagerActor runs additional Julia kernels; the main actors form the default kernel
+        let kernel_manager_actor = KernelManagerActor::new(
+            process_actor.clone(),
+            communication_actor.clone(),
+            execution_actor.clone(),
+            event_manager.get_event_emitter().clone(),
+            event_manager.clone(),
+            installation_actor.clone(),
+            config_actor.clone(),
+            plot_actor.clone(),
+        ).start();
+        
         let orchestrator_actor = OrchestratorActor::new(
             event_manager.clone(),
         ).start();
@@ -186,6 +202,7 @@ impl ActorSystem {
             filesystem_actor,
             file_watcher_actor,
             project_actor,
+            kernel_manager_actor,
             // sysimage_actor,
             event_manager,
         }
@@ -324,6 +324,13 @@ async fn execute_single_request(
     } // Release the lock here
     debug!("[CommunicationActor::Execution] Current request set, lock released");
 
+    // Track peak memory and wall time for ExecutionComplete metadata
+    if let Some(process_actor) = state.process_actor.lock().await.clone() {
+        process_actor.do_send(crate::messages::process::BeginExecutionTracking {
+            execution_id: request_id.clone(),
+        });
+    }
+
     // Send the message
     let message_sender_guard = state.message_sender.lock().await;
     if let Some(sender) = message_sender_guard.as_ref() {
@@ -3,8 +3,10 @@
 
 use crate::services::events::EventService;
 use crate::messages::plot::HandlePlotDataReceived;
+use crate::actors::plot_actor::filter_alternatives;
 use actix::prelude::*;
 use log::{debug, error, trace};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -77,9 +79,10 @@ impl MessageHandler {
                 line_number,
                 code_context,
                 session_id,
+                alternatives,
             } => self.handle_plot_data(
                 id, mime_type, data, *timestamp, title, description,
-                source_file, line_number, code_context, session_id,
+                source_file, line_number, code_context, session_id, alternatives,
             ).await,
             
             crate::messages::JuliaMessage::SessionStatus { status, details } => {
@@ -146,6 +149,30 @@ impl MessageHandler {
         }
     }
 
+    /// Add the execution's peak memory and wall time to the ExecutionComplete metadata
+    async fn with_resource_usage(
+        &self,
+        message: &crate::messages::JuliaMessage,
+        id: &str,
+    ) -> crate::messages::JuliaMessage {
+        let mut message = message.clone();
+        let Some(process_actor) = &self.process_actor else {
+            return message;
+        };
+        if let crate::messages::JuliaMessage::ExecutionComplete { metadata, .. } = &mut message {
+            match process_actor.send(crate::messages::process::FinishExecutionTracking {
+                execution_id: id.to_string(),
+                julia_metadata: metadata.clone(),
+            }).await {
+                Ok(Ok(Some(usage))) => *metadata = Some(usage.merge_into_metadata(metadata.take())),
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => debug!("[CommunicationActor::MessageHandler] Failed to get execution resource usage: {}", e),
+                Err(e) => debug!("[CommunicationActor::MessageHandler] Failed to send FinishExecutionTracking: {:?}", e),
+            }
+        }
+        message
+    }
+
     /// Clean array strings in result if present
     fn clean_array_string_result(result: &Option<String>) -> Option<String> {
         if let Some(result_str) = result {
@@ -189,7 +216,8 @@ impl MessageHandler {
     ) -> Result<(), String> {
         debug!("[CommunicationActor::MessageHandler] Received execution complete: {} (type: {:?})", id, execution_type);
         
-        Self::process_pending_request(current_request, message, id).await;
+        let message = self.with_resource_usage(message, id).await;
+        Self::process_pending_request(current_request, &message, id).await;
         
         let cleaned_result = Self::clean_array_string_result(result);
         
@@ -239,14 +267,17 @@ impl MessageHandler {
         line_number: &Option<u32>,
         code_context: &Option<String>,
         session_id: &Option<String>,
+        alternatives: &BTreeMap<String, String>,
     ) -> Result<(), String> {
         debug!("[CommunicationActor::MessageHandler] Received plot data: {}", id);
+        let alternatives = filter_alternatives(alternatives.clone());
         
         // Check if a notebook cell is executing and buffer the plot
         if let Some(process_actor) = &self.process_actor {
+            let mut bundle = alternatives.clone();
+            bundle.insert(mime_type.to_string(), data.to_string());
             if let Err(e) = process_actor.send(crate::messages::process::BufferNotebookCellPlot {
-                mime_type: mime_type.to_string(),
-                data: data.to_string(),
+                bundle,
             }).await {
                 debug!("[CommunicationActor::MessageHandler] Failed to buffer plot for notebook cell: {:?}", e);
             } else {
@@ -264,7 +295,8 @@ impl MessageHandler {
             "source_file": source_file,
             "line_number": line_number,
             "code_context": code_context,
-            "session_id": session_id
+            "session_id": session_id,
+            "alternatives": alternatives
         });
         
         // Route through PlotActor for proper serialization (for plot pane)
@@ -325,6 +325,28 @@ impl Handler<DisconnectFromPipes> for CommunicationActor {
     }
 }
 
+impl Handler<ShutdownCommunication> for CommunicationActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, _msg: ShutdownCommunication, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("CommunicationActor: Received ShutdownCommunication message");
+
+        self.is_connected = false;
+        // Drop the ProcessActor's address so neither actor keeps the other alive
+        self.process_actor = None;
+
+        let state = self.state.clone();
+        Box::pin(
+            async move { connection::disconnect_from_pipes(&state).await }
+                .into_actor(self)
+                .map(|res, _actor, ctx| {
+                    ctx.stop();
+                    res
+                }),
+        )
+    }
+}
+
 impl Handler<ExecuteCode> for CommunicationActor {
     type Result = ResponseActFuture<Self, Result<JuliaMessage, String>>;
     
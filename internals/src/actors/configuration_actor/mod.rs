@@ -9,7 +9,7 @@ use serde_json::Value;
 
 use crate::messages::configuration::*;
 use crate::services::events::EventService;
-use crate::types::{Configuration, UserPreferences};
+use crate::types::{Configuration, JuliaLaunchProfile, ProjectLaunchProfiles, UserPreferences, DEFAULT_LAUNCH_PROFILE};
 use persistence::PersistenceHelper;
 
 /// ConfigurationActor - manages application configuration
@@ -210,6 +210,26 @@ impl ConfigurationActor {
         self.save_configuration(&config).await
     }
     
+    /// Get the launch profiles stored for a project
+    async fn get_launch_profiles_internal(&self, project_path: &str) -> ProjectLaunchProfiles {
+        let config = self.get_config().await;
+        config.launch_profiles.get(project_path).cloned().unwrap_or_default()
+    }
+    
+    /// Update the launch profiles of a project and save the configuration
+    async fn update_launch_profiles<F>(&self, project_path: String, update: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut ProjectLaunchProfiles) -> Result<(), String>,
+    {
+        let mut config = self.get_config().await;
+        let profiles = config.launch_profiles.entry(project_path.clone()).or_default();
+        update(profiles)?;
+        if profiles.profiles.is_empty() && profiles.active.is_none() {
+            config.launch_profiles.remove(&project_path);
+        }
+        self.save_configuration(&config).await
+    }
+    
     /// Save configuration to external service
     async fn save_config(&mut self, config_value: Value) -> Result<(), String> {
         debug!("ConfigurationActor: Saving configuration");
@@ -240,6 +260,7 @@ impl ConfigurationActor {
             editor_line_numbers: existing.editor_line_numbers,
             editor_minimap: existing.editor_minimap,
             editor_color_scheme: existing.editor_color_scheme.clone(),
+            launch_profiles: existing.launch_profiles.clone(),
         };
         self.save_configuration(&app_config).await?;
         
@@ -433,6 +454,104 @@ impl Handler<SetUserEmail> for ConfigurationActor {
 }
 
 
+impl Handler<GetLaunchProfiles> for ConfigurationActor {
+    type Result = ResponseActFuture<Self, Result<ProjectLaunchProfiles, String>>;
+    
+    fn handle(&mut self, msg: GetLaunchProfiles, _ctx: &mut Context<Self>) -> Self::Result {
+        let actor = self.clone();
+        
+        Box::pin(async move {
+            Ok(actor.get_launch_profiles_internal(&msg.project_path).await)
+        }.into_actor(self))
+    }
+}
+
+impl Handler<GetActiveLaunchProfile> for ConfigurationActor {
+    type Result = ResponseActFuture<Self, Result<JuliaLaunchProfile, String>>;
+    
+    fn handle(&mut self, msg: GetActiveLaunchProfile, _ctx: &mut Context<Self>) -> Self::Result {
+        let actor = self.clone();
+        
+        Box::pin(async move {
+            let project_path = match msg.project_path {
+                Some(path) => Some(path),
+                None => actor.get_root_folder_internal().await?,
+            };
+            let profile = match project_path {
+                Some(path) => actor.get_launch_profiles_internal(&path).await.active_profile(),
+                None => JuliaLaunchProfile::default(),
+            };
+            debug!("ConfigurationActor: GetActiveLaunchProfile result: {}", profile.name);
+            Ok(profile)
+        }.into_actor(self))
+    }
+}
+
+impl Handler<SaveLaunchProfile> for ConfigurationActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+    
+    fn handle(&mut self, msg: SaveLaunchProfile, _ctx: &mut Context<Self>) -> Self::Result {
+        let actor = self.clone();
+        
+        Box::pin(async move {
+            msg.profile.validate()?;
+            let result = actor.update_launch_profiles(msg.project_path, |profiles| {
+                match profiles.profiles.iter_mut().find(|p| p.name == msg.profile.name) {
+                    Some(existing) => *existing = msg.profile,
+                    None => profiles.profiles.push(msg.profile),
+                }
+                Ok(())
+            }).await;
+            debug!("ConfigurationActor: SaveLaunchProfile result: {:?}", result);
+            result
+        }.into_actor(self))
+    }
+}
+
+impl Handler<DeleteLaunchProfile> for ConfigurationActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+    
+    fn handle(&mut self, msg: DeleteLaunchProfile, _ctx: &mut Context<Self>) -> Self::Result {
+        let actor = self.clone();
+        
+        Box::pin(async move {
+            actor.update_launch_profiles(msg.project_path, |profiles| {
+                let count = profiles.profiles.len();
+                profiles.profiles.retain(|p| p.name != msg.name);
+                if profiles.profiles.len() == count {
+                    return Err(format!("Launch profile '{}' not found", msg.name));
+                }
+                if profiles.active.as_deref() == Some(msg.name.as_str()) {
+                    profiles.active = None;
+                }
+                Ok(())
+            }).await
+        }.into_actor(self))
+    }
+}
+
+impl Handler<SetActiveLaunchProfile> for ConfigurationActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+    
+    fn handle(&mut self, msg: SetActiveLaunchProfile, _ctx: &mut Context<Self>) -> Self::Result {
+        let actor = self.clone();
+        
+        Box::pin(async move {
+            actor.update_launch_profiles(msg.project_path, |profiles| {
+                profiles.active = match msg.name {
+                    Some(name) if name == DEFAULT_LAUNCH_PROFILE && !profiles.profiles.iter().any(|p| p.name == name) => None,
+                    Some(name) if !profiles.profiles.iter().any(|p| p.name == name) => {
+                        return Err(format!("Launch profile '{}' not found", name));
+                    }
+                    name => name,
+                };
+                Ok(())
+            }).await
+        }.into_actor(self))
+    }
+}
+
+
 /// Get demo folder path from resource directory
 /// The demo folder is bundled as a Tauri resource at compile time
 fn get_demo_folder_path() -> Option<PathBuf> {
@@ -91,6 +91,71 @@ impl ExecutionActor {
             _ => Ok("Unknown message type".to_string()),
         }
     }
+
+    /// Execute notebook cells sequentially, emitting busy/done only around the whole batch
+    async fn execute_cells_batch(
+        cells: Vec<NotebookCellBatchItem>,
+        communication_actor: Addr<CommunicationActor>,
+        event_manager: EventService,
+    ) -> Result<Vec<(String, Result<String, String>)>, String> {
+        // Check if connected
+        let is_connected = communication_actor.send(IsConnected).await
+            .map_err(|e| format!("Failed to check connection status: {}", e))?
+            .map_err(|e| format!("Connection check failed: {}", e))?;
+        
+        if !is_connected {
+            return Err("Not connected to Julia process".to_string());
+        }
+        
+        // Emit backend-busy event at the start of batch
+        let batch_request_id = uuid::Uuid::new_v4().to_string();
+        if let Err(e) = event_manager.emit_backend_busy(&batch_request_id).await {
+            error!("[ExecutionActor] Failed to emit backend-busy event for batch: {}", e);
+        }
+        
+        // Execute all cells sequentially with suppressed busy events
+        let mut results = Vec::new();
+        for cell in cells {
+            let cell_id = cell.cell_id.clone();
+            let notebook_path = cell.notebook_path.clone();
+            let result = communication_actor.send(ExecuteCode {
+                code: cell.code,
+                execution_type: ExecutionType::NotebookCell { cell_id: cell.cell_id },
+                file_path: notebook_path,
+                suppress_busy_events: true, // Suppress individual busy events
+            }).await
+                .map_err(|e| format!("Failed to send execute code message: {}", e))?
+                .map_err(|e| format!("Code execution failed: {}", e));
+            
+            // Process the result
+            let cell_result = match &result {
+                Ok(message) => {
+                    match message {
+                        crate::messages::JuliaMessage::ExecutionComplete { result, error, success, .. } => {
+                            if *success {
+                                Ok(result.clone().unwrap_or_default())
+                            } else {
+                                Err(error.clone().unwrap_or_else(|| "Execution failed".to_string()))
+                            }
+                        }
+                        crate::messages::JuliaMessage::PlotData { .. } => Ok("Plot generated".to_string()),
+                        crate::messages::JuliaMessage::Error { message, .. } => Err(message.clone()),
+                        _ => Ok("Unknown message type".to_string()),
+                    }
+                }
+                Err(e) => Err(e.clone()),
+            };
+            
+            results.push((cell_id, cell_result));
+        }
+        
+        // Emit backend-done event at the end of batch
+        if let Err(e) = event_manager.emit_backend_done(&batch_request_id).await {
+            error!("[ExecutionActor] Failed to emit backend-done event for batch: {}", e);
+        }
+        
+        Ok(results)
+    }
 }
 
 impl Actor for ExecutionActor {
@@ -167,75 +232,72 @@ impl Handler<ExecuteNotebookCellsBatch> for ExecutionActor {
     type Result = ResponseActFuture<Self, Result<Vec<(String, Result<String, String>)>, String>>;
     
     fn handle(&mut self, msg: ExecuteNotebookCellsBatch, _ctx: &mut Context<Self>) -> Self::Result {
-        let cells = msg.cells;
         let communication_actor = self.communication_actor.clone();
         let event_manager = self.event_manager.clone();
         
         Box::pin(
             async move {
-                // Check if connected
-                let is_connected = communication_actor.send(IsConnected).await
-                    .map_err(|e| format!("Failed to check connection status: {}", e))?
-                    .map_err(|e| format!("Connection check failed: {}", e))?;
-                
-                if !is_connected {
-                    return Err("Not connected to Julia process".to_string());
-                }
-                
-                // Emit backend-busy event at the start of batch
-                let batch_request_id = uuid::Uuid::new_v4().to_string();
-                if let Err(e) = event_manager.emit_backend_busy(&batch_request_id).await {
-                    error!("[ExecutionActor] Failed to emit backend-busy event for batch: {}", e);
-                }
-                
-                // Execute all cells sequentially with suppressed busy events
-                let mut results = Vec::new();
-                for cell in cells {
-                    let cell_id = cell.cell_id.clone();
-                    let notebook_path = cell.notebook_path.clone();
-                    let result = communication_actor.send(ExecuteCode {
-                        code: cell.code,
-                        execution_type: ExecutionType::NotebookCell { cell_id: cell.cell_id },
-                        file_path: notebook_path,
-                        suppress_busy_events: true, // Suppress individual busy events
-                    }).await
-                        .map_err(|e| format!("Failed to send execute code message: {}", e))?
-                        .map_err(|e| format!("Code execution failed: {}", e));
-                    
-                    // Process the result
-                    let cell_result = match &result {
-                        Ok(message) => {
-                            match message {
-                                crate::messages::JuliaMessage::ExecutionComplete { result, error, success, .. } => {
-                                    if *success {
-                                        Ok(result.clone().unwrap_or_default())
-                                    } else {
-                                        Err(error.clone().unwrap_or_else(|| "Execution failed".to_string()))
-                                    }
-                                }
-                                crate::messages::JuliaMessage::PlotData { .. } => Ok("Plot generated".to_string()),
-                                crate::messages::JuliaMessage::Error { message, .. } => Err(message.clone()),
-                                _ => Ok("Unknown message type".to_string()),
-                            }
-                        }
-                        Err(e) => Err(e.clone()),
-                    };
-                    
-                    results.push((cell_id, cell_result));
-                }
+                Self::execute_cells_batch(msg.cells, communication_actor, event_manager).await
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<AnalyzeNotebookDependencies> for ExecutionActor {
+    type Result = ResponseActFuture<Self, Result<crate::types::CellDependencyGraph, String>>;
+    
+    fn handle(&mut self, msg: AnalyzeNotebookDependencies, _ctx: &mut Context<Self>) -> Self::Result {
+        Box::pin(
+            async move { notebook_dependency_graph(&msg.cells).await }
+                .into_actor(self)
+        )
+    }
+}
+
+impl Handler<ExecuteNotebookCellsWithDependents> for ExecutionActor {
+    type Result = ResponseActFuture<Self, Result<Vec<(String, Result<String, String>)>, String>>;
+    
+    fn handle(&mut self, msg: ExecuteNotebookCellsWithDependents, _ctx: &mut Context<Self>) -> Self::Result {
+        let communication_actor = self.communication_actor.clone();
+        let event_manager = self.event_manager.clone();
+        
+        Box::pin(
+            async move {
+                let graph = notebook_dependency_graph(&msg.cells).await?;
+                let order = graph.execution_order(&msg.changed_cell_ids)?;
+                debug!("[ExecutionActor] Re-executing {} notebook cells in dependency order: {:?}", order.len(), order);
                 
-                // Emit backend-done event at the end of batch
-                if let Err(e) = event_manager.emit_backend_done(&batch_request_id).await {
-                    error!("[ExecutionActor] Failed to emit backend-done event for batch: {}", e);
-                }
+                let mut cells_by_id: std::collections::HashMap<String, NotebookCellBatchItem> = msg.cells
+                    .into_iter()
+                    .map(|cell| (cell.cell_id.clone(), cell))
+                    .collect();
+                let cells = order
+                    .iter()
+                    .filter_map(|cell_id| cells_by_id.remove(cell_id))
+                    .collect();
                 
-                Ok(results)
+                Self::execute_cells_batch(cells, communication_actor, event_manager).await
             }
             .into_actor(self)
         )
     }
 }
 
+/// Build the dependency graph of a notebook from its cells
+///
+/// Parsing every cell is CPU-bound, so it runs on the blocking pool instead of the actor's thread.
+async fn notebook_dependency_graph(cells: &[NotebookCellBatchItem]) -> Result<crate::types::CellDependencyGraph, String> {
+    let sources: Vec<(String, String)> = cells
+        .iter()
+        .map(|cell| (cell.cell_id.clone(), cell.code.clone()))
+        .collect();
+    tokio::task::spawn_blocking(move || crate::types::CellDependencyGraph::analyze(&sources))
+        .await
+        .map_err(|e| format!("Notebook dependency analysis task failed: {}", e))?
+        .map_err(|e| format!("Failed to analyze notebook cell dependencies: {}", e))
+}
+
 
 impl Handler<ExecuteFile> for ExecutionActor {
     type Result = ResponseActFuture<Self, Result<String, String>>;
@@ -51,7 +51,7 @@ pub fn parse_csv_content(csv_text: &str) -> Result<Value, Box<dyn std::error::Er
     }))
 }
 
-fn calculate_column_widths(headers: &[String], rows: &[Vec<Value>]) -> Vec<u32> {
+pub(super) fn calculate_column_widths(headers: &[String], rows: &[Vec<Value>]) -> Vec<u32> {
     let num_columns = headers.len();
     if num_columns == 0 {
         return Vec::new();
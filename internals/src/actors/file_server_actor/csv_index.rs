@@ -0,0 +1,771 @@
+//! Paged access to large delimited text files.
+//!
+//! A [`CsvIndex`] is built once per file with a single streaming pass. It records the byte
+//! offset of every `INDEX_STRIDE`-th row, so any window of rows can be read by seeking to the
+//! nearest checkpoint instead of re-parsing the file. Sorted and filtered views keep the exact
+//! offsets of their matching rows and are cached on the index.
+
+use csv::{ByteRecord, Position, Reader, ReaderBuilder};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use super::csv::calculate_column_widths;
+
+/// Number of leading bytes used to detect encoding, delimiter and quoting
+const SNIFF_BYTES: usize = 64 * 1024;
+/// A checkpoint offset is stored every `INDEX_STRIDE` rows
+const INDEX_STRIDE: u64 = 256;
+/// Number of data rows sampled for type inference and column widths
+const TYPE_SAMPLE_ROWS: usize = 1000;
+/// Maximum number of sorted/filtered views cached per file
+const MAX_CACHED_VIEWS: usize = 8;
+const DELIMITER_CANDIDATES: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+pub const DEFAULT_PAGE_SIZE: usize = 200;
+pub const MAX_PAGE_SIZE: usize = 10_000;
+
+/// Column type inferred from a sample of the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Integer,
+    Float,
+    Boolean,
+    Date,
+    String,
+}
+
+impl ColumnType {
+    fn is_numeric(self) -> bool {
+        matches!(self, ColumnType::Integer | ColumnType::Float)
+    }
+}
+
+/// Comparison applied by a [`ColumnFilter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+    StartsWith,
+    IsEmpty,
+    NotEmpty,
+}
+
+/// A simple column filter, e.g. `{"column": "age", "op": "gt", "value": "30"}`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ColumnFilter {
+    pub column: String,
+    pub op: FilterOp,
+    #[serde(default)]
+    pub value: String,
+}
+
+/// Sort and filter settings identifying a view of the file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViewSpec {
+    pub sort_column: Option<String>,
+    pub descending: bool,
+    pub filters: Vec<ColumnFilter>,
+}
+
+impl ViewSpec {
+    fn is_identity(&self) -> bool {
+        self.sort_column.is_none() && self.filters.is_empty()
+    }
+}
+
+/// Delimiter and quote character detected for a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self { delimiter: b',', quote: b'"' }
+    }
+}
+
+/// One window of rows, in the shape returned by the table endpoints
+#[derive(Debug, Serialize)]
+pub struct CsvPage {
+    pub headers: Vec<String>,
+    pub column_types: Vec<ColumnType>,
+    pub column_widths: Vec<u32>,
+    pub rows: Vec<Vec<Value>>,
+    pub offset: u64,
+    pub total_rows: u64,
+    pub filtered_rows: u64,
+    pub delimiter: String,
+    pub quote: String,
+    pub encoding: String,
+    pub has_header: bool,
+}
+
+#[derive(Debug)]
+struct CachedView {
+    spec: ViewSpec,
+    offsets: Arc<Vec<u64>>,
+}
+
+/// Row-offset index over a CSV/TSV file
+#[derive(Debug)]
+pub struct CsvIndex {
+    source_len: u64,
+    source_modified: Option<SystemTime>,
+    /// File rows are read from: the source itself, or a UTF-8 transcoded copy
+    data_path: PathBuf,
+    _transcoded: Option<tempfile::NamedTempFile>,
+    encoding: &'static Encoding,
+    field_encoding: &'static Encoding,
+    dialect: CsvDialect,
+    has_header: bool,
+    headers: Vec<String>,
+    column_types: Vec<ColumnType>,
+    column_widths: Vec<u32>,
+    /// Byte offset of the first data row
+    data_start: u64,
+    /// Byte offset of every `INDEX_STRIDE`-th data row
+    checkpoints: Vec<u64>,
+    total_rows: u64,
+    views: Mutex<Vec<CachedView>>,
+}
+
+impl CsvIndex {
+    /// Scan `path` once, detecting its encoding and dialect and recording row checkpoints
+    pub fn build(path: &Path, has_header: bool) -> Result<Self, String> {
+        let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read metadata: {}", e))?;
+        let sample = read_sample(path)?;
+        let (encoding, bom_len) = detect_encoding(&sample);
+
+        // Multi-byte encodings that are not ASCII compatible cannot be split on delimiter
+        // bytes, so they are transcoded to a temporary UTF-8 copy first
+        let (data_path, transcoded, field_encoding, content_start) = if encoding == UTF_16LE || encoding == UTF_16BE {
+            let copy = transcode_to_utf8(path, encoding)?;
+            (copy.path().to_path_buf(), Some(copy), UTF_8, 0)
+        } else {
+            (path.to_path_buf(), None, encoding, bom_len as u64)
+        };
+
+        let (sample_text, _, _) = encoding.decode(&sample);
+        let tsv_hint = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("tsv") || ext.eq_ignore_ascii_case("tab"))
+            .unwrap_or(false);
+        let dialect = detect_dialect(&sample_text, tsv_hint);
+
+        let mut reader = open_reader(&data_path, dialect, content_start)?;
+        let mut record = ByteRecord::new();
+
+        let mut headers = Vec::new();
+        let mut data_start = content_start;
+        if has_header {
+            if read_record(&mut reader, &mut record)? {
+                headers = record.iter().map(|field| decode_field(field_encoding, field)).collect();
+            }
+            data_start = reader.position().byte();
+        }
+
+        let mut checkpoints = Vec::new();
+        let mut sample_rows: Vec<Vec<String>> = Vec::new();
+        let mut total_rows = 0u64;
+        let mut max_columns = headers.len();
+        loop {
+            let offset = reader.position().byte();
+            if !read_record(&mut reader, &mut record)? {
+                break;
+            }
+            if total_rows.is_multiple_of(INDEX_STRIDE) {
+                checkpoints.push(offset);
+            }
+            if sample_rows.len() < TYPE_SAMPLE_ROWS {
+                max_columns = max_columns.max(record.len());
+                sample_rows.push(record.iter().map(|field| decode_field(field_encoding, field)).collect());
+            }
+            total_rows += 1;
+        }
+
+        // Name any columns that only appear in data rows
+        for column in headers.len()..max_columns {
+            headers.push(format!("Column{}", column + 1));
+        }
+
+        let column_types: Vec<ColumnType> = (0..headers.len())
+            .map(|column| infer_column_type(sample_rows.iter().filter_map(|row| row.get(column).map(String::as_str))))
+            .collect();
+        let typed_sample: Vec<Vec<Value>> = sample_rows
+            .iter()
+            .map(|row| typed_row(row, &column_types))
+            .collect();
+        let column_widths = calculate_column_widths(&headers, &typed_sample);
+
+        Ok(Self {
+            source_len: metadata.len(),
+            source_modified: metadata.modified().ok(),
+            data_path,
+            _transcoded: transcoded,
+            encoding,
+            field_encoding,
+            dialect,
+            has_header,
+            headers,
+            column_types,
+            column_widths,
+            data_start,
+            checkpoints,
+            total_rows,
+            views: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Whether the index still describes the file at `path`
+    pub fn is_current(&self, path: &Path, has_header: bool) -> bool {
+        if self.has_header != has_header {
+            return false;
+        }
+        match std::fs::metadata(path) {
+            Ok(metadata) => metadata.len() == self.source_len && metadata.modified().ok() == self.source_modified,
+            Err(_) => false,
+        }
+    }
+
+    /// Read `limit` rows starting at `offset` within the view described by `spec`
+    pub fn page(&self, spec: &ViewSpec, offset: u64, limit: usize) -> Result<CsvPage, String> {
+        let mut reader = open_reader(&self.data_path, self.dialect, self.data_start)?;
+        let mut record = ByteRecord::new();
+        let mut rows = Vec::new();
+
+        let filtered_rows = if spec.is_identity() {
+            if offset < self.total_rows {
+                let checkpoint = (offset / INDEX_STRIDE) as usize;
+                seek(&mut reader, self.checkpoints[checkpoint])?;
+                for _ in 0..offset % INDEX_STRIDE {
+                    read_record(&mut reader, &mut record)?;
+                }
+                while rows.len() < limit && read_record(&mut reader, &mut record)? {
+                    rows.push(self.typed_record(&record));
+                }
+            }
+            self.total_rows
+        } else {
+            let offsets = self.view_offsets(spec)?;
+            for &row_offset in offsets.iter().skip(offset as usize).take(limit) {
+                seek(&mut reader, row_offset)?;
+                if read_record(&mut reader, &mut record)? {
+                    rows.push(self.typed_record(&record));
+                }
+            }
+            offsets.len() as u64
+        };
+
+        Ok(CsvPage {
+            headers: self.headers.clone(),
+            column_types: self.column_types.clone(),
+            column_widths: self.column_widths.clone(),
+            rows,
+            offset,
+            total_rows: self.total_rows,
+            filtered_rows,
+            delimiter: (self.dialect.delimiter as char).to_string(),
+            quote: (self.dialect.quote as char).to_string(),
+            encoding: self.encoding.name().to_string(),
+            has_header: self.has_header,
+        })
+    }
+
+    fn column_index(&self, name: &str) -> Result<usize, String> {
+        self.headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| format!("Unknown column: {}", name))
+    }
+
+    fn decode(&self, field: &[u8]) -> String {
+        decode_field(self.field_encoding, field)
+    }
+
+    fn typed_record(&self, record: &ByteRecord) -> Vec<Value> {
+        let fields: Vec<String> = record.iter().map(|field| self.decode(field)).collect();
+        typed_row(&fields, &self.column_types)
+    }
+
+    /// Offsets of the rows matching `spec`, in display order
+    fn view_offsets(&self, spec: &ViewSpec) -> Result<Arc<Vec<u64>>, String> {
+        if let Some(view) = self.views.lock().unwrap().iter().find(|view| &view.spec == spec) {
+            return Ok(view.offsets.clone());
+        }
+
+        let sort_column = spec.sort_column.as_deref().map(|name| self.column_index(name)).transpose()?;
+        let filters = spec
+            .filters
+            .iter()
+            .map(|filter| Ok((self.column_index(&filter.column)?, filter)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut reader = open_reader(&self.data_path, self.dialect, self.data_start)?;
+        let mut record = ByteRecord::new();
+        let mut matches: Vec<(SortKey, u64)> = Vec::new();
+        loop {
+            let offset = reader.position().byte();
+            if !read_record(&mut reader, &mut record)? {
+                break;
+            }
+            let keep = filters.iter().all(|(column, filter)| {
+                let value = record.get(*column).map(|field| self.decode(field)).unwrap_or_default();
+                filter_matches(filter, &value, self.column_types[*column])
+            });
+            if !keep {
+                continue;
+            }
+            let key = match sort_column {
+                Some(column) => {
+                    let value = record.get(column).map(|field| self.decode(field)).unwrap_or_default();
+                    SortKey::new(&value, self.column_types[column])
+                }
+                None => SortKey::Null,
+            };
+            matches.push((key, offset));
+        }
+
+        if sort_column.is_some() {
+            // Stable sort keeps file order among equal keys; empty cells always go last
+            matches.sort_by(|(a, _), (b, _)| match (a, b) {
+                (SortKey::Null, SortKey::Null) => Ordering::Equal,
+                (SortKey::Null, _) => Ordering::Greater,
+                (_, SortKey::Null) => Ordering::Less,
+                _ if spec.descending => b.cmp_value(a),
+                _ => a.cmp_value(b),
+            });
+        }
+
+        let offsets = Arc::new(matches.into_iter().map(|(_, offset)| offset).collect::<Vec<_>>());
+        let mut views = self.views.lock().unwrap();
+        if views.len() >= MAX_CACHED_VIEWS {
+            views.remove(0);
+        }
+        views.push(CachedView { spec: spec.clone(), offsets: offsets.clone() });
+        Ok(offsets)
+    }
+}
+
+enum SortKey {
+    Null,
+    Number(f64),
+    Text(String),
+}
+
+impl SortKey {
+    fn new(value: &str, column_type: ColumnType) -> Self {
+        let value = value.trim();
+        if value.is_empty() {
+            return SortKey::Null;
+        }
+        if column_type.is_numeric() {
+            if let Ok(number) = value.parse::<f64>() {
+                return SortKey::Number(number);
+            }
+        }
+        SortKey::Text(value.to_string())
+    }
+
+    fn cmp_value(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            // Numbers sort before stray text values in numeric columns
+            (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+            (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+fn filter_matches(filter: &ColumnFilter, value: &str, column_type: ColumnType) -> bool {
+    let value = value.trim();
+    let target = filter.value.trim();
+    let ordering = || {
+        if column_type.is_numeric() {
+            if let (Ok(a), Ok(b)) = (value.parse::<f64>(), target.parse::<f64>()) {
+                return a.partial_cmp(&b);
+            }
+        }
+        Some(value.cmp(target))
+    };
+    match filter.op {
+        FilterOp::Eq => ordering() == Some(Ordering::Equal),
+        FilterOp::Ne => ordering() != Some(Ordering::Equal),
+        FilterOp::Gt => !value.is_empty() && ordering() == Some(Ordering::Greater),
+        FilterOp::Ge => !value.is_empty() && matches!(ordering(), Some(Ordering::Greater | Ordering::Equal)),
+        FilterOp::Lt => !value.is_empty() && ordering() == Some(Ordering::Less),
+        FilterOp::Le => !value.is_empty() && matches!(ordering(), Some(Ordering::Less | Ordering::Equal)),
+        FilterOp::Contains => value.to_lowercase().contains(&target.to_lowercase()),
+        FilterOp::StartsWith => value.to_lowercase().starts_with(&target.to_lowercase()),
+        FilterOp::IsEmpty => value.is_empty(),
+        FilterOp::NotEmpty => !value.is_empty(),
+    }
+}
+
+fn read_sample(path: &Path) -> Result<Vec<u8>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut sample = Vec::with_capacity(SNIFF_BYTES);
+    file.take(SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(sample)
+}
+
+/// Detect the text encoding from a BOM, UTF-16 zero-byte patterns or UTF-8 validity,
+/// falling back to Windows-1252. Returns the encoding and the BOM length.
+fn detect_encoding(sample: &[u8]) -> (&'static Encoding, usize) {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(sample) {
+        return (encoding, bom_len);
+    }
+
+    if sample.len() >= 4 {
+        let even_zeros = sample.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_zeros = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+        let half = sample.len() / 2;
+        if odd_zeros > half / 2 && even_zeros == 0 {
+            return (UTF_16LE, 0);
+        }
+        if even_zeros > half / 2 && odd_zeros == 0 {
+            return (UTF_16BE, 0);
+        }
+    }
+
+    match std::str::from_utf8(sample) {
+        Ok(_) => (UTF_8, 0),
+        // The sample may end in the middle of a multi-byte sequence
+        Err(e) if e.error_len().is_none() => (UTF_8, 0),
+        Err(_) => (WINDOWS_1252, 0),
+    }
+}
+
+/// Detect the quote character and the delimiter giving the most consistent field count
+fn detect_dialect(sample: &str, tsv_hint: bool) -> CsvDialect {
+    let quote = detect_quote(sample);
+
+    // Drop a trailing partial line so it does not skew field counts
+    let sample = match sample.rfind('\n') {
+        Some(end) if sample.len() >= SNIFF_BYTES / 2 => &sample[..end],
+        _ => sample,
+    };
+
+    let mut best: Option<(u8, usize, usize)> = None;
+    for delimiter in DELIMITER_CANDIDATES {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .quote(quote)
+            .from_reader(sample.as_bytes());
+        let counts: Vec<usize> = reader.byte_records().take(100).filter_map(Result::ok).map(|r| r.len()).collect();
+
+        let mut frequencies: Vec<(usize, usize)> = Vec::new();
+        for count in counts {
+            match frequencies.iter_mut().find(|(fields, _)| *fields == count) {
+                Some((_, frequency)) => *frequency += 1,
+                None => frequencies.push((count, 1)),
+            }
+        }
+        let Some(&(fields, consistent)) = frequencies.iter().max_by_key(|(fields, frequency)| (*frequency, *fields)) else {
+            continue;
+        };
+        if fields < 2 {
+            continue;
+        }
+        if tsv_hint && delimiter == b'\t' {
+            return CsvDialect { delimiter, quote };
+        }
+        let better = match best {
+            Some((_, best_fields, best_consistent)) => (consistent, fields) > (best_consistent, best_fields),
+            None => true,
+        };
+        if better {
+            best = Some((delimiter, fields, consistent));
+        }
+    }
+
+    CsvDialect {
+        delimiter: best.map(|(delimiter, _, _)| delimiter).unwrap_or(b','),
+        quote,
+    }
+}
+
+/// Pick `'` over `"` only when it is the one opening fields in the sample
+fn detect_quote(sample: &str) -> u8 {
+    let mut double = 0usize;
+    let mut single = 0usize;
+    let mut previous = '\n';
+    for ch in sample.chars() {
+        let at_field_start = previous == '\n' || (previous.is_ascii() && DELIMITER_CANDIDATES.contains(&(previous as u8)));
+        if at_field_start {
+            match ch {
+                '"' => double += 1,
+                '\'' => single += 1,
+                _ => {}
+            }
+        }
+        previous = ch;
+    }
+    if single > double {
+        b'\''
+    } else {
+        b'"'
+    }
+}
+
+fn transcode_to_utf8(path: &Path, encoding: &'static Encoding) -> Result<tempfile::NamedTempFile, String> {
+    let mut input = BufReader::new(File::open(path).map_err(|e| format!("Failed to open file: {}", e))?);
+    let mut output = tempfile::Builder::new()
+        .prefix("compute42-csv-")
+        .suffix(".csv")
+        .tempfile()
+        .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+
+    let mut decoder = encoding.new_decoder_with_bom_removal();
+    let mut buffer = vec![0u8; SNIFF_BYTES];
+    let mut text = String::new();
+    loop {
+        let read = input.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+        let last = read == 0;
+        text.clear();
+        text.reserve(decoder.max_utf8_buffer_length(read).unwrap_or(read * 3 + 4));
+        let _ = decoder.decode_to_string(&buffer[..read], &mut text, last);
+        output
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write temporary file: {}", e))?;
+        if last {
+            break;
+        }
+    }
+    output.flush().map_err(|e| format!("Failed to write temporary file: {}", e))?;
+    Ok(output)
+}
+
+fn open_reader(path: &Path, dialect: CsvDialect, start: u64) -> Result<Reader<BufReader<File>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .from_reader(BufReader::new(file));
+    seek(&mut reader, start)?;
+    Ok(reader)
+}
+
+fn seek(reader: &mut Reader<BufReader<File>>, byte: u64) -> Result<(), String> {
+    let mut position = Position::new();
+    position.set_byte(byte);
+    reader.seek(position).map_err(|e| format!("Failed to seek: {}", e))
+}
+
+fn read_record(reader: &mut Reader<BufReader<File>>, record: &mut ByteRecord) -> Result<bool, String> {
+    reader.read_byte_record(record).map_err(|e| format!("Failed to read row: {}", e))
+}
+
+fn decode_field(encoding: &'static Encoding, field: &[u8]) -> String {
+    if encoding == UTF_8 {
+        String::from_utf8_lossy(field).into_owned()
+    } else {
+        encoding.decode_without_bom_handling(field).0.into_owned()
+    }
+}
+
+/// Leading zeros usually mean identifiers such as zip codes, which are kept as text
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
+
+fn is_integer(value: &str) -> bool {
+    !has_leading_zero(value) && value.parse::<i64>().is_ok()
+}
+
+fn is_float(value: &str) -> bool {
+    !has_leading_zero(value) && value.chars().any(|c| c.is_ascii_digit()) && value.parse::<f64>().is_ok()
+}
+
+fn is_boolean(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+}
+
+fn is_date(value: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+fn infer_column_type<'a>(values: impl Iterator<Item = &'a str>) -> ColumnType {
+    let (mut integer, mut float, mut boolean, mut date) = (true, true, true, true);
+    let mut any = false;
+    for value in values.map(str::trim).filter(|value| !value.is_empty()) {
+        any = true;
+        integer &= is_integer(value);
+        float &= is_float(value);
+        boolean &= is_boolean(value);
+        date &= is_date(value);
+        if !(integer || float || boolean || date) {
+            return ColumnType::String;
+        }
+    }
+    match (any, integer, float, boolean, date) {
+        (false, ..) => ColumnType::String,
+        (_, true, ..) => ColumnType::Integer,
+        (_, _, true, ..) => ColumnType::Float,
+        (_, _, _, true, _) => ColumnType::Boolean,
+        (_, _, _, _, true) => ColumnType::Date,
+        _ => ColumnType::String,
+    }
+}
+
+fn typed_value(value: &str, column_type: ColumnType) -> Value {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match column_type {
+        ColumnType::Integer => trimmed.parse::<i64>().map(Value::from).unwrap_or_else(|_| Value::String(value.to_string())),
+        ColumnType::Float => trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(value.to_string())),
+        ColumnType::Boolean => Value::Bool(trimmed.eq_ignore_ascii_case("true")),
+        ColumnType::Date | ColumnType::String => Value::String(value.to_string()),
+    }
+}
+
+fn typed_row(fields: &[String], column_types: &[ColumnType]) -> Vec<Value> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(column, field)| typed_value(field, column_types.get(column).copied().unwrap_or(ColumnType::String)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(dir: &tempfile::TempDir, name: &str, content: &[u8]) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_detects_delimiters_and_quotes() {
+        assert_eq!(detect_dialect("a\tb\tc\n1\t2\t3\n", false).delimiter, b'\t');
+        assert_eq!(detect_dialect("a;b\n1,5;2,5\n3,0;4,0\n", false).delimiter, b';');
+        assert_eq!(detect_dialect("a|b|c\nx|y|z\n", false).delimiter, b'|');
+        assert_eq!(detect_dialect("name,city\n\"Doe, J\",Paris\n", false), CsvDialect::default());
+        assert_eq!(detect_dialect("name,city\n'Doe, J',Paris\n'Roe, K',Rome\n", false).quote, b'\'');
+    }
+
+    #[test]
+    fn test_detects_encodings() {
+        assert_eq!(detect_encoding(b"\xEF\xBB\xBFa,b"), (UTF_8, 3));
+        assert_eq!(detect_encoding("a,é".as_bytes()).0, UTF_8);
+        assert_eq!(detect_encoding(b"a,\xE9t\xE9").0, WINDOWS_1252);
+        assert_eq!(detect_encoding(b"a\0,\0b\0").0, UTF_16LE);
+    }
+
+    #[test]
+    fn test_infers_column_types() {
+        assert_eq!(infer_column_type(["1", "-2", ""].into_iter()), ColumnType::Integer);
+        assert_eq!(infer_column_type(["1", "2.5"].into_iter()), ColumnType::Float);
+        assert_eq!(infer_column_type(["true", "FALSE"].into_iter()), ColumnType::Boolean);
+        assert_eq!(infer_column_type(["2024-01-31"].into_iter()), ColumnType::Date);
+        assert_eq!(infer_column_type(["00123", "00456"].into_iter()), ColumnType::String);
+        assert_eq!(infer_column_type(["1", "x"].into_iter()), ColumnType::String);
+    }
+
+    #[test]
+    fn test_pages_across_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut content = String::from("id,label\n");
+        for i in 0..1000 {
+            content.push_str(&format!("{},\"row, {}\"\n", i, i));
+        }
+        let path = write_file(&dir, "rows.csv", content.as_bytes());
+        let index = CsvIndex::build(&path, true).unwrap();
+
+        assert_eq!(index.total_rows, 1000);
+        assert_eq!(index.column_types, vec![ColumnType::Integer, ColumnType::String]);
+
+        let page = index.page(&ViewSpec::default(), 510, 3).unwrap();
+        assert_eq!(page.rows.len(), 3);
+        assert_eq!(page.rows[0], vec![Value::from(510), Value::from("row, 510")]);
+        assert_eq!(page.rows[2][0], Value::from(512));
+
+        let tail = index.page(&ViewSpec::default(), 998, 10).unwrap();
+        assert_eq!(tail.rows.len(), 2);
+        assert!(index.page(&ViewSpec::default(), 5000, 10).unwrap().rows.is_empty());
+    }
+
+    #[test]
+    fn test_sorts_and_filters_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(&dir, "people.csv", b"name;age\nAda;36\nBob;\nCy;9\nDee;41\n");
+        let index = CsvIndex::build(&path, true).unwrap();
+
+        let sorted = ViewSpec {
+            sort_column: Some("age".to_string()),
+            descending: true,
+            filters: Vec::new(),
+        };
+        let page = index.page(&sorted, 0, 10).unwrap();
+        let names: Vec<&str> = page.rows.iter().filter_map(|row| row[0].as_str()).collect();
+        assert_eq!(names, vec!["Dee", "Ada", "Cy", "Bob"]);
+
+        let filtered = ViewSpec {
+            sort_column: Some("age".to_string()),
+            descending: false,
+            filters: vec![ColumnFilter { column: "age".to_string(), op: FilterOp::Gt, value: "10".to_string() }],
+        };
+        let page = index.page(&filtered, 0, 10).unwrap();
+        assert_eq!(page.filtered_rows, 2);
+        assert_eq!(page.total_rows, 4);
+        assert_eq!(page.rows[0][0], Value::from("Ada"));
+
+        let unknown = ViewSpec {
+            sort_column: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(index.page(&unknown, 0, 10).is_err());
+    }
+
+    #[test]
+    fn test_reads_utf16_and_windows_1252_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "city\tpop\nZürich\t421878\n".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        let path = write_file(&dir, "cities.tsv", &utf16);
+        let page = CsvIndex::build(&path, true).unwrap().page(&ViewSpec::default(), 0, 10).unwrap();
+        assert_eq!(page.encoding, "UTF-16LE");
+        assert_eq!(page.delimiter, "\t");
+        assert_eq!(page.rows, vec![vec![Value::from("Zürich"), Value::from(421878)]]);
+
+        let path = write_file(&dir, "latin.csv", b"name,score\nJos\xE9,1.5\n");
+        let page = CsvIndex::build(&path, true).unwrap().page(&ViewSpec::default(), 0, 10).unwrap();
+        assert_eq!(page.encoding, "windows-1252");
+        assert_eq!(page.rows[0][0], Value::from("José"));
+    }
+}
@@ -1,15 +1,46 @@
 use axum::{
-    extract::{Path as AxumPath, State},
+    extract::{Path as AxumPath, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
 use log::{debug, error};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use super::server::FileServerState;
 use super::csv;
+use super::csv_index::{ColumnFilter, CsvIndex, ViewSpec, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use super::table::TableIndex;
+
+/// Maximum number of CSV and table indexes each kept in memory by the file server
+const MAX_CACHED_INDEXES: usize = 8;
+
+/// Query parameters for the paged CSV endpoint
+#[derive(Debug, Deserialize)]
+pub struct CsvPageQuery {
+    #[serde(default)]
+    pub offset: u64,
+    pub limit: Option<usize>,
+    /// Column name to sort by
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub desc: bool,
+    /// JSON array of column filters, e.g. `[{"column":"age","op":"gt","value":"30"}]`
+    pub filters: Option<String>,
+    /// Treat the first row as a header (defaults to true)
+    pub header: Option<bool>,
+}
+
+/// Query parameters for the Parquet/Arrow table endpoint
+#[derive(Debug, Deserialize)]
+pub struct TablePageQuery {
+    #[serde(default)]
+    pub offset: u64,
+    pub limit: Option<usize>,
+}
 
 pub async fn index_handler() -> impl IntoResponse {
     (
@@ -217,3 +248,202 @@ pub async fn parse_csv_handler(
     }
 }
 
+
+/// Resolve a path requested by the frontend to an existing file inside the base directory
+async fn resolve_project_file(
+    state: &Arc<Mutex<FileServerState>>,
+    requested_path: &str,
+) -> Result<PathBuf, axum::response::Response> {
+    let base_path = match state.lock().await.base_path.clone() {
+        Some(path) => path,
+        None => {
+            error!("File server: No base path configured");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "File server not properly configured",
+            )
+                .into_response());
+        }
+    };
+
+    let full_path = Path::new(&base_path).join(requested_path);
+
+    // Security check: ensure the requested path is within the base directory
+    if !full_path.starts_with(PathBuf::from(&base_path)) {
+        debug!("Attempted directory traversal attack: {}", requested_path);
+        return Err((StatusCode::FORBIDDEN, "Access denied").into_response());
+    }
+
+    if !full_path.is_file() {
+        error!("File server: File not found: {}", full_path.display());
+        return Err((StatusCode::NOT_FOUND, "File not found").into_response());
+    }
+
+    Ok(full_path)
+}
+
+/// Insert a freshly built index, evicting another one when the cache is full
+fn cache_index<T>(cache: &mut HashMap<PathBuf, Arc<T>>, path: PathBuf, index: Arc<T>) {
+    if cache.len() >= MAX_CACHED_INDEXES && !cache.contains_key(&path) {
+        if let Some(evicted) = cache.keys().next().cloned() {
+            cache.remove(&evicted);
+        }
+    }
+    cache.insert(path, index);
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> axum::response::Response {
+    (
+        status,
+        [("content-type", "application/json")],
+        serde_json::to_string(&body).unwrap(),
+    )
+        .into_response()
+}
+
+fn page_error_response(status: StatusCode, error: String, requested_path: &str) -> axum::response::Response {
+    json_response(
+        status,
+        serde_json::json!({
+            "success": false,
+            "error": error,
+            "file_path": requested_path
+        }),
+    )
+}
+
+/// Serve a window of rows from a CSV/TSV file without loading the whole file.
+///
+/// The first request for a file builds a row-offset index; later requests, including
+/// sorted and filtered ones, reuse it until the file changes on disk.
+pub async fn csv_page_handler(
+    State(state): State<Arc<Mutex<FileServerState>>>,
+    AxumPath(requested_path): AxumPath<String>,
+    Query(query): Query<CsvPageQuery>,
+) -> impl IntoResponse {
+    debug!("File server: Received paged CSV request for path: {} ({:?})", requested_path, query);
+
+    let full_path = match resolve_project_file(&state, &requested_path).await {
+        Ok(path) => path,
+        Err(response) => return response,
+    };
+
+    let filters: Vec<ColumnFilter> = match query.filters.as_deref().filter(|f| !f.trim().is_empty()) {
+        Some(filters) => match serde_json::from_str(filters) {
+            Ok(filters) => filters,
+            Err(e) => {
+                return page_error_response(StatusCode::BAD_REQUEST, format!("Invalid filters: {}", e), &requested_path);
+            }
+        },
+        None => Vec::new(),
+    };
+    let spec = ViewSpec {
+        sort_column: query.sort.filter(|column| !column.is_empty()),
+        descending: query.desc,
+        filters,
+    };
+    let has_header = query.header.unwrap_or(true);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+    let cached = state
+        .lock()
+        .await
+        .csv_indexes
+        .get(&full_path)
+        .cloned()
+        .filter(|index| index.is_current(&full_path, has_header));
+
+    let offset = query.offset;
+    let path = full_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let (index, built) = match cached {
+            Some(index) => (index, false),
+            None => (Arc::new(CsvIndex::build(&path, has_header)?), true),
+        };
+        let page = index.page(&spec, offset, limit)?;
+        Ok::<_, String>((index, built, page))
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("CSV task failed: {}", e)));
+
+    match result {
+        Ok((index, built, page)) => {
+            if built {
+                debug!("File server: Indexed {} rows of {}", page.total_rows, requested_path);
+                cache_index(&mut state.lock().await.csv_indexes, full_path, index);
+            }
+            json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                    "success": true,
+                    "data": page,
+                    "file_path": requested_path
+                }),
+            )
+        }
+        Err(e) => {
+            error!("Failed to page CSV file {}: {}", requested_path, e);
+            page_error_response(StatusCode::BAD_REQUEST, e, &requested_path)
+        }
+    }
+}
+
+/// Serve schema, row-group statistics and a window of rows from a Parquet or Arrow IPC file.
+///
+/// Only the footer (Parquet) or batch layout (Arrow) is read up front; each request then
+/// decodes just the row groups or batches overlapping the requested window.
+pub async fn table_page_handler(
+    State(state): State<Arc<Mutex<FileServerState>>>,
+    AxumPath(requested_path): AxumPath<String>,
+    Query(query): Query<TablePageQuery>,
+) -> impl IntoResponse {
+    debug!("File server: Received table request for path: {} ({:?})", requested_path, query);
+
+    let full_path = match resolve_project_file(&state, &requested_path).await {
+        Ok(path) => path,
+        Err(response) => return response,
+    };
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    let cached = state
+        .lock()
+        .await
+        .table_indexes
+        .get(&full_path)
+        .cloned()
+        .filter(|index| index.is_current(&full_path));
+
+    let offset = query.offset;
+    let path = full_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let (index, built) = match cached {
+            Some(index) => (index, false),
+            None => (Arc::new(TableIndex::build(&path)?), true),
+        };
+        let page = index.page(&path, offset, limit)?;
+        Ok::<_, String>((index, built, page))
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("Table task failed: {}", e)));
+
+    match result {
+        Ok((index, built, page)) => {
+            if built {
+                debug!("File server: Indexed {} rows of {}", page.total_rows, requested_path);
+                cache_index(&mut state.lock().await.table_indexes, full_path, index);
+            }
+            json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                    "success": true,
+                    "data": page,
+                    "file_path": requested_path
+                }),
+            )
+        }
+        Err(e) => {
+            error!("Failed to read table file {}: {}", requested_path, e);
+            page_error_response(StatusCode::BAD_REQUEST, e, &requested_path)
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod server;
 mod handlers;
 mod csv;
+mod csv_index;
+mod table;
 
 use actix::prelude::*;
 use std::sync::Arc;
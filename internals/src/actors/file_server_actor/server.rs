@@ -1,4 +1,6 @@
 use log::{debug, error};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::sync::Mutex;
@@ -8,13 +10,19 @@ use axum::{
 };
 use tower_http::cors::{CorsLayer, Any};
 
+use super::csv_index::CsvIndex;
 use super::handlers;
+use super::table::TableIndex;
 
 #[derive(Clone, Debug, Default)]
 pub struct FileServerState {
     pub base_path: Option<String>,
     pub server_port: Option<u16>,
     pub is_running: bool,
+    /// Row-offset indexes of recently paged CSV files, keyed by absolute path
+    pub csv_indexes: HashMap<PathBuf, Arc<CsvIndex>>,
+    /// Schema and row-group layout of recently viewed Parquet/Arrow files
+    pub table_indexes: HashMap<PathBuf, Arc<TableIndex>>,
 }
 
 /// Find an available port by actually binding to it (atomic operation)
@@ -75,6 +83,8 @@ pub async fn start_server_internal(
             .route("/", get(handlers::index_handler))
             .route("/files/*path", get(handlers::serve_file_handler))
             .route("/csv/*path", get(handlers::parse_csv_handler))
+            .route("/csv-page/*path", get(handlers::csv_page_handler))
+            .route("/table/*path", get(handlers::table_page_handler))
             .route("/health", get(handlers::health_check_handler))
             .layer(cors)
             .with_state(server_state.clone());
@@ -84,6 +94,8 @@ pub async fn start_server_internal(
         debug!("File server:   - GET / (index)");
         debug!("File server:   - GET /files/*path (serve files)");
         debug!("File server:   - GET /csv/*path (parse CSV)");
+        debug!("File server:   - GET /csv-page/*path (paged CSV/TSV rows)");
+        debug!("File server:   - GET /table/*path (Parquet/Arrow schema and rows)");
         debug!("File server: Base path: {}", base_path);
 
         // Start serving with proper error handling
@@ -112,6 +124,8 @@ pub async fn stop_server_internal(
     state_guard.is_running = false;
     state_guard.base_path = None;
     state_guard.server_port = None;
+    state_guard.csv_indexes.clear();
+    state_guard.table_indexes.clear();
 
     Ok(())
 }
@@ -0,0 +1,636 @@
+//! Paged access to Parquet and Arrow IPC/Feather files.
+//!
+//! A [`TableIndex`] holds the schema, row-group (or record batch) layout and a few sample
+//! rows of a file. Pages are read by decoding only the row groups or batches that overlap
+//! the requested window, and are returned in the same shape as the CSV viewer pages.
+
+use arrow_array::{Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_ipc::reader::{FileReader, StreamReader};
+use arrow_schema::{DataType, SchemaRef};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::LogicalType;
+use parquet::file::metadata::ParquetMetaData;
+use parquet::file::statistics::Statistics;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::csv::calculate_column_widths;
+use super::csv_index::ColumnType;
+
+/// Number of leading rows used to size columns
+const WIDTH_SAMPLE_ROWS: usize = 200;
+const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Columnar file formats served by the `/table` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableFormat {
+    Parquet,
+    /// Arrow IPC file format, which Feather v2 also uses
+    ArrowFile,
+    /// Arrow IPC streaming format
+    ArrowStream,
+}
+
+impl TableFormat {
+    /// Detect the format from the file's magic bytes, falling back to its extension
+    pub fn detect(path: &Path) -> Result<Self, String> {
+        let mut magic = [0u8; 6];
+        let read = File::open(path)
+            .and_then(|mut file| file.read(&mut magic))
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let magic = &magic[..read];
+
+        if magic.starts_with(PARQUET_MAGIC) {
+            return Ok(TableFormat::Parquet);
+        }
+        if magic.starts_with(ARROW_FILE_MAGIC) {
+            return Ok(TableFormat::ArrowFile);
+        }
+        // Streams start with a continuation marker or a message length
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "arrows" | "ipc" | "arrow" => Ok(TableFormat::ArrowStream),
+            "feather" if magic.starts_with(b"FEA1") => {
+                Err("Feather v1 files are not supported, re-save them as Feather v2 / Arrow IPC".to_string())
+            }
+            "feather" => Ok(TableFormat::ArrowStream),
+            _ => Err("Not a Parquet or Arrow IPC file".to_string()),
+        }
+    }
+}
+
+/// Schema information for one top-level column
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    /// Arrow data type, e.g. `Int64` or `Timestamp(Microsecond, Some("UTC"))`
+    pub data_type: String,
+    pub nullable: bool,
+    /// Parquet physical type, e.g. `INT64` or `BYTE_ARRAY`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_type: Option<String>,
+    /// Parquet logical type, e.g. `STRING` or `TIMESTAMP(MICROS, UTC)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logical_type: Option<String>,
+}
+
+/// Statistics for one column chunk of a row group
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnChunkStats {
+    pub column: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<i64>,
+    pub null_count: Option<u64>,
+    pub distinct_count: Option<u64>,
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+}
+
+/// A Parquet row group, or an Arrow record batch
+#[derive(Debug, Clone, Serialize)]
+pub struct RowGroupInfo {
+    pub index: usize,
+    pub first_row: u64,
+    pub num_rows: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_byte_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<i64>,
+    pub columns: Vec<ColumnChunkStats>,
+}
+
+/// One window of rows, in the same shape as the CSV viewer pages
+#[derive(Debug, Serialize)]
+pub struct TablePage {
+    pub headers: Vec<String>,
+    pub column_types: Vec<ColumnType>,
+    pub column_widths: Vec<u32>,
+    pub rows: Vec<Vec<Value>>,
+    pub offset: u64,
+    pub total_rows: u64,
+    pub filtered_rows: u64,
+    pub format: TableFormat,
+    pub schema: Vec<ColumnSchema>,
+    pub row_groups: Vec<RowGroupInfo>,
+}
+
+/// Schema and row-group layout of a Parquet or Arrow file
+#[derive(Debug)]
+pub struct TableIndex {
+    source_len: u64,
+    source_modified: Option<SystemTime>,
+    format: TableFormat,
+    headers: Vec<String>,
+    column_types: Vec<ColumnType>,
+    column_widths: Vec<u32>,
+    schema: Vec<ColumnSchema>,
+    row_groups: Vec<RowGroupInfo>,
+    total_rows: u64,
+}
+
+impl TableIndex {
+    /// Read the file's metadata. Parquet files only need their footer; Arrow files are
+    /// scanned one record batch at a time to learn the batch sizes.
+    pub fn build(path: &Path) -> Result<Self, String> {
+        let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read metadata: {}", e))?;
+        let format = TableFormat::detect(path)?;
+
+        let (arrow_schema, schema, row_groups) = match format {
+            TableFormat::Parquet => {
+                let builder = ParquetRecordBatchReaderBuilder::try_new(open(path)?)
+                    .map_err(|e| format!("Failed to read Parquet metadata: {}", e))?;
+                let arrow_schema = builder.schema().clone();
+                let schema = parquet_schema(&arrow_schema, builder.metadata());
+                let row_groups = parquet_row_groups(builder.metadata());
+                (arrow_schema, schema, row_groups)
+            }
+            TableFormat::ArrowFile | TableFormat::ArrowStream => {
+                let batches = arrow_batches(path, format)?;
+                let arrow_schema = batches.schema();
+                let mut row_groups = Vec::new();
+                let mut first_row = 0u64;
+                for (index, batch) in batches.enumerate() {
+                    let batch = batch?;
+                    let num_rows = batch.num_rows() as u64;
+                    row_groups.push(RowGroupInfo {
+                        index,
+                        first_row,
+                        num_rows,
+                        total_byte_size: Some(batch.get_array_memory_size() as i64),
+                        compressed_size: None,
+                        columns: arrow_schema
+                            .fields()
+                            .iter()
+                            .zip(batch.columns())
+                            .map(|(field, column)| ColumnChunkStats {
+                                column: field.name().clone(),
+                                compression: None,
+                                compressed_size: None,
+                                null_count: Some(column.null_count() as u64),
+                                distinct_count: None,
+                                min: None,
+                                max: None,
+                            })
+                            .collect(),
+                    });
+                    first_row += num_rows;
+                }
+                let schema = arrow_schema
+                    .fields()
+                    .iter()
+                    .map(|field| ColumnSchema {
+                        name: field.name().clone(),
+                        data_type: field.data_type().to_string(),
+                        nullable: field.is_nullable(),
+                        physical_type: None,
+                        logical_type: None,
+                    })
+                    .collect();
+                (arrow_schema, schema, row_groups)
+            }
+        };
+
+        let headers: Vec<String> = arrow_schema.fields().iter().map(|field| field.name().clone()).collect();
+        let column_types = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| column_type(field.data_type()))
+            .collect();
+        let total_rows = row_groups.iter().map(|group| group.num_rows).sum();
+
+        let mut index = Self {
+            source_len: metadata.len(),
+            source_modified: metadata.modified().ok(),
+            format,
+            headers,
+            column_types,
+            column_widths: Vec::new(),
+            schema,
+            row_groups,
+            total_rows,
+        };
+        let sample = index.read_rows(path, 0, WIDTH_SAMPLE_ROWS)?;
+        index.column_widths = calculate_column_widths(&index.headers, &sample);
+        Ok(index)
+    }
+
+    /// Whether the index still describes the file at `path`
+    pub fn is_current(&self, path: &Path) -> bool {
+        match std::fs::metadata(path) {
+            Ok(metadata) => metadata.len() == self.source_len && metadata.modified().ok() == self.source_modified,
+            Err(_) => false,
+        }
+    }
+
+    /// Read `limit` rows starting at `offset`
+    pub fn page(&self, path: &Path, offset: u64, limit: usize) -> Result<TablePage, String> {
+        Ok(TablePage {
+            headers: self.headers.clone(),
+            column_types: self.column_types.clone(),
+            column_widths: self.column_widths.clone(),
+            rows: self.read_rows(path, offset, limit)?,
+            offset,
+            total_rows: self.total_rows,
+            filtered_rows: self.total_rows,
+            format: self.format,
+            schema: self.schema.clone(),
+            row_groups: self.row_groups.clone(),
+        })
+    }
+
+    /// Row groups overlapping `[offset, offset + limit)`
+    fn overlapping_groups(&self, offset: u64, limit: usize) -> Vec<&RowGroupInfo> {
+        let end = offset.saturating_add(limit as u64);
+        self.row_groups
+            .iter()
+            .filter(|group| group.num_rows > 0 && group.first_row < end && group.first_row + group.num_rows > offset)
+            .collect()
+    }
+
+    fn read_rows(&self, path: &Path, offset: u64, limit: usize) -> Result<Vec<Vec<Value>>, String> {
+        let groups = self.overlapping_groups(offset, limit);
+        let Some(first) = groups.first() else {
+            return Ok(Vec::new());
+        };
+        let skip = (offset - first.first_row) as usize;
+
+        let mut rows = Vec::new();
+        match self.format {
+            TableFormat::Parquet => {
+                let reader = ParquetRecordBatchReaderBuilder::try_new(open(path)?)
+                    .map_err(|e| format!("Failed to read Parquet metadata: {}", e))?
+                    .with_row_groups(groups.iter().map(|group| group.index).collect())
+                    .with_offset(skip)
+                    .with_limit(limit)
+                    .with_batch_size(limit.max(1))
+                    .build()
+                    .map_err(|e| format!("Failed to read Parquet file: {}", e))?;
+                for batch in reader {
+                    let batch = batch.map_err(|e| format!("Failed to read Parquet rows: {}", e))?;
+                    append_rows(&batch, 0, limit - rows.len(), &mut rows)?;
+                }
+            }
+            TableFormat::ArrowFile => {
+                let mut reader = FileReader::try_new(open(path)?, None)
+                    .map_err(|e| format!("Failed to read Arrow file: {}", e))?;
+                reader
+                    .set_index(first.index)
+                    .map_err(|e| format!("Failed to read Arrow file: {}", e))?;
+                let mut skip = skip;
+                for batch in reader.take(groups.len()) {
+                    let batch = batch.map_err(|e| format!("Failed to read Arrow rows: {}", e))?;
+                    append_rows(&batch, skip, limit - rows.len(), &mut rows)?;
+                    skip = 0;
+                }
+            }
+            TableFormat::ArrowStream => {
+                // Streams have no footer, so earlier batches have to be read and dropped
+                let mut skip = skip;
+                for batch in arrow_batches(path, self.format)?.skip(first.index).take(groups.len()) {
+                    append_rows(&batch?, skip, limit - rows.len(), &mut rows)?;
+                    skip = 0;
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
+
+fn open(path: &Path) -> Result<File, String> {
+    File::open(path).map_err(|e| format!("Failed to open file: {}", e))
+}
+
+/// Record batches of an Arrow IPC file or stream
+enum ArrowBatches {
+    File(FileReader<BufReader<File>>),
+    Stream(StreamReader<BufReader<File>>),
+}
+
+impl ArrowBatches {
+    fn schema(&self) -> SchemaRef {
+        match self {
+            ArrowBatches::File(reader) => reader.schema(),
+            ArrowBatches::Stream(reader) => reader.schema(),
+        }
+    }
+}
+
+impl Iterator for ArrowBatches {
+    type Item = Result<RecordBatch, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self {
+            ArrowBatches::File(reader) => reader.next(),
+            ArrowBatches::Stream(reader) => reader.next(),
+        };
+        batch.map(|batch| batch.map_err(|e| format!("Failed to read Arrow batch: {}", e)))
+    }
+}
+
+fn arrow_batches(path: &Path, format: TableFormat) -> Result<ArrowBatches, String> {
+    let reader = BufReader::new(open(path)?);
+    match format {
+        TableFormat::ArrowFile => FileReader::try_new(reader, None)
+            .map(ArrowBatches::File)
+            .map_err(|e| format!("Failed to read Arrow file: {}", e)),
+        _ => StreamReader::try_new(reader, None)
+            .map(ArrowBatches::Stream)
+            .map_err(|e| format!("Failed to read Arrow stream: {}", e)),
+    }
+}
+
+/// Append up to `limit` rows of `batch`, starting at row `skip`
+fn append_rows(batch: &RecordBatch, skip: usize, limit: usize, rows: &mut Vec<Vec<Value>>) -> Result<(), String> {
+    let options = FormatOptions::default().with_null("");
+    let formatters = batch
+        .columns()
+        .iter()
+        .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to format column: {}", e))?;
+
+    let end = batch.num_rows().min(skip.saturating_add(limit));
+    for row in skip..end {
+        rows.push(
+            batch
+                .columns()
+                .iter()
+                .zip(&formatters)
+                .map(|(column, formatter)| cell_value(column.as_ref(), formatter, row))
+                .collect(),
+        );
+    }
+    Ok(())
+}
+
+fn cell_value(array: &dyn Array, formatter: &ArrayFormatter, row: usize) -> Value {
+    if array.is_null(row) {
+        return Value::Null;
+    }
+    let text = formatter.value(row).to_string();
+    match array.data_type() {
+        DataType::Boolean => Value::Bool(text == "true"),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            text.parse::<i64>().map(Value::from).unwrap_or(Value::String(text))
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            text.parse::<u64>().map(Value::from).unwrap_or(Value::String(text))
+        }
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(Value::String(text)),
+        _ => Value::String(text),
+    }
+}
+
+/// Map an Arrow type onto the column types used by the CSV viewer
+fn column_type(data_type: &DataType) -> ColumnType {
+    match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => ColumnType::Integer,
+        DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Decimal128(..)
+        | DataType::Decimal256(..) => ColumnType::Float,
+        DataType::Boolean => ColumnType::Boolean,
+        DataType::Date32 | DataType::Date64 => ColumnType::Date,
+        DataType::Dictionary(_, value_type) => column_type(value_type),
+        _ => ColumnType::String,
+    }
+}
+
+fn parquet_schema(arrow_schema: &SchemaRef, metadata: &ParquetMetaData) -> Vec<ColumnSchema> {
+    let descriptor = metadata.file_metadata().schema_descr();
+    arrow_schema
+        .fields()
+        .iter()
+        .map(|field| {
+            // Only flat columns map one-to-one onto a Parquet leaf column
+            let leaf = descriptor.columns().iter().find(|column| column.path().string() == *field.name());
+            ColumnSchema {
+                name: field.name().clone(),
+                data_type: field.data_type().to_string(),
+                nullable: field.is_nullable(),
+                physical_type: leaf.map(|column| column.physical_type().to_string()),
+                logical_type: leaf.and_then(|column| column.logical_type()).map(|logical| format_logical_type(&logical)),
+            }
+        })
+        .collect()
+}
+
+fn format_logical_type(logical: &LogicalType) -> String {
+    match logical {
+        LogicalType::Decimal { scale, precision } => format!("DECIMAL({}, {})", precision, scale),
+        LogicalType::Integer { bit_width, is_signed } => {
+            format!("{}INT{}", if *is_signed { "" } else { "U" }, bit_width)
+        }
+        LogicalType::Time { is_adjusted_to_u_t_c, unit } | LogicalType::Timestamp { is_adjusted_to_u_t_c, unit } => {
+            let name = if matches!(logical, LogicalType::Time { .. }) { "TIME" } else { "TIMESTAMP" };
+            let unit = format!("{:?}", unit);
+            let unit = unit.split('(').next().unwrap_or_default();
+            if *is_adjusted_to_u_t_c {
+                format!("{}({}, UTC)", name, unit)
+            } else {
+                format!("{}({})", name, unit)
+            }
+        }
+        other => format!("{:?}", other).to_uppercase(),
+    }
+}
+
+fn parquet_row_groups(metadata: &ParquetMetaData) -> Vec<RowGroupInfo> {
+    let mut first_row = 0u64;
+    metadata
+        .row_groups()
+        .iter()
+        .enumerate()
+        .map(|(index, group)| {
+            let num_rows = group.num_rows().max(0) as u64;
+            let info = RowGroupInfo {
+                index,
+                first_row,
+                num_rows,
+                total_byte_size: Some(group.total_byte_size()),
+                compressed_size: Some(group.compressed_size()),
+                columns: group
+                    .columns()
+                    .iter()
+                    .map(|chunk| {
+                        let statistics = chunk.statistics();
+                        let (min, max) = statistics.map(statistics_range).unwrap_or((None, None));
+                        ColumnChunkStats {
+                            column: chunk.column_path().string(),
+                            compression: Some(chunk.compression().to_string()),
+                            compressed_size: Some(chunk.compressed_size()),
+                            null_count: statistics.and_then(Statistics::null_count_opt),
+                            distinct_count: statistics.and_then(Statistics::distinct_count_opt),
+                            min,
+                            max,
+                        }
+                    })
+                    .collect(),
+            };
+            first_row += num_rows;
+            info
+        })
+        .collect()
+}
+
+/// Min/max of a column chunk, in the chunk's physical representation
+fn statistics_range(statistics: &Statistics) -> (Option<Value>, Option<Value>) {
+    fn float(value: f64) -> Value {
+        serde_json::Number::from_f64(value).map(Value::Number).unwrap_or(Value::Null)
+    }
+    fn bytes(value: &[u8]) -> Value {
+        match std::str::from_utf8(value) {
+            Ok(text) => Value::String(text.to_string()),
+            Err(_) => Value::String(value.iter().map(|b| format!("{:02x}", b)).collect()),
+        }
+    }
+    match statistics {
+        Statistics::Boolean(s) => (s.min_opt().map(|v| Value::Bool(*v)), s.max_opt().map(|v| Value::Bool(*v))),
+        Statistics::Int32(s) => (s.min_opt().map(|v| Value::from(*v)), s.max_opt().map(|v| Value::from(*v))),
+        Statistics::Int64(s) => (s.min_opt().map(|v| Value::from(*v)), s.max_opt().map(|v| Value::from(*v))),
+        Statistics::Float(s) => (s.min_opt().map(|v| float(*v as f64)), s.max_opt().map(|v| float(*v as f64))),
+        Statistics::Double(s) => (s.min_opt().map(|v| float(*v)), s.max_opt().map(|v| float(*v))),
+        Statistics::ByteArray(s) => (s.min_opt().map(|v| bytes(v.data())), s.max_opt().map(|v| bytes(v.data()))),
+        Statistics::FixedLenByteArray(s) => (s.min_opt().map(|v| bytes(v.data())), s.max_opt().map(|v| bytes(v.data()))),
+        Statistics::Int96(s) => (
+            s.min_opt().map(|v| Value::String(v.to_string())),
+            s.max_opt().map(|v| Value::String(v.to_string())),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{BooleanArray, Float64Array, Int64Array, StringArray};
+    use arrow_schema::{Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    fn batches(rows: i64, batch_size: i64) -> (SchemaRef, Vec<RecordBatch>) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+            Field::new("ok", DataType::Boolean, true),
+        ]));
+        let batches = (0..rows)
+            .step_by(batch_size as usize)
+            .map(|start| {
+                let ids: Vec<i64> = (start..(start + batch_size).min(rows)).collect();
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int64Array::from(ids.clone())),
+                        Arc::new(StringArray::from_iter(ids.iter().map(|i| (i % 5 != 0).then(|| format!("row {}", i))))),
+                        Arc::new(Float64Array::from_iter_values(ids.iter().map(|i| *i as f64 / 2.0))),
+                        Arc::new(BooleanArray::from_iter(ids.iter().map(|i| Some(i % 2 == 0)))),
+                    ],
+                )
+                .unwrap()
+            })
+            .collect();
+        (schema, batches)
+    }
+
+    #[test]
+    fn test_pages_parquet_across_row_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        let (schema, batches) = batches(250, 50);
+        let properties = WriterProperties::builder().set_max_row_group_size(100).build();
+        let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), schema, Some(properties)).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.close().unwrap();
+
+        let index = TableIndex::build(&path).unwrap();
+        assert_eq!(index.format, TableFormat::Parquet);
+        assert_eq!(index.total_rows, 250);
+        assert_eq!(index.row_groups.len(), 3);
+        assert_eq!(
+            index.column_types,
+            vec![ColumnType::Integer, ColumnType::String, ColumnType::Float, ColumnType::Boolean]
+        );
+        assert_eq!(index.schema[0].physical_type.as_deref(), Some("INT64"));
+        assert_eq!(index.schema[1].logical_type.as_deref(), Some("STRING"));
+
+        let stats = &index.row_groups[1].columns[0];
+        assert_eq!((stats.min.clone(), stats.max.clone()), (Some(Value::from(100)), Some(Value::from(199))));
+        assert_eq!(index.row_groups[1].columns[1].null_count, Some(20));
+
+        let page = index.page(&path, 95, 10).unwrap();
+        assert_eq!(page.rows.len(), 10);
+        assert_eq!(page.rows[0], vec![Value::from(95), Value::Null, Value::from(47.5), Value::Bool(false)]);
+        assert_eq!(page.rows[9][0], Value::from(104));
+        assert_eq!(index.page(&path, 245, 10).unwrap().rows.len(), 5);
+        assert!(index.page(&path, 300, 10).unwrap().rows.is_empty());
+    }
+
+    #[test]
+    fn test_pages_arrow_files_and_streams() {
+        let dir = tempfile::tempdir().unwrap();
+        let (schema, batches) = batches(100, 30);
+
+        let file_path = dir.path().join("data.arrow");
+        let mut writer = arrow_ipc::writer::FileWriter::try_new(File::create(&file_path).unwrap(), &schema).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let stream_path = dir.path().join("data.arrows");
+        let mut writer = arrow_ipc::writer::StreamWriter::try_new(File::create(&stream_path).unwrap(), &schema).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap();
+
+        for (path, format) in [(file_path, TableFormat::ArrowFile), (stream_path, TableFormat::ArrowStream)] {
+            let index = TableIndex::build(&path).unwrap();
+            assert_eq!(index.format, format);
+            assert_eq!(index.total_rows, 100);
+            assert_eq!(index.row_groups.iter().map(|g| g.num_rows).collect::<Vec<_>>(), vec![30, 30, 30, 10]);
+
+            let page = index.page(&path, 58, 5).unwrap();
+            let ids: Vec<&Value> = page.rows.iter().map(|row| &row[0]).collect();
+            assert_eq!(ids, vec![&Value::from(58), &Value::from(59), &Value::from(60), &Value::from(61), &Value::from(62)]);
+            assert_eq!(page.rows[1][1], Value::from("row 59"));
+        }
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.txt");
+        std::fs::write(&path, "a,b\n").unwrap();
+        assert!(TableFormat::detect(&path).is_err());
+    }
+}
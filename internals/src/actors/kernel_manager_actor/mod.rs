@@ -0,0 +1,647 @@
+use actix::prelude::*;
+use log::{debug, error, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::actors::{
+    CommunicationActor, ConfigurationActor, ExecutionActor, InstallationActor, PlotActor, ProcessActor,
+};
+use crate::messages::communication::{IsConnected, ShutdownCommunication};
+use crate::messages::execution::{ActivateProject, ExecuteFile, ExecuteNotebookCell};
+use crate::messages::kernel::*;
+use crate::messages::process::{
+    IsMessageLoopReady, RestartJulia, SetActiveProject, SetCommunicationActor, SetOutputSuppression,
+    ShutdownJuliaProcess, StartJuliaProcess,
+};
+use crate::service_traits::EventEmitter;
+use crate::services::events::EventService;
+use crate::types::{KernelInfo, KernelStatus, DEFAULT_KERNEL};
+
+/// Idle kernels are shut down after this long unless configured otherwise
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// First starts can precompile packages, so allow plenty of time
+const READY_TIMEOUT: Duration = Duration::from_secs(300);
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Actors making up one Julia kernel
+#[derive(Clone)]
+struct KernelHandles {
+    process_actor: Addr<ProcessActor>,
+    communication_actor: Addr<CommunicationActor>,
+    execution_actor: Addr<ExecutionActor>,
+}
+
+struct Kernel {
+    handles: KernelHandles,
+    project_path: Option<String>,
+    status: KernelStatus,
+    is_default: bool,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_activity: Instant,
+    active_executions: usize,
+    error: Option<String>,
+}
+
+impl Kernel {
+    fn info(&self, name: &str) -> KernelInfo {
+        let status = if self.status == KernelStatus::Idle && self.active_executions > 0 {
+            KernelStatus::Busy
+        } else {
+            self.status
+        };
+        KernelInfo {
+            name: name.to_string(),
+            project_path: self.project_path.clone(),
+            status,
+            is_default: self.is_default,
+            started_at: self.started_at.map(|t| t.to_rfc3339()),
+            idle_seconds: if self.active_executions > 0 { 0 } else { self.last_activity.elapsed().as_secs() },
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// KernelManagerActor - runs named Julia kernels next to the default Julia process
+///
+/// Each kernel has its own ProcessActor, CommunicationActor and ExecutionActor, so it
+/// gets its own process, pipes and workspace. The default kernel wraps the main actors
+/// that are managed by the orchestrator.
+pub struct KernelManagerActor {
+    kernels: HashMap<String, Kernel>,
+    idle_timeout: Option<Duration>,
+
+    event_emitter: Arc<dyn EventEmitter>,
+    event_manager: EventService,
+    installation_actor: Addr<InstallationActor>,
+    config_actor: Addr<ConfigurationActor>,
+    plot_actor: Addr<PlotActor>,
+}
+
+impl KernelManagerActor {
+    /// Create a new KernelManagerActor instance
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        process_actor: Addr<ProcessActor>,
+        communication_actor: Addr<CommunicationActor>,
+        execution_actor: Addr<ExecutionActor>,
+        event_emitter: Arc<dyn EventEmitter>,
+        event_manager: EventService,
+        installation_actor: Addr<InstallationActor>,
+        config_actor: Addr<ConfigurationActor>,
+        plot_actor: Addr<PlotActor>,
+    ) -> Self {
+        let mut kernels = HashMap::new();
+        kernels.insert(
+            DEFAULT_KERNEL.to_string(),
+            Kernel {
+                handles: KernelHandles { process_actor, communication_actor, execution_actor },
+                project_path: None,
+                status: KernelStatus::Idle,
+                is_default: true,
+                started_at: None,
+                last_activity: Instant::now(),
+                active_executions: 0,
+                error: None,
+            },
+        );
+
+        Self {
+            kernels,
+            idle_timeout: Some(DEFAULT_IDLE_TIMEOUT),
+            event_emitter,
+            event_manager,
+            installation_actor,
+            config_actor,
+            plot_actor,
+        }
+    }
+
+    /// Create the actors for a new kernel (the Julia process is started separately)
+    fn create_kernel_actors(&self) -> KernelHandles {
+        let process_actor = ProcessActor::new(
+            self.event_emitter.clone(),
+            self.event_manager.clone(),
+            Some(self.installation_actor.clone()),
+            Some(self.config_actor.clone()),
+        )
+        .start();
+        let communication_actor = CommunicationActor::new(
+            self.event_emitter.clone(),
+            self.plot_actor.clone(),
+            process_actor.clone(),
+            self.event_manager.clone(),
+        )
+        .start();
+        let execution_actor = ExecutionActor::new(communication_actor.clone(), self.event_manager.clone()).start();
+        KernelHandles { process_actor, communication_actor, execution_actor }
+    }
+
+    /// Start Julia for a kernel and wait until it accepts code
+    async fn launch(handles: KernelHandles, project_path: Option<String>) -> Result<(), String> {
+        handles
+            .process_actor
+            .send(SetCommunicationActor { communication_actor: handles.communication_actor.clone() })
+            .await
+            .map_err(|e| format!("Failed to send SetCommunicationActor message: {}", e))??;
+        handles
+            .process_actor
+            .send(SetActiveProject { project_path: project_path.clone(), remember: false })
+            .await
+            .map_err(|e| format!("Failed to send SetActiveProject message: {}", e))??;
+        handles
+            .process_actor
+            .send(StartJuliaProcess { orchestrator_addr: None })
+            .await
+            .map_err(|e| format!("Failed to send StartJuliaProcess message: {}", e))??;
+
+        Self::wait_until_ready(&handles).await?;
+
+        if let Some(project_path) = project_path {
+            handles
+                .execution_actor
+                .send(ActivateProject { project_path })
+                .await
+                .map_err(|e| format!("Failed to send ActivateProject message: {}", e))??;
+        }
+
+        // Kernel output stays suppressed: the terminal shows the default kernel only,
+        // notebook cells get their output through execution results
+        Ok(())
+    }
+
+    /// Wait until Julia's message loop runs and both pipes are connected
+    async fn wait_until_ready(handles: &KernelHandles) -> Result<(), String> {
+        let deadline = Instant::now() + READY_TIMEOUT;
+        loop {
+            let loop_ready = handles
+                .process_actor
+                .send(IsMessageLoopReady)
+                .await
+                .map_err(|e| format!("Failed to send IsMessageLoopReady message: {}", e))??;
+            let connected = handles
+                .communication_actor
+                .send(IsConnected)
+                .await
+                .map_err(|e| format!("Failed to send IsConnected message: {}", e))??;
+            if loop_ready && connected {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err("Timed out waiting for the kernel to start".to_string());
+            }
+            tokio::time::sleep(READY_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Stop a kernel's Julia process, close its pipes and stop its actors
+    ///
+    /// The ProcessActor and CommunicationActor hold each other's address, so dropping
+    /// the handles alone would never stop them.
+    fn shutdown(handles: &KernelHandles) {
+        handles.communication_actor.do_send(ShutdownCommunication);
+        handles.process_actor.do_send(ShutdownJuliaProcess);
+    }
+
+    /// Shut down a kernel and remove it
+    fn remove_kernel(&mut self, name: &str) {
+        if let Some(mut kernel) = self.kernels.remove(name) {
+            Self::shutdown(&kernel.handles);
+            kernel.status = KernelStatus::Stopped;
+            let info = kernel.info(name);
+            let event_manager = self.event_manager.clone();
+            actix::spawn(async move {
+                let _ = event_manager.emit_kernel_status_changed(info).await;
+            });
+        }
+    }
+
+    /// Shut down a kernel's actors and give it fresh ones, returning them and the project to launch
+    fn replace_kernel_actors(&mut self, name: &str) -> Option<(KernelHandles, Option<String>)> {
+        let handles = self.create_kernel_actors();
+        let kernel = self.kernels.get_mut(name)?;
+        Self::shutdown(&kernel.handles);
+        kernel.handles = handles.clone();
+        kernel.status = KernelStatus::Restarting;
+        kernel.started_at = Some(chrono::Utc::now());
+        kernel.active_executions = 0;
+        kernel.error = None;
+        Some((handles, kernel.project_path.clone()))
+    }
+
+    fn emit_status(&self, name: &str) {
+        if let Some(kernel) = self.kernels.get(name) {
+            let info = kernel.info(name);
+            let event_manager = self.event_manager.clone();
+            actix::spawn(async move {
+                let _ = event_manager.emit_kernel_status_changed(info).await;
+            });
+        }
+    }
+
+    /// Mark a kernel busy and get the actors to run code on
+    fn begin_execution(&mut self, name: &str) -> Result<KernelHandles, String> {
+        let kernel = self.kernels.get_mut(name).ok_or_else(|| format!("Unknown kernel: {}", name))?;
+        match kernel.status {
+            KernelStatus::Idle | KernelStatus::Busy => {}
+            status => return Err(format!("Kernel '{}' is not ready ({:?})", name, status)),
+        }
+        kernel.active_executions += 1;
+        kernel.last_activity = Instant::now();
+        let handles = kernel.handles.clone();
+        if kernel.active_executions == 1 {
+            self.emit_status(name);
+        }
+        Ok(handles)
+    }
+
+    fn end_execution(&mut self, name: &str) {
+        if let Some(kernel) = self.kernels.get_mut(name) {
+            kernel.active_executions = kernel.active_executions.saturating_sub(1);
+            kernel.last_activity = Instant::now();
+            if kernel.active_executions == 0 {
+                self.emit_status(name);
+            }
+        }
+    }
+
+    /// Shut down kernels that have been idle longer than the timeout
+    fn stop_idle_kernels(&mut self) {
+        let Some(timeout) = self.idle_timeout else {
+            return;
+        };
+        let idle: Vec<String> = self
+            .kernels
+            .iter()
+            .filter(|(_, k)| {
+                !k.is_default
+                    && k.status == KernelStatus::Idle
+                    && k.active_executions == 0
+                    && k.last_activity.elapsed() >= timeout
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for name in idle {
+            debug!("KernelManagerActor: Shutting down idle kernel '{}'", name);
+            self.remove_kernel(&name);
+        }
+    }
+}
+
+impl Actor for KernelManagerActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.set_mailbox_capacity(128);
+        ctx.run_interval(IDLE_CHECK_INTERVAL, |actor, _ctx| actor.stop_idle_kernels());
+    }
+
+    fn stopped(&mut self, _ctx: &mut Context<Self>) {
+        debug!("KernelManagerActor: Actor stopped");
+        for kernel in self.kernels.values().filter(|k| !k.is_default) {
+            Self::shutdown(&kernel.handles);
+        }
+    }
+}
+
+// Message handlers
+
+impl Handler<StartKernel> for KernelManagerActor {
+    type Result = ResponseActFuture<Self, Result<KernelInfo, String>>;
+
+    fn handle(&mut self, msg: StartKernel, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Received StartKernel message for '{}'", msg.name);
+        let name = msg.name.trim().to_string();
+        if name.is_empty() {
+            return Box::pin(async { Err("Kernel name must not be empty".to_string()) }.into_actor(self));
+        }
+        if self.kernels.contains_key(&name) {
+            return Box::pin(async move { Err(format!("Kernel '{}' already exists", name)) }.into_actor(self));
+        }
+
+        let handles = self.create_kernel_actors();
+        self.kernels.insert(
+            name.clone(),
+            Kernel {
+                handles: handles.clone(),
+                project_path: msg.project_path.clone(),
+                status: KernelStatus::Starting,
+                is_default: false,
+                started_at: Some(chrono::Utc::now()),
+                last_activity: Instant::now(),
+                active_executions: 0,
+                error: None,
+            },
+        );
+        self.emit_status(&name);
+
+        let project_path = msg.project_path;
+        Box::pin(
+            async move { Self::launch(handles, project_path).await }
+                .into_actor(self)
+                .map(move |res, actor, _| {
+                    let kernel = actor
+                        .kernels
+                        .get_mut(&name)
+                        .ok_or_else(|| format!("Kernel '{}' was removed while starting", name))?;
+                    match res {
+                        Ok(()) => {
+                            kernel.status = KernelStatus::Idle;
+                            kernel.last_activity = Instant::now();
+                        }
+                        Err(ref e) => {
+                            error!("KernelManagerActor: Failed to start kernel '{}': {}", name, e);
+                            Self::shutdown(&kernel.handles);
+                            kernel.status = KernelStatus::Failed;
+                            kernel.error = Some(e.clone());
+                        }
+                    }
+                    let info = kernel.info(&name);
+                    actor.emit_status(&name);
+                    res.map(|_| info)
+                }),
+        )
+    }
+}
+
+impl Handler<ListKernels> for KernelManagerActor {
+    type Result = Result<Vec<KernelInfo>, String>;
+
+    fn handle(&mut self, _msg: ListKernels, _ctx: &mut Context<Self>) -> Self::Result {
+        let mut kernels: Vec<KernelInfo> = self.kernels.iter().map(|(name, k)| k.info(name)).collect();
+        kernels.sort_by(|a, b| b.is_default.cmp(&a.is_default).then_with(|| a.name.cmp(&b.name)));
+        Ok(kernels)
+    }
+}
+
+impl Handler<KillKernel> for KernelManagerActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: KillKernel, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Received KillKernel message for '{}'", msg.name);
+        match self.kernels.get(&msg.name) {
+            None => return Err(format!("Unknown kernel: {}", msg.name)),
+            Some(kernel) if kernel.is_default => {
+                return Err("The default kernel cannot be killed; restart it instead".to_string());
+            }
+            Some(_) => {}
+        }
+
+        self.remove_kernel(&msg.name);
+        Ok(())
+    }
+}
+
+impl Handler<RestartKernel> for KernelManagerActor {
+    type Result = ResponseActFuture<Self, Result<KernelInfo, String>>;
+
+    fn handle(&mut self, msg: RestartKernel, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Received RestartKernel message for '{}'", msg.name);
+        let name = msg.name;
+        let Some(kernel) = self.kernels.get(&name) else {
+            return Box::pin(async move { Err(format!("Unknown kernel: {}", name)) }.into_actor(self));
+        };
+
+        // The default kernel is restarted in place; the orchestrator reconnects it
+        if kernel.is_default {
+            let process_actor = kernel.handles.process_actor.clone();
+            return Box::pin(
+                async move {
+                    process_actor
+                        .send(RestartJulia)
+                        .await
+                        .map_err(|e| format!("Failed to send RestartJulia message: {}", e))?
+                }
+                .into_actor(self)
+                .map(move |res, actor, _| {
+                    let kernel = actor.kernels.get_mut(&name).ok_or("Default kernel missing")?;
+                    kernel.started_at = Some(chrono::Utc::now());
+                    kernel.last_activity = Instant::now();
+                    let info = kernel.info(&name);
+                    res.map(|_| info)
+                }),
+            );
+        }
+
+        if kernel.active_executions > 0 {
+            warn!("KernelManagerActor: Restarting kernel '{}' while code is running", name);
+        }
+
+        // Fresh actors avoid carrying pipe state over from the old process
+        let Some((handles, project_path)) = self.replace_kernel_actors(&name) else {
+            return Box::pin(async move { Err(format!("Unknown kernel: {}", name)) }.into_actor(self));
+        };
+        self.emit_status(&name);
+
+        Box::pin(
+            async move { Self::launch(handles, project_path).await }
+                .into_actor(self)
+                .map(move |res, actor, _| {
+                    let kernel = actor
+                        .kernels
+                        .get_mut(&name)
+                        .ok_or_else(|| format!("Kernel '{}' was removed while restarting", name))?;
+                    match res {
+                        Ok(()) => {
+                            kernel.status = KernelStatus::Idle;
+                            kernel.last_activity = Instant::now();
+                        }
+                        Err(ref e) => {
+                            error!("KernelManagerActor: Failed to restart kernel '{}': {}", name, e);
+                            Self::shutdown(&kernel.handles);
+                            kernel.status = KernelStatus::Failed;
+                            kernel.error = Some(e.clone());
+                        }
+                    }
+                    let info = kernel.info(&name);
+                    actor.emit_status(&name);
+                    res.map(|_| info)
+                }),
+        )
+    }
+}
+
+impl Handler<SetKernelIdleTimeout> for KernelManagerActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: SetKernelIdleTimeout, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Setting idle timeout to {:?}s", msg.timeout_secs);
+        if msg.timeout_secs == Some(0) {
+            return Err("Idle timeout must be greater than zero".to_string());
+        }
+        self.idle_timeout = msg.timeout_secs.map(Duration::from_secs);
+        Ok(())
+    }
+}
+
+impl Handler<ExecuteNotebookCellOnKernel> for KernelManagerActor {
+    type Result = ResponseActFuture<Self, Result<String, String>>;
+
+    fn handle(&mut self, msg: ExecuteNotebookCellOnKernel, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Executing notebook cell {} on kernel '{}'", msg.cell_id, msg.kernel);
+        let kernel = msg.kernel;
+        let handles = match self.begin_execution(&kernel) {
+            Ok(handles) => handles,
+            Err(e) => return Box::pin(async move { Err(e) }.into_actor(self)),
+        };
+        let is_default = kernel == DEFAULT_KERNEL;
+
+        Box::pin(
+            async move {
+                // Keep notebook output out of the terminal (other kernels are always suppressed)
+                if is_default {
+                    let _ = handles.process_actor.send(SetOutputSuppression { suppressed: true }).await;
+                }
+                let result = handles
+                    .execution_actor
+                    .send(ExecuteNotebookCell {
+                        cell_id: msg.cell_id,
+                        code: msg.code,
+                        notebook_path: msg.notebook_path,
+                    })
+                    .await
+                    .map_err(|e| format!("Failed to send ExecuteNotebookCell message: {}", e));
+                if is_default {
+                    let _ = handles.process_actor.send(SetOutputSuppression { suppressed: false }).await;
+                }
+                result?
+            }
+            .into_actor(self)
+            .map(move |res, actor, _| {
+                actor.end_execution(&kernel);
+                res
+            }),
+        )
+    }
+}
+
+impl Handler<ExecuteFileOnKernel> for KernelManagerActor {
+    type Result = ResponseActFuture<Self, Result<String, String>>;
+
+    fn handle(&mut self, msg: ExecuteFileOnKernel, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("KernelManagerActor: Executing file {} on kernel '{}'", msg.file_path, msg.kernel);
+        let kernel = msg.kernel;
+        let handles = match self.begin_execution(&kernel) {
+            Ok(handles) => handles,
+            Err(e) => return Box::pin(async move { Err(e) }.into_actor(self)),
+        };
+
+        Box::pin(
+            async move {
+                handles
+                    .execution_actor
+                    .send(ExecuteFile { file_path: msg.file_path })
+                    .await
+                    .map_err(|e| format!("Failed to send ExecuteFile message: {}", e))?
+            }
+            .into_actor(self)
+            .map(move |res, actor, _| {
+                actor.end_execution(&kernel);
+                res
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::MockEventEmitter;
+
+    /// Address of an actor that is never run (the tests do not message it)
+    fn unstarted<A: Actor<Context = Context<A>>>() -> Addr<A> {
+        Context::<A>::new().address()
+    }
+
+    fn manager() -> KernelManagerActor {
+        let event_emitter: Arc<dyn EventEmitter> = Arc::new(MockEventEmitter::new());
+        let event_manager = EventService::new(event_emitter.clone());
+        KernelManagerActor::new(
+            unstarted(),
+            unstarted(),
+            unstarted(),
+            event_emitter,
+            event_manager,
+            unstarted(),
+            unstarted(),
+            unstarted(),
+        )
+    }
+
+    /// Register a running kernel without launching Julia
+    fn add_kernel(manager: &mut KernelManagerActor, name: &str) -> KernelHandles {
+        let handles = manager.create_kernel_actors();
+        manager.kernels.insert(
+            name.to_string(),
+            Kernel {
+                handles: handles.clone(),
+                project_path: None,
+                status: KernelStatus::Idle,
+                is_default: false,
+                started_at: Some(chrono::Utc::now()),
+                last_activity: Instant::now(),
+                active_executions: 0,
+                error: None,
+            },
+        );
+        handles
+    }
+
+    /// Whether the kernel's ProcessActor and CommunicationActor stop within a few seconds
+    async fn actors_stopped(handles: &KernelHandles) -> bool {
+        for _ in 0..100 {
+            if !handles.process_actor.connected() && !handles.communication_actor.connected() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    #[actix_rt::test]
+    async fn test_kill_kernel_stops_actors() {
+        let mut manager = manager();
+        let handles = add_kernel(&mut manager, "worker");
+        let addr = manager.start();
+
+        addr.send(KillKernel { name: "worker".to_string() }).await.unwrap().unwrap();
+
+        assert!(actors_stopped(&handles).await);
+        let kernels = addr.send(ListKernels).await.unwrap().unwrap();
+        assert!(kernels.iter().all(|k| k.name != "worker"));
+    }
+
+    #[actix_rt::test]
+    async fn test_restart_kernel_stops_old_actors() {
+        let mut manager = manager();
+        let old = add_kernel(&mut manager, "worker");
+
+        let (new, _) = manager.replace_kernel_actors("worker").unwrap();
+
+        assert!(actors_stopped(&old).await);
+        assert!(new.process_actor.connected() && new.communication_actor.connected());
+        assert_eq!(manager.kernels["worker"].status, KernelStatus::Restarting);
+        manager.remove_kernel("worker");
+        assert!(actors_stopped(&new).await);
+    }
+
+    #[actix_rt::test]
+    async fn test_idle_kernels_stopped() {
+        let mut manager = manager();
+        let idle = add_kernel(&mut manager, "idle");
+        let busy = add_kernel(&mut manager, "busy");
+        manager.kernels.get_mut("busy").unwrap().active_executions = 1;
+        manager.idle_timeout = Some(Duration::from_millis(1));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        manager.stop_idle_kernels();
+
+        assert!(actors_stopped(&idle).await);
+        assert!(!manager.kernels.contains_key("idle"));
+        assert!(manager.kernels.contains_key("busy") && manager.kernels.contains_key(DEFAULT_KERNEL));
+        assert!(busy.process_actor.connected());
+        manager.remove_kernel("busy");
+    }
+}
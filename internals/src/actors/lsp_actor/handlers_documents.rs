@@ -85,6 +85,36 @@ impl Handler<UpdateDocument> for LspActorState {
     }
 }
 
+impl Handler<UpdateNotebook> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+    
+    fn handle(&mut self, msg: UpdateNotebook, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.update_notebook(msg.uri, msg.cells).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<CloseNotebook> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+    
+    fn handle(&mut self, msg: CloseNotebook, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.close_notebook(msg.uri).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
 impl Handler<InvalidateCache> for LspActorState {
     type Result = ResponseActFuture<Self, Result<(), String>>;
     
@@ -120,6 +120,111 @@ impl Handler<GetDiagnostics> for LspActorState {
     }
 }
 
+impl Handler<GetNotebookHover> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::LspHover>, String>>;
+    
+    fn handle(&mut self, msg: GetNotebookHover, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_notebook_hover(msg.uri, msg.cell_id, msg.position).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetNotebookCompletions> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Vec<crate::types::LspCompletionItem>, String>>;
+    
+    fn handle(&mut self, msg: GetNotebookCompletions, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_notebook_completions(msg.uri, msg.cell_id, msg.position).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetNotebookDefinition> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Vec<crate::types::LspNotebookLocation>, String>>;
+    
+    fn handle(&mut self, msg: GetNotebookDefinition, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_notebook_definition(msg.uri, msg.cell_id, msg.position).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetNotebookDiagnostics> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Vec<crate::types::LspCellDiagnostic>, String>>;
+    
+    fn handle(&mut self, msg: GetNotebookDiagnostics, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_notebook_diagnostics(msg.uri).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetDependencyHealth> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::DependencyHealthReport>, String>>;
+    
+    fn handle(&mut self, _msg: GetDependencyHealth, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_dependency_health().await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetPackageHealth> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::PackageHealthReport>, String>>;
+    
+    fn handle(&mut self, _msg: GetPackageHealth, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_package_health().await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
+impl Handler<GetDeadCode> for LspActorState {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::DeadCodeReport>, String>>;
+    
+    fn handle(&mut self, _msg: GetDeadCode, _ctx: &mut Context<Self>) -> Self::Result {
+        let lsp_service = self.lsp_service.clone();
+        Box::pin(
+            async move {
+                lsp_service.get_dead_code().await
+            }
+            .into_actor(self)
+            .map(|res, _actor, _| res)
+        )
+    }
+}
+
 
 
 
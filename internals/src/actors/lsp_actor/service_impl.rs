@@ -6,11 +6,11 @@ use std::sync::Arc;
 use std::sync::RwLock;
 use tokio::sync::RwLock as TokioRwLock;
 
-use languageserver::embedded::{EmbeddedLspService, LspConfig};
+use languageserver::embedded::{EmbeddedLspService, LspConfig, NotebookCellSource};
 use crate::types::{
-    LspCallHierarchyItem, LspCodeAction, LspCompletionItem, LspDiagnostic, LspDocumentSymbol,
-    LspHover, LspInlayHint, LspLocation, LspMarkedString, LspPosition, LspRange, LspSemanticToken, LspSignatureHelp,
-    LspTextEdit, LspWorkspaceEdit,
+    LspCallHierarchyItem, LspCellDiagnostic, LspCodeAction, LspCompletionItem, LspDiagnostic, LspDocumentSymbol,
+    LspHover, LspInlayHint, LspLocation, LspMarkedString, LspNotebookLocation, LspPosition, LspRange, LspSemanticToken,
+    LspParameterInformation, LspSignatureHelp, LspSignatureInformation, LspTextEdit, LspWorkspaceEdit,
 };
 
 use super::type_conversions::*;
@@ -347,14 +347,41 @@ impl LspService {
         }
     }
 
-    // Signature help - NOT IMPLEMENTED
+    // Signature help
     pub async fn get_signature_help(
         &self,
-        _uri: String,
-        _position: LspPosition,
+        uri: String,
+        position: LspPosition,
     ) -> Result<Option<LspSignatureHelp>, String> {
-        warn!("LspService: get_signature_help not implemented yet");
-        Ok(None)
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        // Convert URI to proper file path
+        let path_str = self.utils.uri_to_path(&uri);
+        let path = PathBuf::from(&path_str);
+        let languageserver_pos = lsp_position_to_position(position);
+        
+        let Some(help) = service.signature_help(&path, languageserver_pos.line, languageserver_pos.character) else {
+            return Ok(None);
+        };
+        let signatures = help
+            .signatures
+            .into_iter()
+            .map(|sig| LspSignatureInformation {
+                label: sig.label,
+                documentation: sig.documentation,
+                parameters: Some(
+                    sig.parameters
+                        .into_iter()
+                        .map(|p| LspParameterInformation { label: p.label, documentation: p.documentation })
+                        .collect(),
+                ),
+            })
+            .collect();
+        Ok(Some(LspSignatureHelp {
+            signatures,
+            active_signature: help.active_signature,
+            active_parameter: help.active_parameter,
+        }))
     }
 
     // Navigation and references
@@ -636,6 +663,30 @@ impl LspService {
         Ok(lsp_diagnostics)
     }
 
+    // Dependency health
+    pub async fn get_dependency_health(&self) -> Result<Option<crate::types::DependencyHealthReport>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        
+        Ok(service.dependency_health())
+    }
+
+    // Package health
+    pub async fn get_package_health(&self) -> Result<Option<crate::types::PackageHealthReport>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        
+        Ok(service.package_health())
+    }
+
+    // Dead code
+    pub async fn get_dead_code(&self) -> Result<Option<crate::types::DeadCodeReport>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        
+        Ok(service.dead_code())
+    }
+
     // Document management
     pub async fn update_document(&self, uri: String, content: String) -> Result<(), String> {
         let mut service_guard = self.get_service_mut().await?;
@@ -652,6 +703,89 @@ impl LspService {
         Ok(())
     }
 
+    // Notebooks
+    pub async fn update_notebook(&self, uri: String, cells: Vec<(String, String)>) -> Result<(), String> {
+        let mut service_guard = self.get_service_mut().await?;
+        let service = service_guard.as_mut().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        let cells = cells
+            .into_iter()
+            .map(|(cell_id, source)| NotebookCellSource { cell_id, source })
+            .collect();
+        
+        service.update_notebook(path, cells)
+            .map_err(|e| format!("Failed to update notebook: {}", e))
+    }
+
+    pub async fn close_notebook(&self, uri: String) -> Result<(), String> {
+        let mut service_guard = self.get_service_mut().await?;
+        let service = service_guard.as_mut().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        
+        service.close_notebook(&path);
+        Ok(())
+    }
+
+    pub async fn get_notebook_hover(
+        &self,
+        uri: String,
+        cell_id: String,
+        position: LspPosition,
+    ) -> Result<Option<LspHover>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        
+        let hover = service.notebook_hover(&path, &cell_id, position.line, position.character).await;
+        Ok(hover.map(|hover_content| LspHover {
+            contents: vec![LspMarkedString {
+                language: Some("julia".to_string()),
+                value: hover_content,
+            }],
+            range: None,
+        }))
+    }
+
+    pub async fn get_notebook_completions(
+        &self,
+        uri: String,
+        cell_id: String,
+        position: LspPosition,
+    ) -> Result<Vec<LspCompletionItem>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        
+        Ok(service.notebook_complete(&path, &cell_id, position.line, position.character)
+            .map(|list| list.items.into_iter().map(completion_item_to_lsp).collect())
+            .unwrap_or_default())
+    }
+
+    pub async fn get_notebook_definition(
+        &self,
+        uri: String,
+        cell_id: String,
+        position: LspPosition,
+    ) -> Result<Vec<LspNotebookLocation>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        
+        Ok(service.notebook_definition(&path, &cell_id, position.line, position.character)
+            .map(|locations| locations.into_iter().map(notebook_location_to_lsp).collect())
+            .unwrap_or_default())
+    }
+
+    pub async fn get_notebook_diagnostics(&self, uri: String) -> Result<Vec<LspCellDiagnostic>, String> {
+        let service_guard = self.get_service().await?;
+        let service = service_guard.as_ref().unwrap();
+        let path = PathBuf::from(self.utils.uri_to_path(&uri));
+        
+        let diagnostics = service.notebook_diagnostics(&path);
+        debug!("LspService: Found {} notebook diagnostics for {}", diagnostics.len(), uri);
+        Ok(diagnostics.into_iter().map(cell_diagnostic_to_lsp).collect())
+    }
+
     pub async fn invalidate_cache(&self, uri: String) -> Result<(), String> {
         let mut service_guard = self.get_service_mut().await?;
         let service = service_guard.as_mut().unwrap();
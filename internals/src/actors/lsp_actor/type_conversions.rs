@@ -1,8 +1,10 @@
 // Type conversion utilities between languageserver crate types and internals types
 
 use crate::types::{
-    LspCompletionItem, LspDiagnostic, LspHover, LspLocation, LspMarkedString, LspPosition, LspRange,
+    LspCellDiagnostic, LspCompletionItem, LspDiagnostic, LspHover, LspLocation, LspMarkedString, LspNotebookLocation,
+    LspPosition, LspRange,
 };
+use languageserver::embedded::{CellDiagnostic, NotebookLocation};
 use languageserver::types::{
     CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, HoverResult, Location, Position, Range,
 };
@@ -118,6 +120,22 @@ pub fn diagnostic_to_lsp(diagnostic: Diagnostic) -> LspDiagnostic {
     }
 }
 
+/// Convert languageserver CellDiagnostic to internals LspCellDiagnostic
+pub fn cell_diagnostic_to_lsp(diagnostic: CellDiagnostic) -> LspCellDiagnostic {
+    LspCellDiagnostic {
+        cell_id: diagnostic.cell_id,
+        diagnostic: diagnostic_to_lsp(diagnostic.diagnostic),
+    }
+}
+
+/// Convert languageserver NotebookLocation to internals LspNotebookLocation
+pub fn notebook_location_to_lsp(location: NotebookLocation) -> LspNotebookLocation {
+    LspNotebookLocation {
+        cell_id: location.cell_id,
+        location: location_to_lsp(location.location),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
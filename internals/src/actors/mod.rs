@@ -8,6 +8,7 @@ pub mod file_server_actor;
 pub mod filesystem_actor;
 pub mod file_watcher_actor;
 pub mod installation_actor;
+pub mod kernel_manager_actor;
 pub mod lsp_actor;
 pub mod orchestrator_actor;
 pub mod plot_actor;
@@ -23,6 +24,7 @@ pub use file_server_actor::*;
 pub use filesystem_actor::*;
 pub use file_watcher_actor::*;
 pub use installation_actor::*;
+pub use kernel_manager_actor::*;
 pub use lsp_actor::*;
 pub use orchestrator_actor::*;
 pub use plot_actor::*;
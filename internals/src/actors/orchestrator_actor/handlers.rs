@@ -129,6 +129,19 @@ impl Handler<UpdateCurrentProject> for OrchestratorActor {
     fn handle(&mut self, msg: UpdateCurrentProject, ctx: &mut Context<Self>) -> Self::Result {
         debug!("OrchestratorActor: Received UpdateCurrentProject message, updating project to {:?}", msg.project_path);
         
+        // Julia picks the project's sysimage at launch, which happens before project activation
+        if let Some(process_actor) = &self.process_actor {
+            process_actor.do_send(crate::messages::process::SetActiveProject {
+                project_path: msg.project_path.clone(),
+                remember: true,
+            });
+        }
+        if let Some(plot_actor) = &self.plot_actor {
+            plot_actor.do_send(crate::messages::plot::SetPlotProject {
+                project_path: msg.project_path.clone(),
+            });
+        }
+        
         if let Some(ref path) = msg.project_path {
             self.current_project = Some(crate::types::ProjectInfo {
                 path: path.clone(),
@@ -461,6 +461,9 @@ impl OrchestratorActor {
                 julia_version: None,
                 packages: Vec::new(),
             });
+            if let Some(plot_actor) = &self.plot_actor {
+                plot_actor.do_send(crate::messages::plot::SetPlotProject { project_path: Some(project_path.clone()) });
+            }
             return Ok(());
         }
         
@@ -492,6 +495,13 @@ impl OrchestratorActor {
             packages: Vec::new(),
         });
         
+        if let Some(process_actor) = &self.process_actor {
+            process_actor.do_send(SetActiveProject { project_path: Some(project_path.clone()), remember: true });
+        }
+        if let Some(plot_actor) = &self.plot_actor {
+            plot_actor.do_send(crate::messages::plot::SetPlotProject { project_path: Some(project_path.clone()) });
+        }
+        
         // Check if it's a Julia project
         let project_toml = std::path::Path::new(&project_path).join("Project.toml");
         let is_julia_project = project_toml.exists();
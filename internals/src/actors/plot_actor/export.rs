@@ -0,0 +1,502 @@
+// Plot export
+// This module renders stored plots to PNG, SVG and PDF files
+//
+// Every plot is exported from an SVG document: SVG plots are used as they are
+// and raster plots are wrapped in one, so size handling is the same for all
+// formats. Provenance from `PlotData` is written into each file's metadata.
+
+use crate::messages::plot::{PlotData, PlotExportFormat, PlotExportOptions};
+use base64::Engine;
+use once_cell::sync::Lazy;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, TextStr};
+use resvg::tiny_skia;
+use resvg::usvg::{self, fontdb, PostProcessingSteps, TreeParsing, TreePostProc};
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// CSS pixels per inch, the unit of SVG user space and of export sizes
+const CSS_DPI: f32 = 96.0;
+/// Largest width or height of a PNG export in pixels
+const MAX_PIXELS: f32 = 16384.0;
+const RASTER_MIMES: [&str; 4] = ["image/png", "image/jpeg", "image/jpg", "image/gif"];
+const MANIFEST_FILE: &str = "plots.json";
+
+/// System fonts for converting SVG text, loaded on first use
+static FONTS: Lazy<fontdb::Database> = Lazy::new(|| {
+    let mut db = fontdb::Database::new();
+    db.load_system_fonts();
+    db
+});
+
+/// Where a plot came from, as embedded in exported files
+#[derive(Debug, Clone, Serialize)]
+pub struct PlotProvenance {
+    pub plot_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub source_file: Option<String>,
+    pub line_number: Option<u32>,
+    pub code_context: Option<String>,
+    pub session_id: Option<String>,
+    /// Creation time as RFC 3339
+    pub created: Option<String>,
+}
+
+impl PlotProvenance {
+    pub fn from_plot(plot: &PlotData) -> Self {
+        Self {
+            plot_id: plot.id.clone(),
+            title: plot.title.clone(),
+            description: plot.description.clone(),
+            source_file: plot.source_file.clone(),
+            line_number: plot.line_number,
+            code_context: plot.code_context.clone(),
+            session_id: plot.session_id.clone(),
+            created: chrono::DateTime::from_timestamp_millis(plot.timestamp)
+                .filter(|_| plot.timestamp > 0)
+                .map(|time| time.to_rfc3339()),
+        }
+    }
+
+    /// `file:line` of the code that produced the plot
+    pub fn source(&self) -> Option<String> {
+        let file = self.source_file.as_ref()?;
+        Some(match self.line_number {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.clone(),
+        })
+    }
+
+    fn title_or_id(&self) -> String {
+        self.title.clone().unwrap_or_else(|| format!("Plot {}", self.plot_id))
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Render a plot in the requested format
+pub fn export_plot(plot: &PlotData, options: &PlotExportOptions) -> Result<Vec<u8>, String> {
+    let svg = source_svg(plot)?;
+    let provenance = PlotProvenance::from_plot(plot);
+    let mut tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+        .map_err(|e| format!("Failed to parse SVG of plot {}: {}", plot.id, e))?;
+    let natural = (tree.size.width(), tree.size.height());
+    let size = target_size(natural, options)?;
+
+    match options.format {
+        PlotExportFormat::Svg => Ok(write_svg(&svg, natural, size, &provenance).into_bytes()),
+        PlotExportFormat::Png => {
+            tree.postprocess(PostProcessingSteps::default(), &FONTS);
+            write_png(&tree, natural, size, options.dpi, &provenance)
+        }
+        PlotExportFormat::Pdf => {
+            tree.postprocess(PostProcessingSteps::default(), &FONTS);
+            Ok(write_pdf(&tree, size, &provenance))
+        }
+    }
+}
+
+/// Export plots into a folder, with a `plots.json` manifest of their provenance
+///
+/// Plots without an SVG or image representation are skipped. Returns the
+/// written file names.
+pub fn export_plots_to_dir(plots: &[PlotData], dir: &Path, options: &PlotExportOptions) -> Result<Vec<String>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create export folder: {}", e))?;
+    export_batch(plots, options, |name, bytes| {
+        std::fs::write(dir.join(name), bytes).map_err(|e| format!("Failed to write {}: {}", name, e))
+    })
+}
+
+/// Export plots into a zip archive, with a `plots.json` manifest of their provenance
+pub fn export_plots_to_zip(plots: &[PlotData], zip_path: &Path, options: &PlotExportOptions) -> Result<Vec<String>, String> {
+    let file = std::fs::File::create(zip_path).map_err(|e| format!("Failed to create zip archive: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let names = export_batch(plots, options, |name, bytes| {
+        zip.start_file(name, zip::write::FileOptions::default())
+            .map_err(|e| format!("Failed to add {} to zip archive: {}", name, e))?;
+        zip.write_all(bytes).map_err(|e| format!("Failed to write {} to zip archive: {}", name, e))
+    })?;
+    zip.finish().map_err(|e| format!("Failed to finish zip archive: {}", e))?;
+    Ok(names)
+}
+
+#[derive(Serialize)]
+struct ManifestEntry {
+    file: String,
+    #[serde(flatten)]
+    provenance: PlotProvenance,
+}
+
+fn export_batch(
+    plots: &[PlotData],
+    options: &PlotExportOptions,
+    mut write: impl FnMut(&str, &[u8]) -> Result<(), String>,
+) -> Result<Vec<String>, String> {
+    if plots.is_empty() {
+        return Err("No plots to export".to_string());
+    }
+
+    let mut manifest = Vec::new();
+    for (index, plot) in plots.iter().enumerate() {
+        let bytes = match export_plot(plot, options) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!("[PlotActor] Skipping plot {} in export: {}", plot.id, e);
+                continue;
+            }
+        };
+        let name = export_file_name(index, plot, options.format);
+        write(&name, &bytes)?;
+        manifest.push(ManifestEntry {
+            file: name,
+            provenance: PlotProvenance::from_plot(plot),
+        });
+    }
+    if manifest.is_empty() {
+        return Err("None of the plots has an SVG or image representation to export".to_string());
+    }
+
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize export manifest: {}", e))?;
+    write(MANIFEST_FILE, json.as_bytes())?;
+    Ok(manifest.into_iter().map(|entry| entry.file).collect())
+}
+
+/// File name for the `index`th plot of a batch export, e.g. `003-scatter-1a2b3c4d.png`
+pub fn export_file_name(index: usize, plot: &PlotData, format: PlotExportFormat) -> String {
+    let stem = plot
+        .title
+        .clone()
+        .or_else(|| {
+            let source = plot.source_file.as_deref()?;
+            Path::new(source).file_stem().and_then(|s| s.to_str()).map(str::to_string)
+        })
+        .unwrap_or_else(|| "plot".to_string());
+    let stem: String = stem
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .take(40)
+        .collect();
+    let short_id: String = plot.id.chars().filter(|c| c.is_ascii_alphanumeric()).take(8).collect();
+    format!("{:03}-{}-{}.{}", index + 1, stem.trim_matches('-'), short_id, format.extension())
+}
+
+/// The SVG document to export a plot from
+fn source_svg(plot: &PlotData) -> Result<String, String> {
+    let bundle = plot.mime_bundle();
+    if let Some(svg) = bundle.get("image/svg+xml") {
+        let trimmed = svg.trim_start();
+        if trimmed.starts_with('<') {
+            return Ok(trimmed.to_string());
+        }
+        // Some displays send SVG base64 encoded like raster images
+        return base64::engine::general_purpose::STANDARD
+            .decode(trimmed.trim_end())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| format!("Plot {} has unreadable SVG data", plot.id));
+    }
+
+    for mime in RASTER_MIMES {
+        let Some(data) = bundle.get(mime) else {
+            continue;
+        };
+        let data = data.trim();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("Plot {} has invalid {} data: {}", plot.id, mime, e))?;
+        let (width, height) =
+            raster_size(&bytes).ok_or_else(|| format!("Plot {} has an unreadable {} image", plot.id, mime))?;
+        return Ok(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}"><image width="{w}" height="{h}" xlink:href="data:{mime};base64,{data}"/></svg>"#,
+            w = width,
+            h = height,
+            mime = mime,
+            data = data,
+        ));
+    }
+
+    Err(format!(
+        "Plot {} has no SVG or image representation to export ({})",
+        plot.id, plot.mime_type
+    ))
+}
+
+/// Pixel size of a PNG, GIF or JPEG image
+fn raster_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]) as u32);
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+        return Some((width, height));
+    }
+    if bytes.starts_with(b"GIF8") {
+        let width = u16::from_le_bytes([*bytes.get(6)?, *bytes.get(7)?]) as u32;
+        let height = u16::from_le_bytes([*bytes.get(8)?, *bytes.get(9)?]) as u32;
+        return Some((width, height));
+    }
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        // Walk the JPEG segments up to the start-of-frame marker
+        let mut at = 2;
+        while *bytes.get(at)? == 0xFF {
+            let marker = *bytes.get(at + 1)?;
+            if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            at += 2 + be16(at + 2)? as usize;
+        }
+    }
+    None
+}
+
+/// Export size in CSS pixels, keeping the aspect ratio when only one side is given
+fn target_size(natural: (f32, f32), options: &PlotExportOptions) -> Result<(f32, f32), String> {
+    let (width, height) = natural;
+    let size = match (options.width, options.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, w * height / width),
+        (None, Some(h)) => (h * width / height, h),
+        (None, None) => natural,
+    };
+    let valid = |v: f32| v.is_finite() && v > 0.0;
+    if !valid(size.0) || !valid(size.1) {
+        return Err(format!("Invalid export size {}x{}", size.0, size.1));
+    }
+    if !valid(options.dpi) {
+        return Err(format!("Invalid export DPI {}", options.dpi));
+    }
+    Ok(size)
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wrap the plot SVG in a document of the requested size carrying the provenance
+fn write_svg(svg: &str, natural: (f32, f32), size: (f32, f32), provenance: &PlotProvenance) -> String {
+    // Drop the XML declaration and doctype so the plot can be nested
+    let inner = svg.find("<svg").map_or(svg, |start| &svg[start..]);
+    let description = [provenance.description.clone(), provenance.source()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" - ");
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" preserveAspectRatio=\"none\">\n\
+         <title>{}</title>\n<desc>{}</desc>\n<metadata id=\"compute42-provenance\">{}</metadata>\n{}\n</svg>\n",
+        size.0,
+        size.1,
+        natural.0,
+        natural.1,
+        xml_escape(&provenance.title_or_id()),
+        xml_escape(&description),
+        xml_escape(&provenance.to_json()),
+        inner.trim_end(),
+    )
+}
+
+fn write_png(
+    tree: &usvg::Tree,
+    natural: (f32, f32),
+    size: (f32, f32),
+    dpi: f32,
+    provenance: &PlotProvenance,
+) -> Result<Vec<u8>, String> {
+    let scale = dpi / CSS_DPI;
+    let (width, height) = ((size.0 * scale).round().max(1.0), (size.1 * scale).round().max(1.0));
+    if width > MAX_PIXELS || height > MAX_PIXELS {
+        return Err(format!("Export size {}x{} pixels is too large", width, height));
+    }
+    let (width, height) = (width as u32, height as u32);
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or("Failed to allocate export image")?;
+    let transform = tiny_skia::Transform::from_scale(width as f32 / natural.0, height as f32 / natural.1);
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    let mut rgba = Vec::with_capacity(pixmap.data().len());
+    for pixel in pixmap.pixels() {
+        let color = pixel.demultiply();
+        rgba.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+
+    let mut png_data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels_per_meter = (dpi / 0.0254).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_meter,
+        yppu: pixels_per_meter,
+        unit: png::Unit::Meter,
+    }));
+    let text = [
+        ("Title", Some(provenance.title_or_id())),
+        ("Description", provenance.description.clone()),
+        ("Source", provenance.source()),
+        ("Comment", provenance.code_context.clone()),
+        ("Creation Time", provenance.created.clone()),
+        ("Software", Some("Compute42".to_string())),
+        ("Provenance", Some(provenance.to_json())),
+    ];
+    for (keyword, value) in text {
+        if let Some(value) = value {
+            encoder
+                .add_itxt_chunk(keyword.to_string(), value)
+                .map_err(|e| format!("Failed to add PNG metadata: {}", e))?;
+        }
+    }
+    let mut writer = encoder.write_header().map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer.write_image_data(&rgba).map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer.finish().map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(png_data)
+}
+
+fn write_pdf(tree: &usvg::Tree, size: (f32, f32), provenance: &PlotProvenance) -> Vec<u8> {
+    // PDF user space is in points, 72 per inch
+    let (width, height) = (size.0 * 72.0 / CSS_DPI, size.1 * 72.0 / CSS_DPI);
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let content_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let plot_id = Ref::new(6);
+    let plot_name = Name(b"Plot");
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids([page_id]).count(1);
+    {
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, width, height));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources().x_objects().pair(plot_name, plot_id);
+    }
+
+    // The converted plot is a unit-sized form, scaled to the page here
+    let mut content = Content::new();
+    content.transform([width, 0.0, 0.0, height, 0.0, 0.0]);
+    content.x_object(plot_name);
+    pdf.stream(content_id, &content.finish());
+
+    let title = provenance.title_or_id();
+    let source = provenance.source();
+    let json = provenance.to_json();
+    {
+        let mut info = pdf.document_info(info_id);
+        info.title(TextStr(&title));
+        if let Some(description) = &provenance.description {
+            info.subject(TextStr(description));
+        }
+        if let Some(source) = &source {
+            info.keywords(TextStr(source));
+        }
+        info.creator(TextStr("Compute42"));
+        info.pair(Name(b"Compute42Provenance"), TextStr(&json));
+    }
+
+    let mut chunk = pdf_writer::Chunk::new();
+    svg2pdf::convert_tree_into(tree, svg2pdf::Options::default(), &mut chunk, plot_id);
+    pdf.extend(&chunk);
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::plot::PlotExportOptions;
+    use std::collections::BTreeMap;
+
+    const SVG: &str = r##"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" width="200" height="100"><rect width="200" height="100" fill="#36c"/></svg>"##;
+
+    fn plot() -> PlotData {
+        PlotData {
+            id: "0f3c9a7e-plot".to_string(),
+            mime_type: "image/svg+xml".to_string(),
+            data: SVG.to_string(),
+            timestamp: 1_700_000_000_000,
+            title: Some("Sales by region".to_string()),
+            description: None,
+            source_file: Some("/work/report.jl".to_string()),
+            line_number: Some(12),
+            code_context: Some("plot(sales)".to_string()),
+            session_id: None,
+            alternatives: BTreeMap::new(),
+        }
+    }
+
+    fn options(format: PlotExportFormat) -> PlotExportOptions {
+        PlotExportOptions::new(format)
+    }
+
+    #[test]
+    fn test_svg_export_resizes_and_embeds_provenance() {
+        let mut opts = options(PlotExportFormat::Svg);
+        opts.width = Some(400.0);
+        let svg = String::from_utf8(export_plot(&plot(), &opts).unwrap()).unwrap();
+        assert!(svg.contains(r#"width="400" height="200" viewBox="0 0 200 100""#));
+        assert!(svg.contains("<title>Sales by region</title>"));
+        assert!(svg.contains("/work/report.jl:12"));
+        assert_eq!(svg.matches("<?xml").count(), 1);
+    }
+
+    #[test]
+    fn test_png_export_uses_dpi() {
+        let mut opts = options(PlotExportFormat::Png);
+        opts.dpi = 192.0;
+        let png = export_plot(&plot(), &opts).unwrap();
+        assert_eq!(raster_size(&png), Some((400, 200)));
+        assert!(png.windows(4).any(|w| w == b"iTXt"));
+    }
+
+    #[test]
+    fn test_pdf_export_has_document_info() {
+        let pdf = export_plot(&plot(), &options(PlotExportFormat::Pdf)).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF"));
+        assert!(text.contains("/Title (Sales by region)"));
+        assert!(text.contains("/MediaBox [0 0 150 75]"));
+    }
+
+    #[test]
+    fn test_raster_plot_exported_through_svg() {
+        let png = export_plot(&plot(), &options(PlotExportFormat::Png)).unwrap();
+        let mut raster = plot();
+        raster.mime_type = "image/png".to_string();
+        raster.data = base64::engine::general_purpose::STANDARD.encode(&png);
+        let mut opts = options(PlotExportFormat::Png);
+        opts.height = Some(50.0);
+        assert_eq!(raster_size(&export_plot(&raster, &opts).unwrap()), Some((100, 50)));
+    }
+
+    #[test]
+    fn test_interactive_plot_uses_image_alternative() {
+        let mut figure = plot();
+        figure.mime_type = "application/vnd.plotly.v1+json".to_string();
+        figure.data = r#"{"data":[]}"#.to_string();
+        assert!(export_plot(&figure, &options(PlotExportFormat::Svg)).is_err());
+        figure.alternatives.insert("image/svg+xml".to_string(), SVG.to_string());
+        assert!(export_plot(&figure, &options(PlotExportFormat::Svg)).is_ok());
+    }
+
+    #[test]
+    fn test_batch_export_to_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("figures.zip");
+        let mut second = plot();
+        second.id = "77aa".to_string();
+        second.title = None;
+        let names = export_plots_to_zip(&[plot(), second], &zip_path, &options(PlotExportFormat::Svg)).unwrap();
+        assert_eq!(names, vec!["001-Sales-by-region-0f3c9a7e.svg", "002-report-77aa.svg"]);
+
+        let archive = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        let mut entries: Vec<&str> = archive.file_names().collect();
+        entries.sort();
+        assert_eq!(entries, vec!["001-Sales-by-region-0f3c9a7e.svg", "002-report-77aa.svg", MANIFEST_FILE]);
+    }
+}
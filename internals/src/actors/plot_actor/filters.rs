@@ -2,6 +2,23 @@
 // This module handles filtering out non-plot data
 
 use log::debug;
+use std::collections::BTreeMap;
+
+pub const PLOTLY_MIME: &str = "application/vnd.plotly.v1+json";
+pub const WEBIO_MIME: &str = "application/vnd.webio.node+json";
+
+/// Vega-Lite and Vega MIME types emitted by VegaLite.jl and friends
+const VEGALITE_MIMES: [&str; 4] = [
+    "application/vnd.vegalite.v5+json",
+    "application/vnd.vegalite.v4+json",
+    "application/vnd.vegalite.v3+json",
+    "application/vnd.vegalite.v2+json",
+];
+const VEGA_MIMES: [&str; 3] = [
+    "application/vnd.vega.v5+json",
+    "application/vnd.vega.v4+json",
+    "application/vnd.vega.v3+json",
+];
 
 /// Check if plot data should be filtered out (not actual plot content)
 pub fn should_filter_plot_data(data: &str, mime_type: &str) -> bool {
@@ -15,12 +32,91 @@ pub fn should_filter_plot_data(data: &str, mime_type: &str) -> bool {
     match mime_type {
         "image/svg+xml" => filter_svg_plot(data),
         "image/png" | "image/jpeg" | "image/jpg" | "image/gif" | "image/webp" => filter_image_plot(data),
-        "text/html" => filter_html_plot(data),
+        "text/html" | "juliavscode/html" => filter_html_plot(data),
         "text/plain" => filter_text_plot(data),
+        _ if is_interactive_mime(mime_type) => match validate_interactive_spec(data, mime_type) {
+            Ok(()) => false,
+            Err(e) => {
+                debug!("[PlotActor] Filtering out invalid {} data: {}", mime_type, e);
+                true
+            }
+        },
         _ => true, // For any other MIME types, be conservative and filter out
     }
 }
 
+/// Whether the MIME type is a JSON spec rendered by a JavaScript plotting library
+pub fn is_interactive_mime(mime_type: &str) -> bool {
+    mime_type == PLOTLY_MIME
+        || mime_type == WEBIO_MIME
+        || VEGALITE_MIMES.contains(&mime_type)
+        || VEGA_MIMES.contains(&mime_type)
+}
+
+/// Check that a JSON plot spec has the shape its renderer expects
+pub fn validate_interactive_spec(data: &str, mime_type: &str) -> Result<(), String> {
+    let spec: serde_json::Value = serde_json::from_str(data).map_err(|e| format!("invalid JSON: {}", e))?;
+    let object = spec.as_object().ok_or("spec is not a JSON object")?;
+    let schema = object.get("$schema").and_then(|s| s.as_str()).unwrap_or("");
+
+    if mime_type == PLOTLY_MIME {
+        // Plotly figures need a trace array; layout and config are optional
+        match object.get("data") {
+            Some(serde_json::Value::Array(_)) => Ok(()),
+            _ => Err("Plotly figure has no `data` array".to_string()),
+        }
+    } else if VEGALITE_MIMES.contains(&mime_type) {
+        let has_view = ["mark", "layer", "concat", "hconcat", "vconcat", "facet", "repeat", "spec"]
+            .iter()
+            .any(|key| object.contains_key(*key));
+        if has_view || schema.contains("vega-lite") {
+            Ok(())
+        } else {
+            Err("Vega-Lite spec has no mark or view composition".to_string())
+        }
+    } else if VEGA_MIMES.contains(&mime_type) {
+        if object.contains_key("marks") || (schema.contains("vega") && !schema.contains("vega-lite")) {
+            Ok(())
+        } else {
+            Err("Vega spec has no marks".to_string())
+        }
+    } else if mime_type == WEBIO_MIME {
+        if object.contains_key("nodeType") || object.contains_key("type") {
+            Ok(())
+        } else {
+            Err("WebIO node has no node type".to_string())
+        }
+    } else {
+        Err(format!("unsupported MIME type {}", mime_type))
+    }
+}
+
+/// Content type to serve plot data with
+pub fn content_type(mime_type: &str) -> String {
+    match mime_type {
+        "text/html" | "juliavscode/html" => "text/html; charset=utf-8".to_string(),
+        "text/plain" => "text/plain; charset=utf-8".to_string(),
+        _ if is_interactive_mime(mime_type) => format!("{}; charset=utf-8", mime_type),
+        _ => mime_type.to_string(),
+    }
+}
+
+/// Keep the alternative representations worth showing alongside the primary one
+///
+/// `text/plain` is always kept as the fallback every notebook front end understands.
+pub fn filter_alternatives(alternatives: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    alternatives
+        .into_iter()
+        .filter(|(mime_type, data)| {
+            if mime_type == "text/plain" {
+                !data.trim().is_empty()
+            } else {
+                !should_filter_plot_data(data, mime_type)
+            }
+        })
+        .collect()
+}
+
 /// Filter SVG plot data
 fn filter_svg_plot(data: &str) -> bool {
     // Filter out very small SVG content that's likely empty
@@ -43,7 +139,8 @@ fn filter_image_plot(data: &str) -> bool {
 fn filter_html_plot(data: &str) -> bool {
     // Filter out if it doesn't contain actual plot content
     !(data.contains("<svg") || data.contains("plotly") || data.contains("chart") || 
-      data.contains("canvas") || data.contains("d3"))
+      data.contains("canvas") || data.contains("d3") || data.contains("vega") ||
+      data.contains("webio") || data.contains("WebIO"))
 }
 
 /// Filter text/plain plot data
@@ -89,3 +186,43 @@ fn is_simple_value(data: &str) -> bool {
     )
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plotly_spec_validation() {
+        let figure = r#"{"data":[{"type":"scatter","x":[1,2],"y":[3,4]}],"layout":{}}"#;
+        assert!(!should_filter_plot_data(figure, PLOTLY_MIME));
+        assert!(should_filter_plot_data(r#"{"layout":{}}"#, PLOTLY_MIME));
+        assert!(should_filter_plot_data("not json", PLOTLY_MIME));
+    }
+
+    #[test]
+    fn test_vega_spec_validation() {
+        let vegalite = r#"{"$schema":"https://vega.github.io/schema/vega-lite/v5.json","mark":"bar"}"#;
+        assert!(!should_filter_plot_data(vegalite, "application/vnd.vegalite.v5+json"));
+        assert!(should_filter_plot_data(r#"{"data":[]}"#, "application/vnd.vegalite.v5+json"));
+        assert!(!should_filter_plot_data(r#"{"marks":[]}"#, "application/vnd.vega.v5+json"));
+        assert!(should_filter_plot_data(vegalite, "application/vnd.vega.v5+json"));
+    }
+
+    #[test]
+    fn test_alternatives_keep_plain_text_fallback() {
+        let alternatives = BTreeMap::from([
+            ("text/plain".to_string(), "Plot{...}".to_string()),
+            ("text/html".to_string(), "<div>hello</div>".to_string()),
+            (PLOTLY_MIME.to_string(), r#"{"data":[]}"#.to_string()),
+        ]);
+        let kept = filter_alternatives(alternatives);
+        assert_eq!(kept.keys().collect::<Vec<_>>(), vec![PLOTLY_MIME, "text/plain"]);
+    }
+
+    #[test]
+    fn test_content_types() {
+        assert_eq!(content_type("juliavscode/html"), "text/html; charset=utf-8");
+        assert_eq!(content_type(PLOTLY_MIME), "application/vnd.plotly.v1+json; charset=utf-8");
+        assert_eq!(content_type("image/png"), "image/png");
+    }
+}
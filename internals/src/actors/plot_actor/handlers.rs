@@ -1,35 +1,40 @@
 // HTTP route handlers for the plot server
 // This module contains the HTTP endpoint handlers
 
+use crate::actors::plot_actor::filters::content_type;
+use crate::actors::plot_actor::storage::PlotStorage;
 use crate::messages::plot::PlotData;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
 use base64::Engine;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 
-/// Get all plots
+/// Query parameters for the plot content endpoint
+#[derive(Debug, Deserialize)]
+pub struct ContentQuery {
+    /// Representation to serve, defaults to the plot's primary MIME type
+    pub mime: Option<String>,
+}
+
+/// Get the metadata of all plots (plot data is served per plot)
 pub async fn get_all_plots(
-    State(plots): State<Arc<Mutex<HashMap<String, PlotData>>>>,
+    State(plots): State<PlotStorage>,
 ) -> Result<Json<Vec<PlotData>>, StatusCode> {
-    let plots_guard = plots.lock().await;
-    let plots_vec: Vec<PlotData> = plots_guard.values().cloned().collect();
-    Ok(Json(plots_vec))
+    Ok(Json(plots.list().await))
 }
 
 /// Get a specific plot by ID
 pub async fn get_plot(
     Path(plot_id): Path<String>,
-    State(plots): State<Arc<Mutex<HashMap<String, PlotData>>>>,
+    State(plots): State<PlotStorage>,
 ) -> Result<Json<PlotData>, StatusCode> {
-    let plots_guard = plots.lock().await;
-    if let Some(plot) = plots_guard.get(&plot_id) {
-        Ok(Json(plot.clone()))
+    if let Some(plot) = plots.get(&plot_id).await {
+        Ok(Json(plot))
     } else {
         Err(StatusCode::NOT_FOUND)
     }
@@ -38,10 +43,9 @@ pub async fn get_plot(
 /// Delete a plot by ID
 pub async fn delete_plot(
     Path(plot_id): Path<String>,
-    State(plots): State<Arc<Mutex<HashMap<String, PlotData>>>>,
+    State(plots): State<PlotStorage>,
 ) -> StatusCode {
-    let mut plots_guard = plots.lock().await;
-    if plots_guard.remove(&plot_id).is_some() {
+    if plots.remove(&plot_id).await {
         StatusCode::OK
     } else {
         StatusCode::NOT_FOUND
@@ -56,17 +60,16 @@ pub async fn health_check() -> impl IntoResponse {
 /// Get plot image endpoint
 pub async fn get_plot_image(
     Path(plot_id): Path<String>,
-    State(plots): State<Arc<Mutex<HashMap<String, PlotData>>>>,
+    State(plots): State<PlotStorage>,
 ) -> Result<axum::response::Response<axum::body::Body>, StatusCode> {
-    let plots_guard = plots.lock().await;
-    if let Some(plot) = plots_guard.get(&plot_id) {
+    if let Some(plot) = plots.get(&plot_id).await {
         if plot.mime_type.starts_with("image/") {
             // For SVG data, return the SVG content with proper content type
             if plot.mime_type == "image/svg+xml" {
                 let response = axum::response::Response::builder()
                     .status(StatusCode::OK)
                     .header("Content-Type", "image/svg+xml")
-                    .body(axum::body::Body::from(plot.data.clone()))
+                    .body(axum::body::Body::from(plot.data))
                     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
                 Ok(response)
             } else {
@@ -91,3 +94,39 @@ pub async fn get_plot_image(
     }
 }
 
+
+/// Get plot content endpoint, serving any representation with its own content type
+pub async fn get_plot_content(
+    Path(plot_id): Path<String>,
+    Query(query): Query<ContentQuery>,
+    State(plots): State<PlotStorage>,
+) -> Result<axum::response::Response<axum::body::Body>, StatusCode> {
+    let plot = plots.get(&plot_id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mime_type = query.mime.unwrap_or_else(|| plot.mime_type.clone());
+    let mut bundle = plot.mime_bundle();
+    let data = bundle.remove(&mime_type).ok_or(StatusCode::NOT_FOUND)?;
+
+    // Raster images travel base64 encoded, everything else is text
+    let body = if mime_type.starts_with("image/") && mime_type != "image/svg+xml" {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&data)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        axum::body::Body::from(bytes)
+    } else {
+        axum::body::Body::from(data)
+    };
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", content_type(&mime_type))
+        .body(body)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Get all representations of a plot as a MIME bundle
+pub async fn get_plot_bundle(
+    Path(plot_id): Path<String>,
+    State(plots): State<PlotStorage>,
+) -> Result<Json<BTreeMap<String, String>>, StatusCode> {
+    let plot = plots.get(&plot_id).await.ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(plot.mime_bundle()))
+}
@@ -1,6 +1,7 @@
 // PlotActor - manages plot server lifecycle and plot data
 // This actor handles all plot-related functionality including HTTP server, storage, and filtering
 
+mod export;
 mod filters;
 mod handlers;
 mod server;
@@ -17,9 +18,15 @@ use crate::service_traits::{PlotService as PlotServiceTrait, EventEmitter};
 use crate::types::PlotServerInfo;
 
 use self::filters::should_filter_plot_data;
+pub use self::filters::filter_alternatives;
 use self::server::PlotServer;
 use self::storage::PlotStorage;
 
+/// URL serving a plot's data with its own content type
+fn content_url(port: u16, plot_id: &str) -> String {
+    format!("http://127.0.0.1:{}/plots/{}/content", port, plot_id)
+}
+
 /// PlotActor - manages plot server lifecycle and plot data
 /// This replaces the mutex-based PlotServer with a clean actor model
 #[derive(Clone)]
@@ -95,12 +102,22 @@ impl PlotActor {
             line_number: plot_data_json["line_number"].as_u64().map(|n| n as u32),
             code_context: plot_data_json["code_context"].as_str().map(|s| s.to_string()),
             session_id: plot_data_json["session_id"].as_str().map(|s| s.to_string()),
+            alternatives: plot_data_json["alternatives"]
+                .as_object()
+                .map(|alternatives| {
+                    alternatives
+                        .iter()
+                        .filter_map(|(mime, data)| Some((mime.clone(), data.as_str()?.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default(),
         };
+        let alternative_mimes: Vec<String> = plot_data_struct.alternatives.keys().cloned().collect();
 
         debug!("[PlotActor] Processing plot: {}", id);
 
         // Add plot to storage
-        self.plot_storage.insert(plot_data_struct.clone()).await;
+        self.store_plot(plot_data_struct).await?;
 
         // Get the plot port for URL generation - only create URL if server is running
         let port_opt = self.plot_server.get_port().await;
@@ -113,6 +130,8 @@ impl PlotActor {
                 "id": id,
                 "mime_type": mime_type,
                 "image_url": image_url,
+                "content_url": content_url(port, &id),
+                "alternative_mime_types": alternative_mimes,
                 "timestamp": timestamp,
                 "title": plot_data_json["title"],
                 "description": plot_data_json["description"],
@@ -128,6 +147,7 @@ impl PlotActor {
                 "id": id,
                 "mime_type": mime_type,
                 "data": data,
+                "alternative_mime_types": alternative_mimes,
                 "timestamp": timestamp,
                 "title": plot_data_json["title"],
                 "description": plot_data_json["description"],
@@ -162,9 +182,51 @@ impl PlotActor {
         }
     }
 
+    /// Store a plot, notifying the frontend of plots evicted by the retention limits
+    async fn store_plot(&self, plot_data: PlotData) -> Result<(), String> {
+        let evicted = self.plot_storage.insert(plot_data).await?;
+        for plot_id in evicted {
+            debug!("[PlotActor] Evicted plot {} from history", plot_id);
+            self.event_manager.emit_plot_deleted(&plot_id).await.ok();
+        }
+        Ok(())
+    }
+
+    /// Change the retention limits, notifying the frontend of plots evicted to fit them
+    async fn set_retention(&self, retention: PlotRetention) -> Result<(), String> {
+        let evicted = self.plot_storage.set_retention(retention).await?;
+        for plot_id in evicted {
+            debug!("[PlotActor] Evicted plot {} from history", plot_id);
+            self.event_manager.emit_plot_deleted(&plot_id).await.ok();
+        }
+        Ok(())
+    }
+
+    /// Switch to the plot history of a project, or keep plots in memory when None
+    async fn open_project_history(&self, project_path: Option<String>) -> Result<(), String> {
+        match project_path {
+            Some(path) => {
+                let dir = PlotStorage::project_store_dir(&path)?;
+                debug!("[PlotActor] Opening plot history for {} at {}", path, dir.display());
+                self.plot_storage.open(dir).await?;
+            }
+            None => self.plot_storage.close().await,
+        }
+
+        // Let the frontend reload the plot list
+        let navigator_update = serde_json::json!({
+            "event_type": "PlotNavigatorUpdate",
+            "plot_data": serde_json::Value::Null
+        });
+        if let Err(e) = self.event_emitter.emit("julia-plot", navigator_update).await {
+            error!("[PlotActor] Failed to emit plot history update: {}", e);
+        }
+        Ok(())
+    }
+
     /// Update all plot URLs when server restarts on a new port
     async fn update_plot_urls_for_new_port_internal(&self, new_port: u16) -> Result<(), String> {
-        let plots = self.plot_storage.get_all().await;
+        let plots = self.plot_storage.list().await;
 
         if plots.is_empty() {
             debug!("[PlotActor] Server restarted on port {}, no plots to update", new_port);
@@ -183,6 +245,7 @@ impl PlotActor {
                 "id": plot.id,
                 "mime_type": plot.mime_type,
                 "image_url": image_url,
+                "content_url": content_url(new_port, &plot.id),
                 "timestamp": plot.timestamp,
                 "title": plot.title,
                 "description": plot.description,
@@ -227,7 +290,7 @@ impl PlotServiceTrait for PlotActor {
         let (port, listener) = self.plot_server.find_available_port().await?;
 
         // Get plots storage for the server
-        let plots = self.plot_storage.clone_plots();
+        let plots = self.plot_storage.clone();
 
         // Start the HTTP server with the pre-bound listener
         self.plot_server.start_with_listener(port, listener, plots).await?;
@@ -252,12 +315,12 @@ impl PlotServiceTrait for PlotActor {
     }
 
     async fn add_plot(&self, plot_data: PlotData) -> Result<(), String> {
-        self.plot_storage.insert(plot_data).await;
-        Ok(())
+        self.store_plot(plot_data).await
     }
 
     async fn get_plots(&self) -> Result<Vec<PlotData>, String> {
-        let plots = self.plot_storage.get_all().await;
+        // Metadata only: the frontend loads images by URL and plot data through GetPlot
+        let plots = self.plot_storage.list().await;
         let port_opt = self.plot_server.get_port().await;
         
         // Log warning if plots exist but server is not running (plots won't have image URLs)
@@ -292,16 +355,20 @@ impl PlotServiceTrait for PlotActor {
     }
 
     async fn save_plot_to_file(&self, plot: &PlotData, file_path: &str) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(plot)
-            .map_err(|e| format!("Failed to serialize plot: {}", e))?;
-        tokio::fs::write(file_path, json)
+        let format = PlotExportFormat::from_path(file_path)
+            .ok_or_else(|| format!("Unsupported plot export format: {}", file_path))?;
+        let options = PlotExportOptions::new(format);
+        let plot = plot.clone();
+        let bytes = tokio::task::spawn_blocking(move || export::export_plot(&plot, &options))
+            .await
+            .map_err(|e| format!("Plot export task failed: {}", e))??;
+        tokio::fs::write(file_path, bytes)
             .await
             .map_err(|e| format!("Failed to write plot file: {}", e))
     }
 
     async fn update_plot(&self, plot: &PlotData) -> Result<(), String> {
-        self.plot_storage.insert(plot.clone()).await;
-        Ok(())
+        self.store_plot(plot.clone()).await
     }
 
     async fn update_plot_urls_for_new_port(&self, new_port: u16) -> Result<(), String> {
@@ -548,3 +615,155 @@ impl Handler<HandlePlotDataReceived> for PlotActor {
         )
     }
 }
+
+impl Handler<GetPlot> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<Option<PlotData>, String>>;
+
+    fn handle(&mut self, msg: GetPlot, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received GetPlot message for plot {}", msg.plot_id);
+        let plot_storage = self.plot_storage.clone();
+
+        Box::pin(
+            async move {
+                Ok(plot_storage.get(&msg.plot_id).await)
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<GetPlotsBySourceFile> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<Vec<PlotData>, String>>;
+
+    fn handle(&mut self, msg: GetPlotsBySourceFile, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received GetPlotsBySourceFile message for {}", msg.source_file);
+        let plot_storage = self.plot_storage.clone();
+
+        Box::pin(
+            async move {
+                Ok(plot_storage.list_by_source_file(&msg.source_file).await)
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<SetPlotProject> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: SetPlotProject, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received SetPlotProject message: {:?}", msg.project_path);
+        let plot_actor = self.clone();
+
+        Box::pin(
+            async move {
+                plot_actor.open_project_history(msg.project_path).await
+            }
+            .into_actor(self)
+            .map(|res, actor, _| {
+                match res {
+                    Ok(_) => {
+                        // Local state is refreshed on the next GetPlots
+                        actor.plots.clear();
+                        Ok(())
+                    }
+                    Err(e) => {
+                        error!("PlotActor: Failed to open plot history: {}", e);
+                        Err(e)
+                    }
+                }
+            })
+        )
+    }
+}
+
+impl Handler<SetPlotRetention> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: SetPlotRetention, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received SetPlotRetention message: {:?}", msg.retention);
+        let plot_actor = self.clone();
+
+        Box::pin(
+            async move {
+                plot_actor.set_retention(msg.retention).await
+            }
+            .into_actor(self)
+            .map(|res, actor, _| {
+                match res {
+                    Ok(_) => {
+                        // Local state is refreshed on the next GetPlots
+                        actor.plots.clear();
+                        Ok(())
+                    }
+                    Err(e) => {
+                        error!("PlotActor: Failed to change plot retention: {}", e);
+                        Err(e)
+                    }
+                }
+            })
+        )
+    }
+}
+
+impl Handler<ExportPlot> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: ExportPlot, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received ExportPlot message for plot {} to {}", msg.plot_id, msg.file_path);
+        let plot_storage = self.plot_storage.clone();
+
+        Box::pin(
+            async move {
+                let plot = plot_storage
+                    .get(&msg.plot_id)
+                    .await
+                    .ok_or_else(|| format!("Plot {} not found", msg.plot_id))?;
+                let options = msg.options;
+                let bytes = tokio::task::spawn_blocking(move || export::export_plot(&plot, &options))
+                    .await
+                    .map_err(|e| format!("Plot export task failed: {}", e))??;
+                tokio::fs::write(&msg.file_path, bytes)
+                    .await
+                    .map_err(|e| format!("Failed to write plot file: {}", e))
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<ExportPlots> for PlotActor {
+    type Result = ResponseActFuture<Self, Result<Vec<String>, String>>;
+
+    fn handle(&mut self, msg: ExportPlots, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!("PlotActor: Received ExportPlots message to {}", msg.destination);
+        let plot_storage = self.plot_storage.clone();
+
+        Box::pin(
+            async move {
+                // Load plot data one plot at a time so the store is not locked for the whole export
+                let mut plots = Vec::new();
+                for meta in plot_storage
+                    .list_filtered(msg.source_file.as_deref(), msg.session_id.as_deref())
+                    .await
+                {
+                    if let Some(plot) = plot_storage.get(&meta.id).await {
+                        plots.push(plot);
+                    }
+                }
+                let destination = std::path::PathBuf::from(msg.destination);
+                let options = msg.options;
+                tokio::task::spawn_blocking(move || {
+                    if msg.zip {
+                        export::export_plots_to_zip(&plots, &destination, &options)
+                    } else {
+                        export::export_plots_to_dir(&plots, &destination, &options)
+                    }
+                })
+                .await
+                .map_err(|e| format!("Plot export task failed: {}", e))?
+            }
+            .into_actor(self)
+        )
+    }
+}
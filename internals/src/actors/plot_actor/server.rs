@@ -2,14 +2,13 @@
 // This module handles starting, stopping, and managing the plot HTTP server
 
 use crate::actors::plot_actor::handlers;
-use crate::messages::plot::PlotData;
+use crate::actors::plot_actor::storage::PlotStorage;
 use crate::service_traits::EventEmitter;
 use axum::{
     routing::{delete, get},
     Router,
 };
 use log::{debug, error};
-use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::sync::Mutex;
@@ -38,7 +37,7 @@ impl PlotServer {
         &self,
         _port: u16,
         listener: TcpListener,
-        plots: Arc<Mutex<HashMap<String, PlotData>>>,
+        plots: PlotStorage,
     ) -> Result<(), String> {
         let event_emitter = self.event_emitter.clone();
         let plot_port = self.plot_port.clone();
@@ -58,6 +57,8 @@ impl PlotServer {
             .route("/plots/:plot_id", get(handlers::get_plot))
             .route("/plots/:plot_id", delete(handlers::delete_plot))
             .route("/plots/:plot_id/image", get(handlers::get_plot_image))
+            .route("/plots/:plot_id/content", get(handlers::get_plot_content))
+            .route("/plots/:plot_id/bundle", get(handlers::get_plot_bundle))
             .route("/health", get(handlers::health_check))
             .layer(cors)
             .with_state(plots);
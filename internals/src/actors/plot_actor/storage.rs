@@ -1,65 +1,522 @@
 // Plot storage management
 // This module handles storing and retrieving plot data
+//
+// Plots are kept per project on disk: `index.json` holds the metadata of every
+// plot ordered by timestamp and `blobs/` holds the plot data, one file per plot
+// plus a JSON file with its alternative representations when it has any.
+// Only the index is kept in memory; blobs are read when a plot is requested.
+// Until a project is opened, plots are kept in memory only.
 
-use crate::messages::plot::PlotData;
-use std::collections::HashMap;
+use crate::messages::plot::{PlotData, PlotRetention};
+use crate::services::base::file_utils::fnv1a_hex;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+const INDEX_FILE: &str = "index.json";
+const BLOB_DIR: &str = "blobs";
+const INDEX_VERSION: u32 = 1;
+const ALTERNATIVES_SUFFIX: &str = ".alternatives.json";
+
+/// Plot metadata as stored in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PlotRecord {
+    id: String,
+    mime_type: String,
+    timestamp: i64,
+    title: Option<String>,
+    description: Option<String>,
+    source_file: Option<String>,
+    line_number: Option<u32>,
+    code_context: Option<String>,
+    session_id: Option<String>,
+    /// Blob file name under `blobs/`
+    blob: String,
+    /// MIME types of the alternative representations stored next to the blob
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    alternatives: Vec<String>,
+    /// Size of the plot data and its alternatives in bytes
+    size: u64,
+}
+
+impl PlotRecord {
+    fn new(plot: &PlotData, blob: String) -> Self {
+        Self {
+            id: plot.id.clone(),
+            mime_type: plot.mime_type.clone(),
+            timestamp: plot.timestamp,
+            title: plot.title.clone(),
+            description: plot.description.clone(),
+            source_file: plot.source_file.clone(),
+            line_number: plot.line_number,
+            code_context: plot.code_context.clone(),
+            session_id: plot.session_id.clone(),
+            blob,
+            alternatives: plot.alternatives.keys().cloned().collect(),
+            size: (plot.data.len() + plot.alternatives.values().map(String::len).sum::<usize>()) as u64,
+        }
+    }
+
+    fn alternatives_file(&self) -> Option<String> {
+        (!self.alternatives.is_empty()).then(|| format!("{}{}", self.blob, ALTERNATIVES_SUFFIX))
+    }
+
+    fn to_plot(&self, data: String) -> PlotData {
+        PlotData {
+            id: self.id.clone(),
+            mime_type: self.mime_type.clone(),
+            data,
+            timestamp: self.timestamp,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            source_file: self.source_file.clone(),
+            line_number: self.line_number,
+            code_context: self.code_context.clone(),
+            session_id: self.session_id.clone(),
+            alternatives: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PlotIndex {
+    version: u32,
+    plots: Vec<PlotRecord>,
+}
+
+#[derive(Default)]
+struct StoreState {
+    /// Directory of the open project store, None while plots are kept in memory
+    dir: Option<PathBuf>,
+    /// Plot metadata ordered by timestamp, oldest first
+    records: Vec<PlotRecord>,
+    /// Plot data by file name when no store is open
+    memory_blobs: HashMap<String, String>,
+    retention: PlotRetention,
+}
+
+impl StoreState {
+    fn position(&self, plot_id: &str) -> Option<usize> {
+        self.records.iter().position(|r| r.id == plot_id)
+    }
+
+    /// Load a plot's data and alternative representations
+    async fn load(&self, record: &PlotRecord) -> Option<PlotData> {
+        let data = self.read_file(&record.blob).await?;
+        let mut plot = record.to_plot(data);
+        if let Some(file) = record.alternatives_file() {
+            plot.alternatives = self
+                .read_file(&file)
+                .await
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+        }
+        Some(plot)
+    }
+
+    async fn save(&mut self, record: &PlotRecord, plot: PlotData) -> Result<(), String> {
+        if let Some(file) = record.alternatives_file() {
+            let json = serde_json::to_string(&plot.alternatives)
+                .map_err(|e| format!("Failed to serialize plot alternatives: {}", e))?;
+            self.write_file(&file, json).await?;
+        }
+        self.write_file(&record.blob, plot.data).await
+    }
+
+    async fn discard(&mut self, record: &PlotRecord) {
+        self.remove_file(&record.blob).await;
+        if let Some(file) = record.alternatives_file() {
+            self.remove_file(&file).await;
+        }
+    }
+
+    async fn read_file(&self, name: &str) -> Option<String> {
+        match &self.dir {
+            Some(dir) => match tokio::fs::read_to_string(dir.join(BLOB_DIR).join(name)).await {
+                Ok(data) => Some(data),
+                Err(e) => {
+                    warn!("[PlotStorage] Failed to read plot data {}: {}", name, e);
+                    None
+                }
+            },
+            None => self.memory_blobs.get(name).cloned(),
+        }
+    }
+
+    async fn write_file(&mut self, name: &str, data: String) -> Result<(), String> {
+        match &self.dir {
+            Some(dir) => tokio::fs::write(dir.join(BLOB_DIR).join(name), data)
+                .await
+                .map_err(|e| format!("Failed to write plot data: {}", e)),
+            None => {
+                self.memory_blobs.insert(name.to_string(), data);
+                Ok(())
+            }
+        }
+    }
+
+    async fn remove_file(&mut self, name: &str) {
+        match &self.dir {
+            Some(dir) => {
+                if let Err(e) = tokio::fs::remove_file(dir.join(BLOB_DIR).join(name)).await {
+                    warn!("[PlotStorage] Failed to remove plot data {}: {}", name, e);
+                }
+            }
+            None => {
+                self.memory_blobs.remove(name);
+            }
+        }
+    }
+
+    async fn save_index(&self) -> Result<(), String> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        let index = PlotIndex {
+            version: INDEX_VERSION,
+            plots: self.records.clone(),
+        };
+        let json = serde_json::to_string_pretty(&index)
+            .map_err(|e| format!("Failed to serialize plot index: {}", e))?;
+        // Write to a temporary file first so a crash never leaves a truncated index
+        let tmp = dir.join(format!("{}.tmp", INDEX_FILE));
+        tokio::fs::write(&tmp, json)
+            .await
+            .map_err(|e| format!("Failed to write plot index: {}", e))?;
+        tokio::fs::rename(&tmp, dir.join(INDEX_FILE))
+            .await
+            .map_err(|e| format!("Failed to write plot index: {}", e))
+    }
+
+    async fn insert(&mut self, plot: PlotData) -> Result<Vec<String>, String> {
+        if let Some(index) = self.position(&plot.id) {
+            let old = self.records.remove(index);
+            self.discard(&old).await;
+        }
+
+        let record = PlotRecord::new(&plot, uuid::Uuid::new_v4().to_string());
+        let plot_id = plot.id.clone();
+        self.save(&record, plot).await?;
+        // Plots with equal timestamps keep their insertion order
+        let at = self.records.partition_point(|r| r.timestamp <= record.timestamp);
+        self.records.insert(at, record);
+        Ok(self.enforce_retention(&plot_id).await)
+    }
+
+    /// Drop the oldest plots until the retention limits hold, never evicting `keep`
+    async fn enforce_retention(&mut self, keep: &str) -> Vec<String> {
+        let mut evicted = Vec::new();
+        let mut total: u64 = self.records.iter().map(|r| r.size).sum();
+        while self.records.len() > self.retention.max_plots || total > self.retention.max_total_bytes {
+            let Some(index) = self.records.iter().position(|r| r.id != keep) else {
+                break;
+            };
+            let record = self.records.remove(index);
+            total -= record.size;
+            self.discard(&record).await;
+            evicted.push(record.id);
+        }
+        evicted
+    }
+}
+
 /// Plot storage structure
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct PlotStorage {
-    plots: Arc<Mutex<HashMap<String, PlotData>>>,
+    state: Arc<Mutex<StoreState>>,
 }
 
 impl PlotStorage {
-    /// Create a new PlotStorage instance
+    /// Create a new PlotStorage instance that keeps plots in memory
     pub fn new() -> Self {
-        Self {
-            plots: Arc::new(Mutex::new(HashMap::new())),
+        Self::default()
+    }
+
+    /// Directory holding the plot history of a project
+    pub fn project_store_dir(project_path: &str) -> Result<PathBuf, String> {
+        let base_dir = if let Ok(test_dir) = std::env::var("COMPUTE42_TEST_DATA_DIR") {
+            PathBuf::from(test_dir)
+        } else {
+            dirs::data_local_dir()
+                .ok_or("Could not determine app data directory")?
+                .join("com.compute42.dev")
+        };
+        let name = Path::new(project_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("project");
+        Ok(base_dir
+            .join("plots")
+            .join(format!("{}-{}", name, fnv1a_hex(project_path.as_bytes()))))
+    }
+
+    /// Open the on-disk store at `dir`, replacing the plots currently held
+    ///
+    /// Plots held in memory because no store was open yet are moved into it.
+    pub async fn open(&self, dir: PathBuf) -> Result<(), String> {
+        std::fs::create_dir_all(dir.join(BLOB_DIR))
+            .map_err(|e| format!("Failed to create plot store directory: {}", e))?;
+
+        let mut records = match tokio::fs::read_to_string(dir.join(INDEX_FILE)).await {
+            Ok(content) => match serde_json::from_str::<PlotIndex>(&content) {
+                Ok(index) => index.plots,
+                Err(e) => {
+                    warn!("[PlotStorage] Ignoring unreadable plot index in {}: {}", dir.display(), e);
+                    Vec::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read plot index: {}", e)),
+        };
+        // Drop entries whose data is gone, e.g. after a crash between writes
+        records.retain(|r| dir.join(BLOB_DIR).join(&r.blob).is_file());
+        records.sort_by_key(|r| r.timestamp);
+
+        let mut state = self.state.lock().await;
+        let mut pending = Vec::new();
+        if state.dir.is_none() {
+            for record in &state.records {
+                if let Some(plot) = state.load(record).await {
+                    pending.push(plot);
+                }
+            }
+        }
+
+        state.dir = Some(dir);
+        state.records = records;
+        state.memory_blobs.clear();
+        for plot in pending {
+            state.insert(plot).await?;
+        }
+        debug!("[PlotStorage] Opened plot store with {} plots", state.records.len());
+        state.save_index().await
+    }
+
+    /// Stop writing to disk and keep new plots in memory
+    pub async fn close(&self) {
+        let mut state = self.state.lock().await;
+        state.dir = None;
+        state.records.clear();
+        state.memory_blobs.clear();
+    }
+
+    /// Change the retention limits, returning the IDs of plots evicted to fit them
+    pub async fn set_retention(&self, retention: PlotRetention) -> Result<Vec<String>, String> {
+        retention.validate()?;
+        let mut state = self.state.lock().await;
+        state.retention = retention;
+        let evicted = state.enforce_retention("").await;
+        if !evicted.is_empty() {
+            state.save_index().await?;
         }
+        Ok(evicted)
     }
 
-    /// Add or update a plot
-    pub async fn insert(&self, plot: PlotData) {
-        let mut plots_guard = self.plots.lock().await;
-        plots_guard.insert(plot.id.clone(), plot);
+    /// Add or update a plot, returning the IDs of plots evicted to make room
+    pub async fn insert(&self, plot: PlotData) -> Result<Vec<String>, String> {
+        let mut state = self.state.lock().await;
+        let evicted = state.insert(plot).await?;
+        state.save_index().await?;
+        Ok(evicted)
     }
 
-    /// Get a plot by ID
-    #[allow(dead_code)]
+    /// Get a plot by ID, loading its data
     pub async fn get(&self, plot_id: &str) -> Option<PlotData> {
-        let plots_guard = self.plots.lock().await;
-        plots_guard.get(plot_id).cloned()
+        let state = self.state.lock().await;
+        let record = state.records.iter().find(|r| r.id == plot_id)?;
+        state.load(record).await
     }
 
-    /// Get all plots
-    pub async fn get_all(&self) -> Vec<PlotData> {
-        let plots_guard = self.plots.lock().await;
-        plots_guard.values().cloned().collect()
+    /// Get plot metadata ordered by timestamp, without loading plot data or alternatives
+    ///
+    /// Plot data is loaded one plot at a time through `get`.
+    pub async fn list(&self) -> Vec<PlotData> {
+        self.list_matching(|_| true).await
+    }
+
+    /// Metadata of the plots created from a source file, ordered by timestamp
+    pub async fn list_by_source_file(&self, source_file: &str) -> Vec<PlotData> {
+        self.list_matching(|plot| plot.source_file.as_deref() == Some(source_file)).await
+    }
+
+    /// Metadata of the plots of a session and/or source file, ordered by timestamp
+    pub async fn list_filtered(&self, source_file: Option<&str>, session_id: Option<&str>) -> Vec<PlotData> {
+        self.list_matching(|plot| {
+            source_file.is_none_or(|file| plot.source_file.as_deref() == Some(file))
+                && session_id.is_none_or(|session| plot.session_id.as_deref() == Some(session))
+        })
+        .await
+    }
+
+    async fn list_matching(&self, filter: impl Fn(&PlotData) -> bool) -> Vec<PlotData> {
+        let state = self.state.lock().await;
+        state
+            .records
+            .iter()
+            .map(|r| r.to_plot(String::new()))
+            .filter(|plot| filter(plot))
+            .collect()
     }
 
     /// Remove a plot by ID
     pub async fn remove(&self, plot_id: &str) -> bool {
-        let mut plots_guard = self.plots.lock().await;
-        plots_guard.remove(plot_id).is_some()
+        let mut state = self.state.lock().await;
+        let Some(index) = state.position(plot_id) else {
+            return false;
+        };
+        let record = state.records.remove(index);
+        state.discard(&record).await;
+        if let Err(e) = state.save_index().await {
+            warn!("[PlotStorage] {}", e);
+        }
+        true
     }
 
     /// Clear all plots
     pub async fn clear(&self) {
-        let mut plots_guard = self.plots.lock().await;
-        plots_guard.clear();
+        let mut state = self.state.lock().await;
+        let records = std::mem::take(&mut state.records);
+        for record in records {
+            state.discard(&record).await;
+        }
+        if let Err(e) = state.save_index().await {
+            warn!("[PlotStorage] {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plot(id: &str, timestamp: i64, data: &str) -> PlotData {
+        PlotData {
+            id: id.to_string(),
+            mime_type: "image/svg+xml".to_string(),
+            data: data.to_string(),
+            timestamp,
+            title: None,
+            description: None,
+            source_file: Some(format!("/work/{}.jl", id)),
+            line_number: None,
+            code_context: None,
+            session_id: None,
+            alternatives: BTreeMap::new(),
+        }
     }
 
-    /// Get a clone of the plots HashMap for use in HTTP handlers
-    pub fn clone_plots(&self) -> Arc<Mutex<HashMap<String, PlotData>>> {
-        self.plots.clone()
+    fn ids(plots: &[PlotData]) -> Vec<&str> {
+        plots.iter().map(|p| p.id.as_str()).collect()
     }
-}
 
-impl Default for PlotStorage {
-    fn default() -> Self {
-        Self::new()
+    #[tokio::test]
+    async fn test_plots_ordered_by_timestamp() {
+        let storage = PlotStorage::new();
+        storage.insert(plot("b", 20, "<svg/>")).await.unwrap();
+        storage.insert(plot("a", 10, "<svg/>")).await.unwrap();
+        storage.insert(plot("c", 20, "<svg/>")).await.unwrap();
+        assert_eq!(ids(&storage.list().await), vec!["a", "b", "c"]);
     }
-}
 
+    #[tokio::test]
+    async fn test_history_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = PlotStorage::new();
+        storage.insert(plot("early", 1, "<svg>early</svg>")).await.unwrap();
+        storage.open(dir.path().to_path_buf()).await.unwrap();
+        storage.insert(plot("later", 2, "<svg>later</svg>")).await.unwrap();
+
+        let reopened = PlotStorage::new();
+        reopened.open(dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(ids(&reopened.list().await), vec!["early", "later"]);
+        assert!(reopened.list().await.iter().all(|p| p.data.is_empty()));
+        assert_eq!(reopened.get("later").await.unwrap().data, "<svg>later</svg>");
+        assert_eq!(ids(&reopened.list_by_source_file("/work/early.jl").await), vec!["early"]);
+
+        assert!(reopened.remove("early").await);
+        let again = PlotStorage::new();
+        again.open(dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(ids(&again.list().await), vec!["later"]);
+        assert_eq!(std::fs::read_dir(dir.path().join(BLOB_DIR)).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retention_evicts_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = PlotStorage::new();
+        storage.open(dir.path().to_path_buf()).await.unwrap();
+        storage.set_retention(PlotRetention { max_plots: 2, max_total_bytes: 10 }).await.unwrap();
+
+        storage.insert(plot("one", 1, "1234")).await.unwrap();
+        storage.insert(plot("two", 2, "1234")).await.unwrap();
+        assert_eq!(storage.insert(plot("three", 3, "1234")).await.unwrap(), vec!["one"]);
+        assert_eq!(storage.insert(plot("big", 4, "123456789")).await.unwrap(), vec!["two", "three"]);
+        assert_eq!(ids(&storage.list().await), vec!["big"]);
+    }
+
+    #[tokio::test]
+    async fn test_lowered_retention_evicts_existing_plots() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = PlotStorage::new();
+        storage.open(dir.path().to_path_buf()).await.unwrap();
+        for (id, timestamp) in [("one", 1), ("two", 2), ("three", 3)] {
+            storage.insert(plot(id, timestamp, "1234")).await.unwrap();
+        }
+
+        let evicted = storage.set_retention(PlotRetention { max_plots: 2, max_total_bytes: 1024 }).await.unwrap();
+        assert_eq!(evicted, vec!["one"]);
+        let evicted = storage.set_retention(PlotRetention { max_plots: 2, max_total_bytes: 4 }).await.unwrap();
+        assert_eq!(evicted, vec!["two"]);
+        assert!(storage.set_retention(PlotRetention { max_plots: 0, max_total_bytes: 4 }).await.is_err());
+
+        // The new limits apply to later plots, and evictions are written to the index
+        assert_eq!(storage.insert(plot("four", 4, "1234")).await.unwrap(), vec!["three"]);
+        let reopened = PlotStorage::new();
+        reopened.open(dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(ids(&reopened.list().await), vec!["four"]);
+        assert_eq!(std::fs::read_dir(dir.path().join(BLOB_DIR)).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_alternatives_stored_next_to_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = PlotStorage::new();
+        storage.open(dir.path().to_path_buf()).await.unwrap();
+        let mut figure = plot("figure", 1, r#"{"data":[]}"#);
+        figure.mime_type = "application/vnd.plotly.v1+json".to_string();
+        figure.alternatives.insert("text/plain".to_string(), "Plot".to_string());
+        storage.insert(figure).await.unwrap();
+
+        let reopened = PlotStorage::new();
+        reopened.open(dir.path().to_path_buf()).await.unwrap();
+        assert!(reopened.list().await[0].alternatives.is_empty());
+        let loaded = reopened.get("figure").await.unwrap();
+        assert_eq!(loaded.alternatives.get("text/plain").map(String::as_str), Some("Plot"));
+        assert_eq!(loaded.mime_bundle().len(), 2);
+
+        assert!(reopened.remove("figure").await);
+        assert_eq!(std::fs::read_dir(dir.path().join(BLOB_DIR)).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_replaces_plot() {
+        let storage = PlotStorage::new();
+        storage.insert(plot("a", 1, "old")).await.unwrap();
+        storage.insert(plot("a", 1, "new")).await.unwrap();
+        assert_eq!(storage.list().await.len(), 1);
+        assert_eq!(storage.get("a").await.unwrap().data, "new");
+    }
+
+    #[test]
+    fn test_project_store_dir_is_stable() {
+        let first = PlotStorage::project_store_dir("/work/Analysis").unwrap();
+        let second = PlotStorage::project_store_dir("/work/Analysis").unwrap();
+        let other = PlotStorage::project_store_dir("/other/Analysis").unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(first.file_name().unwrap().to_str().unwrap().starts_with("Analysis-"));
+    }
+}
@@ -1,4 +1,7 @@
-use log::debug;
+use actix::Addr;
+use log::{debug, warn};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
@@ -9,36 +12,117 @@ use super::session::PersistentJuliaSession;
 use super::file_creation;
 use super::setup;
 use super::output_monitoring;
+use super::sysimage::SysimageManager;
+use crate::actors::ConfigurationActor;
+use crate::messages::configuration::GetActiveLaunchProfile;
 use crate::service_traits::EventEmitter;
+use crate::types::JuliaLaunchProfile;
 
 /// Start Julia process with communication setup
 pub async fn start_julia_with_communication(
     state: Arc<ProcessState>,
     event_emitter: Arc<dyn EventEmitter>,
     julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
+    sysimage_manager: Arc<SysimageManager>,
+    config_actor: Option<Addr<ConfigurationActor>>,
 ) -> Result<(), String> {
     // Create Julia files from embedded sources
     file_creation::create_julia_files(state.as_ref()).await?;
 
+    let project_path = sysimage_manager.current_project().await;
+    let profile = resolve_launch_profile(config_actor, project_path.map(|p| p.to_string_lossy().to_string())).await;
+    // Sysimages are validated and built against the depot the kernel will use
+    sysimage_manager.set_depot_path(julia_depot_path(&profile)?).await;
+
+    // Use the project's custom sysimage if one is built and loads; otherwise fall back to the default
+    if let Some(sysimage) = sysimage_manager.select_for_launch().await {
+        let (to_julia_pipe, from_julia_pipe) = state.generate_pipe_names();
+        match try_start_julia(
+            state.clone(),
+            event_emitter.clone(),
+            julia_session.clone(),
+            &to_julia_pipe,
+            &from_julia_pipe,
+            &profile,
+            Some(&sysimage.path),
+        )
+        .await
+        {
+            Ok(()) => {
+                sysimage_manager.emit_loaded(sysimage).await;
+                return Ok(());
+            }
+            Err(e) => {
+                warn!("ProcessActor: Failed to start Julia with sysimage {:?}: {}. Falling back to default sysimage.", sysimage.path, e);
+                sysimage_manager.mark_load_failed(&sysimage.path, e).await;
+            }
+        }
+    }
+
     // Generate pipe names
     let (to_julia_pipe, from_julia_pipe) = state.generate_pipe_names();
 
-    // Start Julia process without sysimage for now
-    try_start_julia_without_sysimage(state, event_emitter, julia_session, &to_julia_pipe, &from_julia_pipe).await
+    try_start_julia(state, event_emitter, julia_session, &to_julia_pipe, &from_julia_pipe, &profile, None).await
 }
 
-/// Try to start Julia process without sysimage
-async fn try_start_julia_without_sysimage(
+/// Get the launch profile for the project, falling back to the default profile
+async fn resolve_launch_profile(
+    config_actor: Option<Addr<ConfigurationActor>>,
+    project_path: Option<String>,
+) -> JuliaLaunchProfile {
+    let Some(config_actor) = config_actor else {
+        return JuliaLaunchProfile::default();
+    };
+    match config_actor.send(GetActiveLaunchProfile { project_path }).await {
+        Ok(Ok(profile)) => match profile.validate() {
+            Ok(()) => profile,
+            Err(e) => {
+                warn!("ProcessActor: Ignoring invalid launch profile '{}': {}", profile.name, e);
+                JuliaLaunchProfile::default()
+            }
+        },
+        Ok(Err(e)) => {
+            warn!("ProcessActor: Failed to get launch profile: {}", e);
+            JuliaLaunchProfile::default()
+        }
+        Err(e) => {
+            warn!("ProcessActor: Failed to send GetActiveLaunchProfile message: {:?}", e);
+            JuliaLaunchProfile::default()
+        }
+    }
+}
+
+/// The app's own Julia depot
+pub fn app_depot_path() -> PathBuf {
+    let app_data_dir = dirs::data_local_dir().expect("Failed to get app data directory");
+    app_data_dir.join("com.compute42.dev").join("depot")
+}
+
+/// `JULIA_DEPOT_PATH` for a launch profile
+///
+/// A profile depot is searched first; the app depot stays on the path for the app's own packages.
+pub fn julia_depot_path(profile: &JuliaLaunchProfile) -> Result<OsString, String> {
+    match &profile.depot_path {
+        Some(user_depot) => std::env::join_paths([PathBuf::from(user_depot), app_depot_path()])
+            .map_err(|e| format!("Invalid depot path in launch profile: {}", e)),
+        None => Ok(app_depot_path().into_os_string()),
+    }
+}
+
+/// Try to start Julia process, optionally with a custom sysimage
+async fn try_start_julia(
     state: Arc<ProcessState>,
     event_emitter: Arc<dyn EventEmitter>,
     julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
     to_julia_pipe: &str,
     from_julia_pipe: &str,
+    profile: &JuliaLaunchProfile,
+    sysimage: Option<&Path>,
 ) -> Result<(), String> {
     // Get the Julia path
     let julia_path = state.get_julia_executable_path().await;
 
-    // Build Julia command without sysimage
+    // Build Julia command
     let mut command = Command::new(&julia_path);
 
     // On Windows, prevent the console window from appearing
@@ -59,7 +143,6 @@ async fn try_start_julia_without_sysimage(
 
     // Set Julia environment variables to use specific environment
     let app_data_dir = dirs::data_local_dir().expect("Failed to get app data directory");
-    let julia_depot_path = app_data_dir.join("com.compute42.dev").join("depot");
     let julia_project_path = app_data_dir.join("com.compute42.dev").join("julia-env");
     
     // Ensure the Julia environment directory exists
@@ -67,20 +150,38 @@ async fn try_start_julia_without_sysimage(
         let _ = std::fs::create_dir_all(&julia_project_path);
     }
     
-    command.env("JULIA_DEPOT_PATH", julia_depot_path.to_string_lossy().to_string());
+    command.env("JULIA_DEPOT_PATH", julia_depot_path(profile)?);
     command.env("JULIA_PROJECT", julia_project_path.to_string_lossy().to_string());
 
+    // Profile environment variables (reserved names are rejected by profile validation)
+    command.envs(&profile.env);
+
     // Try to find and activate Julia project environment
     if let Some(project_path) = state.find_julia_project() {
         command.arg(format!("--project={}", project_path.to_string_lossy()));
     }
 
-    // Add basic Julia arguments (no sysimage)
+    if let Some(sysimage) = sysimage {
+        command.arg(format!("--sysimage={}", sysimage.to_string_lossy()));
+    }
+
+    // Add basic Julia arguments
     command
         .arg("--startup-file=no")
-        .arg("-t1")
+        .arg(format!("--threads={}", profile.threads.as_deref().unwrap_or("1")))
         .arg("--history-file=no");
 
+    if let Some(heap_size_hint) = &profile.heap_size_hint {
+        command.arg(format!("--heap-size-hint={}", heap_size_hint));
+    }
+    if let Some(check_bounds) = &profile.check_bounds {
+        command.arg(format!("--check-bounds={}", check_bounds));
+    }
+    if let Some(level) = profile.optimize {
+        command.arg(format!("-O{}", level));
+    }
+    command.args(&profile.extra_args);
+
     // Set up stdin/stdout/stderr
     command
         .stdin(Stdio::piped())
@@ -90,12 +191,13 @@ async fn try_start_julia_without_sysimage(
     // Start the Julia process
     let julia_process = command
         .spawn()
-        .map_err(|e| format!("Failed to start Julia process without sysimage: {}", e))?;
+        .map_err(|e| format!("Failed to start Julia process: {}", e))?;
 
-    debug!("ProcessActor: Julia process started successfully without sysimage");
+    debug!("ProcessActor: Julia process started successfully (profile: {}, sysimage: {:?})", profile.name, sysimage);
 
     // Reset output suppression flag for new Julia process
     state.set_output_suppression(true).await; // Suppress output during initialization
+    *state.message_loop_ready_received.lock().await = false;
 
     // Create persistent session
     let mut session = PersistentJuliaSession::new(julia_process, event_emitter.clone());
@@ -106,27 +208,42 @@ async fn try_start_julia_without_sysimage(
     let stdout = session.julia_process.stdout.take();
     let stderr = session.julia_process.stderr.take();
     
+    let mut monitors = Vec::new();
     if let Some(stdout) = stdout {
-        output_monitoring::start_stdout_monitoring(
+        monitors.push(output_monitoring::start_stdout_monitoring(
             stdout,
             event_emitter.clone(),
             state.output_suppressed.clone(),
             state.notebook_cell_output_buffer.clone(),
             state.current_notebook_cell.clone(),
-        );
+        ));
     }
     
     if let Some(stderr) = stderr {
-        output_monitoring::start_stderr_monitoring(
+        monitors.push(output_monitoring::start_stderr_monitoring(
             stderr,
             event_emitter.clone(),
             state.clone(),
             julia_session.clone(),
-        );
+        ));
     }
 
     // Execute the Julia setup code
-    setup::execute_julia_setup(state.as_ref(), &mut session, to_julia_pipe, from_julia_pipe).await?;
+    let setup_result = setup::execute_julia_setup(state.as_ref(), &mut session, to_julia_pipe, from_julia_pipe).await;
+
+    // A sysimage that cannot be loaded makes Julia exit right away
+    let startup_error = match session.julia_process.try_wait() {
+        Ok(Some(status)) => Some(format!("Julia exited during startup with {}", status)),
+        _ => setup_result.err(),
+    };
+    if let Some(e) = startup_error {
+        // The failed process's monitors must not feed output or pipe events into the next attempt
+        for monitor in monitors {
+            monitor.abort();
+        }
+        let _ = session.julia_process.kill().await;
+        return Err(e);
+    }
 
     // Store the session AFTER starting monitoring and executing setup code
     {
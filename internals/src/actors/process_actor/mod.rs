@@ -4,6 +4,8 @@ mod file_creation;
 mod setup;
 mod output_monitoring;
 mod lifecycle;
+mod sysimage;
+mod resource_monitor;
 
 use actix::prelude::*;
 use std::sync::Arc;
@@ -15,10 +17,12 @@ use crate::messages::installation::GetJuliaPathFromInstallation;
 use crate::messages::orchestrator::{JuliaMessageLoopReady, ProjectActivationComplete};
 use crate::services::events::EventService;
 use crate::types::JuliaInstallation;
-use crate::actors::{InstallationActor, OrchestratorActor};
+use crate::actors::{ConfigurationActor, InstallationActor, OrchestratorActor};
 
 use session::PersistentJuliaSession;
 use state::ProcessState;
+use sysimage::SysimageManager;
+use resource_monitor::ResourceMonitor;
 use lifecycle::{start_julia_with_communication, stop_julia_process, get_pipe_names};
 
 /// ProcessActor - manages Julia process lifecycle
@@ -36,9 +40,13 @@ pub struct ProcessActor {
     julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
     event_emitter: Arc<dyn crate::service_traits::EventEmitter>,
     event_manager: EventService,
+    sysimage_manager: Arc<SysimageManager>,
+    resource_monitor: Arc<ResourceMonitor>,
+    resource_monitor_handle: Option<SpawnHandle>,
     
     // Actor addresses for message passing
     installation_actor: Option<Addr<InstallationActor>>,
+    config_actor: Option<Addr<ConfigurationActor>>,
     orchestrator_actor: Option<Addr<OrchestratorActor>>,
     communication_actor: Option<Addr<crate::actors::CommunicationActor>>,
 }
@@ -49,18 +57,31 @@ impl ProcessActor {
         event_emitter: Arc<dyn crate::service_traits::EventEmitter>,
         event_manager: EventService,
         installation_actor: Option<Addr<InstallationActor>>,
+        config_actor: Option<Addr<ConfigurationActor>>,
     ) -> Self {
+        let state = Arc::new(ProcessState::new());
+        let sysimage_manager = Arc::new(SysimageManager::new(
+            state.get_julia_data_directory(),
+            state.julia_path.clone(),
+            event_manager.clone(),
+        ));
+        let julia_session = Arc::new(Mutex::new(None));
+        let resource_monitor = Arc::new(ResourceMonitor::new(julia_session.clone(), event_manager.clone()));
         Self {
             is_running: false,
             process_id: None,
             to_julia_pipe: None,
             from_julia_pipe: None,
             julia_installation: None,
-            state: Arc::new(ProcessState::new()),
-            julia_session: Arc::new(Mutex::new(None)),
+            state,
+            julia_session,
             event_emitter,
             event_manager,
+            sysimage_manager,
+            resource_monitor,
+            resource_monitor_handle: None,
             installation_actor,
+            config_actor,
             orchestrator_actor: None,
             communication_actor: None,
         }
@@ -144,6 +165,18 @@ impl Actor for ProcessActor {
     fn started(&mut self, ctx: &mut Context<Self>) {
         // debug!("ProcessActor: Actor started");
         ctx.set_mailbox_capacity(128);
+
+        // Sample the Julia process for as long as the actor lives
+        let resource_monitor = self.resource_monitor.clone();
+        self.resource_monitor_handle = Some(ctx.spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(resource_monitor.interval().await).await;
+                    resource_monitor.sample().await;
+                }
+            }
+            .into_actor(self)
+        ));
     }
 
     fn stopped(&mut self, _ctx: &mut Context<Self>) {
@@ -163,6 +196,8 @@ impl Handler<StartJuliaProcess> for ProcessActor {
         let event_emitter = self.event_emitter.clone();
         let installation_actor = self.installation_actor.clone();
         let event_manager = self.event_manager.clone();
+        let sysimage_manager = self.sysimage_manager.clone();
+        let config_actor = self.config_actor.clone();
         let orchestrator_addr = msg.orchestrator_addr;
         Box::pin(
             async move {
@@ -215,7 +250,7 @@ impl Handler<StartJuliaProcess> for ProcessActor {
                 }
                 
                 // Start the process and wait until it has launched
-                match start_julia_with_communication(state, event_emitter, julia_session).await {
+                match start_julia_with_communication(state, event_emitter, julia_session, sysimage_manager, config_actor).await {
                     Ok(()) => {
                         // Fire event for external observers
                         let _ = event_manager.emit_julia_process_started().await;
@@ -254,6 +289,35 @@ impl Handler<StartJuliaProcess> for ProcessActor {
     }
 }
 
+impl Handler<ShutdownJuliaProcess> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, _msg: ShutdownJuliaProcess, ctx: &mut Context<Self>) -> Self::Result {
+        debug!("ProcessActor: Received ShutdownJuliaProcess message");
+
+        if let Some(handle) = self.resource_monitor_handle.take() {
+            ctx.cancel_future(handle);
+        }
+        self.is_running = false;
+        // Drop the CommunicationActor's address so neither actor keeps the other alive
+        self.communication_actor = None;
+
+        let julia_session = self.julia_session.clone();
+        let state = self.state.clone();
+        Box::pin(
+            async move {
+                *state.communication_actor.lock().await = None;
+                stop_julia_process(julia_session).await
+            }
+            .into_actor(self)
+            .map(|res, _actor, ctx| {
+                ctx.stop();
+                res
+            }),
+        )
+    }
+}
+
 impl Handler<StopJuliaProcess> for ProcessActor {
     type Result = Result<(), String>;
     
@@ -332,6 +396,8 @@ impl Handler<RestartJulia> for ProcessActor {
         let state = self.state.clone();
         let julia_session = self.julia_session.clone();
         let event_emitter = self.event_emitter.clone();
+        let sysimage_manager = self.sysimage_manager.clone();
+        let config_actor = self.config_actor.clone();
         
         ctx.spawn(
             async move {
@@ -342,7 +408,7 @@ impl Handler<RestartJulia> for ProcessActor {
                         // Wait a bit for cleanup
                         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
                         // Start new process
-                        match start_julia_with_communication(state, event_emitter, julia_session).await {
+                        match start_julia_with_communication(state, event_emitter, julia_session, sysimage_manager, config_actor).await {
                             Ok(_) => {
                                 debug!("ProcessActor: Julia process restarted successfully");
                             }
@@ -491,7 +557,7 @@ impl Handler<BufferNotebookCellPlot> for ProcessActor {
                     // Buffer the plot
                     let mut buffer_guard = state.notebook_cell_output_buffer.lock().await;
                     if let Some(ref mut buffer) = *buffer_guard {
-                        buffer.plots.push((msg.mime_type, msg.data));
+                        buffer.plots.push(msg.bundle);
                         debug!("ProcessActor: Buffered plot for notebook cell");
                     }
                 }
@@ -503,6 +569,138 @@ impl Handler<BufferNotebookCellPlot> for ProcessActor {
     }
 }
 
+impl Handler<IsMessageLoopReady> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<bool, String>>;
+
+    fn handle(&mut self, _msg: IsMessageLoopReady, _ctx: &mut Self::Context) -> Self::Result {
+        let state = self.state.clone();
+        Box::pin(async move { Ok(*state.message_loop_ready_received.lock().await) }.into_actor(self))
+    }
+}
+
+impl Handler<BuildSysimage> for ProcessActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: BuildSysimage, ctx: &mut Context<Self>) -> Self::Result {
+        debug!("ProcessActor: Received BuildSysimage message for {}", msg.project_path);
+        if self.sysimage_manager.is_build_in_progress() {
+            return Err("A sysimage build is already in progress".to_string());
+        }
+
+        // Builds take minutes; progress and completion are reported through sysimage events
+        let sysimage_manager = self.sysimage_manager.clone();
+        ctx.spawn(
+            async move {
+                if let Err(e) = sysimage_manager.build_for_project(msg.project_path.into()).await {
+                    warn!("ProcessActor: Sysimage build failed: {}", e);
+                }
+            }
+            .into_actor(self)
+        );
+
+        Ok(())
+    }
+}
+
+impl Handler<GetSysimages> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<Vec<crate::types::SysimageInfo>, String>>;
+
+    fn handle(&mut self, _msg: GetSysimages, _ctx: &mut Self::Context) -> Self::Result {
+        let sysimage_manager = self.sysimage_manager.clone();
+        Box::pin(async move { Ok(sysimage_manager.list().await) }.into_actor(self))
+    }
+}
+
+impl Handler<ClearSysimages> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, _msg: ClearSysimages, _ctx: &mut Self::Context) -> Self::Result {
+        debug!("ProcessActor: Received ClearSysimages message");
+        let sysimage_manager = self.sysimage_manager.clone();
+        Box::pin(
+            async move {
+                use crate::service_traits::SysimageService;
+                sysimage_manager.clear_sysimages().await
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<SetActiveProject> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: SetActiveProject, _ctx: &mut Self::Context) -> Self::Result {
+        debug!("ProcessActor: Received SetActiveProject message: {:?}", msg.project_path);
+        let sysimage_manager = self.sysimage_manager.clone();
+        Box::pin(
+            async move {
+                sysimage_manager.set_active_project(msg.project_path.map(Into::into), msg.remember).await;
+                Ok(())
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<GetResourceUsage> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::ResourceSample>, String>>;
+
+    fn handle(&mut self, _msg: GetResourceUsage, _ctx: &mut Context<Self>) -> Self::Result {
+        let resource_monitor = self.resource_monitor.clone();
+        Box::pin(async move { Ok(resource_monitor.latest().await) }.into_actor(self))
+    }
+}
+
+impl Handler<GetResourceMonitorConfig> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<crate::types::ResourceMonitorConfig, String>>;
+
+    fn handle(&mut self, _msg: GetResourceMonitorConfig, _ctx: &mut Context<Self>) -> Self::Result {
+        let resource_monitor = self.resource_monitor.clone();
+        Box::pin(async move { Ok(resource_monitor.config().await) }.into_actor(self))
+    }
+}
+
+impl Handler<SetResourceMonitorConfig> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: SetResourceMonitorConfig, _ctx: &mut Context<Self>) -> Self::Result {
+        let resource_monitor = self.resource_monitor.clone();
+        Box::pin(async move { resource_monitor.set_config(msg.config).await }.into_actor(self))
+    }
+}
+
+impl Handler<BeginExecutionTracking> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<(), String>>;
+
+    fn handle(&mut self, msg: BeginExecutionTracking, _ctx: &mut Context<Self>) -> Self::Result {
+        let resource_monitor = self.resource_monitor.clone();
+        Box::pin(
+            async move {
+                resource_monitor.begin_execution(msg.execution_id).await;
+                Ok(())
+            }
+            .into_actor(self)
+        )
+    }
+}
+
+impl Handler<FinishExecutionTracking> for ProcessActor {
+    type Result = ResponseActFuture<Self, Result<Option<crate::types::ExecutionResourceUsage>, String>>;
+
+    fn handle(&mut self, msg: FinishExecutionTracking, _ctx: &mut Context<Self>) -> Self::Result {
+        let resource_monitor = self.resource_monitor.clone();
+        Box::pin(
+            async move {
+                Ok(resource_monitor
+                    .finish_execution(&msg.execution_id, msg.julia_metadata.as_ref())
+                    .await)
+            }
+            .into_actor(self)
+        )
+    }
+}
+
 // Clone implementation for async operations
 impl Clone for ProcessActor {
     fn clone(&self) -> Self {
@@ -516,7 +714,11 @@ impl Clone for ProcessActor {
             julia_session: self.julia_session.clone(),
             event_emitter: self.event_emitter.clone(),
             event_manager: self.event_manager.clone(),
+            sysimage_manager: self.sysimage_manager.clone(),
+            resource_monitor: self.resource_monitor.clone(),
+            resource_monitor_handle: None,
             installation_actor: self.installation_actor.clone(),
+            config_actor: self.config_actor.clone(),
             orchestrator_actor: self.orchestrator_actor.clone(),
             communication_actor: self.communication_actor.clone(),
         }
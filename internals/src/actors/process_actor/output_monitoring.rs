@@ -4,6 +4,7 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::ChildStdout;
 use tokio::process::ChildStderr;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use crate::service_traits::EventEmitter;
 
 use super::state::ProcessState;
@@ -28,7 +29,7 @@ pub fn start_stdout_monitoring(
     output_suppressed: Arc<tokio::sync::Mutex<bool>>,
     notebook_output_buffer: Arc<tokio::sync::Mutex<Option<super::state::NotebookCellOutputBuffer>>>,
     current_notebook_cell: Arc<tokio::sync::Mutex<Option<String>>>,
-) {
+) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut reader = BufReader::new(stdout).lines();
         while let Ok(Some(line)) = reader.next_line().await {
@@ -84,7 +85,7 @@ pub fn start_stdout_monitoring(
                 }
             }
         }
-    });
+    })
 }
 
 /// Start monitoring Julia's stderr
@@ -93,7 +94,7 @@ pub fn start_stderr_monitoring(
     event_emitter: Arc<dyn EventEmitter>,
     state: Arc<ProcessState>,
     julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
-) {
+) -> JoinHandle<()> {
     let julia_message_loop_ready_sender = state.julia_message_loop_ready_sender.clone();
     let project_activation_complete_sender = state.project_activation_complete_sender.clone();
     let output_suppressed = state.output_suppressed.clone();
@@ -413,6 +414,6 @@ pub fn start_stderr_monitoring(
                 }
             }
         }
-    });
+    })
 }
 
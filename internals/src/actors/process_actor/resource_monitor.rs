@@ -0,0 +1,316 @@
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+use super::session::PersistentJuliaSession;
+use crate::services::events::EventService;
+use crate::types::{ExecutionResourceUsage, ResourceMonitorConfig, ResourceSample};
+
+/// Kernel clock ticks per second used by /proc/<pid>/stat (USER_HZ is 100 on all supported kernels)
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+/// A memory warning is re-armed once RSS drops below this fraction of the threshold
+const WARNING_REARM_RATIO: f64 = 0.9;
+
+/// Raw process statistics read from /proc
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcStats {
+    rss_bytes: u64,
+    cpu_ticks: u64,
+    threads: u32,
+}
+
+/// An execution whose resource usage is being tracked
+struct TrackedExecution {
+    started: Instant,
+    peak_rss_bytes: Option<u64>,
+}
+
+struct MonitorState {
+    config: ResourceMonitorConfig,
+    pid: Option<u32>,
+    /// CPU ticks and time of the previous sample, for CPU usage between samples
+    last_cpu: Option<(u64, Instant)>,
+    last_sample: Option<ResourceSample>,
+    memory_warning_sent: bool,
+    executions: HashMap<String, TrackedExecution>,
+}
+
+/// Samples memory, CPU and thread usage of the Julia process
+pub struct ResourceMonitor {
+    julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
+    event_manager: EventService,
+    state: Mutex<MonitorState>,
+}
+
+impl ResourceMonitor {
+    pub fn new(
+        julia_session: Arc<Mutex<Option<PersistentJuliaSession>>>,
+        event_manager: EventService,
+    ) -> Self {
+        Self {
+            julia_session,
+            event_manager,
+            state: Mutex::new(MonitorState {
+                config: ResourceMonitorConfig::default(),
+                pid: None,
+                last_cpu: None,
+                last_sample: None,
+                memory_warning_sent: false,
+                executions: HashMap::new(),
+            }),
+        }
+    }
+
+    pub async fn config(&self) -> ResourceMonitorConfig {
+        self.state.lock().await.config.clone()
+    }
+
+    pub async fn set_config(&self, config: ResourceMonitorConfig) -> Result<(), String> {
+        config.validate()?;
+        let mut state = self.state.lock().await;
+        state.config = config;
+        state.memory_warning_sent = false;
+        Ok(())
+    }
+
+    /// Time to wait before the next sample
+    pub async fn interval(&self) -> Duration {
+        Duration::from_millis(self.state.lock().await.config.interval_ms)
+    }
+
+    pub async fn latest(&self) -> Option<ResourceSample> {
+        self.state.lock().await.last_sample.clone()
+    }
+
+    /// Take a sample and emit it; returns None when monitoring is disabled or Julia is not running
+    pub async fn sample(&self) -> Option<ResourceSample> {
+        // Don't touch /proc while monitoring is off
+        if !self.state.lock().await.config.enabled {
+            return None;
+        }
+
+        let pid = self.julia_pid().await;
+        self.track_pid(pid).await;
+
+        let pid = pid?;
+        let stats = read_proc_stats(pid)?;
+        let now = Instant::now();
+
+        let (sample, warning) = {
+            let mut state = self.state.lock().await;
+            if !state.config.enabled {
+                return None;
+            }
+
+            let cpu_percent = match state.last_cpu {
+                Some((last_ticks, last_time)) => cpu_percent(last_ticks, stats.cpu_ticks, now.duration_since(last_time)),
+                None => 0.0,
+            };
+            state.last_cpu = Some((stats.cpu_ticks, now));
+
+            for execution in state.executions.values_mut() {
+                execution.peak_rss_bytes = Some(execution.peak_rss_bytes.map_or(stats.rss_bytes, |peak| peak.max(stats.rss_bytes)));
+            }
+
+            let warning = match state.config.memory_warning_threshold_bytes {
+                Some(threshold) if stats.rss_bytes >= threshold && !state.memory_warning_sent => {
+                    state.memory_warning_sent = true;
+                    Some(threshold)
+                }
+                Some(threshold) if (stats.rss_bytes as f64) < threshold as f64 * WARNING_REARM_RATIO => {
+                    state.memory_warning_sent = false;
+                    None
+                }
+                _ => None,
+            };
+
+            let sample = ResourceSample {
+                pid,
+                rss_bytes: stats.rss_bytes,
+                cpu_percent,
+                threads: stats.threads,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64,
+            };
+            state.last_sample = Some(sample.clone());
+            (sample, warning)
+        };
+
+        if let Some(threshold) = warning {
+            warn!("ProcessActor: Julia process memory ({} bytes) crossed the warning threshold ({} bytes)", sample.rss_bytes, threshold);
+            let _ = self.event_manager.emit_julia_memory_warning(sample.rss_bytes, threshold).await;
+        }
+
+        let _ = self.event_manager.emit_performance_event("julia_memory_rss", sample.rss_bytes as f64, "bytes").await;
+        let _ = self.event_manager.emit_performance_event("julia_cpu_usage", sample.cpu_percent, "percent").await;
+        let _ = self.event_manager.emit_performance_event("julia_threads", sample.threads as f64, "count").await;
+        let _ = self.event_manager.emit_julia_resource_usage(sample.clone()).await;
+
+        Some(sample)
+    }
+
+    /// Start tracking an execution, recording the current memory as its first peak
+    pub async fn begin_execution(&self, execution_id: String) {
+        let rss_bytes = match self.julia_pid().await {
+            Some(pid) => read_proc_stats(pid).map(|stats| stats.rss_bytes),
+            None => None,
+        };
+        let mut state = self.state.lock().await;
+        state.executions.insert(execution_id, TrackedExecution {
+            started: Instant::now(),
+            peak_rss_bytes: rss_bytes,
+        });
+    }
+
+    /// Stop tracking an execution and combine its peak memory and wall time with Julia's GC stats
+    pub async fn finish_execution(
+        &self,
+        execution_id: &str,
+        julia_metadata: Option<&serde_json::Value>,
+    ) -> Option<ExecutionResourceUsage> {
+        let rss_bytes = match self.julia_pid().await {
+            Some(pid) => read_proc_stats(pid).map(|stats| stats.rss_bytes),
+            None => None,
+        };
+        let execution = self.state.lock().await.executions.remove(execution_id)?;
+
+        let peak_memory_bytes = match (execution.peak_rss_bytes, rss_bytes) {
+            (Some(peak), Some(rss)) => Some(peak.max(rss)),
+            (peak, rss) => peak.or(rss),
+        };
+        let gc_stat = |key: &str| julia_metadata.and_then(|metadata| metadata.get(key));
+        let usage = ExecutionResourceUsage {
+            wall_time_ms: execution.started.elapsed().as_millis() as u64,
+            peak_memory_bytes,
+            allocated_bytes: gc_stat("allocated_bytes").and_then(|v| v.as_u64()),
+            gc_time_ms: gc_stat("gc_time_ms").and_then(|v| v.as_f64()),
+            gc_count: gc_stat("gc_count").and_then(|v| v.as_u64()),
+            gc_live_bytes: gc_stat("gc_live_bytes").and_then(|v| v.as_u64()),
+        };
+
+        if let Some(allocated_bytes) = usage.allocated_bytes {
+            let _ = self.event_manager.emit_performance_event("julia_allocated_bytes", allocated_bytes as f64, "bytes").await;
+        }
+        if let Some(gc_time_ms) = usage.gc_time_ms {
+            let _ = self.event_manager.emit_performance_event("julia_gc_time", gc_time_ms, "ms").await;
+        }
+        let _ = self.event_manager.emit_execution_resource_usage(execution_id, usage.clone()).await;
+
+        Some(usage)
+    }
+
+    async fn julia_pid(&self) -> Option<u32> {
+        let session = self.julia_session.lock().await;
+        session.as_ref().and_then(|session| session.julia_process.id())
+    }
+
+    /// Reset per-process state when Julia is started, restarted or stopped
+    async fn track_pid(&self, pid: Option<u32>) {
+        let previous = {
+            let mut state = self.state.lock().await;
+            if state.pid == pid {
+                return;
+            }
+            let previous = state.pid;
+            state.pid = pid;
+            state.last_cpu = None;
+            state.last_sample = None;
+            state.memory_warning_sent = false;
+            if previous.is_some() {
+                // Executions on the old process never complete
+                state.executions.clear();
+            }
+            previous
+        };
+
+        if let Some(previous) = previous {
+            debug!("ProcessActor: Resource monitor detached from Julia process {}", previous);
+            let _ = self.event_manager.emit_resource_event("julia_process", &previous.to_string(), "released").await;
+        }
+        if let Some(pid) = pid {
+            debug!("ProcessActor: Resource monitor attached to Julia process {}", pid);
+            let _ = self.event_manager.emit_resource_event("julia_process", &pid.to_string(), "acquired").await;
+        }
+    }
+}
+
+/// CPU usage between two samples, where 100.0 is one fully used core
+fn cpu_percent(last_ticks: u64, ticks: u64, elapsed: Duration) -> f64 {
+    let elapsed = elapsed.as_secs_f64();
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    let cpu_seconds = ticks.saturating_sub(last_ticks) as f64 / CLOCK_TICKS_PER_SECOND;
+    cpu_seconds / elapsed * 100.0
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_stats(pid: u32) -> Option<ProcStats> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_proc_stats(&stat, &status)
+}
+
+/// /proc is only available on Linux; other platforms report no samples
+#[cfg(not(target_os = "linux"))]
+fn read_proc_stats(_pid: u32) -> Option<ProcStats> {
+    None
+}
+
+/// Parse /proc/<pid>/stat (CPU ticks, thread count) and /proc/<pid>/status (VmRSS)
+fn parse_proc_stats(stat: &str, status: &str) -> Option<ProcStats> {
+    // The command name may contain spaces and parentheses, so fields are counted after the last ')'
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    // Fields after the command name start at `state` (field 3 in proc(5))
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let threads: u32 = fields.get(17)?.parse().ok()?;
+
+    let rss_kb: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+
+    Some(ProcStats {
+        rss_bytes: rss_kb * 1024,
+        cpu_ticks: utime + stime,
+        threads,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "4242 (julia (worker)) S 1 4242 4242 0 -1 4194560 91231 0 12 0 1500 250 0 0 20 0 9 0 123456 1234567890 54321 18446744073709551615";
+    const STATUS: &str = "Name:\tjulia\nState:\tS (sleeping)\nVmPeak:\t 2048000 kB\nVmRSS:\t  409600 kB\nThreads:\t9\n";
+
+    #[test]
+    fn test_parse_proc_stats() {
+        let stats = parse_proc_stats(STAT, STATUS).unwrap();
+        assert_eq!(stats.rss_bytes, 409600 * 1024);
+        assert_eq!(stats.cpu_ticks, 1750);
+        assert_eq!(stats.threads, 9);
+    }
+
+    #[test]
+    fn test_parse_proc_stats_rejects_truncated_input() {
+        assert!(parse_proc_stats("4242 (julia) S 1 4242", STATUS).is_none());
+        assert!(parse_proc_stats(STAT, "Name:\tjulia\n").is_none());
+    }
+
+    #[test]
+    fn test_cpu_percent() {
+        let percent = cpu_percent(1000, 1150, Duration::from_secs(1));
+        assert!((percent - 150.0).abs() < 1e-9);
+        assert_eq!(cpu_percent(1000, 1150, Duration::ZERO), 0.0);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -8,7 +9,7 @@ use actix::prelude::*;
 pub struct NotebookCellOutputBuffer {
     pub stdout: Vec<String>,
     pub stderr: Vec<String>,
-    pub plots: Vec<(String, String)>, // (mime_type, data) pairs
+    pub plots: Vec<BTreeMap<String, String>>, // MIME bundles, one per displayed plot
 }
 
 /// Internal state for ProcessActor
@@ -95,14 +96,18 @@ impl ProcessState {
 
     /// Generate unique pipe names for communication
     pub fn generate_pipe_names(&self) -> (String, String) {
+        // Several kernels can start within the same millisecond
+        static PIPE_SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
         let pid = std::process::id();
         // Add timestamp to ensure uniqueness even if PID is reused
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis();
-        let to_julia_pipe = format!("compute42_to_julia_{}_{}", pid, timestamp);
-        let from_julia_pipe = format!("compute42_from_julia_{}_{}", pid, timestamp);
+        let sequence = PIPE_SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let to_julia_pipe = format!("compute42_to_julia_{}_{}_{}", pid, timestamp, sequence);
+        let from_julia_pipe = format!("compute42_from_julia_{}_{}_{}", pid, timestamp, sequence);
         (to_julia_pipe, from_julia_pipe)
     }
 
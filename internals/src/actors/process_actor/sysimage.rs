@@ -0,0 +1,651 @@
+use async_trait::async_trait;
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+use super::lifecycle::app_depot_path;
+use crate::service_traits::SysimageService;
+use crate::services::base::file_utils::fnv1a_hex;
+use crate::services::events::EventService;
+use crate::types::{SysimageCompilationState, SysimageInfo};
+
+#[cfg(target_os = "windows")]
+const SYSIMAGE_EXTENSION: &str = "dll";
+#[cfg(target_os = "macos")]
+const SYSIMAGE_EXTENSION: &str = "dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const SYSIMAGE_EXTENSION: &str = "so";
+
+/// Prefix of progress lines printed by the build script
+const PROGRESS_MARKER: &str = "COMPUTE42_SYSIMAGE_PROGRESS";
+
+/// How long a sysimage may take to load during validation
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Number of stderr lines kept for build error reports
+const ERROR_TAIL_LINES: usize = 20;
+
+/// Julia script that builds a sysimage for a project with PackageCompiler
+///
+/// PackageCompiler is installed into a separate builder environment so the
+/// user's project does not need to depend on it.
+const BUILD_SCRIPT: &str = r#"
+import Pkg
+const builder_env, project_path, output_path = ARGS[1], ARGS[2], ARGS[3]
+progress(pct, msg) = (println("COMPUTE42_SYSIMAGE_PROGRESS ", pct, " ", msg); flush(stdout))
+
+progress(5, "Preparing PackageCompiler")
+Pkg.activate(builder_env; io=devnull)
+if Base.find_package("PackageCompiler") === nothing
+    Pkg.add("PackageCompiler"; io=devnull)
+end
+using PackageCompiler
+
+progress(20, "Instantiating project")
+Pkg.activate(project_path; io=devnull)
+Pkg.instantiate(; io=devnull)
+packages = Symbol.(collect(keys(Pkg.project().dependencies)))
+isempty(packages) && error("The project has no dependencies to compile into a sysimage")
+
+progress(30, "Compiling sysimage with $(length(packages)) packages")
+mkpath(dirname(output_path))
+tmp_path = output_path * ".tmp"
+create_sysimage(packages; sysimage_path=tmp_path, project=project_path)
+mv(tmp_path, output_path; force=true)
+progress(100, "Sysimage ready")
+"#;
+
+/// A built sysimage, keyed by project, Manifest hash and Julia version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SysimageRecord {
+    project_path: PathBuf,
+    manifest_hash: String,
+    julia_version: String,
+    path: PathBuf,
+    created_at: String,
+    size_bytes: u64,
+    /// Set when launching Julia with this sysimage failed
+    #[serde(default)]
+    failed_to_load: bool,
+    /// Set by `unload_sysimage` to stop using the sysimage at launch
+    #[serde(default)]
+    disabled: bool,
+}
+
+impl SysimageRecord {
+    fn to_info(&self) -> SysimageInfo {
+        SysimageInfo {
+            path: self.path.clone(),
+            is_available: self.path.exists() && !self.failed_to_load && !self.disabled,
+            compilation_state: if self.failed_to_load {
+                SysimageCompilationState::Failed
+            } else {
+                SysimageCompilationState::Completed
+            },
+            project_path: Some(self.project_path.clone()),
+            manifest_hash: Some(self.manifest_hash.clone()),
+            julia_version: Some(self.julia_version.clone()),
+            created_at: Some(self.created_at.clone()),
+            size_bytes: Some(self.size_bytes),
+        }
+    }
+}
+
+/// On-disk index of built sysimages (`sysimages/index.json`)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SysimageIndex {
+    records: Vec<SysimageRecord>,
+    /// Last activated project, used to pick a sysimage before the project is activated
+    last_project: Option<PathBuf>,
+}
+
+/// Manages per-project custom sysimages
+///
+/// Sysimages are built with PackageCompiler in a background Julia process and
+/// stored under `{data_dir}/sysimages`, keyed by the project's Manifest.toml
+/// hash and the Julia version. At launch the sysimage matching the active
+/// project is validated and passed with `--sysimage`; a stale or broken
+/// sysimage is skipped so Julia always starts.
+pub struct SysimageManager {
+    root: PathBuf,
+    /// `JULIA_DEPOT_PATH` of the last kernel launch
+    depot_path: Mutex<OsString>,
+    julia_path: Arc<Mutex<PathBuf>>,
+    event_manager: EventService,
+    index: Mutex<SysimageIndex>,
+    active_project: Mutex<Option<PathBuf>>,
+    build_in_progress: AtomicBool,
+    /// Sysimages that loaded successfully in this session
+    validated: Mutex<HashSet<PathBuf>>,
+    /// Julia executable -> version string
+    julia_versions: Mutex<HashMap<PathBuf, String>>,
+}
+
+impl SysimageManager {
+    pub fn new(data_dir: PathBuf, julia_path: Arc<Mutex<PathBuf>>, event_manager: EventService) -> Self {
+        let root = data_dir.join("sysimages");
+        let index = Self::load_index(&root);
+        Self {
+            root,
+            depot_path: Mutex::new(app_depot_path().into_os_string()),
+            julia_path,
+            event_manager,
+            index: Mutex::new(index),
+            active_project: Mutex::new(None),
+            build_in_progress: AtomicBool::new(false),
+            validated: Mutex::new(HashSet::new()),
+            julia_versions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn load_index(root: &Path) -> SysimageIndex {
+        let index_path = root.join("index.json");
+        match std::fs::read_to_string(&index_path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("SysimageManager: Ignoring unreadable sysimage index {:?}: {}", index_path, e);
+                SysimageIndex::default()
+            }),
+            Err(_) => SysimageIndex::default(),
+        }
+    }
+
+    fn save_index(&self, index: &SysimageIndex) {
+        if let Err(e) = std::fs::create_dir_all(&self.root) {
+            error!("SysimageManager: Failed to create sysimage directory: {}", e);
+            return;
+        }
+        match serde_json::to_string_pretty(index) {
+            Ok(content) => {
+                if let Err(e) = std::fs::write(self.root.join("index.json"), content) {
+                    error!("SysimageManager: Failed to write sysimage index: {}", e);
+                }
+            }
+            Err(e) => error!("SysimageManager: Failed to serialize sysimage index: {}", e),
+        }
+    }
+
+    /// Use the depot of the launch profile the kernel starts with
+    pub async fn set_depot_path(&self, depot_path: OsString) {
+        *self.depot_path.lock().await = depot_path;
+    }
+
+    /// Set the project whose sysimage should be used for the next launch
+    ///
+    /// Only the default kernel remembers its project in the shared index; other
+    /// kernels always set their project before they launch.
+    pub async fn set_active_project(&self, project_path: Option<PathBuf>, remember: bool) {
+        *self.active_project.lock().await = project_path.clone();
+        if let Some(project_path) = project_path.filter(|_| remember) {
+            let mut index = self.index.lock().await;
+            if index.last_project.as_ref() != Some(&project_path) {
+                index.last_project = Some(project_path);
+                self.save_index(&index);
+            }
+        }
+    }
+
+    /// The active project, falling back to the last activated one
+    pub async fn current_project(&self) -> Option<PathBuf> {
+        if let Some(project) = self.active_project.lock().await.clone() {
+            return Some(project);
+        }
+        self.index.lock().await.last_project.clone()
+    }
+
+    pub fn is_build_in_progress(&self) -> bool {
+        self.build_in_progress.load(Ordering::SeqCst)
+    }
+
+    /// Get the Julia version string (e.g., "1.10.4") of the configured executable
+    async fn julia_version(&self) -> Result<String, String> {
+        let julia_path = self.julia_path.lock().await.clone();
+        if let Some(version) = self.julia_versions.lock().await.get(&julia_path) {
+            return Ok(version.clone());
+        }
+
+        let mut command = Command::new(&julia_path);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        let output = command
+            .arg("--version")
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run {:?}: {}", julia_path, e))?;
+        let version = parse_julia_version(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| format!("Could not determine Julia version of {:?}", julia_path))?;
+
+        self.julia_versions.lock().await.insert(julia_path, version.clone());
+        Ok(version)
+    }
+
+    /// Path a sysimage for this project/manifest/Julia version is stored at
+    fn sysimage_path_for(&self, project_path: &Path, manifest_hash: &str, julia_version: &str) -> PathBuf {
+        let project_name = project_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        let project_key = format!(
+            "{}-{}",
+            project_name,
+            &fnv1a_hex(project_path.to_string_lossy().as_bytes())[..8]
+        );
+        self.root
+            .join(project_key)
+            .join(format!("sys-{}-julia-{}.{}", manifest_hash, julia_version, SYSIMAGE_EXTENSION))
+    }
+
+    /// Find the sysimage to launch Julia with, validating it if needed
+    ///
+    /// Returns None (and Julia starts with the default sysimage) when there is no
+    /// project, no sysimage for the current Manifest and Julia version, or the
+    /// sysimage fails to load.
+    pub async fn select_for_launch(&self) -> Option<SysimageInfo> {
+        let project_path = self.current_project().await?;
+        let julia_version = self.julia_version().await.ok()?;
+
+        let record = {
+            let index = self.index.lock().await;
+            let mut project_records = index.records.iter().filter(|r| r.project_path == project_path).peekable();
+            project_records.peek()?;
+            let manifest_hash = manifest_hash(&project_path);
+            project_records
+                .find(|r| Some(&r.manifest_hash) == manifest_hash.as_ref() && r.julia_version == julia_version)
+                .cloned()
+        };
+
+        let Some(record) = record else {
+            debug!(
+                "SysimageManager: Sysimage for {:?} is stale (Manifest or Julia version changed)",
+                project_path
+            );
+            let _ = self.event_manager.emit_sysimage_not_available().await;
+            return None;
+        };
+
+        if record.disabled || record.failed_to_load || !record.path.exists() {
+            return None;
+        }
+
+        if !self.validated.lock().await.contains(&record.path) {
+            if let Err(e) = self.validate_path(&record.path).await {
+                warn!("SysimageManager: Sysimage {:?} failed validation: {}", record.path, e);
+                self.mark_load_failed(&record.path, e).await;
+                return None;
+            }
+            self.validated.lock().await.insert(record.path.clone());
+        }
+
+        Some(record.to_info())
+    }
+
+    /// Check that Julia can start with the given sysimage
+    async fn validate_path(&self, sysimage_path: &Path) -> Result<(), String> {
+        let julia_path = self.julia_path.lock().await.clone();
+        let depot_path = self.depot_path.lock().await.clone();
+        let mut command = Command::new(&julia_path);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        command
+            .arg(format!("--sysimage={}", sysimage_path.to_string_lossy()))
+            .arg("--startup-file=no")
+            .arg("--history-file=no")
+            .arg("-e")
+            .arg("exit(0)")
+            .env("JULIA_DEPOT_PATH", &depot_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let output = tokio::time::timeout(VALIDATION_TIMEOUT, command.output())
+            .await
+            .map_err(|_| "Timed out loading sysimage".to_string())?
+            .map_err(|e| format!("Failed to run Julia: {}", e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("Julia exited with {}: {}", output.status, stderr.trim()))
+        }
+    }
+
+    /// Record that a sysimage could not be loaded so it is skipped on later launches
+    pub async fn mark_load_failed(&self, sysimage_path: &Path, error: String) {
+        self.validated.lock().await.remove(sysimage_path);
+        let info = {
+            let mut index = self.index.lock().await;
+            let Some(record) = index.records.iter_mut().find(|r| r.path == sysimage_path) else {
+                return;
+            };
+            record.failed_to_load = true;
+            let info = record.to_info();
+            self.save_index(&index);
+            info
+        };
+        let _ = self.event_manager.emit_sysimage_load_failed(info, error).await;
+    }
+
+    /// Announce that Julia is running with the given sysimage
+    pub async fn emit_loaded(&self, sysimage: SysimageInfo) {
+        let _ = self.event_manager.emit_sysimage_loaded(sysimage).await;
+    }
+
+    /// Build a sysimage for a project (runs until the build finishes)
+    pub async fn build_for_project(&self, project_path: PathBuf) -> Result<SysimageInfo, String> {
+        if self.build_in_progress.swap(true, Ordering::SeqCst) {
+            return Err("A sysimage build is already in progress".to_string());
+        }
+        let result = self.run_build(&project_path).await;
+        self.build_in_progress.store(false, Ordering::SeqCst);
+        result
+    }
+
+    async fn run_build(&self, project_path: &Path) -> Result<SysimageInfo, String> {
+        if !project_path.join("Project.toml").exists() {
+            return Err(format!("No Project.toml found in {:?}", project_path));
+        }
+        let manifest_hash = manifest_hash(project_path)
+            .ok_or_else(|| "The project has no Manifest.toml; instantiate it before building a sysimage".to_string())?;
+        let julia_version = self.julia_version().await?;
+        let output_path = self.sysimage_path_for(project_path, &manifest_hash, &julia_version);
+
+        let pending = SysimageInfo {
+            path: output_path.clone(),
+            is_available: false,
+            compilation_state: SysimageCompilationState::Compiling,
+            project_path: Some(project_path.to_path_buf()),
+            manifest_hash: Some(manifest_hash.clone()),
+            julia_version: Some(julia_version.clone()),
+            created_at: None,
+            size_bytes: None,
+        };
+        let _ = self.event_manager.emit_sysimage_compilation_started().await;
+
+        match self.run_build_process(project_path, &output_path).await {
+            Ok(()) => {}
+            Err(e) => {
+                error!("SysimageManager: Sysimage build for {:?} failed: {}", project_path, e);
+                let failed = SysimageInfo { compilation_state: SysimageCompilationState::Failed, ..pending };
+                let _ = self.event_manager.emit_sysimage_compilation_failed(failed, e.clone()).await;
+                return Err(e);
+            }
+        }
+
+        let record = SysimageRecord {
+            project_path: project_path.to_path_buf(),
+            manifest_hash,
+            julia_version,
+            path: output_path.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            size_bytes: std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0),
+            failed_to_load: false,
+            disabled: false,
+        };
+        let info = record.to_info();
+
+        // Replace older sysimages of this project for the same Julia version
+        let removed = {
+            let mut index = self.index.lock().await;
+            let (removed, kept): (Vec<SysimageRecord>, Vec<SysimageRecord>) = std::mem::take(&mut index.records)
+                .into_iter()
+                .partition(|r| r.project_path == record.project_path && r.julia_version == record.julia_version);
+            index.records = kept;
+            index.records.push(record);
+            self.save_index(&index);
+            removed
+        };
+        for old in removed.into_iter().filter(|r| r.path != output_path) {
+            let _ = std::fs::remove_file(&old.path);
+            self.validated.lock().await.remove(&old.path);
+            let _ = self.event_manager.emit_sysimage_removed(old.to_info()).await;
+        }
+
+        let _ = self.event_manager.emit_sysimage_compilation_completed(info.clone()).await;
+        debug!("SysimageManager: Built sysimage {:?}", output_path);
+        Ok(info)
+    }
+
+    /// Run the PackageCompiler build script in a separate Julia process, forwarding progress
+    async fn run_build_process(&self, project_path: &Path, output_path: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(&self.root).map_err(|e| format!("Failed to create sysimage directory: {}", e))?;
+        let script_path = self.root.join("build_sysimage.jl");
+        std::fs::write(&script_path, BUILD_SCRIPT).map_err(|e| format!("Failed to write build script: {}", e))?;
+        let builder_env = self.root.join("builder-env");
+
+        let julia_path = self.julia_path.lock().await.clone();
+        let depot_path = self.depot_path.lock().await.clone();
+        let mut command = Command::new(&julia_path);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        command
+            .arg("--startup-file=no")
+            .arg("--history-file=no")
+            .arg(&script_path)
+            .arg(&builder_env)
+            .arg(project_path)
+            .arg(output_path)
+            .env("JULIA_DEPOT_PATH", &depot_path)
+            .env("GKSwstype", "nul")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = command.spawn().map_err(|e| format!("Failed to start sysimage build: {}", e))?;
+        let stdout = child.stdout.take().ok_or("Failed to capture build output")?;
+        let stderr = child.stderr.take().ok_or("Failed to capture build errors")?;
+
+        // PackageCompiler reports its steps on stderr; forward them as progress messages
+        let event_manager = self.event_manager.clone();
+        let stderr_task = tokio::spawn(async move {
+            let mut tail = VecDeque::with_capacity(ERROR_TAIL_LINES);
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.contains("PackageCompiler:") {
+                    let message = line.trim_start_matches(|c: char| !c.is_alphanumeric()).to_string();
+                    let _ = event_manager.emit_sysimage_compilation_progress(30, message).await;
+                }
+                if tail.len() == ERROR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+            tail.into_iter().collect::<Vec<_>>().join("\n")
+        });
+
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some((progress, message)) = parse_progress_line(&line) {
+                let _ = self.event_manager.emit_sysimage_compilation_progress(progress, message).await;
+            } else {
+                debug!("SysimageManager: {}", line);
+            }
+        }
+
+        let status = child.wait().await.map_err(|e| format!("Sysimage build process failed: {}", e))?;
+        let stderr_tail = stderr_task.await.unwrap_or_default();
+        if status.success() && output_path.exists() {
+            Ok(())
+        } else {
+            Err(format!("Sysimage build exited with {}:\n{}", status, stderr_tail))
+        }
+    }
+
+    /// All sysimages in the index
+    pub async fn list(&self) -> Vec<SysimageInfo> {
+        self.index.lock().await.records.iter().map(|r| r.to_info()).collect()
+    }
+
+    /// Enable or disable a sysimage for future launches
+    async fn set_disabled(&self, sysimage_path: &Path, disabled: bool) -> Result<SysimageInfo, String> {
+        let mut index = self.index.lock().await;
+        let record = index
+            .records
+            .iter_mut()
+            .find(|r| r.path == sysimage_path)
+            .ok_or_else(|| format!("Unknown sysimage: {:?}", sysimage_path))?;
+        record.disabled = disabled;
+        if !disabled {
+            // Give a previously failing sysimage another chance
+            record.failed_to_load = false;
+        }
+        let info = record.to_info();
+        self.save_index(&index);
+        Ok(info)
+    }
+}
+
+#[async_trait]
+impl SysimageService for SysimageManager {
+    async fn check_sysimage_available(&self) -> Result<bool, String> {
+        Ok(self.select_for_launch().await.is_some())
+    }
+
+    async fn download_sysimage(&self) -> Result<(), String> {
+        Err("Prebuilt sysimages are not available for download; build one for the project instead".to_string())
+    }
+
+    async fn get_sysimage_path(&self) -> Result<Option<PathBuf>, String> {
+        Ok(self.select_for_launch().await.map(|info| info.path))
+    }
+
+    async fn build_sysimage(&self) -> Result<(), String> {
+        let project_path = self.current_project().await.ok_or("No active project to build a sysimage for")?;
+        self.build_for_project(project_path).await.map(|_| ())
+    }
+
+    async fn is_sysimage_download_in_progress(&self) -> bool {
+        false
+    }
+
+    async fn get_available_sysimages(&self) -> Result<Vec<SysimageInfo>, String> {
+        Ok(self.list().await)
+    }
+
+    /// Validate a sysimage and use it from the next Julia launch
+    async fn load_sysimage(&self, sysimage_path: &str) -> Result<SysimageInfo, String> {
+        let path = PathBuf::from(sysimage_path);
+        self.validate_path(&path).await?;
+        self.validated.lock().await.insert(path.clone());
+        let info = self.set_disabled(&path, false).await?;
+        let _ = self.event_manager.emit_current_sysimage_changed(info.clone()).await;
+        Ok(info)
+    }
+
+    /// Stop using a sysimage from the next Julia launch (the file is kept)
+    async fn unload_sysimage(&self, sysimage_path: &str) -> Result<(), String> {
+        let info = self.set_disabled(Path::new(sysimage_path), true).await?;
+        let _ = self.event_manager.emit_sysimage_unloaded(info).await;
+        Ok(())
+    }
+
+    async fn validate_sysimage(&self, sysimage: SysimageInfo) -> Result<bool, String> {
+        if !sysimage.path.exists() {
+            return Ok(false);
+        }
+        Ok(self.validate_path(&sysimage.path).await.is_ok())
+    }
+
+    /// Rebuild a sysimage against the project's current Manifest
+    async fn optimize_sysimage(&self, sysimage: &SysimageInfo) -> Result<SysimageInfo, String> {
+        let project_path = sysimage.project_path.clone().ok_or("Sysimage has no associated project")?;
+        let _ = self.event_manager.emit_sysimage_optimization_started(sysimage.clone()).await;
+        let info = self.build_for_project(project_path).await?;
+        let _ = self.event_manager.emit_sysimage_optimization_completed(info.clone()).await;
+        Ok(info)
+    }
+
+    async fn clear_sysimages(&self) -> Result<(), String> {
+        let removed = {
+            let mut index = self.index.lock().await;
+            let removed = std::mem::take(&mut index.records);
+            self.save_index(&index);
+            removed
+        };
+        for record in removed {
+            let _ = std::fs::remove_file(&record.path);
+            let _ = self.event_manager.emit_sysimage_removed(record.to_info()).await;
+        }
+        self.validated.lock().await.clear();
+        Ok(())
+    }
+}
+
+/// Hash of a project's Manifest.toml (None if the project has no manifest)
+pub fn manifest_hash(project_path: &Path) -> Option<String> {
+    let content = ["JuliaManifest.toml", "Manifest.toml"]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(project_path.join(name)).ok())?;
+    // Line endings differ between platforms for the same manifest
+    Some(fnv1a_hex(content.replace("\r\n", "\n").as_bytes()))
+}
+
+/// Parse `julia --version` output ("julia version 1.10.4")
+fn parse_julia_version(output: &str) -> Option<String> {
+    output
+        .trim()
+        .strip_prefix("julia version")
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parse a build script progress line ("COMPUTE42_SYSIMAGE_PROGRESS 30 Compiling ...")
+fn parse_progress_line(line: &str) -> Option<(u8, String)> {
+    let rest = line.strip_prefix(PROGRESS_MARKER)?.trim_start();
+    let (progress, message) = rest.split_once(' ').unwrap_or((rest, ""));
+    Some((progress.parse::<u8>().ok()?.min(100), message.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fnv1a_hex() {
+        assert_eq!(fnv1a_hex(b""), "cbf29ce484222325");
+        assert_eq!(fnv1a_hex(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_manifest_hash() {
+        let project = TempDir::new().unwrap();
+        assert_eq!(manifest_hash(project.path()), None);
+
+        std::fs::write(project.path().join("Manifest.toml"), "julia_version = \"1.10.4\"\r\n").unwrap();
+        let hash = manifest_hash(project.path()).unwrap();
+        assert_eq!(hash, fnv1a_hex(b"julia_version = \"1.10.4\"\n"));
+
+        // JuliaManifest.toml takes precedence, as in Pkg
+        std::fs::write(project.path().join("JuliaManifest.toml"), "julia_version = \"1.11.0\"\n").unwrap();
+        assert_ne!(manifest_hash(project.path()).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_parse_julia_version() {
+        assert_eq!(parse_julia_version("julia version 1.10.4\n").as_deref(), Some("1.10.4"));
+        assert_eq!(parse_julia_version("julia version \n"), None);
+        assert_eq!(parse_julia_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_parse_progress_line() {
+        assert_eq!(
+            parse_progress_line("COMPUTE42_SYSIMAGE_PROGRESS 30 Compiling sysimage"),
+            Some((30, "Compiling sysimage".to_string()))
+        );
+        assert_eq!(parse_progress_line("COMPUTE42_SYSIMAGE_PROGRESS 250"), Some((100, String::new())));
+        assert_eq!(parse_progress_line("COMPUTE42_SYSIMAGE_PROGRESS soon"), None);
+        assert_eq!(parse_progress_line("Precompiling project..."), None);
+    }
+}
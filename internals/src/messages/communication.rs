@@ -1,5 +1,6 @@
 use actix::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use super::execution::ExecutionType;
 
 // ============================================================================
@@ -43,6 +44,9 @@ pub enum JuliaMessage {
         line_number: Option<u32>,
         code_context: Option<String>,
         session_id: Option<String>,
+        /// Other representations of the plot by MIME type
+        #[serde(default)]
+        alternatives: BTreeMap<String, String>,
     },
 
     // System Messages
@@ -196,6 +200,7 @@ impl JuliaMessage {
             line_number,
             code_context,
             session_id,
+            alternatives: BTreeMap::new(),
         }
     }
 
@@ -374,6 +379,11 @@ pub struct ConnectFromJuliaPipe {
 #[rtype(result = "Result<(), String>")]
 pub struct DisconnectFromPipes;
 
+/// Disconnect from pipes and stop the CommunicationActor itself (kernels that are removed)
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct ShutdownCommunication;
+
 /// Execute code
 #[derive(Message)]
 #[rtype(result = "Result<JuliaMessage, String>")]
@@ -73,4 +73,42 @@ pub struct SetFontSettings {
     pub editor_line_numbers: Option<bool>,
     pub editor_minimap: Option<bool>,
     pub editor_color_scheme: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Get the launch profiles of a project
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::ProjectLaunchProfiles, String>")]
+pub struct GetLaunchProfiles {
+    pub project_path: String,
+}
+
+/// Get the launch profile to start Julia with
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::JuliaLaunchProfile, String>")]
+pub struct GetActiveLaunchProfile {
+    pub project_path: Option<String>, // None to use the last opened folder
+}
+
+/// Validate and create or replace a launch profile (matched by name)
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SaveLaunchProfile {
+    pub project_path: String,
+    pub profile: crate::types::JuliaLaunchProfile,
+}
+
+/// Delete a launch profile
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct DeleteLaunchProfile {
+    pub project_path: String,
+    pub name: String,
+}
+
+/// Select the launch profile used for the next Julia start
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetActiveLaunchProfile {
+    pub project_path: String,
+    pub name: Option<String>, // None for the default profile
+}
@@ -106,6 +106,23 @@ pub struct ExecuteNotebookCellsBatch {
     pub cells: Vec<NotebookCellBatchItem>,
 }
 
+/// Analyze which globals each notebook cell defines and reads
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::CellDependencyGraph, String>")]
+pub struct AnalyzeNotebookDependencies {
+    /// All cells of the notebook, in notebook order
+    pub cells: Vec<NotebookCellBatchItem>,
+}
+
+/// Execute changed notebook cells and every cell that depends on them, in dependency order
+#[derive(Message)]
+#[rtype(result = "Result<Vec<(String, Result<String, String>)>, String>")]
+pub struct ExecuteNotebookCellsWithDependents {
+    /// All cells of the notebook, in notebook order
+    pub cells: Vec<NotebookCellBatchItem>,
+    pub changed_cell_ids: Vec<String>,
+}
+
 /// Execute file
 #[derive(Message)]
 #[rtype(result = "Result<String, String>")]
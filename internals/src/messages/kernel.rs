@@ -0,0 +1,57 @@
+use actix::prelude::*;
+
+// ============================================================================
+// KernelManagerActor Messages
+// ============================================================================
+
+/// Start a named Julia kernel (returns once the kernel accepts code)
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::KernelInfo, String>")]
+pub struct StartKernel {
+    pub name: String,
+    pub project_path: Option<String>, // Activated in the kernel after startup
+}
+
+/// List all kernels, including the default kernel
+#[derive(Message)]
+#[rtype(result = "Result<Vec<crate::types::KernelInfo>, String>")]
+pub struct ListKernels;
+
+/// Stop a kernel and remove it
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct KillKernel {
+    pub name: String,
+}
+
+/// Restart a kernel with a fresh workspace (returns once the kernel accepts code)
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::KernelInfo, String>")]
+pub struct RestartKernel {
+    pub name: String,
+}
+
+/// Set how long a kernel may stay idle before it is shut down
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetKernelIdleTimeout {
+    pub timeout_secs: Option<u64>, // None to keep idle kernels running
+}
+
+/// Execute a notebook cell on a kernel
+#[derive(Message)]
+#[rtype(result = "Result<String, String>")]
+pub struct ExecuteNotebookCellOnKernel {
+    pub kernel: String,
+    pub cell_id: String,
+    pub code: String,
+    pub notebook_path: Option<String>,
+}
+
+/// Execute a file on a kernel
+#[derive(Message)]
+#[rtype(result = "Result<String, String>")]
+pub struct ExecuteFileOnKernel {
+    pub kernel: String,
+    pub file_path: String,
+}
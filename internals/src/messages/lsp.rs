@@ -126,6 +126,70 @@ pub struct GetDiagnostics {
     pub uri: String,
 }
 
+/// Get the dependency health report for the open project
+#[derive(Message)]
+#[rtype(result = "Result<Option<crate::types::DependencyHealthReport>, String>")]
+pub struct GetDependencyHealth;
+
+/// Get the package health report (type piracy, overwritten methods, ambiguities) for the open project
+#[derive(Message)]
+#[rtype(result = "Result<Option<crate::types::PackageHealthReport>, String>")]
+pub struct GetPackageHealth;
+
+/// Get the dead code report (unused definitions and imports, undefined exports, unreachable code)
+#[derive(Message)]
+#[rtype(result = "Result<Option<crate::types::DeadCodeReport>, String>")]
+pub struct GetDeadCode;
+
+/// Open/update a notebook from its code cells, as `(cell_id, source)` in notebook order
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct UpdateNotebook {
+    pub uri: String,
+    pub cells: Vec<(String, String)>,
+}
+
+/// Close a notebook
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct CloseNotebook {
+    pub uri: String,
+}
+
+/// Get hover information in a notebook cell
+#[derive(Message)]
+#[rtype(result = "Result<Option<LspHover>, String>")]
+pub struct GetNotebookHover {
+    pub uri: String,
+    pub cell_id: String,
+    pub position: LspPosition,
+}
+
+/// Get completions in a notebook cell
+#[derive(Message)]
+#[rtype(result = "Result<Vec<LspCompletionItem>, String>")]
+pub struct GetNotebookCompletions {
+    pub uri: String,
+    pub cell_id: String,
+    pub position: LspPosition,
+}
+
+/// Get definition from a notebook cell
+#[derive(Message)]
+#[rtype(result = "Result<Vec<crate::types::LspNotebookLocation>, String>")]
+pub struct GetNotebookDefinition {
+    pub uri: String,
+    pub cell_id: String,
+    pub position: LspPosition,
+}
+
+/// Get diagnostics for all cells of a notebook
+#[derive(Message)]
+#[rtype(result = "Result<Vec<crate::types::LspCellDiagnostic>, String>")]
+pub struct GetNotebookDiagnostics {
+    pub uri: String,
+}
+
 /// Update document content
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
@@ -7,6 +7,7 @@ pub mod state;
 pub mod execution;
 pub mod communication;
 pub mod process;
+pub mod kernel;
 pub mod lsp;
 pub mod plot;
 pub mod file_server;
@@ -1,5 +1,6 @@
 use actix::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // ============================================================================
 // Plot Types
@@ -18,6 +19,106 @@ pub struct PlotData {
     pub line_number: Option<u32>,
     pub code_context: Option<String>,
     pub session_id: Option<String>,
+    /// Other representations of the same plot by MIME type (e.g. `text/html`, `text/plain`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub alternatives: BTreeMap<String, String>,
+}
+
+impl PlotData {
+    /// All representations of the plot by MIME type, as in a Jupyter `display_data` output
+    pub fn mime_bundle(&self) -> BTreeMap<String, String> {
+        let mut bundle = self.alternatives.clone();
+        bundle.insert(self.mime_type.clone(), self.data.clone());
+        bundle
+    }
+}
+
+/// Limits applied to a project's plot history, oldest plots are evicted first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlotRetention {
+    pub max_plots: usize,
+    pub max_total_bytes: u64,
+}
+
+impl Default for PlotRetention {
+    fn default() -> Self {
+        Self {
+            max_plots: 500,
+            max_total_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl PlotRetention {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_plots == 0 {
+            return Err("Plot history must keep at least one plot".to_string());
+        }
+        if self.max_total_bytes == 0 {
+            return Err("Plot history size limit must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// File format for plot export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlotExportFormat {
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl PlotExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PlotExportFormat::Png => "png",
+            PlotExportFormat::Svg => "svg",
+            PlotExportFormat::Pdf => "pdf",
+        }
+    }
+
+    /// Format matching a file name's extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(PlotExportFormat::Png),
+            "svg" => Some(PlotExportFormat::Svg),
+            "pdf" => Some(PlotExportFormat::Pdf),
+            _ => None,
+        }
+    }
+}
+
+/// Format, size and resolution of an exported plot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlotExportOptions {
+    pub format: PlotExportFormat,
+    /// Width in CSS pixels (1/96 inch); the plot's own width when unset
+    #[serde(default)]
+    pub width: Option<f32>,
+    /// Height in CSS pixels; derived from the width's aspect ratio when unset
+    #[serde(default)]
+    pub height: Option<f32>,
+    /// Resolution of PNG exports, SVG and PDF exports stay vector graphics
+    #[serde(default = "default_export_dpi")]
+    pub dpi: f32,
+}
+
+fn default_export_dpi() -> f32 {
+    96.0
+}
+
+impl PlotExportOptions {
+    pub fn new(format: PlotExportFormat) -> Self {
+        Self {
+            format,
+            width: None,
+            height: None,
+            dpi: default_export_dpi(),
+        }
+    }
 }
 
 // ============================================================================
@@ -48,11 +149,39 @@ pub struct AddPlot {
     pub plot_data: PlotData,
 }
 
-/// Get plots
+/// Get plot metadata, oldest first (plot data is loaded with GetPlot)
 #[derive(Message)]
 #[rtype(result = "Result<Vec<PlotData>, String>")]
 pub struct GetPlots;
 
+/// Get a single plot with its data
+#[derive(Message)]
+#[rtype(result = "Result<Option<PlotData>, String>")]
+pub struct GetPlot {
+    pub plot_id: String,
+}
+
+/// Get the metadata of the plots created from a source file, oldest first
+#[derive(Message)]
+#[rtype(result = "Result<Vec<PlotData>, String>")]
+pub struct GetPlotsBySourceFile {
+    pub source_file: String,
+}
+
+/// Switch the plot history to a project's on-disk store (None keeps plots in memory)
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetPlotProject {
+    pub project_path: Option<String>,
+}
+
+/// Change the plot history limits, evicting the oldest plots that no longer fit
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetPlotRetention {
+    pub retention: PlotRetention,
+}
+
 /// Delete plot
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
@@ -65,4 +194,26 @@ pub struct DeletePlot {
 #[rtype(result = "Result<(), String>")]
 pub struct HandlePlotDataReceived {
     pub plot_data_json: serde_json::Value,
-}
\ No newline at end of file
+}
+/// Export a stored plot to a file
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct ExportPlot {
+    pub plot_id: String,
+    pub file_path: String,
+    pub options: PlotExportOptions,
+}
+
+/// Export the plots of a session and/or source file (all plots when both are None)
+///
+/// `destination` is a folder, or a zip archive when `zip` is set. Returns the
+/// written file names.
+#[derive(Message)]
+#[rtype(result = "Result<Vec<String>, String>")]
+pub struct ExportPlots {
+    pub source_file: Option<String>,
+    pub session_id: Option<String>,
+    pub destination: String,
+    pub zip: bool,
+    pub options: PlotExportOptions,
+}
@@ -1,4 +1,5 @@
 use actix::prelude::*;
+use std::collections::BTreeMap;
 
 // ============================================================================
 // ProcessActor Messages
@@ -16,6 +17,11 @@ pub struct StartJuliaProcess {
 #[rtype(result = "Result<(), String>")]
 pub struct StopJuliaProcess;
 
+/// Stop Julia process and the ProcessActor itself (kernels that are removed)
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct ShutdownJuliaProcess;
+
 /// Check if Julia is running
 #[derive(Message)]
 #[rtype(result = "Result<bool, String>")]
@@ -68,6 +74,68 @@ pub struct GetNotebookCellOutput;
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
 pub struct BufferNotebookCellPlot {
-    pub mime_type: String,
-    pub data: String,
-}
\ No newline at end of file
+    /// All representations of the plot by MIME type
+    pub bundle: BTreeMap<String, String>,
+}
+
+/// Check whether Julia's message loop is running (the process accepts code)
+#[derive(Message)]
+#[rtype(result = "Result<bool, String>")]
+pub struct IsMessageLoopReady;
+
+/// Build a custom sysimage for a project in the background
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct BuildSysimage {
+    pub project_path: String,
+}
+
+/// List built sysimages
+#[derive(Message)]
+#[rtype(result = "Result<Vec<crate::types::SysimageInfo>, String>")]
+pub struct GetSysimages;
+
+/// Delete all built sysimages
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct ClearSysimages;
+
+/// Set the project whose sysimage is used when Julia starts
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetActiveProject {
+    pub project_path: Option<String>, // None when no project is open
+    pub remember: bool, // Keep as the project for the next app start (default kernel only)
+}
+
+/// Get the latest resource usage sample of the Julia process
+#[derive(Message)]
+#[rtype(result = "Result<Option<crate::types::ResourceSample>, String>")]
+pub struct GetResourceUsage;
+
+/// Get the resource monitor settings
+#[derive(Message)]
+#[rtype(result = "Result<crate::types::ResourceMonitorConfig, String>")]
+pub struct GetResourceMonitorConfig;
+
+/// Update the resource monitor settings
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetResourceMonitorConfig {
+    pub config: crate::types::ResourceMonitorConfig,
+}
+
+/// Start tracking resource usage for a code execution
+#[derive(Message)]
+#[rtype(result = "Result<(), String>")]
+pub struct BeginExecutionTracking {
+    pub execution_id: String,
+}
+
+/// Stop tracking a code execution and get the resources it used
+#[derive(Message)]
+#[rtype(result = "Result<Option<crate::types::ExecutionResourceUsage>, String>")]
+pub struct FinishExecutionTracking {
+    pub execution_id: String,
+    pub julia_metadata: Option<serde_json::Value>, // GC and allocation stats from ExecutionComplete
+}
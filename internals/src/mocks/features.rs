@@ -95,6 +95,7 @@ impl PlotService for MockPlotService {
                 line_number: plot_data_json["line_number"].as_u64().map(|n| n as u32),
                 code_context: plot_data_json["code_context"].as_str().map(|s| s.to_string()),
                 session_id: plot_data_json["session_id"].as_str().map(|s| s.to_string()),
+                alternatives: Default::default(),
             };
             self.plots.lock().await.push(plot_data);
         }
@@ -64,6 +64,18 @@ impl BaseService for FileUtilsService {
     }
 }
 
+/// 64-bit FNV-1a hash as hex (stable across Rust versions, unlike `DefaultHasher`)
+///
+/// Used for file and directory names that must be found again by later builds.
+pub fn fnv1a_hex(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
 /// Convert a Windows path to Julia-compatible format
 /// Julia expects forward slashes and proper drive letter format
 pub fn convert_path_for_julia(path: &str) -> String {
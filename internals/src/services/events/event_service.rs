@@ -169,6 +169,7 @@ pub struct SysimageEventPayload {
     pub status: Option<String>,
     pub message: Option<String>,
     pub error: Option<String>,
+    pub progress: Option<u8>,
 }
 
 /// EventService - manages event emission and coordination
@@ -948,6 +949,45 @@ impl EventService {
         self.emit_event(event).await
     }
 
+    pub async fn emit_kernel_status_changed(&self, kernel: crate::types::KernelInfo) -> Result<(), String> {
+        let payload = serde_json::to_value(kernel)
+            .map_err(|e| format!("Failed to serialize kernel event: {}", e))?;
+
+        let event = Self::create_event(EventCategory::Julia, "kernel-status-changed", payload);
+        self.emit_event(event).await
+    }
+
+    pub async fn emit_julia_resource_usage(&self, sample: crate::types::ResourceSample) -> Result<(), String> {
+        let payload = serde_json::to_value(sample)
+            .map_err(|e| format!("Failed to serialize resource usage event: {}", e))?;
+
+        let event = Self::create_event(EventCategory::Julia, "resource-usage", payload);
+        self.emit_event(event).await
+    }
+
+    pub async fn emit_julia_memory_warning(&self, rss_bytes: u64, threshold_bytes: u64) -> Result<(), String> {
+        let payload = serde_json::json!({
+            "rss_bytes": rss_bytes,
+            "threshold_bytes": threshold_bytes,
+        });
+
+        let event = Self::create_event(EventCategory::Julia, "memory-warning", payload);
+        self.emit_event(event).await
+    }
+
+    pub async fn emit_execution_resource_usage(
+        &self,
+        execution_id: &str,
+        usage: crate::types::ExecutionResourceUsage,
+    ) -> Result<(), String> {
+        let mut payload = serde_json::to_value(usage)
+            .map_err(|e| format!("Failed to serialize execution resource event: {}", e))?;
+        payload["execution_id"] = serde_json::Value::String(execution_id.to_string());
+
+        let event = Self::create_event(EventCategory::Julia, "execution-resource-usage", payload);
+        self.emit_event(event).await
+    }
+
     pub async fn emit_julia_daemon_status_changed(&self, status: &str) -> Result<(), String> {
         let payload = serde_json::to_value(JuliaEventPayload {
             status: Some(status.to_string()),
@@ -1157,6 +1197,42 @@ impl EventService {
         self.emit_event(event).await
     }
 
+    pub async fn emit_sysimage_compilation_progress(&self, progress: u8, message: String) -> Result<(), String> {
+        let payload = serde_json::to_value(SysimageEventPayload {
+            status: Some("compilation-progress".to_string()),
+            message: Some(message),
+            progress: Some(progress),
+            ..Default::default()
+        }).map_err(|e| format!("Failed to serialize sysimage event: {}", e))?;
+
+        let event = Self::create_event(EventCategory::System, "sysimage-compilation-progress", payload);
+        self.emit_event(event).await
+    }
+
+    pub async fn emit_sysimage_compilation_failed(&self, sysimage: crate::types::SysimageInfo, error: String) -> Result<(), String> {
+        let payload = serde_json::to_value(SysimageEventPayload {
+            sysimage: Some(sysimage),
+            status: Some("compilation-failed".to_string()),
+            error: Some(error),
+            ..Default::default()
+        }).map_err(|e| format!("Failed to serialize sysimage event: {}", e))?;
+
+        let event = Self::create_event(EventCategory::System, "sysimage-compilation-failed", payload);
+        self.emit_event(event).await
+    }
+
+    pub async fn emit_sysimage_load_failed(&self, sysimage: crate::types::SysimageInfo, error: String) -> Result<(), String> {
+        let payload = serde_json::to_value(SysimageEventPayload {
+            sysimage: Some(sysimage),
+            status: Some("load-failed".to_string()),
+            error: Some(error),
+            ..Default::default()
+        }).map_err(|e| format!("Failed to serialize sysimage event: {}", e))?;
+
+        let event = Self::create_event(EventCategory::System, "sysimage-load-failed", payload);
+        self.emit_event(event).await
+    }
+
     pub async fn emit_current_sysimage_changed(&self, sysimage: crate::types::SysimageInfo) -> Result<(), String> {
         let payload = serde_json::to_value(SysimageEventPayload {
             sysimage: Some(sysimage),
@@ -26,6 +26,9 @@ pub struct UserPreferences {
     pub editor_minimap: Option<bool>,
     /// Editor color scheme/theme
     pub editor_color_scheme: Option<String>,
+    /// Julia launch profiles keyed by project path
+    #[serde(default)]
+    pub launch_profiles: std::collections::BTreeMap<String, ProjectLaunchProfiles>,
 }
 
 impl UserPreferences {
@@ -43,6 +46,7 @@ impl UserPreferences {
             editor_line_numbers: None,
             editor_minimap: None,
             editor_color_scheme: None,
+            launch_profiles: std::collections::BTreeMap::new(),
         }
     }
     
@@ -123,6 +127,12 @@ impl UserPreferences {
                 }
             }
             
+            if let Some(launch_profiles) = obj.get("launch_profiles") {
+                if !launch_profiles.is_null() {
+                    prefs.launch_profiles = serde_json::from_value(launch_profiles.clone()).unwrap_or_default();
+                }
+            }
+            
             return Ok(prefs);
         }
         
@@ -131,6 +141,149 @@ impl UserPreferences {
     }
 }
 
+/// Name of the profile used when a project has none selected
+pub const DEFAULT_LAUNCH_PROFILE: &str = "default";
+
+/// Command line arguments that are set by the app or by dedicated profile fields
+const RESERVED_JULIA_ARGS: &[&str] = &[
+    "--project", "-J", "--sysimage", "-e", "--eval", "-E", "--print", "-L", "--load",
+    "-t", "--threads", "--heap-size-hint", "--check-bounds", "-O", "--optimize",
+];
+
+/// Environment variables that are set by the app or by dedicated profile fields
+const RESERVED_JULIA_ENV: &[&str] = &["COMPUTE42_DATA_DIR", "JULIA_PROJECT", "JULIA_DEPOT_PATH", "JULIA_NUM_THREADS"];
+
+/// Settings used to launch the Julia process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JuliaLaunchProfile {
+    pub name: String,
+    /// Value for `--threads` ("auto", "4", or "4,1" for default and interactive threads); None keeps one thread
+    pub threads: Option<String>,
+    /// Value for `--heap-size-hint` (e.g. "4G")
+    pub heap_size_hint: Option<String>,
+    /// Value for `--check-bounds` ("yes", "no" or "auto")
+    pub check_bounds: Option<String>,
+    /// Optimization level for `-O` (0-3)
+    pub optimize: Option<u8>,
+    /// Additional command line arguments
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// Additional environment variables
+    #[serde(default)]
+    pub env: std::collections::BTreeMap<String, String>,
+    /// User depot searched before the app's depot (the first depot receives new packages)
+    pub depot_path: Option<String>,
+}
+
+impl Default for JuliaLaunchProfile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_LAUNCH_PROFILE.to_string(),
+            threads: None,
+            heap_size_hint: None,
+            check_bounds: None,
+            optimize: None,
+            extra_args: Vec::new(),
+            env: std::collections::BTreeMap::new(),
+            depot_path: None,
+        }
+    }
+}
+
+impl JuliaLaunchProfile {
+    /// Check that the profile produces a valid Julia command line
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > 64 || name != self.name {
+            return Err("Profile name must be 1-64 characters without leading or trailing whitespace".to_string());
+        }
+
+        if let Some(threads) = &self.threads {
+            let valid_count = |s: &str| s.parse::<u16>().map(|n| n > 0).unwrap_or(false);
+            let (default, interactive) = match threads.split_once(',') {
+                Some((d, i)) => (d, Some(i)),
+                None => (threads.as_str(), None),
+            };
+            let interactive_valid = match interactive {
+                Some(i) => i == "0" || valid_count(i),
+                None => true,
+            };
+            if !(default == "auto" || valid_count(default)) || !interactive_valid {
+                return Err(format!("Invalid thread count '{}': use \"auto\", N or N,M", threads));
+            }
+        }
+
+        if let Some(hint) = &self.heap_size_hint {
+            let number = hint.trim_end_matches(|c: char| "kKmMgGtT%".contains(c));
+            let unit_len = hint.len() - number.len();
+            if number.is_empty() || unit_len > 1 || number.parse::<f64>().map(|n| n <= 0.0).unwrap_or(true) {
+                return Err(format!("Invalid heap size hint '{}': use a size such as 512M or 4G", hint));
+            }
+        }
+
+        if let Some(check_bounds) = &self.check_bounds {
+            if !["yes", "no", "auto"].contains(&check_bounds.as_str()) {
+                return Err(format!("Invalid check-bounds value '{}': use yes, no or auto", check_bounds));
+            }
+        }
+
+        if let Some(level) = self.optimize {
+            if level > 3 {
+                return Err(format!("Invalid optimization level {}: use 0-3", level));
+            }
+        }
+
+        for arg in &self.extra_args {
+            if !arg.starts_with('-') {
+                return Err(format!("Argument '{}' is not an option; scripts cannot be passed to the Julia session", arg));
+            }
+            let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+            let reserved = RESERVED_JULIA_ARGS.iter().any(|r| {
+                flag == *r || (r.len() == 2 && !r.starts_with("--") && flag.starts_with(r))
+            });
+            if reserved {
+                return Err(format!("Argument '{}' is managed by Compute42 or a profile field and cannot be set directly", arg));
+            }
+        }
+
+        for key in self.env.keys() {
+            if key.is_empty() || key.contains('=') || key.contains('\0') {
+                return Err(format!("Invalid environment variable name '{}'", key));
+            }
+            if RESERVED_JULIA_ENV.contains(&key.as_str()) {
+                return Err(format!("Environment variable {} is managed by Compute42 or a profile field", key));
+            }
+        }
+
+        if let Some(depot_path) = &self.depot_path {
+            if !std::path::Path::new(depot_path).is_absolute() {
+                return Err(format!("Depot path '{}' must be absolute", depot_path));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Named launch profiles of a project and the one currently selected
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLaunchProfiles {
+    pub active: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<JuliaLaunchProfile>,
+}
+
+impl ProjectLaunchProfiles {
+    /// The selected profile, or the default profile if none is selected
+    pub fn active_profile(&self) -> JuliaLaunchProfile {
+        self.active
+            .as_ref()
+            .and_then(|name| self.profiles.iter().find(|p| &p.name == name))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 
 // ============================================================================
 // LSP Data Structures
@@ -250,6 +403,18 @@ pub struct LspCodeAction {
     pub data: Option<serde_json::Value>,
 }
 
+/// Dependency health report produced by the embedded language server
+pub use languageserver::features::DependencyHealthReport;
+
+/// Package health report (type piracy, overwritten methods, ambiguities) produced by the embedded language server
+pub use languageserver::features::PackageHealthReport;
+
+/// Workspace dead code report produced by the embedded language server
+pub use languageserver::features::DeadCodeReport;
+
+/// Notebook cell dependency graph produced by the embedded language server
+pub use languageserver::features::CellDependencyGraph;
+
 /// LSP Diagnostic structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LspDiagnostic {
@@ -263,6 +428,21 @@ pub struct LspDiagnostic {
     pub data: Option<serde_json::Value>,
 }
 
+/// Diagnostic reported in a notebook cell, with a cell-relative range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspCellDiagnostic {
+    pub cell_id: String,
+    pub diagnostic: LspDiagnostic,
+}
+
+/// Location that may point into a notebook cell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspNotebookLocation {
+    /// Cell the range is relative to, or None for locations in other files
+    pub cell_id: Option<String>,
+    pub location: LspLocation,
+}
+
 /// LSP Diagnostic Related Information structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LspDiagnosticRelatedInformation {
@@ -444,6 +624,14 @@ pub struct SysimageInfo {
     pub path: std::path::PathBuf,
     pub is_available: bool,
     pub compilation_state: SysimageCompilationState,
+    /// Project the sysimage was built for
+    pub project_path: Option<std::path::PathBuf>,
+    /// Hash of the Manifest.toml the sysimage was built from
+    pub manifest_hash: Option<String>,
+    pub julia_version: Option<String>,
+    /// RFC 3339 build timestamp
+    pub created_at: Option<String>,
+    pub size_bytes: Option<u64>,
 }
 
 /// Sysimage compilation state
@@ -455,6 +643,119 @@ pub enum SysimageCompilationState {
     Failed,
 }
 
+/// Name of the kernel backed by the main Julia process
+pub const DEFAULT_KERNEL: &str = "default";
+
+/// Julia kernel information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelInfo {
+    pub name: String,
+    pub project_path: Option<String>,
+    pub status: KernelStatus,
+    /// True for the main Julia process started with the app
+    pub is_default: bool,
+    /// RFC 3339 timestamp of the last (re)start
+    pub started_at: Option<String>,
+    /// Seconds since the kernel last finished an execution
+    pub idle_seconds: u64,
+    pub error: Option<String>,
+}
+
+/// Julia kernel status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KernelStatus {
+    Starting,
+    Idle,
+    Busy,
+    Restarting,
+    Stopped,
+    Failed,
+}
+
+/// Resource usage sample of the Julia process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub pid: u32,
+    pub rss_bytes: u64,
+    /// CPU usage since the previous sample (100.0 = one fully used core)
+    pub cpu_percent: f64,
+    pub threads: u32,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// Resource monitor settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceMonitorConfig {
+    pub enabled: bool,
+    pub interval_ms: u64,
+    /// Emit a memory warning when the Julia process RSS crosses this many bytes
+    pub memory_warning_threshold_bytes: Option<u64>,
+}
+
+/// Shortest allowed sampling interval
+pub const MIN_RESOURCE_MONITOR_INTERVAL_MS: u64 = 250;
+
+impl Default for ResourceMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 2000,
+            memory_warning_threshold_bytes: Some(8 * 1024 * 1024 * 1024),
+        }
+    }
+}
+
+impl ResourceMonitorConfig {
+    /// Check the settings before they are applied
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_ms < MIN_RESOURCE_MONITOR_INTERVAL_MS {
+            return Err(format!(
+                "Sampling interval must be at least {} ms",
+                MIN_RESOURCE_MONITOR_INTERVAL_MS
+            ));
+        }
+        if self.memory_warning_threshold_bytes == Some(0) {
+            return Err("Memory warning threshold must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Resources used by a single code execution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionResourceUsage {
+    pub wall_time_ms: u64,
+    /// Highest Julia process RSS observed while the execution ran
+    pub peak_memory_bytes: Option<u64>,
+    /// Bytes allocated by the execution, as reported by Julia
+    pub allocated_bytes: Option<u64>,
+    /// Time spent in garbage collection, as reported by Julia
+    pub gc_time_ms: Option<f64>,
+    /// Number of garbage collections, as reported by Julia
+    pub gc_count: Option<u64>,
+    /// Live heap size after the execution, as reported by Julia
+    pub gc_live_bytes: Option<u64>,
+}
+
+impl ExecutionResourceUsage {
+    /// Add the usage fields to `ExecutionComplete` metadata, keeping any other keys
+    pub fn merge_into_metadata(&self, metadata: Option<serde_json::Value>) -> serde_json::Value {
+        let mut map = match metadata {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        if let Ok(serde_json::Value::Object(usage)) = serde_json::to_value(self) {
+            for (key, value) in usage {
+                if !value.is_null() {
+                    map.insert(key, value);
+                }
+            }
+        }
+        serde_json::Value::Object(map)
+    }
+}
+
 // ============================================================================
 // Account Management Types
 // ============================================================================
@@ -469,3 +770,58 @@ pub enum AccountState {
     SetupIncomplete,
     Error { message: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_profile() -> JuliaLaunchProfile {
+        JuliaLaunchProfile { name: "Fast".to_string(), ..JuliaLaunchProfile::default() }
+    }
+
+    #[test]
+    fn test_valid_launch_profile() {
+        let depot = std::env::temp_dir().join("depot").to_string_lossy().to_string();
+        let profile = JuliaLaunchProfile {
+            threads: Some("4,1".to_string()),
+            heap_size_hint: Some("4G".to_string()),
+            check_bounds: Some("no".to_string()),
+            optimize: Some(3),
+            extra_args: vec!["--compiled-modules=yes".to_string()],
+            env: [("JULIA_PKG_OFFLINE".to_string(), "true".to_string())].into_iter().collect(),
+            depot_path: Some(depot),
+            ..base_profile()
+        };
+        assert_eq!(profile.validate(), Ok(()));
+        assert_eq!(JuliaLaunchProfile::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_thread_count() {
+        for threads in ["0", "-1", "many", "4,", "4,x", ""] {
+            let profile = JuliaLaunchProfile { threads: Some(threads.to_string()), ..base_profile() };
+            assert!(profile.validate().is_err(), "threads = {:?}", threads);
+        }
+        let profile = JuliaLaunchProfile { threads: Some("auto,0".to_string()), ..base_profile() };
+        assert_eq!(profile.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_relative_depot_path_rejected() {
+        let profile = JuliaLaunchProfile { depot_path: Some("depot".to_string()), ..base_profile() };
+        assert!(profile.validate().unwrap_err().contains("must be absolute"));
+        let profile = JuliaLaunchProfile { depot_path: None, ..base_profile() };
+        assert_eq!(profile.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_project_path_reserved() {
+        // The project is chosen by the app; profiles cannot point the session elsewhere
+        let profile = JuliaLaunchProfile { extra_args: vec!["--project=/tmp/other".to_string()], ..base_profile() };
+        assert!(profile.validate().is_err());
+        let mut env = std::collections::BTreeMap::new();
+        env.insert("JULIA_PROJECT".to_string(), "/tmp/other".to_string());
+        let profile = JuliaLaunchProfile { env, ..base_profile() };
+        assert!(profile.validate().is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod service;
+pub mod notebook;
 // Settings facade for toggles (headless; UI owns persistence)
 #[derive(Debug, Clone, Default)]
 pub struct EmbeddedSettings {
@@ -7,3 +8,4 @@ pub struct EmbeddedSettings {
 }
 
 pub use service::{EmbeddedLspService, LspConfig};
+pub use notebook::{CellDiagnostic, NotebookCellSource, NotebookDocument, NotebookLocation};
@@ -0,0 +1,242 @@
+use crate::types::{Diagnostic, Location, Position, Range};
+use serde::{Deserialize, Serialize};
+
+/// Source of one code cell, as sent by the notebook editor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotebookCellSource {
+    pub cell_id: String,
+    pub source: String,
+}
+
+/// A cell's place in the virtual document
+#[derive(Debug, Clone, PartialEq)]
+struct CellSpan {
+    cell_id: String,
+    /// First line of the cell in the virtual document
+    start_line: u32,
+    /// Number of lines the cell occupies (at least one, even when empty)
+    line_count: u32,
+}
+
+/// A location that may point into a notebook cell
+#[derive(Debug, Clone)]
+pub struct NotebookLocation {
+    /// Cell the range is relative to, or None for locations in other files
+    pub cell_id: Option<String>,
+    pub location: Location,
+}
+
+/// A diagnostic with its range relative to the cell it was reported in
+#[derive(Debug, Clone)]
+pub struct CellDiagnostic {
+    pub cell_id: String,
+    pub diagnostic: Diagnostic,
+}
+
+/// The code cells of a notebook concatenated into one Julia document
+///
+/// Each cell is followed by a newline, so later cells see the definitions of
+/// earlier ones and a cell position maps to the virtual document by adding the
+/// cell's start line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotebookDocument {
+    cells: Vec<CellSpan>,
+    text: String,
+}
+
+impl NotebookDocument {
+    pub fn new(cells: &[NotebookCellSource]) -> Self {
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(cells.len());
+        let mut line = 0;
+        for cell in cells {
+            let source = cell.source.strip_suffix('\n').unwrap_or(&cell.source);
+            let line_count = source.split('\n').count() as u32;
+            text.push_str(source);
+            text.push('\n');
+            spans.push(CellSpan {
+                cell_id: cell.cell_id.clone(),
+                start_line: line,
+                line_count,
+            });
+            line += line_count;
+        }
+        Self { cells: spans, text }
+    }
+
+    /// Concatenated source of all cells
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cell_ids(&self) -> impl Iterator<Item = &str> {
+        self.cells.iter().map(|cell| cell.cell_id.as_str())
+    }
+
+    /// Position in the virtual document for a cell-relative position
+    pub fn to_virtual(&self, cell_id: &str, position: Position) -> Option<Position> {
+        let cell = self.cells.iter().find(|cell| cell.cell_id == cell_id)?;
+        if position.line >= cell.line_count {
+            return None;
+        }
+        Some(Position {
+            line: cell.start_line + position.line,
+            character: position.character,
+        })
+    }
+
+    /// Cell and cell-relative position for a virtual document position
+    pub fn to_cell(&self, position: Position) -> Option<(&str, Position)> {
+        let cell = self.cell_at_line(position.line)?;
+        Some((
+            cell.cell_id.as_str(),
+            Position {
+                line: position.line - cell.start_line,
+                character: position.character,
+            },
+        ))
+    }
+
+    /// Cell and cell-relative range for a virtual document range
+    ///
+    /// Ranges that run past the end of their starting cell are clipped to it.
+    pub fn range_to_cell(&self, range: &Range) -> Option<(&str, Range)> {
+        let cell = self.cell_at_line(range.start.line)?;
+        let last_line = cell.start_line + cell.line_count - 1;
+        let end = if range.end.line > last_line {
+            let line_len = self.text.split('\n').nth(last_line as usize).map_or(0, str::len);
+            Position {
+                line: cell.line_count - 1,
+                character: line_len as u32,
+            }
+        } else {
+            Position {
+                line: range.end.line - cell.start_line,
+                character: range.end.character,
+            }
+        };
+        Some((
+            cell.cell_id.as_str(),
+            Range {
+                start: Position {
+                    line: range.start.line - cell.start_line,
+                    character: range.start.character,
+                },
+                end,
+            },
+        ))
+    }
+
+    /// Map a location to a cell when it points into the virtual document at `uri`
+    pub fn map_location(&self, uri: &str, location: Location) -> NotebookLocation {
+        if location.uri == uri {
+            if let Some((cell_id, range)) = self.range_to_cell(&location.range) {
+                return NotebookLocation {
+                    cell_id: Some(cell_id.to_string()),
+                    location: Location {
+                        uri: location.uri,
+                        range,
+                    },
+                };
+            }
+        }
+        NotebookLocation {
+            cell_id: None,
+            location,
+        }
+    }
+
+    /// Map a virtual document diagnostic back to the cell it starts in
+    pub fn map_diagnostic(&self, uri: &str, mut diagnostic: Diagnostic) -> Option<CellDiagnostic> {
+        let (cell_id, range) = self.range_to_cell(&diagnostic.range)?;
+        let cell_id = cell_id.to_string();
+        diagnostic.range = range;
+        if let Some(related) = diagnostic.related_information.as_mut() {
+            for info in related.iter_mut() {
+                if info.location.uri == uri {
+                    if let Some((related_cell, range)) = self.range_to_cell(&info.location.range) {
+                        info.location.range = range;
+                        info.message = format!("{} (cell {})", info.message, related_cell);
+                    }
+                }
+            }
+        }
+        Some(CellDiagnostic { cell_id, diagnostic })
+    }
+
+    fn cell_at_line(&self, line: u32) -> Option<&CellSpan> {
+        self.cells
+            .iter()
+            .find(|cell| line >= cell.start_line && line < cell.start_line + cell.line_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DiagnosticSeverity;
+
+    fn notebook() -> NotebookDocument {
+        NotebookDocument::new(&[
+            NotebookCellSource { cell_id: "a".to_string(), source: "x = 1\ny = 2\n".to_string() },
+            NotebookCellSource { cell_id: "b".to_string(), source: String::new() },
+            NotebookCellSource { cell_id: "c".to_string(), source: "z = x + y".to_string() },
+        ])
+    }
+
+    fn pos(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_concatenated_text() {
+        assert_eq!(notebook().text(), "x = 1\ny = 2\n\nz = x + y\n");
+    }
+
+    #[test]
+    fn test_position_mapping_round_trip() {
+        let doc = notebook();
+        assert_eq!(doc.to_virtual("c", pos(0, 4)), Some(pos(3, 4)));
+        assert_eq!(doc.to_cell(pos(3, 4)), Some(("c", pos(0, 4))));
+        assert_eq!(doc.to_cell(pos(1, 0)), Some(("a", pos(1, 0))));
+        assert_eq!(doc.to_cell(pos(2, 0)), Some(("b", pos(0, 0))));
+        assert_eq!(doc.to_virtual("a", pos(2, 0)), None);
+        assert_eq!(doc.to_virtual("missing", pos(0, 0)), None);
+        assert_eq!(doc.to_cell(pos(4, 0)), None);
+    }
+
+    #[test]
+    fn test_range_clipped_to_cell() {
+        let doc = notebook();
+        let range = Range { start: pos(1, 2), end: pos(3, 1) };
+        let (cell_id, range) = doc.range_to_cell(&range).unwrap();
+        assert_eq!(cell_id, "a");
+        assert_eq!(range.start, pos(1, 2));
+        assert_eq!(range.end, pos(1, 5));
+    }
+
+    #[test]
+    fn test_map_location_and_diagnostic() {
+        let doc = notebook();
+        let uri = "/work/analysis.ipynb";
+        let location = Location {
+            uri: uri.to_string(),
+            range: Range { start: pos(0, 0), end: pos(0, 1) },
+        };
+        let external = Location { uri: "/work/src/Lib.jl".to_string(), ..location.clone() };
+        assert_eq!(doc.map_location(uri, location).cell_id.as_deref(), Some("a"));
+        assert_eq!(doc.map_location(uri, external).cell_id, None);
+
+        let diagnostic = Diagnostic {
+            range: Range { start: pos(3, 4), end: pos(3, 5) },
+            severity: Some(DiagnosticSeverity::Warning),
+            code: None,
+            source: None,
+            message: "undefined".to_string(),
+            related_information: None,
+        };
+        let mapped = doc.map_diagnostic(uri, diagnostic).unwrap();
+        assert_eq!(mapped.cell_id, "c");
+        assert_eq!(mapped.diagnostic.range.start, pos(0, 4));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::pipeline::sources::{Document, ProjectContext};
+use crate::pipeline::sources::{Document, LocalRegistry, ProjectContext};
+use crate::pipeline::sources::project_context::ProjectToml;
 use crate::pipeline::parser::JuliaParser;
 use crate::pipeline::storage::CacheManager;
 use crate::pipeline::{
@@ -9,9 +10,13 @@ use crate::pipeline::{
     PackagePipelineInput,
     Pipeline,
 };
-use crate::features::{HoverProvider, DefinitionProvider, ReferencesProvider, DiagnosticsProvider};
+use crate::features::{HoverProvider, DefinitionProvider, ReferencesProvider, DiagnosticsProvider, SignatureHelpProvider};
+use crate::features::{DependencyHealthProvider, DependencyHealthReport, PackageHealthProvider, PackageHealthReport};
+use crate::features::ProjectTomlProvider;
+use crate::features::{DeadCodeProvider, DeadCodeReport};
 use crate::features::diagnostics::incremental::IncrementalDiagnostics;
-use crate::types::{Position, LspError, CompletionList, Location, Diagnostic};
+use crate::types::{Position, LspError, CompletionList, Location, Diagnostic, DiagnosticSeverity, Range, SignatureHelp};
+use super::notebook::{CellDiagnostic, NotebookCellSource, NotebookDocument, NotebookLocation};
 use std::collections::HashMap;
 use std::path::{PathBuf, Path};
 
@@ -74,6 +79,8 @@ impl LspConfig {
 pub struct EmbeddedLspService {
     config: LspConfig,
     documents: HashMap<PathBuf, Document>,
+    // Open notebooks; each is analyzed as the virtual document stored under its path
+    notebooks: HashMap<PathBuf, NotebookDocument>,
     parser: JuliaParser,
     
     // Unified index (replaces SymbolTable, ReferenceTable, TypeRegistry)
@@ -82,6 +89,11 @@ pub struct EmbeddedLspService {
     // Project infrastructure
     project_context: Option<ProjectContext>,
     cache_manager: CacheManager,
+    
+    // Package registries from the depot (for dependency health checks)
+    registry: Option<LocalRegistry>,
+    // Latest content of the root Project.toml, if opened in the editor
+    project_toml_text: Option<String>,
     instance_id: usize,
     
     // Note: Base and package documentation are now stored in the Index
@@ -98,10 +110,13 @@ impl EmbeddedLspService {
         let mut service = Self {
             config: config.clone(),
             documents: HashMap::new(),
+            notebooks: HashMap::new(),
             parser: JuliaParser::new(),
             index: Index::new(),
             project_context: None,
             cache_manager: CacheManager::new(),
+            registry: None,
+            project_toml_text: None,
             instance_id,
             incremental_diagnostics: IncrementalDiagnostics::new(),
         };
@@ -119,11 +134,12 @@ impl EmbeddedLspService {
         self.config.project_root = Some(project_root.clone());
 
         // Create project context with depot path if available
-        let context = if let Some(ref depot_path) = self.config.julia_depot_path {
-            ProjectContext::with_depot_path(project_root.clone(), Some(depot_path.clone()))?
-        } else {
-            ProjectContext::new(project_root.clone())?
-        };
+        // and the Julia installation (stdlib versions, versioned manifests, `@v#.#`)
+        let context = ProjectContext::with_environment(
+            project_root.clone(),
+            self.config.julia_depot_path.clone(),
+            Some(&self.config.julia_executable),
+        )?;
         log::debug!("LSP Service: Opening project at {:?}", project_root);
         
         // Step 1: Extract Base/stdlib metadata (signatures, types, exports)
@@ -189,8 +205,21 @@ impl EmbeddedLspService {
             self.index.get_all_symbols().len()
         );
         
+        // Step 5: Load the local package registries for dependency health checks
+        self.registry = self.config.julia_depot_path.as_ref().and_then(|depot_path| {
+            match LocalRegistry::load(depot_path) {
+                Ok(registry) => Some(registry),
+                Err(e) => {
+                    log::warn!("EmbeddedLspService: Failed to load package registries: {}", e);
+                    None
+                }
+            }
+        });
+        
         // Store project context
         self.project_context = Some(context);
+        self.project_toml_text = None;
+        self.load_dependency_versions();
         
         log::debug!("LSP Service: Project opened - {} documents, {} symbols", 
                    self.documents.len(), self.index.get_all_symbols().len());
@@ -226,6 +255,12 @@ impl EmbeddedLspService {
         // Invalidate cache for this document
         self.invalidate_cache(&uri);
         
+        // Project.toml is not Julia code: keep its text for dependency diagnostics
+        if self.is_root_project_toml(&uri) {
+            self.update_project_toml(content);
+            return Ok(());
+        }
+        
         // Process file using pipeline
         let source_item = crate::pipeline::types::SourceItem {
             path: uri.clone(),
@@ -239,8 +274,10 @@ impl EmbeddedLspService {
             ),
         };
         
+        // Keep the module placement resolved when the workspace was indexed
+        let module = self.index.get_file_module(&uri).map(|m| m.context.clone());
         let workspace_pipeline = WorkspacePipeline::new();
-        let analysis = workspace_pipeline.run_single_file(source_item)?;
+        let analysis = workspace_pipeline.run_single_file_in_module(source_item, module.as_deref())?;
         
         // Merge analysis result into main index (replaces data for this file)
         self.index.merge_file(&uri, analysis)?;
@@ -265,8 +302,13 @@ impl EmbeddedLspService {
     pub async fn hover(&self, uri: &PathBuf, line: u32, character: u32) -> Option<String> {
         log::trace!("LSP Service: Hover request at {}:{}", line, character);
         
-        let doc = self.documents.get(uri)?;
         let position = Position { line, character };
+        if self.is_root_project_toml(uri) {
+            let text = self.project_toml_text.as_ref()?;
+            return ProjectTomlProvider::hover(text, position, self.project_context.as_ref()?, self.registry.as_ref());
+        }
+        
+        let doc = self.documents.get(uri)?;
         
         // Use query engine for symbol resolution
         let symbol_query = SymbolQuery::new(&self.index);
@@ -291,8 +333,13 @@ impl EmbeddedLspService {
     
     /// Get completion suggestions (synchronous for embedded use)
     pub fn complete(&self, uri: &PathBuf, line: u32, character: u32) -> Option<CompletionList> {
-        let doc = self.documents.get(uri)?;
         let position = Position { line, character };
+        if self.is_root_project_toml(uri) {
+            let text = self.project_toml_text.as_ref()?;
+            return ProjectTomlProvider::complete(text, position, self.project_context.as_ref()?, self.registry.as_ref());
+        }
+        
+        let doc = self.documents.get(uri)?;
         
         // Use query engine for completion
         let query = CompletionQuery::new(&self.index);
@@ -330,6 +377,14 @@ impl EmbeddedLspService {
         DefinitionProvider::find_definition(&self.index, doc, position)
     }
     
+    /// Get signature help for the call at position
+    pub fn signature_help(&self, uri: &PathBuf, line: u32, character: u32) -> Option<SignatureHelp> {
+        let doc = self.documents.get(uri)?;
+        let position = Position { line, character };
+        
+        SignatureHelpProvider::signature_help(&self.index, doc, position)
+    }
+    
     /// Find references to symbol at position
     pub fn find_references(&self, uri: &PathBuf, line: u32, character: u32, include_declaration: bool) -> Option<Vec<Location>> {
         let doc = self.documents.get(uri)?;
@@ -343,6 +398,10 @@ impl EmbeddedLspService {
     pub fn get_diagnostics(&self, uri: &PathBuf) -> Vec<Diagnostic> {
         log::trace!("LSP Service: Computing diagnostics for {:?}", uri);
         
+        if self.is_root_project_toml(uri) {
+            return self.project_toml_diagnostics();
+        }
+        
         let Some(doc) = self.documents.get(uri) else {
             return Vec::new();
         };
@@ -367,13 +426,18 @@ impl EmbeddedLspService {
         // Compute diagnostics with context using Index
         let depot_path = self.config.julia_depot_path.as_deref();
         let manifest = self.project_context.as_ref().and_then(|ctx| ctx.manifest_toml.as_ref());
-        let diagnostics = DiagnosticsProvider::compute_diagnostics_with_context(
+        let mut diagnostics = DiagnosticsProvider::compute_diagnostics_with_context(
             doc,
             Some(&self.index),
             depot_path,
             manifest,
         );
         
+        // Method-level package checks for files of the open project
+        if let Some(report) = self.package_health() {
+            diagnostics.extend(PackageHealthProvider::to_diagnostics(&report, &uri_str));
+        }
+        
         // Cache the results
         self.cache_manager.diagnostics_cache.put(&uri_str, version, diagnostics.clone());
         
@@ -382,6 +446,172 @@ impl EmbeddedLspService {
         diagnostics
     }
     
+    /// Check whether a path is the Project.toml (or JuliaProject.toml) of the open project
+    fn is_root_project_toml(&self, uri: &Path) -> bool {
+        self.project_context
+            .as_ref()
+            .map(|ctx| match ctx.project_file {
+                Some(ref project_file) => uri == project_file,
+                // Not created yet: Pkg accepts either name
+                None => ["JuliaProject.toml", "Project.toml"].iter().any(|name| uri == ctx.root_path.join(name)),
+            })
+            .unwrap_or(false)
+    }
+    
+    /// Store edited Project.toml content and refresh the parsed project
+    fn update_project_toml(&mut self, content: String) {
+        match ProjectToml::parse(&content) {
+            Ok(project_toml) => {
+                if let Some(ctx) = self.project_context.as_mut() {
+                    ctx.project_toml = Some(project_toml);
+                }
+                self.load_dependency_versions();
+            }
+            Err(e) => log::debug!("LSP Service: Project.toml does not parse: {}", e),
+        }
+        self.project_toml_text = Some(content);
+    }
+    
+    /// Load registry versions for the project's dependencies (no-op for already loaded ones)
+    fn load_dependency_versions(&mut self) {
+        let (Some(registry), Some(ctx)) = (self.registry.as_mut(), self.project_context.as_ref()) else {
+            return;
+        };
+        if let Some(deps) = ctx.dependencies() {
+            let uuids = deps.values().cloned().collect();
+            registry.load_versions(&uuids);
+        }
+    }
+    
+    /// Cross-reference Project.toml, Manifest.toml, the registry and workspace imports
+    pub fn dependency_health(&self) -> Option<DependencyHealthReport> {
+        let ctx = self.project_context.as_ref()?;
+        if !ctx.has_project_toml() {
+            return None;
+        }
+        Some(DependencyHealthProvider::analyze(ctx, &self.index, self.registry.as_ref()))
+    }
+    
+    /// Check the workspace package's methods for type piracy, overwritten methods and ambiguities
+    pub fn package_health(&self) -> Option<PackageHealthReport> {
+        let ctx = self.project_context.as_ref()?;
+        Some(PackageHealthProvider::analyze(ctx, &self.index))
+    }
+    
+    /// Unused definitions and imports, undefined exports and unreachable code across the workspace
+    pub fn dead_code(&self) -> Option<DeadCodeReport> {
+        let ctx = self.project_context.as_ref()?;
+        let source_items = match WorkspaceSource::new(ctx.root_path.clone()).discover() {
+            Ok(items) => items,
+            Err(e) => {
+                log::warn!("LSP Service: Failed to discover workspace files for dead code report: {}", e);
+                return None;
+            }
+        };
+        
+        let mut report = DeadCodeReport::default();
+        for item in source_items {
+            // Prefer the editor's content for open files
+            let parsed;
+            let doc = match self.documents.get(&item.path) {
+                Some(doc) => doc,
+                None => {
+                    let mut doc = Document::new(item.path.to_string_lossy().to_string(), item.content);
+                    let Ok(mut parser) = self.parser.create_parser() else { continue };
+                    if doc.parse(&mut parser).is_err() {
+                        continue;
+                    }
+                    parsed = doc;
+                    &parsed
+                }
+            };
+            if let Some(tree) = doc.tree() {
+                report.items.extend(DeadCodeProvider::analyze_file(tree, &doc.text(), doc.uri(), &self.index));
+            }
+        }
+        report.items.sort_by(|a, b| a.file_uri.cmp(&b.file_uri));
+        Some(report)
+    }
+    
+    /// Diagnostics for the root Project.toml
+    fn project_toml_diagnostics(&self) -> Vec<Diagnostic> {
+        let Some(ref text) = self.project_toml_text else {
+            return Vec::new();
+        };
+        let project = match ProjectToml::parse(text) {
+            Ok(project) => project,
+            Err(e) => return vec![Diagnostic {
+                range: Range {
+                    start: Position { line: 0, character: 0 },
+                    end: Position { line: 0, character: 0 },
+                },
+                severity: Some(DiagnosticSeverity::Error),
+                code: Some("invalid_toml".to_string()),
+                source: Some("dependencies".to_string()),
+                message: e.to_string(),
+                related_information: None,
+            }],
+        };
+        let mut diagnostics = self.dependency_health()
+            .map(|report| DependencyHealthProvider::to_diagnostics(&report, text))
+            .unwrap_or_default();
+        if let Some(ref ctx) = self.project_context {
+            diagnostics.extend(ProjectTomlProvider::diagnostics(&project, text, ctx, self.registry.as_ref()));
+        }
+        diagnostics
+    }
+    
+    /// Open/update a notebook from the sources of its code cells, in notebook order
+    pub fn update_notebook(&mut self, uri: PathBuf, cells: Vec<NotebookCellSource>) -> Result<(), LspError> {
+        let notebook = NotebookDocument::new(&cells);
+        log::trace!("LSP Service: Updating notebook {:?} ({} cells)", uri, cells.len());
+        self.update_document(uri.clone(), notebook.text().to_string())?;
+        self.notebooks.insert(uri, notebook);
+        Ok(())
+    }
+    
+    /// Forget a closed notebook and remove its definitions from the index
+    pub fn close_notebook(&mut self, uri: &PathBuf) {
+        if self.notebooks.remove(uri).is_some() {
+            self.invalidate_cache(uri);
+            self.documents.remove(uri);
+            self.index.remove_file(uri);
+        }
+    }
+    
+    /// Hover inside a notebook cell
+    pub async fn notebook_hover(&self, uri: &PathBuf, cell_id: &str, line: u32, character: u32) -> Option<String> {
+        let position = self.notebooks.get(uri)?.to_virtual(cell_id, Position { line, character })?;
+        self.hover(uri, position.line, position.character).await
+    }
+    
+    /// Completions inside a notebook cell, including globals from every cell
+    pub fn notebook_complete(&self, uri: &PathBuf, cell_id: &str, line: u32, character: u32) -> Option<CompletionList> {
+        let position = self.notebooks.get(uri)?.to_virtual(cell_id, Position { line, character })?;
+        self.complete(uri, position.line, position.character)
+    }
+    
+    /// Definition of the symbol at a notebook cell position, mapped back to cells
+    pub fn notebook_definition(&self, uri: &PathBuf, cell_id: &str, line: u32, character: u32) -> Option<Vec<NotebookLocation>> {
+        let notebook = self.notebooks.get(uri)?;
+        let position = notebook.to_virtual(cell_id, Position { line, character })?;
+        let uri_str = uri.to_string_lossy();
+        let locations = self.find_definition(uri, position.line, position.character)?;
+        Some(locations.into_iter().map(|location| notebook.map_location(&uri_str, location)).collect())
+    }
+    
+    /// Diagnostics for a notebook, with ranges relative to the cell they start in
+    pub fn notebook_diagnostics(&self, uri: &PathBuf) -> Vec<CellDiagnostic> {
+        let Some(notebook) = self.notebooks.get(uri) else {
+            return Vec::new();
+        };
+        let uri_str = uri.to_string_lossy();
+        self.get_diagnostics(uri)
+            .into_iter()
+            .filter_map(|diagnostic| notebook.map_diagnostic(&uri_str, diagnostic))
+            .collect()
+    }
+    
     /// Get code actions for a diagnostic
     pub fn get_code_actions(&self, uri: &PathBuf, diagnostic: &Diagnostic) -> Vec<crate::types::CodeAction> {
         let Some(doc) = self.documents.get(uri) else {
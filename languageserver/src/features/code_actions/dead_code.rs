@@ -0,0 +1,188 @@
+use crate::types::{CodeAction, Diagnostic, TextEdit, Range, Position, WorkspaceEdit};
+use tree_sitter::{Node, Point, Tree};
+
+/// Generate code action to remove an unused function method, type or constant
+pub fn remove_dead_definition_action(
+    diagnostic: &Diagnostic,
+    tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let name = extract_quoted_name(&diagnostic.message)?;
+    let mut node = node_at(tree, &diagnostic.range)?;
+    while !matches!(
+        node.kind(),
+        "function_definition" | "assignment" | "struct_definition" | "abstract_definition" | "const_statement"
+    ) {
+        node = node.parent()?;
+    }
+    if node.kind() == "assignment" && node.parent().is_some_and(|p| p.kind() == "const_statement") {
+        node = node.parent()?;
+    }
+
+    // Take the docstring along
+    let mut start = node.start_position();
+    if let Some(previous) = node.prev_named_sibling() {
+        if previous.kind() == "string_literal" && previous.end_position().row + 1 == start.row {
+            start = previous.start_position();
+        }
+    }
+
+    Some(quickfix(
+        format!("Remove unused `{}`", name),
+        vec![TextEdit { range: whole_lines(text, start.row, node.end_position().row), new_text: String::new() }],
+    ))
+}
+
+/// Generate code action to remove an unused name from a `using`/`import` statement
+pub fn remove_unused_import_action(
+    diagnostic: &Diagnostic,
+    tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let name = extract_quoted_name(&diagnostic.message)?;
+    let mut statement = node_at(tree, &diagnostic.range)?;
+    while !matches!(statement.kind(), "using_statement" | "import_statement") {
+        statement = statement.parent()?;
+    }
+
+    let statement_text = statement.utf8_text(text.as_bytes()).ok()?;
+    let list_start = match statement_text.find(':') {
+        Some(colon) => colon + 1,
+        None => statement_text.find(char::is_whitespace)?,
+    };
+    let items: Vec<(usize, usize)> = split_items(&statement_text[list_start..])
+        .into_iter()
+        .map(|(from, to)| (list_start + from, list_start + to))
+        .collect();
+
+    // The last name goes with the whole statement
+    let edit = if items.len() <= 1 {
+        TextEdit {
+            range: whole_lines(text, statement.start_position().row, statement.end_position().row),
+            new_text: String::new(),
+        }
+    } else {
+        let offset = position_to_byte(text, diagnostic.range.start)?.checked_sub(statement.start_byte())?;
+        let i = items.iter().position(|&(from, to)| from <= offset && offset < to)?;
+        // Remove the name with the comma after it, or before it for the last name
+        let (from, to) = if i + 1 < items.len() {
+            (items[i].0, items[i + 1].0)
+        } else {
+            (items[i - 1].1, items[i].1)
+        };
+        TextEdit {
+            range: Range {
+                start: byte_to_position(text, statement.start_byte() + from),
+                end: byte_to_position(text, statement.start_byte() + to),
+            },
+            new_text: String::new(),
+        }
+    };
+
+    Some(quickfix(format!("Remove unused import `{}`", name), vec![edit]))
+}
+
+/// Generate code action to delete unreachable statements
+pub fn remove_unreachable_code_action(
+    diagnostic: &Diagnostic,
+    _tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let range = whole_lines(text, diagnostic.range.start.line as usize, diagnostic.range.end.line as usize);
+    Some(quickfix("Remove unreachable code".to_string(), vec![TextEdit { range, new_text: String::new() }]))
+}
+
+fn quickfix(title: String, edits: Vec<TextEdit>) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some("quickfix".to_string()),
+        edit: Some(WorkspaceEdit {
+            changes: vec![(String::new(), edits)], // URI will be filled by caller
+        }),
+        command: None,
+    }
+}
+
+fn node_at<'t>(tree: &'t Tree, range: &Range) -> Option<Node<'t>> {
+    tree.root_node().descendant_for_point_range(Point::from(range.start), Point::from(range.end))
+}
+
+/// Lines `first..=last` including the trailing newline
+fn whole_lines(text: &str, first: usize, last: usize) -> Range {
+    let line_count = text.lines().count();
+    let end = if last + 1 < line_count {
+        Position { line: last as u32 + 1, character: 0 }
+    } else {
+        Position {
+            line: last as u32,
+            character: text.lines().nth(last).map(|l| l.len()).unwrap_or(0) as u32,
+        }
+    };
+    Range { start: Position { line: first as u32, character: 0 }, end }
+}
+
+/// Byte spans of the trimmed, comma-separated items in `list`
+fn split_items(list: &str) -> Vec<(usize, usize)> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+    for item in list.split(',') {
+        let leading = item.len() - item.trim_start().len();
+        let trimmed = item.trim();
+        if !trimmed.is_empty() {
+            items.push((offset + leading, offset + leading + trimmed.len()));
+        }
+        offset += item.len() + 1;
+    }
+    items
+}
+
+fn position_to_byte(text: &str, position: Position) -> Option<usize> {
+    let line_start: usize = text
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(|line| line.len())
+        .sum();
+    let offset = line_start + position.character as usize;
+    (offset <= text.len()).then_some(offset)
+}
+
+fn byte_to_position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: (offset - line_start) as u32,
+    }
+}
+
+fn extract_quoted_name(message: &str) -> Option<&str> {
+    let start = message.find('`')?;
+    let end = message[start + 1..].find('`')?;
+    Some(&message[start + 1..start + 1 + end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_items() {
+        assert_eq!(split_items(" mean, std"), vec![(1, 5), (7, 10)]);
+        assert_eq!(split_items(" DataFrames"), vec![(1, 11)]);
+    }
+
+    #[test]
+    fn test_whole_lines() {
+        let text = "a\nb\nc";
+        assert_eq!(whole_lines(text, 0, 1).end, Position { line: 2, character: 0 });
+        assert_eq!(whole_lines(text, 2, 2).end, Position { line: 2, character: 1 });
+    }
+
+    #[test]
+    fn test_position_byte_roundtrip() {
+        let text = "using A: x, y\nz";
+        let offset = position_to_byte(text, Position { line: 1, character: 0 }).unwrap();
+        assert_eq!(offset, 14);
+        assert_eq!(byte_to_position(text, offset), Position { line: 1, character: 0 });
+    }
+}
@@ -6,12 +6,16 @@ mod delimiters;
 mod unused_vars;
 mod imports;
 mod undefined_vars;
+mod performance;
+mod dead_code;
 
 pub use missing_end::add_missing_end_action;
 pub use delimiters::fix_delimiter_action;
 pub use unused_vars::remove_unused_variable_action;
 pub use imports::add_import_action;
 pub use undefined_vars::fix_undefined_variable_action;
+pub use performance::{add_const_action, add_type_parameter_action, remove_timing_macro_action};
+pub use dead_code::{remove_dead_definition_action, remove_unreachable_code_action, remove_unused_import_action};
 
 /// Code actions provider
 pub struct CodeActionsProvider;
@@ -52,6 +56,36 @@ impl CodeActionsProvider {
                         actions.push(action);
                     }
                 }
+                "non_const_global" => {
+                    if let Some(action) = add_const_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
+                "untyped_field" | "abstract_field" => {
+                    if let Some(action) = add_type_parameter_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
+                "timing_macro" => {
+                    if let Some(action) = remove_timing_macro_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
+                "unused_function" | "unused_type" | "unused_constant" => {
+                    if let Some(action) = remove_dead_definition_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
+                "unused_import" => {
+                    if let Some(action) = remove_unused_import_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
+                "unreachable_code" => {
+                    if let Some(action) = remove_unreachable_code_action(diagnostic, tree, text) {
+                        actions.push(action);
+                    }
+                }
                 _ => {}
             }
         }
@@ -0,0 +1,200 @@
+use crate::types::{CodeAction, Diagnostic, TextEdit, Range, Position, WorkspaceEdit};
+use tree_sitter::{Node, Point, Tree};
+
+/// Generate code action to declare a non-const global as `const`
+pub fn add_const_action(
+    diagnostic: &Diagnostic,
+    tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let name = extract_quoted_name(&diagnostic.message)?;
+
+    // `const` is only safe when the global is assigned exactly once
+    let mut assignments = Vec::new();
+    find_global_assignments(tree.root_node(), text, name, &mut assignments);
+    let [assignment] = assignments.as_slice() else {
+        return None;
+    };
+
+    let position = Position::from(assignment.start_position());
+    Some(quickfix(
+        format!("Declare `{}` as const", name),
+        vec![TextEdit {
+            range: Range { start: position, end: position },
+            new_text: "const ".to_string(),
+        }],
+    ))
+}
+
+/// Generate code action to replace an untyped or abstract struct field type with a type parameter
+pub fn add_type_parameter_action(
+    diagnostic: &Diagnostic,
+    tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let field = tree.root_node().descendant_for_point_range(
+        Point::from(diagnostic.range.start),
+        Point::from(diagnostic.range.end),
+    )?;
+    let mut struct_node = field.parent()?;
+    while struct_node.kind() != "struct_definition" {
+        struct_node = struct_node.parent()?;
+    }
+    let type_head = (0..struct_node.child_count())
+        .filter_map(|i| struct_node.child(i))
+        .find(|c| c.kind() == "type_head")?;
+    let type_name = first_identifier(type_head)?;
+
+    let head_text = type_head.utf8_text(text.as_bytes()).ok()?;
+    let param = fresh_type_parameter(head_text);
+    let (field_edit, declaration) = match field.kind() {
+        // x  ->  x::T
+        "identifier" => {
+            let end = Position::from(field.end_position());
+            let edit = TextEdit { range: Range { start: end, end }, new_text: format!("::{}", param) };
+            (edit, param.clone())
+        }
+        // x::Real  ->  x::T, with T<:Real
+        "typed_expression" => {
+            let type_node = field.child(field.child_count().checked_sub(1)?)?;
+            let bound = type_node.utf8_text(text.as_bytes()).ok()?;
+            let declaration = if bound == "Any" { param.clone() } else { format!("{}<:{}", param, bound) };
+            let edit = TextEdit {
+                range: Range {
+                    start: Position::from(type_node.start_position()),
+                    end: Position::from(type_node.end_position()),
+                },
+                new_text: param.clone(),
+            };
+            (edit, declaration)
+        }
+        _ => return None,
+    };
+
+    // Point  ->  Point{T}, Point{S}  ->  Point{S, T}
+    let name_end = type_name.end_byte();
+    let head_edit = if text[name_end..].starts_with('{') {
+        let close = name_end + matching_brace(&text[name_end..])?;
+        let position = byte_to_position(text, close);
+        TextEdit { range: Range { start: position, end: position }, new_text: format!(", {}", declaration) }
+    } else {
+        let position = Position::from(type_name.end_position());
+        TextEdit { range: Range { start: position, end: position }, new_text: format!("{{{}}}", declaration) }
+    };
+
+    Some(quickfix(
+        format!("Add type parameter `{}`", declaration),
+        vec![head_edit, field_edit],
+    ))
+}
+
+/// Generate code action to remove a timing macro such as `@time`
+pub fn remove_timing_macro_action(
+    diagnostic: &Diagnostic,
+    _tree: &Tree,
+    text: &str,
+) -> Option<CodeAction> {
+    let macro_name = extract_quoted_name(&diagnostic.message)?;
+    let line = text.lines().nth(diagnostic.range.start.line as usize)?;
+    let after = line.get(diagnostic.range.end.character as usize..)?;
+    let whitespace = after.len() - after.trim_start().len();
+
+    Some(quickfix(
+        format!("Remove `{}`", macro_name),
+        vec![TextEdit {
+            range: Range {
+                start: diagnostic.range.start,
+                end: Position {
+                    line: diagnostic.range.end.line,
+                    character: diagnostic.range.end.character + whitespace as u32,
+                },
+            },
+            new_text: String::new(),
+        }],
+    ))
+}
+
+fn quickfix(title: String, edits: Vec<TextEdit>) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some("quickfix".to_string()),
+        edit: Some(WorkspaceEdit {
+            changes: vec![(String::new(), edits)], // URI will be filled by caller
+        }),
+        command: None,
+    }
+}
+
+/// Module-level assignments to `name`
+fn find_global_assignments<'a>(node: Node<'a>, text: &str, name: &str, found: &mut Vec<Node<'a>>) {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        match child.kind() {
+            "assignment" => {
+                let lhs = child.child(0).and_then(|lhs| lhs.utf8_text(text.as_bytes()).ok());
+                if lhs == Some(name) {
+                    found.push(child);
+                }
+            }
+            "module_definition" | "block" | "begin_statement" | "if_statement" | "elseif_clause"
+            | "else_clause" => find_global_assignments(child, text, name, found),
+            _ => {}
+        }
+    }
+}
+
+fn first_identifier(node: Node) -> Option<Node> {
+    if node.kind() == "identifier" {
+        return Some(node);
+    }
+    (0..node.child_count())
+        .filter_map(|i| node.child(i))
+        .find_map(first_identifier)
+}
+
+/// `T`, or `T1`, `T2`, ... if the struct head already uses that name
+fn fresh_type_parameter(head_text: &str) -> String {
+    let used = |candidate: &str| {
+        head_text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == candidate)
+    };
+    std::iter::once("T".to_string())
+        .chain((1..).map(|i| format!("T{}", i)))
+        .find(|candidate| !used(candidate))
+        .unwrap_or_else(|| "T".to_string())
+}
+
+/// Byte offset of the `}` closing the `{` at the start of `text`
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn byte_to_position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: (offset - line_start) as u32,
+    }
+}
+
+fn extract_quoted_name(message: &str) -> Option<&str> {
+    // Diagnostics name their subject first, e.g. "Global variable `x` is not `const`"
+    let start = message.find('`')?;
+    let end = message[start + 1..].find('`')?;
+    Some(&message[start + 1..start + 1 + end])
+}
@@ -0,0 +1,477 @@
+use crate::pipeline::module_tree::qualify;
+use crate::pipeline::storage::Index;
+use crate::types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::{Node, Tree};
+
+/// Calls that never return normally
+const TERMINATING_CALLS: &[&str] = &["throw", "error", "rethrow", "exit"];
+
+/// Nodes whose children are executed in order
+const STATEMENT_CONTAINERS: &[&str] = &[
+    "source_file", "block", "module_definition", "function_definition", "macro_definition", "if_statement",
+    "elseif_clause", "else_clause", "for_statement", "while_statement", "let_statement", "try_statement",
+    "catch_clause", "finally_clause", "begin_statement", "do_clause",
+];
+
+/// Children of a statement container that are not statements
+const NON_STATEMENTS: &[&str] = &[
+    "comment", "line_comment", "block_comment", "elseif_clause", "else_clause", "catch_clause", "finally_clause",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadCodeKind {
+    UnusedFunction,
+    UnusedType,
+    UnusedConstant,
+    UnusedImport,
+    UndefinedExport,
+    UnreachableCode,
+}
+
+impl DeadCodeKind {
+    /// Diagnostic code reported for this kind
+    pub fn code(self) -> &'static str {
+        match self {
+            DeadCodeKind::UnusedFunction => "unused_function",
+            DeadCodeKind::UnusedType => "unused_type",
+            DeadCodeKind::UnusedConstant => "unused_constant",
+            DeadCodeKind::UnusedImport => "unused_import",
+            DeadCodeKind::UndefinedExport => "undefined_export",
+            DeadCodeKind::UnreachableCode => "unreachable_code",
+        }
+    }
+}
+
+/// A dead definition, import, export or statement range
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadCodeItem {
+    pub kind: DeadCodeKind,
+    /// Name of the definition, import or export (None for unreachable code)
+    pub name: Option<String>,
+    pub file_uri: String,
+    pub range: Range,
+}
+
+/// Dead code across all workspace files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeadCodeReport {
+    pub items: Vec<DeadCodeItem>,
+}
+
+impl DeadCodeReport {
+    pub fn count(&self, kind: DeadCodeKind) -> usize {
+        self.items.iter().filter(|item| item.kind == kind).count()
+    }
+}
+
+/// Dead code analysis based on the workspace references recorded in the index
+pub struct DeadCodeProvider;
+
+impl DeadCodeProvider {
+    /// Find dead code in one file.
+    ///
+    /// Unused definitions are only reported for package code (files placed in a module
+    /// through `include()`): top-level functions of scripts are often meant for the REPL.
+    pub fn analyze_file(tree: &Tree, text: &str, file_uri: &str, index: &Index) -> Vec<DeadCodeItem> {
+        let file_module = index.get_file_module(Path::new(file_uri));
+        let mut analyzer = DeadCodeAnalyzer {
+            text,
+            file_uri,
+            index,
+            exports: HashSet::new(),
+            extended: HashSet::new(),
+            items: Vec::new(),
+        };
+        let root = tree.root_node();
+
+        analyzer.collect_exports(root);
+        analyzer.check_imports(root);
+        if file_module.is_some_and(|m| m.is_entry || m.primary != "Main") {
+            let module = file_module.map(|m| m.context.as_str()).unwrap_or("Main");
+            analyzer.check_definitions(root, module);
+        }
+        analyzer.check_unreachable(root);
+
+        analyzer.items.sort_by_key(|item| (item.range.start.line, item.range.start.character));
+        analyzer.items
+    }
+
+    pub fn to_diagnostic(item: &DeadCodeItem) -> Diagnostic {
+        let name = item.name.as_deref().unwrap_or_default();
+        let message = match item.kind {
+            DeadCodeKind::UnusedFunction => format!("Function `{}` is never used", name),
+            DeadCodeKind::UnusedType => format!("Type `{}` is never used", name),
+            DeadCodeKind::UnusedConstant => format!("Constant `{}` is never used", name),
+            DeadCodeKind::UnusedImport => format!("`{}` is imported but never used", name),
+            DeadCodeKind::UndefinedExport => format!("`{}` is exported but never defined", name),
+            DeadCodeKind::UnreachableCode => "Unreachable code".to_string(),
+        };
+        Diagnostic {
+            range: item.range.clone(),
+            severity: Some(DiagnosticSeverity::Warning),
+            code: Some(item.kind.code().to_string()),
+            source: Some("dead_code".to_string()),
+            message,
+            related_information: None,
+        }
+    }
+}
+
+struct DeadCodeAnalyzer<'a> {
+    text: &'a str,
+    file_uri: &'a str,
+    index: &'a Index,
+    /// Names listed in this file's `export`/`public` statements
+    exports: HashSet<String>,
+    /// Names brought in with `import M: name` (definitions of these extend M's function)
+    extended: HashSet<String>,
+    items: Vec<DeadCodeItem>,
+}
+
+impl<'a> DeadCodeAnalyzer<'a> {
+    fn node_text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.text.as_bytes()).unwrap_or_default()
+    }
+
+    fn push(&mut self, kind: DeadCodeKind, name: Option<&str>, range: Range) {
+        self.items.push(DeadCodeItem {
+            kind,
+            name: name.map(|n| n.to_string()),
+            file_uri: self.file_uri.to_string(),
+            range,
+        });
+    }
+
+    fn collect_exports(&mut self, node: Node) {
+        if matches!(node.kind(), "export_statement" | "public_statement") {
+            let keyword = if node.kind() == "export_statement" { "export" } else { "public" };
+            for (name, range) in list_items(self.node_text(node), node.start_position(), keyword.len()) {
+                if !name.starts_with('@') && !self.is_defined(name) {
+                    self.push(DeadCodeKind::UndefinedExport, Some(name), range);
+                }
+                self.exports.insert(name.to_string());
+            }
+            return;
+        }
+        for child in children(node) {
+            self.collect_exports(child);
+        }
+    }
+
+    /// Whether an exported name is defined in the workspace or brought in from a dependency
+    fn is_defined(&self, name: &str) -> bool {
+        let index = self.index;
+        !index.find_symbols(name).is_empty()
+            || !index.find_signatures_by_name(name).is_empty()
+            || index.get_all_type_modules().iter().any(|m| index.find_type(m, name).is_some())
+            || index.get_all_modules().iter().any(|m| m == name || m.ends_with(&format!(".{}", name)))
+            || index.get_all_imports().iter().any(|i| {
+                i.symbols.iter().any(|s| s == name) || i.module.rsplit('.').next() == Some(name)
+            })
+    }
+
+    fn check_imports(&mut self, node: Node) {
+        let keyword = match node.kind() {
+            "using_statement" => "using",
+            "import_statement" => "import",
+            _ => {
+                for child in children(node) {
+                    self.check_imports(child);
+                }
+                return;
+            }
+        };
+        let statement = self.node_text(node);
+        let start = node.start_position();
+        let statement_range = node_range(node);
+
+        let imported: Vec<(&str, Range)> = match statement.find(':') {
+            // using M: a, b as c  /  import M: a
+            Some(colon) => list_items(&statement[colon + 1..], start_after(statement, start, colon + 1), 0)
+                .into_iter()
+                .map(|(item, range)| (bound_name(item), range))
+                .collect(),
+            // `using M` brings in M's exports, which can't be checked name by name
+            None if keyword == "using" => return,
+            // import M, N.f, P as Q
+            None => list_items(statement, start, keyword.len())
+                .into_iter()
+                .map(|(item, range)| (bound_name(item).rsplit('.').next().unwrap_or_default(), range))
+                .collect(),
+        };
+
+        for (name, range) in imported {
+            if name.is_empty() || name.starts_with('@') {
+                continue;
+            }
+            if keyword == "import" {
+                self.extended.insert(name.to_string());
+            }
+            if !self.is_used_outside_imports(name, &statement_range) {
+                self.push(DeadCodeKind::UnusedImport, Some(name), range);
+            }
+        }
+    }
+
+    /// References to `name` other than those inside `using`/`import` statements
+    fn is_used_outside_imports(&self, name: &str, statement: &Range) -> bool {
+        let index = self.index;
+        index.find_references(name).iter().any(|r| {
+            if r.file_uri == self.file_uri && contains(statement, &r.range) {
+                return false;
+            }
+            // Names listed in other files' import statements aren't uses either
+            !index
+                .get_file_imports(&r.file_uri.clone().into())
+                .iter()
+                .any(|i| i.range.start.line == r.range.start.line)
+        })
+    }
+
+    fn check_definitions(&mut self, node: Node, module: &str) {
+        for child in children(node) {
+            match child.kind() {
+                "module_definition" | "baremodule_definition" => {
+                    let name = children(child).find(|c| c.kind() == "identifier").map(|c| self.node_text(c));
+                    let nested = name.map(|n| qualify(module, n)).unwrap_or_else(|| module.to_string());
+                    self.check_definitions(child, &nested);
+                }
+                "block" => self.check_definitions(child, module),
+                "function_definition" => {
+                    if let Some(name_node) = function_name(child) {
+                        self.check_definition(DeadCodeKind::UnusedFunction, name_node, child, module);
+                    }
+                }
+                "assignment" => {
+                    // f(x) = ...
+                    let name_node = child.child(0).filter(|lhs| lhs.kind() == "call_expression").and_then(call_name);
+                    if let Some(name_node) = name_node {
+                        self.check_definition(DeadCodeKind::UnusedFunction, name_node, child, module);
+                    }
+                }
+                "struct_definition" | "abstract_definition" => {
+                    let name_node = children(child)
+                        .find(|c| c.kind() == "type_head")
+                        .and_then(first_identifier);
+                    if let Some(name_node) = name_node {
+                        self.check_definition(DeadCodeKind::UnusedType, name_node, child, module);
+                    }
+                }
+                "const_statement" => {
+                    let name_node = children(child)
+                        .find(|c| c.kind() == "assignment")
+                        .and_then(|a| a.child(0))
+                        .filter(|lhs| lhs.kind() == "identifier");
+                    if let Some(name_node) = name_node {
+                        self.check_definition(DeadCodeKind::UnusedConstant, name_node, child, module);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_definition(&mut self, kind: DeadCodeKind, name_node: Node, definition: Node, module: &str) {
+        let name = self.node_text(name_node);
+        if name.is_empty()
+            || name == "__init__"
+            || self.exports.contains(name)
+            || self.extended.contains(name)
+            || self.index.get_module_exports(module).contains(name)
+        {
+            return;
+        }
+
+        // Definitions of the same name don't use it: methods calling each other,
+        // a struct's constructors, or the reference recorded for a method's own signature
+        let mut definitions = vec![(self.file_uri.to_string(), node_range(definition))];
+        definitions.extend(
+            self.index
+                .find_signatures_by_name(name)
+                .into_iter()
+                .map(|sig| (sig.file_uri, sig.range)),
+        );
+        for module in self.index.get_all_type_modules() {
+            if let Some(type_def) = self.index.find_type(&module, name) {
+                // An outer constructor is a method of the type, not a separate function
+                if kind == DeadCodeKind::UnusedFunction {
+                    return;
+                }
+                definitions.push((type_def.file_uri, type_def.range));
+            }
+        }
+
+        let used = self.index.find_references(name).iter().any(|r| {
+            !definitions.iter().any(|(uri, range)| *uri == r.file_uri && contains(range, &r.range))
+        });
+        if !used {
+            self.push(kind, Some(name), node_range(name_node));
+        }
+    }
+
+    fn check_unreachable(&mut self, node: Node) {
+        if STATEMENT_CONTAINERS.contains(&node.kind()) {
+            let statements: Vec<Node> = named_children(node)
+                .filter(|c| !NON_STATEMENTS.contains(&c.kind()))
+                .collect();
+            let terminator = statements.iter().position(|s| self.is_terminator(*s));
+            if let Some(last) = terminator.filter(|&i| i + 1 < statements.len()) {
+                let first = statements[last + 1];
+                let end = statements[statements.len() - 1];
+                self.push(
+                    DeadCodeKind::UnreachableCode,
+                    None,
+                    Range {
+                        start: Position::from(first.start_position()),
+                        end: Position::from(end.end_position()),
+                    },
+                );
+            }
+        }
+        for child in children(node) {
+            self.check_unreachable(child);
+        }
+    }
+
+    fn is_terminator(&self, node: Node) -> bool {
+        match node.kind() {
+            "return_statement" | "break_statement" | "continue_statement" => true,
+            "call_expression" => node
+                .child(0)
+                .filter(|callee| callee.kind() == "identifier")
+                .is_some_and(|callee| TERMINATING_CALLS.contains(&self.node_text(callee))),
+            _ => false,
+        }
+    }
+}
+
+fn children(node: Node) -> impl Iterator<Item = Node> {
+    (0..node.child_count()).filter_map(move |i| node.child(i))
+}
+
+fn named_children(node: Node) -> impl Iterator<Item = Node> {
+    (0..node.named_child_count()).filter_map(move |i| node.named_child(i))
+}
+
+/// Name of `function f(...)`, `function f(...) where T` or `function f(...)::T`
+fn function_name(node: Node) -> Option<Node> {
+    let signature = children(node).find(|c| c.kind() == "signature")?;
+    let mut call = signature.named_child(0)?;
+    while matches!(call.kind(), "where_expression" | "typed_expression") {
+        call = call.named_child(0)?;
+    }
+    call_name(call)
+}
+
+/// Plain identifier callee; qualified (`Base.show`) and operator methods extend other functions
+fn call_name(call: Node) -> Option<Node> {
+    if call.kind() != "call_expression" {
+        return None;
+    }
+    call.child(0).filter(|callee| callee.kind() == "identifier")
+}
+
+fn first_identifier(node: Node) -> Option<Node> {
+    if node.kind() == "identifier" {
+        return Some(node);
+    }
+    children(node).find_map(first_identifier)
+}
+
+/// `x` of `x`, `a as x`
+fn bound_name(item: &str) -> &str {
+    match item.rfind(" as ") {
+        Some(pos) => item[pos + 4..].trim(),
+        None => item,
+    }
+}
+
+/// Comma-separated items of `text[skip..]` with their document ranges; `start` is the position of `text`
+fn list_items(text: &str, start: tree_sitter::Point, skip: usize) -> Vec<(&str, Range)> {
+    let mut items = Vec::new();
+    let mut offset = skip;
+    for item in text[skip.min(text.len())..].split(',') {
+        let leading = item.len() - item.trim_start().len();
+        let trimmed = item.trim();
+        if !trimmed.is_empty() {
+            let from = offset + leading;
+            items.push((
+                trimmed,
+                Range {
+                    start: offset_position(text, start, from),
+                    end: offset_position(text, start, from + trimmed.len()),
+                },
+            ));
+        }
+        offset += item.len() + 1;
+    }
+    items
+}
+
+/// Point of `text[offset..]`, where `text` starts at `start`
+fn start_after(text: &str, start: tree_sitter::Point, offset: usize) -> tree_sitter::Point {
+    tree_sitter::Point::from(offset_position(text, start, offset))
+}
+
+fn offset_position(text: &str, start: tree_sitter::Point, offset: usize) -> Position {
+    let before = &text[..offset.min(text.len())];
+    match before.rfind('\n') {
+        Some(newline) => Position {
+            line: (start.row + before.matches('\n').count()) as u32,
+            character: (before.len() - newline - 1) as u32,
+        },
+        None => Position {
+            line: start.row as u32,
+            character: (start.column + before.len()) as u32,
+        },
+    }
+}
+
+fn node_range(node: Node) -> Range {
+    Range {
+        start: Position::from(node.start_position()),
+        end: Position::from(node.end_position()),
+    }
+}
+
+fn contains(outer: &Range, inner: &Range) -> bool {
+    let key = |p: &Position| (p.line, p.character);
+    key(&outer.start) <= key(&inner.start) && key(&inner.end) <= key(&outer.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn test_list_items_ranges() {
+        let statement = "export foo,\n    bar";
+        let items = list_items(statement, tree_sitter::Point { row: 3, column: 4 }, "export".len());
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].0, "foo");
+        assert_eq!(items[0].1.start, position(3, 11));
+        assert_eq!(items[1].0, "bar");
+        assert_eq!(items[1].1.start, position(4, 4));
+        assert_eq!(items[1].1.end, position(4, 7));
+    }
+
+    #[test]
+    fn test_bound_name() {
+        assert_eq!(bound_name("mean"), "mean");
+        assert_eq!(bound_name("DataFrames as DF"), "DF");
+    }
+
+    #[test]
+    fn test_contains() {
+        let outer = Range { start: position(1, 0), end: position(5, 3) };
+        assert!(contains(&outer, &Range { start: position(1, 4), end: position(1, 8) }));
+        assert!(!contains(&outer, &Range { start: position(5, 2), end: position(6, 0) }));
+    }
+}
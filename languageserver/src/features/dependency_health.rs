@@ -0,0 +1,515 @@
+use crate::pipeline::sources::{LocalRegistry, ProjectContext, VersionNumber, VersionSpec};
+use crate::pipeline::storage::Index;
+use crate::pipeline::types::ImportStatement;
+use crate::types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range, SymbolKind,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Julia standard library packages (shipped with Julia, versioned with it)
+pub const JULIA_STDLIBS: &[&str] = &[
+    "ArgTools", "Artifacts", "Base64", "CRC32c", "Dates", "DelimitedFiles", "Distributed",
+    "Downloads", "FileWatching", "Future", "InteractiveUtils", "JuliaSyntaxHighlighting",
+    "LazyArtifacts", "LibCURL", "LibGit2", "Libdl", "LinearAlgebra", "Logging", "Markdown",
+    "Mmap", "NetworkOptions", "Pkg", "Printf", "Profile", "REPL", "Random", "SHA",
+    "Serialization", "SharedArrays", "Sockets", "SparseArrays", "Statistics", "StyledStrings",
+    "SuiteSparse", "TOML", "Tar", "Test", "UUIDs", "Unicode",
+];
+
+/// Modules that are always available without a `[deps]` entry
+const BUILTIN_MODULES: &[&str] = &["Base", "Core", "Main"];
+
+pub fn is_julia_stdlib(name: &str) -> bool {
+    JULIA_STDLIBS.contains(&name)
+}
+
+/// A dependency whose manifest version is behind the latest registered release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutdatedDependency {
+    pub name: String,
+    pub uuid: String,
+    pub current: String,
+    pub latest: String,
+    /// Whether the `[compat]` entry already admits the latest version
+    pub compat_allows_latest: bool,
+}
+
+/// A `[compat]` entry that excludes the version resolved in Manifest.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatViolation {
+    pub name: String,
+    pub compat: String,
+    pub manifest_version: String,
+}
+
+/// A `[compat]` entry that cannot be parsed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvalidCompat {
+    pub name: String,
+    pub compat: String,
+    pub error: String,
+}
+
+/// A package that is loaded in workspace code but not declared in `[deps]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndeclaredImport {
+    pub name: String,
+    pub imports: Vec<ImportStatement>,
+}
+
+/// Result of cross-referencing Project.toml, Manifest.toml, the registry and workspace imports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DependencyHealthReport {
+    pub project_name: Option<String>,
+    /// False when no registry was available, so outdated checks were skipped
+    pub registry_available: bool,
+    pub outdated: Vec<OutdatedDependency>,
+    pub compat_violations: Vec<CompatViolation>,
+    pub invalid_compat: Vec<InvalidCompat>,
+    pub missing_compat: Vec<String>,
+    pub unused_dependencies: Vec<String>,
+    pub undeclared_imports: Vec<UndeclaredImport>,
+}
+
+impl DependencyHealthReport {
+    pub fn issue_count(&self) -> usize {
+        self.outdated.len()
+            + self.compat_violations.len()
+            + self.invalid_compat.len()
+            + self.missing_compat.len()
+            + self.unused_dependencies.len()
+            + self.undeclared_imports.len()
+    }
+}
+
+/// Dependency health analysis for the project's Project.toml
+pub struct DependencyHealthProvider;
+
+impl DependencyHealthProvider {
+    /// Analyze the project's dependencies
+    ///
+    /// `registry` must have versions loaded for the project's dependencies
+    /// (see `LocalRegistry::load_versions`) for outdated checks to report anything.
+    pub fn analyze(context: &ProjectContext, index: &Index, registry: Option<&LocalRegistry>) -> DependencyHealthReport {
+        let mut report = DependencyHealthReport {
+            project_name: context.project_name().map(|s| s.to_string()),
+            registry_available: registry.map(|r| !r.is_empty()).unwrap_or(false),
+            ..Default::default()
+        };
+        let Some(project) = context.project_toml.as_ref() else {
+            return report;
+        };
+
+        // BTreeMap keeps the report ordering stable
+        let deps: BTreeMap<&String, &String> = project.dependencies.iter().flatten().collect();
+        let compat: BTreeMap<&String, &String> = project.compat.iter().flatten().collect();
+
+        // Outdated packages (registered packages only; stdlibs and unregistered deps are skipped)
+        if let Some(registry) = registry {
+            for (name, uuid) in &deps {
+                let Some(current) = context.manifest_version(name).and_then(|v| VersionNumber::parse(v).ok()) else {
+                    continue;
+                };
+                let Some(latest) = registry.latest_version(uuid) else {
+                    continue;
+                };
+                if *latest > current {
+                    let compat_allows_latest = compat
+                        .get(name)
+                        .and_then(|spec| VersionSpec::parse(spec).ok())
+                        .map(|spec| spec.contains(latest))
+                        .unwrap_or(true);
+                    report.outdated.push(OutdatedDependency {
+                        name: name.to_string(),
+                        uuid: uuid.to_string(),
+                        current: current.to_string(),
+                        latest: latest.to_string(),
+                        compat_allows_latest,
+                    });
+                }
+            }
+        }
+
+        // Compat entries: invalid specifiers and specifiers excluding the manifest version
+        for (name, spec) in &compat {
+            if name.as_str() == "julia" {
+                continue;
+            }
+            match VersionSpec::parse(spec) {
+                Ok(parsed) => {
+                    let Some(version) = context.manifest_version(name) else {
+                        continue;
+                    };
+                    if let Ok(parsed_version) = VersionNumber::parse(version) {
+                        if !parsed.contains(&parsed_version) {
+                            report.compat_violations.push(CompatViolation {
+                                name: name.to_string(),
+                                compat: spec.to_string(),
+                                manifest_version: version.to_string(),
+                            });
+                        }
+                    }
+                }
+                Err(error) => report.invalid_compat.push(InvalidCompat {
+                    name: name.to_string(),
+                    compat: spec.to_string(),
+                    error,
+                }),
+            }
+        }
+
+        // Dependencies without a compat bound (stdlibs are versioned with Julia)
+        for name in deps.keys() {
+            if !compat.contains_key(name) && !is_julia_stdlib(name) {
+                report.missing_compat.push(name.to_string());
+            }
+        }
+
+        // Cross-reference with workspace imports
+        let imports = Self::root_project_imports(context, index);
+        let imported: HashSet<&str> = imports.iter().filter_map(|i| i.root_module()).collect();
+
+        for name in deps.keys() {
+            if !imported.contains(name.as_str()) {
+                report.unused_dependencies.push(name.to_string());
+            }
+        }
+
+        let declared: HashSet<&str> = deps
+            .keys()
+            .map(|k| k.as_str())
+            .chain(project.weakdeps.iter().flatten().map(|(k, _)| k.as_str()))
+            .chain(project.extras.iter().flatten().map(|(k, _)| k.as_str()))
+            .collect();
+        let workspace_modules = Self::workspace_modules(context, index);
+        // Outside a package, stdlibs can be loaded from the default environment without a [deps] entry
+        let stdlibs_need_declaring = project.uuid.is_some();
+
+        let mut undeclared: BTreeMap<&str, Vec<ImportStatement>> = BTreeMap::new();
+        for import in &imports {
+            let Some(root) = import.root_module() else {
+                continue;
+            };
+            if declared.contains(root)
+                || BUILTIN_MODULES.contains(&root)
+                || context.project_name() == Some(root)
+                || workspace_modules.contains(root)
+                || (!stdlibs_need_declaring && is_julia_stdlib(root))
+            {
+                continue;
+            }
+            undeclared.entry(root).or_default().push(import.clone());
+        }
+        report.undeclared_imports = undeclared
+            .into_iter()
+            .map(|(name, imports)| UndeclaredImport { name: name.to_string(), imports })
+            .collect();
+
+        report
+    }
+
+    /// Imports from files governed by the root Project.toml
+    ///
+    /// Files under a directory with its own Project.toml (e.g., `test/`, `docs/`)
+    /// belong to that environment and are excluded.
+    fn root_project_imports(context: &ProjectContext, index: &Index) -> Vec<ImportStatement> {
+        index
+            .get_all_imports()
+            .into_iter()
+            .filter(|import| {
+                let path = Path::new(&import.file_uri);
+                context.is_project_file(path) && !Self::has_nested_environment(&context.root_path, path)
+            })
+            .collect()
+    }
+
+    fn has_nested_environment(root: &Path, file: &Path) -> bool {
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            if current == root || !current.starts_with(root) {
+                return false;
+            }
+            if current.join("Project.toml").exists() || current.join("JuliaProject.toml").exists() {
+                return true;
+            }
+            dir = current.parent();
+        }
+        false
+    }
+
+    /// Modules defined in workspace files (imports of these are not package loads)
+    fn workspace_modules(context: &ProjectContext, index: &Index) -> HashSet<String> {
+        index
+            .get_all_symbols()
+            .into_iter()
+            .filter(|s| s.kind == SymbolKind::Module && context.is_project_file(Path::new(&s.file_uri)))
+            .map(|s| s.name)
+            .collect()
+    }
+
+    /// Convert a report to diagnostics on the Project.toml document
+    pub fn to_diagnostics(report: &DependencyHealthReport, project_toml_text: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let deps_range = |name: &str| {
+            find_key_range(project_toml_text, "deps", name).unwrap_or_else(|| section_range(project_toml_text, "deps"))
+        };
+        let compat_range = |name: &str| {
+            find_key_range(project_toml_text, "compat", name).unwrap_or_else(|| section_range(project_toml_text, "compat"))
+        };
+
+        for outdated in &report.outdated {
+            let hint = if outdated.compat_allows_latest {
+                "run `Pkg.update` to upgrade"
+            } else {
+                "the [compat] entry does not allow it"
+            };
+            diagnostics.push(make_diagnostic(
+                deps_range(&outdated.name),
+                DiagnosticSeverity::Information,
+                "outdated_dependency",
+                format!("{} {} is outdated, latest is {} ({})", outdated.name, outdated.current, outdated.latest, hint),
+            ));
+        }
+
+        for violation in &report.compat_violations {
+            diagnostics.push(make_diagnostic(
+                compat_range(&violation.name),
+                DiagnosticSeverity::Warning,
+                "compat_violation",
+                format!(
+                    "Compat entry \"{}\" for {} excludes the manifest version {}",
+                    violation.compat, violation.name, violation.manifest_version
+                ),
+            ));
+        }
+
+        for invalid in &report.invalid_compat {
+            diagnostics.push(make_diagnostic(
+                compat_range(&invalid.name),
+                DiagnosticSeverity::Error,
+                "invalid_compat",
+                format!("Invalid compat entry \"{}\" for {}: {}", invalid.compat, invalid.name, invalid.error),
+            ));
+        }
+
+        for name in &report.missing_compat {
+            diagnostics.push(make_diagnostic(
+                deps_range(name),
+                DiagnosticSeverity::Warning,
+                "missing_compat",
+                format!("Dependency {} has no [compat] entry", name),
+            ));
+        }
+
+        for name in &report.unused_dependencies {
+            diagnostics.push(make_diagnostic(
+                deps_range(name),
+                DiagnosticSeverity::Hint,
+                "unused_dependency",
+                format!("Dependency {} is never loaded with `using` or `import` in the workspace", name),
+            ));
+        }
+
+        for undeclared in &report.undeclared_imports {
+            let mut diagnostic = make_diagnostic(
+                section_range(project_toml_text, "deps"),
+                DiagnosticSeverity::Warning,
+                "undeclared_dependency",
+                format!("Package {} is imported but not declared in [deps]", undeclared.name),
+            );
+            diagnostic.related_information = Some(
+                undeclared
+                    .imports
+                    .iter()
+                    .map(|import| DiagnosticRelatedInformation {
+                        location: Location { uri: import.file_uri.clone(), range: import.range.clone() },
+                        message: format!("{} imported here", undeclared.name),
+                    })
+                    .collect(),
+            );
+            diagnostics.push(diagnostic);
+        }
+
+        diagnostics
+    }
+}
+
+fn make_diagnostic(range: Range, severity: DiagnosticSeverity, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(code.to_string()),
+        source: Some("dependencies".to_string()),
+        message,
+        related_information: None,
+    }
+}
+
+/// Header of the TOML table on this line, if any (e.g., "deps" for "[deps]")
+pub(crate) fn table_header(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if trimmed.starts_with("[[") {
+        return Some(trimmed.trim_matches(|c| c == '[' || c == ']').trim());
+    }
+    trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.split(']').next())
+        .map(|s| s.trim())
+}
+
+/// Range of a key inside a TOML table (e.g., `DataFrames` inside `[deps]`);
+/// `section` is empty for top-level keys such as `uuid`
+pub(crate) fn find_key_range(text: &str, section: &str, key: &str) -> Option<Range> {
+    let mut in_section = section.is_empty();
+    for (line_number, line) in text.lines().enumerate() {
+        if let Some(header) = table_header(line) {
+            in_section = header == section;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some((lhs, _)) = line.split_once('=') else {
+            continue;
+        };
+        if lhs.trim().trim_matches('"') == key {
+            let start = line.len() - line.trim_start().len();
+            let end = start + lhs.trim().len();
+            return Some(Range {
+                start: Position { line: line_number as u32, character: start as u32 },
+                end: Position { line: line_number as u32, character: end as u32 },
+            });
+        }
+    }
+    None
+}
+
+/// Range of a table header line, or the start of the document if the table is absent
+pub(crate) fn section_range(text: &str, section: &str) -> Range {
+    for (line_number, line) in text.lines().enumerate() {
+        if table_header(line) == Some(section) {
+            return Range {
+                start: Position { line: line_number as u32, character: 0 },
+                end: Position { line: line_number as u32, character: line.trim_end().len() as u32 },
+            };
+        }
+    }
+    Range {
+        start: Position { line: 0, character: 0 },
+        end: Position { line: 0, character: 0 },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::sources::registry::LocalRegistry;
+    use crate::pipeline::WorkspacePipeline;
+    use std::collections::HashSet;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const DATAFRAMES_UUID: &str = "a93c6f00-e57d-5684-b7b6-d8193f3e46c0";
+    const JSON_UUID: &str = "682c06a0-de6a-54ab-a142-c8b1cf79cde6";
+
+    const PROJECT_TOML: &str = r#"name = "TestProject"
+uuid = "12345678-1234-1234-1234-123456789012"
+
+[deps]
+DataFrames = "a93c6f00-e57d-5684-b7b6-d8193f3e46c0"
+JSON = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
+
+[compat]
+DataFrames = "1.5"
+julia = "1.9"
+"#;
+
+    fn create_project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Project.toml"), PROJECT_TOML).unwrap();
+        fs::write(
+            root.join("Manifest.toml"),
+            format!(
+                "[[deps.DataFrames]]\nuuid = \"{}\"\nversion = \"1.5.0\"\n\n[[deps.JSON]]\nuuid = \"{}\"\nversion = \"0.21.4\"\n",
+                DATAFRAMES_UUID, JSON_UUID
+            ),
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src").join("TestProject.jl"),
+            "module TestProject\nusing DataFrames\nusing CSV\nusing LinearAlgebra\nend\n",
+        )
+        .unwrap();
+
+        let registry = root.join("depot").join("registries").join("General");
+        fs::create_dir_all(registry.join("D").join("DataFrames")).unwrap();
+        fs::write(
+            registry.join("Registry.toml"),
+            format!("[packages]\n{} = {{ name = \"DataFrames\", path = \"D/DataFrames\" }}\n", DATAFRAMES_UUID),
+        )
+        .unwrap();
+        fs::write(
+            registry.join("D").join("DataFrames").join("Versions.toml"),
+            "[\"1.5.0\"]\ngit-tree-sha1 = \"a\"\n\n[\"1.6.1\"]\ngit-tree-sha1 = \"b\"\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn analyze_project(dir: &TempDir) -> DependencyHealthReport {
+        let root = dir.path().to_path_buf();
+        let context = ProjectContext::new(root.clone()).unwrap();
+        let sources = crate::pipeline::sources::WorkspaceSource::new(root.clone()).discover().unwrap();
+        let index = WorkspacePipeline::new().run_with_index(sources, None).unwrap();
+        let mut registry = LocalRegistry::load(&root.join("depot")).unwrap();
+        let uuids: HashSet<String> = context.dependencies().unwrap().values().cloned().collect();
+        registry.load_versions(&uuids);
+        DependencyHealthProvider::analyze(&context, &index, Some(&registry))
+    }
+
+    #[test]
+    fn test_dependency_health_report() {
+        let dir = create_project();
+        let report = analyze_project(&dir);
+
+        assert_eq!(report.outdated.len(), 1);
+        assert_eq!(report.outdated[0].name, "DataFrames");
+        assert_eq!(report.outdated[0].latest, "1.6.1");
+        assert!(report.outdated[0].compat_allows_latest);
+        assert_eq!(report.missing_compat, vec!["JSON".to_string()]);
+        assert_eq!(report.unused_dependencies, vec!["JSON".to_string()]);
+
+        // LinearAlgebra is a stdlib but still has to be declared by a package
+        let undeclared: Vec<&str> = report.undeclared_imports.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(undeclared, vec!["CSV", "LinearAlgebra"]);
+    }
+
+    #[test]
+    fn test_compat_violation_diagnostics() {
+        let dir = create_project();
+        let text = PROJECT_TOML.replace("DataFrames = \"1.5\"", "DataFrames = \"1.6\"");
+        fs::write(dir.path().join("Project.toml"), &text).unwrap();
+
+        let report = analyze_project(&dir);
+        assert_eq!(report.compat_violations.len(), 1);
+        assert_eq!(report.compat_violations[0].manifest_version, "1.5.0");
+
+        let diagnostics = DependencyHealthProvider::to_diagnostics(&report, &text);
+        let violation = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("compat_violation"))
+            .unwrap();
+        // Points at the DataFrames key in [compat]
+        assert_eq!(violation.range.start.line, 8);
+
+        let undeclared = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("undeclared_dependency"))
+            .unwrap();
+        assert!(undeclared.related_information.as_ref().is_some_and(|r| !r.is_empty()));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::pipeline::analyzers::macros::{BindingScope, MacroRegistry};
 use crate::pipeline::storage::Index;
 use crate::types::{Position, Range};
 use tree_sitter::Node;
@@ -306,6 +307,22 @@ pub(super) fn collect_definitions(
                 }
             }
         }
+        "macrocall_expression" => {
+            // Names defined by known macros; local ones are scoped to the macro call like parameters
+            if let Some(expansion) = MacroRegistry::builtin().expand(node, text) {
+                for binding in expansion.bindings {
+                    match binding.scope {
+                        BindingScope::Enclosing => {
+                            defined_symbols.insert(binding.name.clone());
+                            symbol_definitions.insert(binding.name, binding.range);
+                        }
+                        BindingScope::Local => {
+                            function_scopes.entry(node.id()).or_default().insert(binding.name);
+                        }
+                    }
+                }
+            }
+        }
         "using_statement" | "import_statement" => {
             // When we see a using/import statement, we should mark that module as available
             // For now, we'll just skip checking identifiers that might come from these modules
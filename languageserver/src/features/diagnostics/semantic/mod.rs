@@ -2,6 +2,8 @@ use crate::types::ImportContext;
 use crate::pipeline::sources::Document;
 use crate::pipeline::storage::Index;
 use crate::types::Diagnostic;
+use crate::features::dead_code::DeadCodeProvider;
+use crate::features::struct_fields::StructFieldProvider;
 
 mod definitions;
 mod parameters;
@@ -9,6 +11,7 @@ mod references;
 mod usage;
 mod types;
 mod imports;
+mod performance;
 mod debug;
 mod utils;
 
@@ -43,8 +46,21 @@ impl SemanticAnalyzer {
         // Analyze unused variables
         usage::check_unused_variables(tree, &text, index, &mut diagnostics);
         
-        // Analyze type mismatches (basic checks)
-        types::check_type_mismatches(tree, &text, index, &mut diagnostics);
+        // Analyze calls against known methods (arity, keywords, literal argument types)
+        types::check_type_mismatches(tree, &text, index, document.uri(), &mut diagnostics);
+        
+        // Accesses of fields a known struct doesn't have
+        diagnostics.extend(StructFieldProvider::check_field_access(tree, &text, index));
+        
+        // Flag common performance pitfalls (globals, abstract fields, boxing, ...)
+        performance::check_performance(tree, &text, index, document.uri(), &mut diagnostics);
+        
+        // Unused definitions and imports, undefined exports, unreachable code
+        diagnostics.extend(
+            DeadCodeProvider::analyze_file(tree, &text, document.uri(), index)
+                .iter()
+                .map(DeadCodeProvider::to_diagnostic),
+        );
         
         // Analyze import/module resolution (enhanced)
         imports::check_import_resolution(
@@ -82,6 +98,21 @@ mod tests {
         SemanticAnalyzer::analyze_with_imports(&doc, &index, None, None, None)
     }
 
+    /// Analyze with the document's own definitions merged into the index
+    fn analyze_indexed_code(code: &str) -> Vec<Diagnostic> {
+        let doc = create_document(code);
+        let path = std::path::PathBuf::from("test.jl");
+        let source_item = crate::pipeline::types::SourceItem {
+            path: path.clone(),
+            content: code.to_string(),
+            metadata: crate::pipeline::types::FileMetadata::new(0, code.len() as u64),
+        };
+        let analysis = crate::pipeline::WorkspacePipeline::new().run_single_file(source_item).unwrap();
+        let mut index = Index::new();
+        index.merge_file(&path, analysis).unwrap();
+        SemanticAnalyzer::analyze_with_imports(&doc, &index, None, None, None)
+    }
+
     // ========== Basic Variable Assignments ==========
 
     #[test]
@@ -544,6 +575,19 @@ x_val = p.x
         assert!(diagnostics.iter().all(|d| !d.message.contains("undefined")));
     }
 
+    #[test]
+    fn test_macro_defined_names() {
+        let code = r#"
+model = nothing
+@enum Fruit apple orange
+@variable(model, 0 <= y[i = 1:3] <= i)
+@variables t x(t)
+basket = (apple, orange, y, x, t)
+"#;
+        let diagnostics = analyze_code(code);
+        assert!(!has_code(&diagnostics, "undefined_variable"), "{:?}", diagnostics);
+    }
+
     // ========== Multiple Dispatch ==========
 
     #[test]
@@ -884,4 +928,125 @@ dist = Calculator.distance(p1, p2)
             d.severity == Some(DiagnosticSeverity::Warning)
         ));
     }
+
+    // ========== Method Calls ==========
+
+    #[test]
+    fn test_call_arity_mismatch() {
+        let code = "scale(x, factor=2) = x * factor\nscale(1, 2, 3)";
+        let diagnostics = analyze_indexed_code(code);
+        let arity = diagnostics.iter().find(|d| d.code.as_deref() == Some("method_arity"));
+        assert!(arity.is_some(), "Expected arity diagnostic, got: {:?}", diagnostics);
+        assert_eq!(arity.unwrap().related_information.as_ref().map(|r| r.len()), Some(1));
+    }
+
+    #[test]
+    fn test_call_keyword_checks() {
+        let code = "fit(x; tol=1e-6, maxiter) = x\nfit(1; verbose=true)";
+        let diagnostics = analyze_indexed_code(code);
+        assert!(diagnostics.iter().any(|d| d.code.as_deref() == Some("unknown_keyword")));
+        assert!(diagnostics.iter().any(|d| d.code.as_deref() == Some("missing_keyword")
+            && d.message.contains("maxiter")));
+    }
+
+    #[test]
+    fn test_valid_calls_not_reported() {
+        let code = "area(r) = 3.14 * r^2\narea(w, h) = w * h\narea(2)\narea(2, 3)\nargs = (1, 2)\narea(args...)";
+        let diagnostics = analyze_indexed_code(code);
+        assert!(diagnostics.iter().all(|d| d.code.as_deref() != Some("method_arity")));
+    }
+
+    #[test]
+    fn test_call_literal_type_mismatch() {
+        let code = "half(x::Integer) = x ÷ 2\nhalf(\"ten\")";
+        let diagnostics = analyze_indexed_code(code);
+        assert!(diagnostics.iter().any(|d| d.code.as_deref() == Some("no_applicable_method")));
+    }
+
+    #[test]
+    fn test_unsigned_and_float32_literals_not_reported() {
+        let code = "f(x::UInt8) = x\nf(0x01)\ng(x::Float32) = x\ng(1f0)";
+        let diagnostics = analyze_indexed_code(code);
+        assert!(!has_code(&diagnostics, "no_applicable_method"), "{:?}", diagnostics);
+
+        let diagnostics = analyze_indexed_code("f(x::UInt8) = x\nf(1)");
+        assert!(has_code(&diagnostics, "no_applicable_method"));
+    }
+
+    // ========== Performance ==========
+
+    fn has_code(diagnostics: &[Diagnostic], code: &str) -> bool {
+        diagnostics.iter().any(|d| d.code.as_deref() == Some(code))
+    }
+
+    #[test]
+    fn test_non_const_global_in_function() {
+        let code = "rate = 0.5\nconst LIMIT = 10\nfunction apply(x)\n    x * rate + LIMIT\nend";
+        let diagnostics = analyze_code(code);
+        let globals: Vec<_> = diagnostics.iter()
+            .filter(|d| d.code.as_deref() == Some("non_const_global"))
+            .collect();
+        assert_eq!(globals.len(), 1, "Expected only `rate` to be flagged: {:?}", globals);
+        assert!(globals[0].message.contains("`rate`"));
+    }
+
+    #[test]
+    fn test_abstract_and_untyped_fields() {
+        let code = "struct Sample{T}\n    a::Real\n    b\n    c::T\n    d::Vector{Float64}\nend";
+        let diagnostics = analyze_code(code);
+        assert!(has_code(&diagnostics, "abstract_field"));
+        assert!(has_code(&diagnostics, "untyped_field"));
+        let field_diagnostics = diagnostics.iter()
+            .filter(|d| matches!(d.code.as_deref(), Some("abstract_field") | Some("untyped_field")))
+            .count();
+        assert_eq!(field_diagnostics, 2);
+    }
+
+    #[test]
+    fn test_loop_performance_traps() {
+        let code = "function report(items)\n    s = \"\"\n    for item in items\n        seen = Dict()\n        s *= string(item)\n    end\n    s\nend";
+        let diagnostics = analyze_code(code);
+        assert!(has_code(&diagnostics, "untyped_container_in_loop"));
+        assert!(has_code(&diagnostics, "string_concat_in_loop"));
+    }
+
+    #[test]
+    fn test_boxed_capture() {
+        let code = "function counter()\n    count = 0\n    inc = () -> (count += 1)\n    inc()\n    count\nend";
+        let diagnostics = analyze_code(code);
+        assert!(has_code(&diagnostics, "boxed_capture"));
+    }
+
+    #[test]
+    fn test_unreachable_code_after_return() {
+        let code = "function f(x)\n    if x < 0\n        error(\"negative\")\n    end\n    return x\n    println(x)\nend";
+        let diagnostics = analyze_code(code);
+        let unreachable: Vec<_> = diagnostics.iter()
+            .filter(|d| d.code.as_deref() == Some("unreachable_code"))
+            .collect();
+        assert_eq!(unreachable.len(), 1, "Only the statement after `return` is dead: {:?}", unreachable);
+        assert_eq!(unreachable[0].range.start.line, 5);
+    }
+
+    #[test]
+    fn test_unused_import_names() {
+        let code = "using Statistics: mean, std\nprintln(mean([1, 2]))";
+        let diagnostics = analyze_indexed_code(code);
+        let unused: Vec<_> = diagnostics.iter()
+            .filter(|d| d.code.as_deref() == Some("unused_import"))
+            .collect();
+        assert_eq!(unused.len(), 1, "Expected only `std` to be unused: {:?}", unused);
+        assert!(unused[0].message.contains("`std`"));
+    }
+
+    #[test]
+    fn test_undefined_export() {
+        let code = "module M\nexport area, volume\narea(r) = pi * r^2\nend";
+        let diagnostics = analyze_indexed_code(code);
+        let undefined: Vec<_> = diagnostics.iter()
+            .filter(|d| d.code.as_deref() == Some("undefined_export"))
+            .collect();
+        assert_eq!(undefined.len(), 1);
+        assert!(undefined[0].message.contains("`volume`"));
+    }
 }
@@ -0,0 +1,600 @@
+use crate::pipeline::storage::Index;
+use crate::types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tree_sitter::{Node, Tree};
+
+/// Abstract types that make a struct field (and every access to it) type-unstable
+const ABSTRACT_TYPES: &[&str] = &[
+    "Any", "Number", "Real", "Integer", "Signed", "Unsigned", "AbstractFloat", "AbstractString",
+    "AbstractChar", "AbstractArray", "AbstractVector", "AbstractMatrix", "AbstractVecOrMat",
+    "AbstractDict", "AbstractSet", "AbstractRange", "Function", "IO",
+];
+
+/// Parametric types that are abstract (UnionAll) when written without parameters
+const UNPARAMETERIZED_TYPES: &[&str] = &["Vector", "Matrix", "Array", "Dict", "Set", "Tuple", "Ref", "Complex", "Rational"];
+
+/// Containers with `Any` element types, written without spaces
+const UNTYPED_CONTAINERS: &[&str] = &[
+    "[]", "Any[]", "Vector{Any}()", "Vector{Any}(undef,0)", "Array{Any,1}()",
+    "Dict()", "Dict{Any,Any}()", "Set()", "Set{Any}()",
+];
+
+const TIMING_MACROS: &[&str] = &["@time", "@timev", "@btime", "@benchmark"];
+
+/// Check for common Julia performance pitfalls
+pub(super) fn check_performance(
+    tree: &Tree,
+    text: &str,
+    index: &Index,
+    uri: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let root = tree.root_node();
+    let mut checker = PerformanceChecker {
+        text,
+        globals: HashMap::new(),
+        string_variables: HashSet::new(),
+        // Files reached through a package's include() tree
+        is_package_code: index.get_file_module(Path::new(uri)).is_some(),
+        diagnostics,
+    };
+    checker.collect_globals(root);
+    checker.collect_string_variables(root);
+    checker.walk(root, false, false);
+}
+
+struct PerformanceChecker<'t, 'd> {
+    text: &'t str,
+    /// Non-const, untyped globals by name
+    globals: HashMap<String, Range>,
+    /// Variables assigned a string literal somewhere in the file
+    string_variables: HashSet<String>,
+    is_package_code: bool,
+    diagnostics: &'d mut Vec<Diagnostic>,
+}
+
+impl<'t> PerformanceChecker<'t, '_> {
+    fn walk(&mut self, node: Node, in_function: bool, in_loop: bool) {
+        let is_function = is_function_node(node);
+        if is_function && !in_function {
+            self.check_function(node);
+        }
+
+        match node.kind() {
+            "struct_definition" => self.check_struct_fields(node),
+            "macrocall_expression" if self.is_package_code => self.check_timing_macro(node),
+            "assignment" | "compound_assignment_expression" if in_loop => self.check_string_concatenation(node),
+            "call_expression" | "vector_expression" | "index_expression" if in_loop => {
+                self.check_untyped_container(node)
+            }
+            _ => {}
+        }
+
+        // A function body only runs per iteration if it is called there
+        let in_loop = (in_loop && !is_function) || matches!(node.kind(), "for_statement" | "while_statement");
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.walk(child, in_function || is_function, in_loop);
+            }
+        }
+    }
+
+    /// Module-level `x = ...` assignments that aren't `const` or type-annotated
+    fn collect_globals(&mut self, node: Node) {
+        for i in 0..node.child_count() {
+            let Some(child) = node.child(i) else { continue };
+            match child.kind() {
+                "assignment" => {
+                    if let Some(lhs) = child.child(0).filter(|lhs| lhs.kind() == "identifier") {
+                        self.globals.entry(self.node_text(lhs).to_string()).or_insert_with(|| node_range(lhs));
+                    }
+                }
+                "module_definition" | "block" | "begin_statement" | "if_statement" | "elseif_clause"
+                | "else_clause" => self.collect_globals(child),
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_string_variables(&mut self, node: Node) {
+        if node.kind() == "assignment" {
+            if let (Some(lhs), Some(rhs)) = (node.child(0), node.child(node.child_count().saturating_sub(1))) {
+                if lhs.kind() == "identifier" && rhs.kind() == "string_literal" {
+                    self.string_variables.insert(self.node_text(lhs).to_string());
+                }
+            }
+        }
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.collect_string_variables(child);
+            }
+        }
+    }
+
+    /// Globals read from the function body, and variables a closure captures and reassigns
+    fn check_function(&mut self, function: Node) {
+        let (params, body) = split_function(function);
+        let mut param_names = HashSet::new();
+        if let Some(params) = params {
+            collect_identifiers(params, self.text, &mut param_names);
+        }
+
+        let mut locals = param_names.clone();
+        let mut declared_global = HashSet::new();
+        for part in &body {
+            let mut assigned = Vec::new();
+            collect_assigned(*part, self.text, true, &mut assigned);
+            locals.extend(assigned.into_iter().map(|(name, _)| name));
+            collect_declared(*part, self.text, "local_statement", &mut locals);
+            collect_declared(*part, self.text, "global_statement", &mut declared_global);
+        }
+
+        let mut reported = HashSet::new();
+        for part in &body {
+            self.check_global_reads(*part, &locals, &declared_global, &mut reported);
+        }
+        self.check_boxed_captures(&param_names, &body);
+    }
+
+    fn check_global_reads(
+        &mut self,
+        node: Node,
+        locals: &HashSet<String>,
+        declared_global: &HashSet<String>,
+        reported: &mut HashSet<String>,
+    ) {
+        if node.kind() == "identifier" && is_value_position(node) {
+            let name = self.node_text(node);
+            let is_global = !locals.contains(name) || declared_global.contains(name);
+            if is_global && self.globals.contains_key(name) && reported.insert(name.to_string()) {
+                self.diagnostics.push(performance_diagnostic(
+                    node_range(node),
+                    "non_const_global",
+                    format!(
+                        "Global variable `{}` is not `const`: its type can change at any time, so code \
+                         using it inside a function can't be type-inferred. Declare it `const` or pass it \
+                         as an argument",
+                        name
+                    ),
+                ));
+            }
+            return;
+        }
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.check_global_reads(child, locals, declared_global, reported);
+            }
+        }
+    }
+
+    /// Julia stores a captured variable in a `Core.Box` when it is assigned more than
+    /// once (or inside the closure), after which its type is no longer inferred
+    fn check_boxed_captures(&mut self, param_names: &HashSet<String>, body: &[Node]) {
+        let mut outer_assignments: HashMap<String, usize> = param_names.iter().map(|p| (p.clone(), 1)).collect();
+        let mut closures = Vec::new();
+        for part in body {
+            let mut assigned = Vec::new();
+            collect_assigned(*part, self.text, false, &mut assigned);
+            for (name, _) in assigned {
+                *outer_assignments.entry(name).or_default() += 1;
+            }
+            collect_closures(*part, &mut closures);
+        }
+
+        let mut reported = HashSet::new();
+        for closure in closures {
+            let (params, closure_body) = split_function(closure);
+            let mut closure_locals = HashSet::new();
+            if let Some(params) = params {
+                collect_identifiers(params, self.text, &mut closure_locals);
+            }
+            let mut assigned_inside = Vec::new();
+            for part in &closure_body {
+                collect_assigned(*part, self.text, true, &mut assigned_inside);
+                collect_declared(*part, self.text, "local_statement", &mut closure_locals);
+            }
+
+            let mut used = Vec::new();
+            for part in &closure_body {
+                collect_identifier_nodes(*part, self.text, &mut used);
+            }
+            for (name, node) in used {
+                let Some(&outer_count) = outer_assignments.get(&name) else { continue };
+                if closure_locals.contains(&name) || reported.contains(&name) {
+                    continue;
+                }
+                let reassigned_inside = assigned_inside.iter().any(|(assigned, _)| *assigned == name);
+                if reassigned_inside || outer_count > 1 {
+                    reported.insert(name.clone());
+                    self.diagnostics.push(performance_diagnostic(
+                        node_range(node),
+                        "boxed_capture",
+                        format!(
+                            "Variable `{}` is captured by a closure and reassigned, so Julia boxes it \
+                             (`Core.Box`) and can no longer infer its type. Avoid reassigning it, or \
+                             wrap it in a `Ref` or a `let` block",
+                            name
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_struct_fields(&mut self, node: Node) {
+        // Without a type_head the name can't be told apart from an untyped field
+        let Some(type_head) = child_of_kind(node, "type_head") else {
+            return;
+        };
+        let type_params = struct_type_parameters(self.node_text(type_head));
+        let mut fields = Vec::new();
+        for i in 0..node.child_count() {
+            let Some(child) = node.child(i) else { continue };
+            if child.kind() == "block" {
+                fields.extend((0..child.child_count()).filter_map(|j| child.child(j)));
+            } else {
+                fields.push(child);
+            }
+        }
+
+        for field in fields {
+            // `@kwdef` defaults: field::T = value
+            let field = match field.kind() {
+                "assignment" => match field.child(0) {
+                    Some(lhs) => lhs,
+                    None => continue,
+                },
+                _ => field,
+            };
+            match field.kind() {
+                "identifier" => self.diagnostics.push(performance_diagnostic(
+                    node_range(field),
+                    "untyped_field",
+                    format!(
+                        "Field `{}` has no type annotation, so it is `Any` and every access to it is \
+                         dynamically dispatched. Annotate it with a concrete type or a type parameter",
+                        self.node_text(field)
+                    ),
+                )),
+                "typed_expression" => {
+                    let field_text = self.node_text(field);
+                    let Some((name, field_type)) = field_text.split_once("::") else { continue };
+                    if is_abstract_field_type(field_type.trim(), &type_params) {
+                        self.diagnostics.push(performance_diagnostic(
+                            node_range(field),
+                            "abstract_field",
+                            format!(
+                                "Field `{}` has abstract type `{}`, so its concrete type isn't known from \
+                                 the struct type. Use a concrete type or a type parameter",
+                                name.trim(),
+                                field_type.trim()
+                            ),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_untyped_container(&mut self, node: Node) {
+        let normalized: String = self.node_text(node).chars().filter(|c| !c.is_whitespace()).collect();
+        if UNTYPED_CONTAINERS.contains(&normalized.as_str()) {
+            self.diagnostics.push(performance_diagnostic(
+                node_range(node),
+                "untyped_container_in_loop",
+                format!(
+                    "`{}` builds a container with `Any` elements on every iteration; reading from it is \
+                     type-unstable. Give it a concrete element type (e.g. `Float64[]`, \
+                     `Dict{{String,Int}}()`) and consider allocating it once outside the loop",
+                    normalized
+                ),
+            ));
+        }
+    }
+
+    fn check_string_concatenation(&mut self, node: Node) {
+        let (Some(lhs), Some(operator), Some(rhs)) = (node.child(0), node.child(1), node.child(2)) else {
+            return;
+        };
+        if lhs.kind() != "identifier" {
+            return;
+        }
+        let name = self.node_text(lhs);
+        let is_string = |n: Node| self.string_variables.contains(name) || contains_kind(n, "string_literal");
+        let concatenates = match self.node_text(operator) {
+            "*=" => is_string(rhs),
+            "=" => match rhs.kind() {
+                // s = s * x
+                "binary_expression" => {
+                    rhs.child(1).is_some_and(|op| self.node_text(op) == "*")
+                        && rhs.child(0).is_some_and(|first| self.node_text(first) == name)
+                        && is_string(rhs)
+                }
+                // s = string(s, x)
+                "call_expression" => {
+                    rhs.child(0).is_some_and(|callee| self.node_text(callee) == "string")
+                        && child_of_kind(rhs, "argument_list")
+                            .and_then(|args| args.named_child(0))
+                            .is_some_and(|first| self.node_text(first) == name)
+                }
+                // s = "$s ..."
+                "string_literal" => {
+                    let literal = self.node_text(rhs);
+                    literal.contains(&format!("${}", name)) || literal.contains(&format!("$({})", name))
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+        if concatenates {
+            self.diagnostics.push(performance_diagnostic(
+                node_range(node),
+                "string_concat_in_loop",
+                format!(
+                    "Concatenating onto `{}` in a loop copies the whole string on every iteration. \
+                     Write the parts to an `IOBuffer` (or collect them and `join`) instead",
+                    name
+                ),
+            ));
+        }
+    }
+
+    fn check_timing_macro(&mut self, node: Node) {
+        let Some(macro_name) = node.child(0) else { return };
+        let name = self.node_text(macro_name);
+        if TIMING_MACROS.contains(&name) {
+            self.diagnostics.push(performance_diagnostic(
+                node_range(macro_name),
+                "timing_macro",
+                format!(
+                    "`{}` left in package code runs and prints on every call. Remove it, or move the \
+                     measurement to a benchmark script",
+                    name
+                ),
+            ));
+        }
+    }
+
+    fn node_text(&self, node: Node) -> &'t str {
+        node.utf8_text(self.text.as_bytes()).unwrap_or("")
+    }
+}
+
+/// `function f(...) ... end`, `f(...) = ...`, `x -> ...` and `do` blocks
+fn is_function_node(node: Node) -> bool {
+    match node.kind() {
+        "function_definition" | "arrow_function_expression" | "do_clause" => true,
+        "assignment" => node.child(0).is_some_and(is_call_head),
+        _ => false,
+    }
+}
+
+fn is_call_head(node: Node) -> bool {
+    match node.kind() {
+        "call_expression" => true,
+        "where_expression" | "typed_expression" => node.child(0).is_some_and(is_call_head),
+        _ => false,
+    }
+}
+
+/// Split a function-like node into its parameter part and body nodes
+fn split_function(node: Node) -> (Option<Node>, Vec<Node>) {
+    let children: Vec<Node> = (0..node.child_count()).filter_map(|i| node.child(i)).collect();
+    let params = match node.kind() {
+        "function_definition" => children.iter().copied().find(|c| c.kind() == "signature"),
+        "do_clause" => children
+            .get(1)
+            .copied()
+            .filter(|c| c.start_position().row == node.start_position().row && c.kind() != "block"),
+        _ => children.first().copied(),
+    };
+    let body = children
+        .into_iter()
+        .filter(|c| Some(c.id()) != params.map(|p| p.id()) && c.is_named())
+        .collect();
+    (params, body)
+}
+
+/// Closures nested in a function body (not descending into closures of closures)
+fn collect_closures<'a>(node: Node<'a>, closures: &mut Vec<Node<'a>>) {
+    if is_function_node(node) {
+        closures.push(node);
+        return;
+    }
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect_closures(child, closures);
+        }
+    }
+}
+
+/// Names assigned by `x = ...`, `x += ...`, `(a, b) = ...` and `for x in ...`
+fn collect_assigned<'a>(node: Node<'a>, text: &str, into_closures: bool, assigned: &mut Vec<(String, Node<'a>)>) {
+    if !into_closures && is_function_node(node) {
+        return;
+    }
+    match node.kind() {
+        "assignment" | "compound_assignment_expression" if !is_function_node(node) => {
+            let in_arguments = node
+                .parent()
+                .is_some_and(|p| matches!(p.kind(), "argument_list" | "macro_argument_list"));
+            if let Some(lhs) = node.child(0).filter(|_| !in_arguments) {
+                collect_targets(lhs, text, assigned);
+            }
+        }
+        "for_binding" => {
+            if let Some(target) = node.child(0) {
+                collect_targets(target, text, assigned);
+            }
+        }
+        _ => {}
+    }
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect_assigned(child, text, into_closures, assigned);
+        }
+    }
+}
+
+fn collect_targets<'a>(lhs: Node<'a>, text: &str, assigned: &mut Vec<(String, Node<'a>)>) {
+    match lhs.kind() {
+        "identifier" => {
+            if let Ok(name) = lhs.utf8_text(text.as_bytes()) {
+                assigned.push((name.to_string(), lhs));
+            }
+        }
+        "typed_expression" => {
+            if let Some(target) = lhs.child(0) {
+                collect_targets(target, text, assigned);
+            }
+        }
+        "tuple_expression" | "parenthesized_expression" => {
+            for i in 0..lhs.child_count() {
+                if let Some(child) = lhs.child(i) {
+                    collect_targets(child, text, assigned);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names listed in `local x, y` / `global x` statements
+fn collect_declared(node: Node, text: &str, kind: &str, names: &mut HashSet<String>) {
+    if node.kind() == kind {
+        collect_identifiers(node, text, names);
+        return;
+    }
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect_declared(child, text, kind, names);
+        }
+    }
+}
+
+fn collect_identifiers(node: Node, text: &str, names: &mut HashSet<String>) {
+    let mut nodes = Vec::new();
+    collect_identifier_nodes(node, text, &mut nodes);
+    names.extend(nodes.into_iter().map(|(name, _)| name));
+}
+
+fn collect_identifier_nodes<'a>(node: Node<'a>, text: &str, names: &mut Vec<(String, Node<'a>)>) {
+    if node.kind() == "identifier" {
+        if let Ok(name) = node.utf8_text(text.as_bytes()) {
+            names.push((name.to_string(), node));
+        }
+        return;
+    }
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect_identifier_nodes(child, text, names);
+        }
+    }
+}
+
+/// Whether an identifier reads a variable (rather than naming a field, keyword or symbol)
+fn is_value_position(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return true;
+    };
+    let is_first_child = parent.child(0).is_some_and(|first| first.id() == node.id());
+    match parent.kind() {
+        "field_expression" | "field_access" => is_first_child,
+        "named_argument" | "keyword_argument" => !is_first_child,
+        "quote_expression" | "macro_identifier" | "signature" => false,
+        _ => true,
+    }
+}
+
+fn contains_kind(node: Node, kind: &str) -> bool {
+    node.kind() == kind
+        || (0..node.child_count())
+            .filter_map(|i| node.child(i))
+            .any(|child| contains_kind(child, kind))
+}
+
+fn child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    (0..node.child_count()).filter_map(|i| node.child(i)).find(|c| c.kind() == kind)
+}
+
+/// Type parameter names of a struct head such as `Point{T<:Real, N} <: AbstractPoint`
+fn struct_type_parameters(type_head: &str) -> Vec<String> {
+    let Some(open) = type_head.find('{') else {
+        return Vec::new();
+    };
+    let mut depth = 0;
+    let mut params = Vec::new();
+    let mut current = String::new();
+    for c in type_head[open + 1..].chars() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => break,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    params.push(current);
+    params
+        .iter()
+        .filter_map(|p| p.split(['<', '>']).next())
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Whether a field annotation leaves the field's concrete type unknown
+fn is_abstract_field_type(field_type: &str, type_params: &[String]) -> bool {
+    let base = field_type.split('{').next().unwrap_or(field_type).trim();
+    if type_params.iter().any(|p| p == base) {
+        return false;
+    }
+    ABSTRACT_TYPES.contains(&base) || (base == field_type && UNPARAMETERIZED_TYPES.contains(&base))
+}
+
+fn performance_diagnostic(range: Range, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::Information),
+        code: Some(code.to_string()),
+        source: Some("performance".to_string()),
+        message,
+        related_information: None,
+    }
+}
+
+fn node_range(node: Node) -> Range {
+    Range {
+        start: Position::from(node.start_position()),
+        end: Position::from(node.end_position()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_type_parameters() {
+        assert_eq!(struct_type_parameters("Point{T<:Real, N} <: AbstractPoint"), vec!["T", "N"]);
+        assert_eq!(struct_type_parameters("Wrapper{A<:AbstractVector{Float64}}"), vec!["A"]);
+        assert!(struct_type_parameters("Plain").is_empty());
+    }
+
+    #[test]
+    fn test_abstract_field_types() {
+        let params = vec!["T".to_string()];
+        assert!(is_abstract_field_type("Real", &params));
+        assert!(is_abstract_field_type("AbstractVector{Float64}", &params));
+        assert!(is_abstract_field_type("Vector", &params));
+        assert!(!is_abstract_field_type("Vector{Float64}", &params));
+        assert!(!is_abstract_field_type("T", &params));
+        assert!(!is_abstract_field_type("Float64", &params));
+    }
+}
@@ -88,6 +88,10 @@ pub(super) fn check_identifier_references(
             // This identifier is part of a symbol literal, skip it
             return;
         }
+        // `enum` in `@enum` names the macro `@enum`, not a variable
+        if p.kind() == "macro_identifier" {
+            return;
+        }
         check_parent = p.parent();
     }
     
@@ -292,7 +296,8 @@ pub(super) fn check_identifier_references(
                     }
                 }
                 // Check for short function syntax (assignment with call_expression on left)
-                if parent.kind() == "assignment" {
+                // and names local to a macro call (JuMP index sets, Turing `~` statements)
+                if matches!(parent.kind(), "assignment" | "macrocall_expression") {
                     if let Some(params) = function_scopes.get(&parent.id()) {
                         if params.contains(name) {
                             is_in_function_scope = true;
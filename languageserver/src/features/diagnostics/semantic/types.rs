@@ -1,5 +1,11 @@
 use crate::pipeline::storage::Index;
-use crate::types::Diagnostic;
+use crate::types::{
+    builtin_supertype, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, FunctionSignature, Location,
+    ParameterKind, Position, Range, TypeExpr,
+};
+use super::utils::is_builtin_or_keyword;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use tree_sitter::{Node, Tree};
 
 /// Check for type mismatches using stored inferred types from Index
@@ -8,10 +14,11 @@ pub(super) fn check_type_mismatches(
     tree: &Tree,
     text: &str,
     index: &Index,
+    uri: &str,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
     let root = tree.root_node();
-    check_type_mismatches_recursive(root, text, index, diagnostics);
+    check_type_mismatches_recursive(root, text, index, uri, diagnostics);
 }
 
 /// Recursively check for type mismatches in AST nodes
@@ -19,56 +26,382 @@ pub(super) fn check_type_mismatches_recursive(
     node: Node,
     text: &str,
     index: &Index,
+    uri: &str,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
-    // Check function calls for type mismatches
-    if node.kind() == "call_expression" {
-        check_function_call_types(node, text, index, diagnostics);
+    // Check function calls against the methods known for the callee
+    if node.kind() == "call_expression" && !is_definition_head(node) {
+        check_function_call_types(node, text, index, uri, diagnostics);
     }
-    
+
     // Check assignments for type mismatches
     if node.kind() == "assignment" {
         check_assignment_types(node, text, index, diagnostics);
     }
-    
+
     // Recursively check children
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            check_type_mismatches_recursive(child, text, index, diagnostics);
+            check_type_mismatches_recursive(child, text, index, uri, diagnostics);
         }
     }
 }
 
-/// Check function call for type mismatches
+/// Arguments of a call site, as far as they can be read from the syntax
+#[derive(Debug, Default)]
+struct CallShape<'a> {
+    /// Literal type of each positional argument (None if not a literal)
+    positional: Vec<Option<&'static str>>,
+    keywords: Vec<(String, Node<'a>)>,
+    /// `f(xs...)`: positional count unknown
+    positional_splat: bool,
+    /// `f(; kw...)`: keyword names unknown
+    keyword_splat: bool,
+}
+
+/// Check a function call against the methods known for the callee: number of
+/// positional arguments, keyword names and, for literal arguments, argument types
 pub(super) fn check_function_call_types(
     call_node: Node,
     text: &str,
     index: &Index,
-    _diagnostics: &mut Vec<Diagnostic>,
+    uri: &str,
+    diagnostics: &mut Vec<Diagnostic>,
 ) {
-    // Extract function name
-    let func_name = extract_function_name(call_node, text);
-    if func_name.is_none() {
+    let Some(callee) = call_node.child(0) else {
+        return;
+    };
+    let Some(func_name) = extract_function_name(call_node, text) else {
+        return;
+    };
+    let candidates = find_candidate_methods(&func_name, index, uri);
+    // Docstring-only entries don't know their parameters, so any arity would be a guess
+    if candidates.is_empty() || candidates.iter().any(|sig| !sig.parameters_known) {
+        return;
+    }
+    let shape = read_call_shape(call_node, text);
+    let name = candidates[0].name.clone();
+    let related = candidate_information(&candidates);
+
+    let applicable: Vec<&FunctionSignature> = if shape.positional_splat {
+        candidates.iter().collect()
+    } else {
+        candidates.iter().filter(|sig| accepts_arity(sig, shape.positional.len())).collect()
+    };
+    if applicable.is_empty() {
+        let count = shape.positional.len();
+        diagnostics.push(call_diagnostic(
+            node_range(callee),
+            "method_arity",
+            format!(
+                "No method of `{}` accepts {} positional argument{}",
+                name,
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            &related,
+        ));
         return;
     }
-    let func_name = func_name.unwrap();
-    
-    // Find function signatures in Index
-    // Try qualified name first (e.g., "CSV.read")
-    let (module, name): (&str, &str) = if let Some((m, n)) = func_name.split_once('.') {
-        (m, n)
+
+    if !shape.positional_splat
+        && shape.positional.iter().any(|t| t.is_some())
+        && !applicable.iter().any(|sig| accepts_literal_types(sig, &shape.positional))
+    {
+        let types: Vec<&str> = shape.positional.iter().map(|t| t.unwrap_or("Any")).collect();
+        diagnostics.push(call_diagnostic(
+            node_range(callee),
+            "no_applicable_method",
+            format!("No method of `{}` is applicable to arguments ({})", name, types.join(", ")),
+            &related,
+        ));
+        return;
+    }
+
+    if shape.keyword_splat || applicable.iter().any(|sig| has_kind(sig, ParameterKind::KeywordVarargs)) {
+        return;
+    }
+    let accepted: HashSet<&str> = applicable
+        .iter()
+        .flat_map(|sig| sig.parameters.iter())
+        .filter(|p| p.kind.is_keyword())
+        .map(|p| p.name.as_str())
+        .collect();
+    for (keyword, node) in &shape.keywords {
+        if !accepted.contains(keyword.as_str()) {
+            diagnostics.push(call_diagnostic(
+                node_range(*node),
+                "unknown_keyword",
+                format!("No method of `{}` accepts keyword argument `{}`", name, keyword),
+                &related,
+            ));
+        }
+    }
+
+    // Only report keywords that every applicable method requires
+    let passed: HashSet<&str> = shape.keywords.iter().map(|(k, _)| k.as_str()).collect();
+    let mut missing: Option<Vec<&str>> = None;
+    for sig in &applicable {
+        let required: Vec<&str> = sig
+            .parameters
+            .iter()
+            .filter(|p| p.kind == ParameterKind::RequiredKeyword && !passed.contains(p.name.as_str()))
+            .map(|p| p.name.as_str())
+            .collect();
+        missing = Some(match missing {
+            None => required,
+            Some(prev) => prev.into_iter().filter(|k| required.contains(k)).collect(),
+        });
+    }
+    for keyword in missing.unwrap_or_default() {
+        diagnostics.push(call_diagnostic(
+            node_range(callee),
+            "missing_keyword",
+            format!("Call to `{}` is missing required keyword argument `{}`", name, keyword),
+            &related,
+        ));
+    }
+}
+
+/// Methods the callee of a call may dispatch to, or nothing when the call
+/// can't be checked (Base/Core functions, constructors, unknown names)
+fn find_candidate_methods(func_name: &str, index: &Index, uri: &str) -> Vec<FunctionSignature> {
+    if let Some((module, name)) = func_name.rsplit_once('.') {
+        if matches!(module, "Base" | "Core") || index.find_type(module, name).is_some() {
+            return Vec::new();
+        }
+        return index.find_signatures(module, name);
+    }
+
+    let name = func_name;
+    if is_builtin_or_keyword(name)
+        || !index.find_signatures("Base", name).is_empty()
+        || !index.find_signatures("Core", name).is_empty()
+    {
+        return Vec::new();
+    }
+
+    let mut modules: Vec<String> = Vec::new();
+    match index.get_file_module(Path::new(uri)) {
+        Some(file_module) => modules.push(file_module.context.clone()),
+        None => {
+            // Files outside the module tree are indexed under a module guessed from
+            // their path, so use whichever module holds this file's own methods
+            let all = index.find_signatures_by_name(name);
+            modules.extend(all.iter().filter(|sig| sig.file_uri == uri).map(|sig| sig.module.clone()));
+            modules.push("Main".to_string());
+        }
+    }
+    for import in index.get_file_imports(&PathBuf::from(uri)) {
+        let listed = import.symbols.iter().any(|s| s == name);
+        if listed || (!import.is_qualified_only && index.is_exported(&import.module, name)) {
+            modules.push(import.module.clone());
+        }
+    }
+
+    let mut seen = HashSet::new();
+    modules.retain(|m| seen.insert(m.clone()));
+    if modules.iter().any(|m| index.find_type(m, name).is_some()) {
+        return Vec::new();
+    }
+    modules.iter().flat_map(|m| index.find_signatures(m, name)).collect()
+}
+
+/// Read positional and keyword arguments from a call_expression
+fn read_call_shape<'a>(call_node: Node<'a>, text: &str) -> CallShape<'a> {
+    let mut shape = CallShape::default();
+    for i in 0..call_node.child_count() {
+        let Some(child) = call_node.child(i) else { continue };
+        match child.kind() {
+            "argument_list" => read_arguments(child, text, false, &mut shape),
+            // `f(x) do y ... end` passes the block as the first argument
+            "do_clause" => shape.positional.insert(0, None),
+            _ => {}
+        }
+    }
+    shape
+}
+
+fn read_arguments<'a>(node: Node<'a>, text: &str, mut keyword: bool, shape: &mut CallShape<'a>) {
+    for i in 0..node.child_count() {
+        let Some(arg) = node.child(i) else { continue };
+        match arg.kind() {
+            "(" | ")" | "," | "comment" => {}
+            ";" => keyword = true,
+            "keyword_arguments" => read_arguments(arg, text, true, shape),
+            "named_argument" | "assignment" | "keyword_argument" => {
+                if let Some(name) = arg.child(0).and_then(|n| n.utf8_text(text.as_bytes()).ok()) {
+                    shape.keywords.push((name.trim().to_string(), arg));
+                }
+            }
+            "splat_expression" => {
+                if keyword {
+                    shape.keyword_splat = true;
+                } else {
+                    shape.positional_splat = true;
+                }
+            }
+            // `f(; x)` is shorthand for `f(; x=x)`
+            "identifier" if keyword => {
+                if let Ok(name) = arg.utf8_text(text.as_bytes()) {
+                    shape.keywords.push((name.to_string(), arg));
+                }
+            }
+            _ if keyword => {}
+            _ => shape.positional.push(literal_type(arg, text)),
+        }
+    }
+}
+
+/// Whether a method accepts this many positional arguments
+fn accepts_arity(sig: &FunctionSignature, count: usize) -> bool {
+    let required = sig.parameters.iter().filter(|p| p.kind == ParameterKind::Required).count();
+    let optional = sig.parameters.iter().filter(|p| p.kind == ParameterKind::Optional).count();
+    count >= required && (has_kind(sig, ParameterKind::Varargs) || count <= required + optional)
+}
+
+fn has_kind(sig: &FunctionSignature, kind: ParameterKind) -> bool {
+    sig.parameters.iter().any(|p| p.kind == kind)
+}
+
+/// Whether the literal positional arguments fit the declared parameter types.
+/// Non-literal arguments and types we can't reason about are assumed to fit.
+fn accepts_literal_types(sig: &FunctionSignature, arg_types: &[Option<&'static str>]) -> bool {
+    let positional: Vec<_> = sig.parameters.iter().filter(|p| !p.kind.is_keyword()).collect();
+    arg_types.iter().enumerate().all(|(i, arg_type)| {
+        let param = positional
+            .get(i)
+            .or_else(|| positional.last().filter(|p| p.kind == ParameterKind::Varargs));
+        match (arg_type, param.and_then(|p| p.param_type.as_ref())) {
+            (Some(arg_type), Some(param_type)) => literal_fits(arg_type, param_type),
+            _ => true,
+        }
+    })
+}
+
+fn literal_fits(arg_type: &str, param_type: &TypeExpr) -> bool {
+    match param_type {
+        TypeExpr::Concrete(name) => {
+            let name = match name.as_str() {
+                "Int" => "Int64",
+                "UInt" => "UInt64",
+                other => other,
+            };
+            if name == "Any" || builtin_supertype(name).is_none() {
+                return true;
+            }
+            let mut current = Some(arg_type);
+            while let Some(t) = current {
+                if t == name {
+                    return true;
+                }
+                current = builtin_supertype(t);
+            }
+            false
+        }
+        TypeExpr::Union(members) => members.iter().any(|m| literal_fits(arg_type, m)),
+        _ => true,
+    }
+}
+
+/// Type of a literal argument (None when it can't be told exactly from the syntax)
+fn literal_type(node: Node, text: &str) -> Option<&'static str> {
+    match node.kind() {
+        "integer_literal" => integer_literal_type(node.utf8_text(text.as_bytes()).ok()?),
+        "float_literal" => float_literal_type(node.utf8_text(text.as_bytes()).ok()?),
+        "string_literal" => Some("String"),
+        "character_literal" => Some("Char"),
+        "boolean_literal" | "true" | "false" => Some("Bool"),
+        "quote_expression" => Some("Symbol"),
+        _ => None,
+    }
+}
+
+/// Type of an integer literal: hex and binary literals are unsigned and sized by their
+/// digit count, decimal literals are the smallest of Int64 and Int128 that holds them
+fn integer_literal_type(literal: &str) -> Option<&'static str> {
+    let literal = literal.replace('_', "");
+    let bits = if let Some(digits) = literal.strip_prefix("0x") {
+        digits.len() * 4
+    } else if let Some(digits) = literal.strip_prefix("0b") {
+        digits.len()
+    } else if literal.starts_with("0o") {
+        // Octal literals are sized by value in a way that is not worth mirroring
+        return None;
+    } else if literal.parse::<i64>().is_ok() {
+        return Some("Int64");
+    } else if literal.parse::<i128>().is_ok() {
+        return Some("Int128");
     } else {
-        ("Main", func_name.as_str())
+        // BigInt
+        return None;
     };
-    
-    let signatures = index.find_signatures(module, name);
-    if signatures.is_empty() {
-        // Function not found in index - skip type checking
+    match bits {
+        1..=8 => Some("UInt8"),
+        9..=16 => Some("UInt16"),
+        17..=32 => Some("UInt32"),
+        33..=64 => Some("UInt64"),
+        65..=128 => Some("UInt128"),
+        _ => None,
+    }
+}
+
+/// Type of a float literal: `1f0` is a Float32, other decimal and hex floats are Float64
+fn float_literal_type(literal: &str) -> Option<&'static str> {
+    // Hex digits may contain `f`, but hex floats always use a `p` exponent
+    if !literal.starts_with("0x") && literal.contains('f') {
+        Some("Float32")
+    } else {
+        Some("Float64")
+    }
+}
+
+/// Whether a call_expression is the head of a method definition rather than a call
+fn is_definition_head(call_node: Node) -> bool {
+    let mut current = call_node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "signature" => return true,
+            "where_expression" | "typed_expression" => current = parent,
+            // Short-form definition: f(x) = ...
+            "assignment" => return parent.child(0).is_some_and(|lhs| lhs.id() == current.id()),
+            _ => return false,
+        }
+    }
+    false
+}
+
+fn candidate_information(candidates: &[FunctionSignature]) -> Vec<DiagnosticRelatedInformation> {
+    candidates
+        .iter()
+        .map(|sig| DiagnosticRelatedInformation {
+            location: Location { uri: sig.file_uri.clone(), range: sig.range.clone() },
+            message: format!("Candidate: {}", sig.label()),
+        })
+        .collect()
+}
+
+fn call_diagnostic(
+    range: Range,
+    code: &str,
+    message: String,
+    related: &[DiagnosticRelatedInformation],
+) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::Warning),
+        code: Some(code.to_string()),
+        source: Some("semantic".to_string()),
+        message,
+        related_information: Some(related.to_vec()),
+    }
+}
+
+fn node_range(node: Node) -> Range {
+    Range {
+        start: Position::from(node.start_position()),
+        end: Position::from(node.end_position()),
     }
-    
-    // Get argument types from Index (if available)
-    // For now, we just check if function exists - full type checking can be enhanced later
-    // TODO: Compare argument types with function signature parameter types
 }
 
 /// Check assignment for type mismatches
@@ -81,18 +414,6 @@ pub(super) fn check_assignment_types(
     // Type inference is not supported for Julia
 }
 
-/// Validate a function call: check if function exists, argument count, and argument types
-/// Type inference is not supported for Julia
-#[allow(dead_code)]
-pub(super) fn validate_function_call(
-    _call_node: Node,
-    _text: &str,
-    _index: &Index,
-    _diagnostics: &mut Vec<Diagnostic>,
-) {
-    // Type inference is not supported for Julia
-}
-
 /// Extract function name from a call_expression node
 pub(super) fn extract_function_name(call_node: Node, text: &str) -> Option<String> {
     // Find the function identifier or field_access
@@ -117,3 +438,97 @@ pub(super) fn extract_function_name(call_node: Node, text: &str) -> Option<Strin
     None
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Parameter;
+
+    fn param(name: &str, param_type: Option<&str>, kind: ParameterKind) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            param_type: param_type.map(TypeExpr::parse),
+            kind,
+        }
+    }
+
+    fn signature(parameters: Vec<Parameter>) -> FunctionSignature {
+        FunctionSignature {
+            module: "Main".to_string(),
+            name: "f".to_string(),
+            parameters,
+            return_type: None,
+            doc_comment: None,
+            file_uri: "test.jl".to_string(),
+            range: Range {
+                start: Position { line: 0, character: 0 },
+                end: Position { line: 0, character: 0 },
+            },
+            parameters_known: true,
+            extends_module: None,
+        }
+    }
+
+    #[test]
+    fn test_arity_with_defaults_and_varargs() {
+        let sig = signature(vec![
+            param("x", None, ParameterKind::Required),
+            param("y", None, ParameterKind::Optional),
+            param("verbose", None, ParameterKind::Keyword),
+        ]);
+        assert!(!accepts_arity(&sig, 0));
+        assert!(accepts_arity(&sig, 1));
+        assert!(accepts_arity(&sig, 2));
+        assert!(!accepts_arity(&sig, 3));
+
+        let varargs = signature(vec![
+            param("x", None, ParameterKind::Required),
+            param("rest...", None, ParameterKind::Varargs),
+        ]);
+        assert!(!accepts_arity(&varargs, 0));
+        assert!(accepts_arity(&varargs, 5));
+    }
+
+    #[test]
+    fn test_literal_types_against_parameter_types() {
+        let sig = signature(vec![
+            param("x", Some("Real"), ParameterKind::Required),
+            param("s", Some("Union{AbstractString, Nothing}"), ParameterKind::Required),
+            param("rest...", Some("Int"), ParameterKind::Varargs),
+        ]);
+        assert!(accepts_literal_types(&sig, &[Some("Int64"), Some("String")]));
+        assert!(accepts_literal_types(&sig, &[Some("Bool"), None, Some("Int64")]));
+        assert!(!accepts_literal_types(&sig, &[Some("String"), None]));
+        assert!(!accepts_literal_types(&sig, &[None, None, Some("Float64")]));
+
+        // Types outside the builtin hierarchy are assumed to accept anything
+        let custom = signature(vec![param("x", Some("MyType"), ParameterKind::Required)]);
+        assert!(accepts_literal_types(&custom, &[Some("Int64")]));
+    }
+
+    #[test]
+    fn test_numeric_literal_types() {
+        assert_eq!(integer_literal_type("42"), Some("Int64"));
+        assert_eq!(integer_literal_type("1_000"), Some("Int64"));
+        assert_eq!(integer_literal_type("9223372036854775808"), Some("Int128"));
+        assert_eq!(integer_literal_type("170141183460469231731687303715884105728"), None);
+        assert_eq!(integer_literal_type("0x01"), Some("UInt8"));
+        assert_eq!(integer_literal_type("0x123"), Some("UInt16"));
+        assert_eq!(integer_literal_type("0x0000_0001"), Some("UInt32"));
+        assert_eq!(integer_literal_type("0b101"), Some("UInt8"));
+        assert_eq!(integer_literal_type("0o17"), None);
+        assert_eq!(float_literal_type("1.5"), Some("Float64"));
+        assert_eq!(float_literal_type("1e-6"), Some("Float64"));
+        assert_eq!(float_literal_type("1f0"), Some("Float32"));
+        assert_eq!(float_literal_type("0x1p3"), Some("Float64"));
+    }
+
+    #[test]
+    fn test_signature_label() {
+        let sig = signature(vec![
+            param("x", Some("Int"), ParameterKind::Required),
+            param("rest...", None, ParameterKind::Varargs),
+            param("verbose", None, ParameterKind::Keyword),
+        ]);
+        assert_eq!(sig.label(), "f(x::Int, rest...; verbose=…)");
+    }
+}
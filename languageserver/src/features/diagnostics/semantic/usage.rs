@@ -1,3 +1,4 @@
+use crate::pipeline::analyzers::macros::MacroRegistry;
 use crate::pipeline::storage::Index;
 use crate::types::{Diagnostic, DiagnosticSeverity, Position, Range};
 use tree_sitter::{Node, Tree};
@@ -42,7 +43,7 @@ pub(super) fn check_unused_variables(
 /// Check if an assignment node is actually a keyword argument (not a variable assignment)
 /// In Julia, keyword arguments like `pkg=DecisionTree` in macro calls are parsed as assignments
 /// by tree-sitter, but they're not variable definitions.
-pub(super) fn is_keyword_argument_assignment(assignment_node: Node, _text: &str) -> bool {
+pub(super) fn is_keyword_argument_assignment(assignment_node: Node, text: &str) -> bool {
     // Walk up the tree to find if we're inside a macro_call or keyword_argument
     let mut current = assignment_node.parent();
     let mut found_macro_call = false;
@@ -60,6 +61,14 @@ pub(super) fn is_keyword_argument_assignment(assignment_node: Node, _text: &str)
             return true;
         }
         
+        // Code in passthrough macros (`@inbounds x = ...`) and `@testset` bodies is ordinary code
+        let macro_call = if kind == "macro_argument_list" { n.parent() } else { Some(n) };
+        if macro_call.is_some_and(|call| {
+            call.kind() == "macrocall_expression" && MacroRegistry::builtin().evaluates_arguments_as_code(call, text)
+        }) {
+            return false;
+        }
+        
         // Check for macro_argument_list - assignments inside macro argument lists are keyword args
         // This is the most direct check since tree-sitter Julia parses macro args this way
         if kind == "macro_argument_list" {
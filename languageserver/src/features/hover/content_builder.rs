@@ -8,6 +8,7 @@ use super::symbol_hover::{
     build_function_hover, build_type_constant_macro_hover, build_module_hover, build_variable_hover,
 };
 use super::variable_analysis::infer_variable_type;
+use super::docstring::render_docstring;
 use super::helpers::{extract_assignment_info, find_prior_assignment_in_scope, find_definition_assignment_node, is_function_call};
 
 /// Clean and normalize documentation formatting
@@ -112,7 +113,7 @@ pub async fn build_hover_content<'a>(
     
     if let Some(doc) = doc {
         if !doc.trim().is_empty() {
-            let cleaned_doc = clean_documentation(&doc);
+            let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
             content.push_str(&cleaned_doc);
             content.push_str("\n\n");
             has_julia_docs = true;
@@ -137,7 +138,7 @@ pub async fn build_hover_content<'a>(
                     // Strategy 1: Direct qualified lookup across all packages
                     for (_package_name, registry) in package_docs.iter() {
                         if let Some(doc) = registry.get_documentation(symbol_name) {
-                            let cleaned_doc = clean_documentation(&doc);
+                            let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                             content.push_str(&cleaned_doc);
                             content.push_str("\n\n");
                             has_julia_docs = true;
@@ -149,7 +150,7 @@ pub async fn build_hover_content<'a>(
                     if !has_julia_docs {
                         for (_package_name, registry) in package_docs.iter() {
                             if let Some(doc) = registry.get_documentation_by_module(module_name, func_name) {
-                                let cleaned_doc = clean_documentation(&doc);
+                                let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                 content.push_str(&cleaned_doc);
                                 content.push_str("\n\n");
                                 has_julia_docs = true;
@@ -163,14 +164,14 @@ pub async fn build_hover_content<'a>(
                     if !has_julia_docs {
                         if let Some(registry) = package_docs.get(module_name) {
                             if let Some(doc) = registry.get_documentation_by_module(module_name, func_name) {
-                                let cleaned_doc = clean_documentation(&doc);
+                                let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                 content.push_str(&cleaned_doc);
                                 content.push_str("\n\n");
                                 has_julia_docs = true;
                             } else {
                                 // Also try bare function name in this package
                                 if let Some(doc) = registry.get_documentation(func_name) {
-                                    let cleaned_doc = clean_documentation(&doc);
+                                    let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                     content.push_str(&cleaned_doc);
                                     content.push_str("\n\n");
                                     has_julia_docs = true;
@@ -190,7 +191,7 @@ pub async fn build_hover_content<'a>(
                         if let Some(registry) = package_docs.get(module_name) {
                             // Try module+name lookup first (searches submodules too) - important for functions in submodules
                             if let Some(doc) = registry.get_documentation_by_module(module_name, symbol_name) {
-                                let cleaned_doc = clean_documentation(&doc);
+                                let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                 content.push_str(&cleaned_doc);
                                 content.push_str("\n\n");
                                 has_julia_docs = true;
@@ -198,7 +199,7 @@ pub async fn build_hover_content<'a>(
                             }
                             // Try bare name (searches across all modules in the package)
                             if let Some(doc) = registry.get_documentation(symbol_name) {
-                                let cleaned_doc = clean_documentation(&doc);
+                                let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                 content.push_str(&cleaned_doc);
                                 content.push_str("\n\n");
                                 has_julia_docs = true;
@@ -207,7 +208,7 @@ pub async fn build_hover_content<'a>(
                             // Also try qualified name (e.g., "DataFrames.select")
                             let qualified = format!("{}.{}", module_name, symbol_name);
                             if let Some(doc) = registry.get_documentation(&qualified) {
-                                let cleaned_doc = clean_documentation(&doc);
+                                let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                                 content.push_str(&cleaned_doc);
                                 content.push_str("\n\n");
                                 has_julia_docs = true;
@@ -223,7 +224,7 @@ pub async fn build_hover_content<'a>(
                         // Try module+name lookup with package name as module first (searches submodules too)
                         // This is important for functions like "select" which are in "DataFrames.Selection"
                         if let Some(doc) = registry.get_documentation_by_module(package_name, symbol_name) {
-                            let cleaned_doc = clean_documentation(&doc);
+                            let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                             content.push_str(&cleaned_doc);
                             content.push_str("\n\n");
                             has_julia_docs = true;
@@ -231,7 +232,7 @@ pub async fn build_hover_content<'a>(
                         }
                         // Try bare name (searches across all modules in the package)
                         if let Some(doc) = registry.get_documentation(symbol_name) {
-                            let cleaned_doc = clean_documentation(&doc);
+                            let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                             content.push_str(&cleaned_doc);
                             content.push_str("\n\n");
                             has_julia_docs = true;
@@ -240,7 +241,7 @@ pub async fn build_hover_content<'a>(
                         // Also try qualified name (e.g., "DataFrames.select") as fallback
                         let qualified = format!("{}.{}", package_name, symbol_name);
                         if let Some(doc) = registry.get_documentation(&qualified) {
-                            let cleaned_doc = clean_documentation(&doc);
+                            let cleaned_doc = render_docstring(&clean_documentation(&doc), symbol_name, index);
                             content.push_str(&cleaned_doc);
                             content.push_str("\n\n");
                             has_julia_docs = true;
@@ -0,0 +1,339 @@
+use crate::pipeline::storage::Index;
+use crate::types::{FunctionSignature, Parameter, TypeDefinition, TypeDefinitionKind};
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+use super::helpers::file_link;
+
+/// Docstrings longer than this are cut at a paragraph boundary with a "more" link
+const MAX_DOC_LENGTH: usize = 4000;
+
+/// [`foo`](@ref), [text](@ref foo), [text](@ref) to a section
+static REF_LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(@ref(?:\s+([^)]+))?\)").unwrap());
+/// ``x^2`` is LaTeX in Julia docstrings
+static INLINE_MATH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|[^`])``([^`]+)``([^`]|$)").unwrap());
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#{1,2} ").unwrap());
+/// DocStringExtensions abbreviations: `$(TYPEDSIGNATURES)` or `$TYPEDSIGNATURES`
+///
+/// Only the abbreviations rendered below match, so `$HOME` and the like stay in the text.
+static ABBREVIATION: LazyLock<Regex> = LazyLock::new(|| {
+    let names = "SIGNATURES|TYPEDSIGNATURES|FIELDS|TYPEDFIELDS|TYPEDEF|METHODLIST|EXPORTS";
+    Regex::new(&format!(r"\$\(({names})\)|\$({names})\b")).unwrap()
+});
+
+/// How a fenced block of a docstring is rendered
+#[derive(Clone, Copy, PartialEq)]
+enum Fence {
+    /// Shown as a code block (`jldoctest`, `@example` and `@repl` become `julia`)
+    Code,
+    /// ```` ```math ```` shown as a `$$` display block
+    Math,
+    /// Documenter-only blocks (`@meta`, `@setup`, `@docs`) that are left out
+    Hidden,
+}
+
+/// Render a Julia docstring for hover.
+///
+/// Follows Documenter and DocStringExtensions conventions: `@ref` links resolve to
+/// definitions in the index, `$(TYPEDSIGNATURES)` and friends expand from the
+/// indexed methods and fields of `symbol_name`, doctest blocks become `julia` code,
+/// and LaTeX math becomes `$...$`.
+pub fn render_docstring(doc: &str, symbol_name: &str, index: &Index) -> String {
+    let expanded = expand_abbreviations(doc, symbol_name, index);
+
+    let mut lines = Vec::new();
+    let mut fence = None;
+    for line in expanded.lines() {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```");
+        match fence {
+            None if is_fence => {
+                let info = trimmed.trim_start_matches('`').trim();
+                let (kind, opening) = match info.split(|c: char| c.is_whitespace() || c == ';').next().unwrap_or("") {
+                    "jldoctest" | "@example" | "@repl" | "@eval" => (Fence::Code, Some("```julia".to_string())),
+                    "math" => (Fence::Math, Some("$$".to_string())),
+                    "@meta" | "@setup" | "@docs" | "@autodocs" | "@index" | "@contents" => (Fence::Hidden, None),
+                    _ => (Fence::Code, Some(line.to_string())),
+                };
+                lines.extend(opening);
+                fence = Some(kind);
+            }
+            None => lines.push(render_prose_line(line, index)),
+            Some(kind) if is_fence => {
+                match kind {
+                    Fence::Code => lines.push(line.to_string()),
+                    Fence::Math => lines.push("$$".to_string()),
+                    Fence::Hidden => {}
+                }
+                fence = None;
+            }
+            Some(Fence::Hidden) => {}
+            Some(_) => lines.push(line.to_string()),
+        }
+    }
+
+    truncate(&lines.join("\n"), symbol_name, index)
+}
+
+/// Links, inline math and headings of a line outside code blocks
+fn render_prose_line(line: &str, index: &Index) -> String {
+    let line = REF_LINK.replace_all(line, |caps: &Captures| {
+        let text = &caps[1];
+        let target = match caps.get(2) {
+            Some(target) => target.as_str().trim().trim_matches('`'),
+            None if text.starts_with('`') => text.trim_matches('`'),
+            None => return text.to_string(),
+        };
+        match resolve(target, index).filter(|t| !t.file_uri.is_empty()) {
+            Some(t) => format!("[{}]({})", text, file_link(&t.file_uri, t.line)),
+            None => text.to_string(),
+        }
+    });
+
+    let line = INLINE_MATH.replace_all(&line, "${1}$$${2}$$${3}");
+
+    // Keep section headings (`# Examples`) from dwarfing the signature
+    HEADING.replace(&line, "### ").to_string()
+}
+
+/// Expand DocStringExtensions abbreviations; ones without index data are dropped
+fn expand_abbreviations(doc: &str, symbol_name: &str, index: &Index) -> String {
+    if !ABBREVIATION.is_match(doc) {
+        return doc.to_string();
+    }
+    let methods = documented_methods(symbol_name, index);
+    let type_def = documented_type(symbol_name, index);
+
+    ABBREVIATION
+        .replace_all(doc, |caps: &Captures| {
+            let name = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str());
+            match name {
+                "SIGNATURES" => code_block(methods.iter().map(untyped_label)),
+                "TYPEDSIGNATURES" => code_block(methods.iter().map(typed_label)),
+                "FIELDS" | "TYPEDFIELDS" => type_def
+                    .as_ref()
+                    .map(|t| {
+                        let fields: Vec<String> = t
+                            .fields
+                            .iter()
+                            .map(|f| match name {
+                                "FIELDS" => format!("- `{}`", f.name),
+                                _ => format!("- `{}`", f.label()),
+                            })
+                            .collect();
+                        fields.join("\n")
+                    })
+                    .unwrap_or_default(),
+                "TYPEDEF" => type_def.as_ref().map(type_header).map(|h| code_block([h])).unwrap_or_default(),
+                "METHODLIST" => {
+                    let items: Vec<String> = methods
+                        .iter()
+                        .map(|sig| {
+                            let line = sig.range.start.line + 1;
+                            format!("- `{}` at [line {}]({})", sig.label(), line, file_link(&sig.file_uri, line))
+                        })
+                        .collect();
+                    items.join("\n")
+                }
+                "EXPORTS" => {
+                    let mut exports: Vec<String> = index.get_module_exports(symbol_name).into_iter().collect();
+                    exports.sort();
+                    let items: Vec<String> = exports.iter().map(|e| format!("- `{}`", e)).collect();
+                    items.join("\n")
+                }
+                _ => caps[0].to_string(),
+            }
+        })
+        .to_string()
+}
+
+fn documented_methods(symbol_name: &str, index: &Index) -> Vec<FunctionSignature> {
+    let methods = if symbol_name.contains('.') {
+        index.find_function_by_qualified_name(symbol_name).unwrap_or_default()
+    } else {
+        index.find_signatures_by_name(symbol_name)
+    };
+    let mut methods: Vec<FunctionSignature> = methods.into_iter().filter(|sig| sig.parameters_known).collect();
+    if methods.is_empty() {
+        if let Some(type_def) = documented_type(symbol_name, index) {
+            methods = type_def.constructors();
+        }
+    }
+    methods
+}
+
+fn documented_type(symbol_name: &str, index: &Index) -> Option<TypeDefinition> {
+    match symbol_name.rsplit_once('.') {
+        Some((module, name)) => index.find_type(module, name),
+        None => index.find_types_by_name(symbol_name).into_iter().next(),
+    }
+}
+
+/// `f(x, y=…; kw=…)`, as `$(SIGNATURES)` renders it
+fn untyped_label(sig: &FunctionSignature) -> String {
+    let parameters = sig.parameters.iter().map(|p| Parameter { param_type: None, ..p.clone() }).collect();
+    FunctionSignature { parameters, ..sig.clone() }.label()
+}
+
+/// `f(x::Int; kw=…) -> Int`, as `$(TYPEDSIGNATURES)` renders it
+fn typed_label(sig: &FunctionSignature) -> String {
+    match sig.return_type {
+        Some(ref return_type) => format!("{} -> {}", sig.label(), return_type.to_string()),
+        None => sig.label(),
+    }
+}
+
+/// `struct Point{T}`, as `$(TYPEDEF)` renders it
+fn type_header(type_def: &TypeDefinition) -> String {
+    let keyword = match type_def.kind {
+        TypeDefinitionKind::Struct => "struct",
+        TypeDefinitionKind::Abstract => "abstract type",
+        TypeDefinitionKind::Primitive => "primitive type",
+        TypeDefinitionKind::Union => "const",
+    };
+    if type_def.type_parameters.is_empty() {
+        format!("{} {}", keyword, type_def.name)
+    } else {
+        format!("{} {}{{{}}}", keyword, type_def.name, type_def.type_parameters.join(", "))
+    }
+}
+
+fn code_block(lines: impl IntoIterator<Item = String>) -> String {
+    let mut lines: Vec<String> = lines.into_iter().collect();
+    lines.dedup();
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("```julia\n{}\n```", lines.join("\n"))
+}
+
+/// Where a name is defined
+struct Target {
+    module: String,
+    file_uri: String,
+    /// 1-based line
+    line: u32,
+}
+
+/// Definition of an `@ref` target such as `foo`, `Base.foo` or `foo(::Int)`
+fn resolve(target: &str, index: &Index) -> Option<Target> {
+    let name = target.split('(').next()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let (types, methods) = match name.rsplit_once('.') {
+        Some((module, short)) => (
+            index.find_type(module, short).into_iter().collect(),
+            index.find_function_by_qualified_name(name).unwrap_or_default(),
+        ),
+        None => (index.find_types_by_name(name), index.find_signatures_by_name(name)),
+    };
+    if let Some(t) = types.into_iter().next() {
+        return Some(Target { module: t.module, file_uri: t.file_uri, line: t.range.start.line + 1 });
+    }
+    if let Some(sig) = methods.into_iter().next() {
+        return Some(Target { module: sig.module, file_uri: sig.file_uri, line: sig.range.start.line + 1 });
+    }
+    let symbol = index.find_symbols(name).into_iter().next()?;
+    Some(Target { module: String::new(), file_uri: symbol.file_uri, line: symbol.range.start.line + 1 })
+}
+
+/// Cut an overlong docstring at the last paragraph break outside a code block
+fn truncate(doc: &str, symbol_name: &str, index: &Index) -> String {
+    if doc.len() <= MAX_DOC_LENGTH {
+        return doc.to_string();
+    }
+    let mut cut = None;
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in doc.split('\n') {
+        if offset > MAX_DOC_LENGTH {
+            break;
+        }
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed == "$$" {
+            in_fence = !in_fence;
+        } else if trimmed.is_empty() && !in_fence && offset > 0 {
+            cut = Some(offset);
+        }
+        offset += line.len() + 1;
+    }
+    // A single block longer than the limit is shown whole rather than broken
+    let Some(cut) = cut else {
+        return doc.to_string();
+    };
+    format!("{}\n\n[More…]({})", doc[..cut].trim_end(), more_link(symbol_name, index))
+}
+
+/// The full docstring: the Julia manual for Base and stdlib, otherwise the definition
+fn more_link(symbol_name: &str, index: &Index) -> String {
+    let target = resolve(symbol_name, index)
+        .filter(|t| !t.file_uri.is_empty() && !matches!(t.module.split('.').next(), Some("Base" | "Core")));
+    match target {
+        Some(t) => file_link(&t.file_uri, t.line),
+        None => {
+            let name = symbol_name.rsplit('.').next().unwrap_or(symbol_name);
+            format!("https://docs.julialang.org/en/v1/search/?q={}", name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::sources::file::FileSource;
+    use crate::pipeline::WorkspacePipeline;
+    use std::path::PathBuf;
+
+    fn index_for(code: &str) -> Index {
+        let source = FileSource::from_content(PathBuf::from("/project/src/geometry.jl"), code.to_string());
+        WorkspacePipeline::new().run(vec![source]).unwrap()
+    }
+
+    const GEOMETRY: &str = "struct Point{T<:Real}\n    x::T\n    y::T\nend\n\nnorm2(p::Point, scale::Float64=1.0) = scale * (p.x^2 + p.y^2)\n";
+
+    #[test]
+    fn test_ref_links_resolve_to_definitions() {
+        let index = index_for(GEOMETRY);
+        let rendered = render_docstring("See [`norm2`](@ref), [a point](@ref Point) and [`missing`](@ref).", "norm2", &index);
+        assert!(rendered.contains("[`norm2`](file:///"), "{}", rendered);
+        assert!(rendered.contains("geometry.jl:6)"), "{}", rendered);
+        assert!(rendered.contains("[a point](file:///"), "{}", rendered);
+        assert!(rendered.contains("and `missing`."), "{}", rendered);
+    }
+
+    #[test]
+    fn test_abbreviations_expand_from_index() {
+        let index = index_for(GEOMETRY);
+        let rendered = render_docstring("$(TYPEDSIGNATURES)\n\nSquared norm.", "norm2", &index);
+        assert!(rendered.contains("```julia\nnorm2(p::Point, scale::Float64=…)"), "{}", rendered);
+
+        let rendered = render_docstring("$(TYPEDEF)\n\n# Fields\n$(TYPEDFIELDS)", "Point", &index);
+        assert!(rendered.contains("struct Point{T}"), "{}", rendered);
+        assert!(rendered.contains("### Fields"), "{}", rendered);
+        assert!(rendered.contains("- `x::T`\n- `y::T`"), "{}", rendered);
+
+        let rendered = render_docstring("Reads `$HOME` and $(PATH), see $(METHODLIST)", "nothing_here", &index);
+        assert!(rendered.starts_with("Reads `$HOME` and $(PATH), see "), "{}", rendered);
+    }
+
+    #[test]
+    fn test_doctest_and_math() {
+        let index = Index::new();
+        let doc = "Computes ``\\sqrt{x}``.\n\n```jldoctest; setup = :(using Foo)\njulia> f(4)\n2.0\n```\n\n```math\nf(x) = \\sqrt{x}\n```\n\n```@meta\nDocTestSetup = nothing\n```";
+        let rendered = render_docstring(doc, "f", &index);
+        assert!(rendered.contains("Computes $\\sqrt{x}$."), "{}", rendered);
+        assert!(rendered.contains("```julia\njulia> f(4)\n2.0\n```"), "{}", rendered);
+        assert!(rendered.contains("$$\nf(x) = \\sqrt{x}\n$$"), "{}", rendered);
+        assert!(!rendered.contains("DocTestSetup"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_long_base_docs_truncated() {
+        let index = Index::new();
+        let paragraph = "word ".repeat(200);
+        let doc = [paragraph.as_str(); 8].join("\n\n");
+        let rendered = render_docstring(&doc, "Base.sort", &index);
+        assert!(rendered.len() < doc.len());
+        assert!(rendered.ends_with("[More…](https://docs.julialang.org/en/v1/search/?q=sort)"), "{}", rendered);
+    }
+}
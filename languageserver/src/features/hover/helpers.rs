@@ -239,3 +239,17 @@ pub fn type_of_value_like(s: &str) -> Option<String> {
 
 
 
+
+/// Clickable `file://` link to a 1-based line, for hover markdown
+pub fn file_link(file_uri: &str, line_number: u32) -> String {
+    if file_uri.starts_with("file://") {
+        return format!("{}:{}", file_uri, line_number);
+    }
+    let path = std::path::Path::new(file_uri);
+    let uri_path = if cfg!(windows) {
+        path.to_string_lossy().replace('\\', "/")
+    } else {
+        path.to_string_lossy().to_string()
+    };
+    format!("file:///{}:{}", uri_path, line_number)
+}
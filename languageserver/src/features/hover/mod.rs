@@ -3,6 +3,7 @@ mod variable_analysis;
 mod location_hints;
 mod symbol_hover;
 mod content_builder;
+mod docstring;
 
 pub use helpers::{
     extract_assignment_value, find_prior_assignment_in_scope, extract_assignment_info,
@@ -14,7 +15,9 @@ pub use symbol_hover::{
     build_function_hover, build_type_constant_macro_hover, build_module_hover, build_variable_hover,
 };
 pub use content_builder::build_hover_content;
+pub use docstring::render_docstring;
 
+use crate::features::StructFieldProvider;
 use crate::pipeline::sources::{Document, BaseDocsRegistry};
 use crate::pipeline::query::SymbolResolver;
 use crate::pipeline::storage::CacheManager;
@@ -42,6 +45,11 @@ impl HoverProvider {
         let resolver = SymbolResolver::new(tree, &text);
         let node = resolver.node_at_position(position.line, position.character)?;
 
+        // Field of a struct-typed variable (`p.x` where `p::Point`)
+        if let Some(result) = StructFieldProvider::field_hover(node, &text, index) {
+            return Some(result);
+        }
+
         // 2. Extract symbol name - check if we're in a field_access first
         // Also check if we're in a using statement - if so, treat as external module
         let symbol_name = if node.kind() == "identifier" {
@@ -4,6 +4,13 @@ pub mod definition;
 pub mod references;
 pub mod diagnostics;
 pub mod code_actions;
+pub mod dependency_health;
+pub mod project_toml;
+pub mod dead_code;
+pub mod notebook_dependencies;
+pub mod package_health;
+pub mod struct_fields;
+pub mod signature_help;
 
 pub use hover::HoverProvider;
 pub use completion::CompletionProvider;
@@ -11,3 +18,10 @@ pub use definition::DefinitionProvider;
 pub use references::ReferencesProvider;
 pub use diagnostics::DiagnosticsProvider;
 pub use code_actions::CodeActionsProvider;
+pub use dependency_health::{DependencyHealthProvider, DependencyHealthReport};
+pub use project_toml::ProjectTomlProvider;
+pub use dead_code::{DeadCodeProvider, DeadCodeReport};
+pub use package_health::{PackageHealthProvider, PackageHealthReport};
+pub use struct_fields::StructFieldProvider;
+pub use signature_help::SignatureHelpProvider;
+pub use notebook_dependencies::{CellDependencies, CellDependencyGraph, MultipleDefinition};
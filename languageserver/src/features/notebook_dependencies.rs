@@ -0,0 +1,463 @@
+use crate::pipeline::analyzers::{import, reference, scope, signature, symbol};
+use crate::pipeline::parser;
+use crate::pipeline::sources::file::FileSource;
+use crate::pipeline::types::{ScopeNode, ScopeTree};
+use crate::types::{FunctionSignature, LspError, ParameterKind, Range, SymbolKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Globals a single notebook cell defines and reads
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CellDependencies {
+    pub cell_id: String,
+    /// Globals assigned or declared at the top level of the cell
+    pub defines: BTreeSet<String>,
+    /// Globals the cell reads that it does not define itself
+    pub reads: BTreeSet<String>,
+    /// Positional parameter types of the methods the cell defines, by function name
+    #[serde(default)]
+    pub methods: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A global assigned in more than one cell, or a method defined with the same signature in more than one cell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultipleDefinition {
+    pub name: String,
+    /// Defining cells, in notebook order
+    pub cell_ids: Vec<String>,
+}
+
+/// Dependency graph between notebook cells, in notebook order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CellDependencyGraph {
+    pub cells: Vec<CellDependencies>,
+    /// For each cell, the cells defining a global it reads
+    pub depends_on: BTreeMap<String, Vec<String>>,
+    pub multiple_definitions: Vec<MultipleDefinition>,
+    /// Groups of cells that depend on each other, each in notebook order
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl CellDependencies {
+    /// Analyze a cell's source with the symbol, reference, scope and import analyzers
+    pub fn analyze(cell_id: &str, code: &str) -> Result<Self, LspError> {
+        let source = FileSource::from_content(PathBuf::from(format!("{}.jl", cell_id)), code.to_string());
+        let parsed = parser::parse(&source)?;
+        let scopes = scope::analyze(&parsed)?;
+
+        let mut defines = BTreeSet::new();
+        let mut locals: Vec<(String, Range)> = Vec::new();
+        for sym in symbol::analyze(&parsed)? {
+            // A function or module name sits inside the scope it opens
+            let own_scope = matches!(sym.kind, SymbolKind::Function | SymbolKind::Module) as usize;
+            if enclosing_scopes(&scopes, &sym.range).len().saturating_sub(own_scope) == 0 {
+                defines.insert(sym.name);
+            } else {
+                // Parameters and assignments inside a function or module body
+                locals.push((sym.name, sym.range));
+            }
+        }
+        for statement in import::analyze(&parsed)? {
+            if statement.symbols.is_empty() || statement.is_qualified_only {
+                if let Some(name) = statement.module.rsplit('.').next().filter(|n| !n.is_empty()) {
+                    defines.insert(name.to_string());
+                }
+            }
+            defines.extend(statement.symbols.iter().cloned());
+        }
+
+        // Methods in both the `function` and the short `f(x) = ...` form
+        let mut methods: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut method_parameters: Vec<(Range, Vec<String>)> = Vec::new();
+        for sig in signature::analyze(&parsed)?.into_iter().filter(|sig| sig.parameters_known) {
+            // A `function` definition is the scope it opens
+            if enclosing_scopes(&scopes, &sig.range).iter().all(|scope| scope.range == sig.range) {
+                defines.insert(sig.name.clone());
+                methods.entry(sig.name.clone()).or_default().insert(method_key(&sig));
+            }
+            // The short form opens no scope, so its parameters are tracked by range
+            let names = sig.parameters.iter().map(|p| p.name.trim_end_matches("...").to_string()).collect();
+            method_parameters.push((sig.range, names));
+        }
+
+        let mut reads = BTreeSet::new();
+        for reference in reference::analyze(&parsed)? {
+            if defines.contains(&reference.name) {
+                continue;
+            }
+            let shadowed = enclosing_scopes(&scopes, &reference.range).iter().any(|scope| {
+                locals
+                    .iter()
+                    .any(|(name, range)| name == &reference.name && range_contains(&scope.range, range))
+            });
+            let is_parameter = method_parameters
+                .iter()
+                .any(|(range, names)| names.contains(&reference.name) && range_contains(range, &reference.range));
+            if !shadowed && !is_parameter {
+                reads.insert(reference.name);
+            }
+        }
+
+        Ok(Self {
+            cell_id: cell_id.to_string(),
+            defines,
+            reads,
+            methods,
+        })
+    }
+}
+
+impl CellDependencyGraph {
+    /// Analyze `(cell_id, code)` pairs given in notebook order
+    pub fn analyze(cells: &[(String, String)]) -> Result<Self, LspError> {
+        let cells = cells
+            .iter()
+            .map(|(cell_id, code)| CellDependencies::analyze(cell_id, code))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_cells(cells))
+    }
+
+    /// Build the graph from already analyzed cells
+    pub fn from_cells(cells: Vec<CellDependencies>) -> Self {
+        let mut definers: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for cell in &cells {
+            for name in &cell.defines {
+                definers.entry(name.as_str()).or_default().push(cell.cell_id.as_str());
+            }
+        }
+
+        let mut multiple_definitions = Vec::new();
+        for (name, ids) in definers.iter().filter(|(_, ids)| ids.len() > 1) {
+            let cell_ids: Vec<String> = match method_definers(&cells, name) {
+                // Methods with different signatures in different cells extend one function
+                Some(by_signature) => {
+                    let mut colliding: Vec<&str> = Vec::new();
+                    for id in by_signature.values().filter(|ids| ids.len() > 1).flatten() {
+                        if !colliding.contains(id) {
+                            colliding.push(id);
+                        }
+                    }
+                    ids.iter().filter(|id| colliding.contains(id)).map(|id| id.to_string()).collect()
+                }
+                None => ids.iter().map(|id| id.to_string()).collect(),
+            };
+            if !cell_ids.is_empty() {
+                multiple_definitions.push(MultipleDefinition { name: name.to_string(), cell_ids });
+            }
+        }
+
+        let position: HashMap<&str, usize> =
+            cells.iter().enumerate().map(|(i, c)| (c.cell_id.as_str(), i)).collect();
+        let mut depends_on = BTreeMap::new();
+        for cell in &cells {
+            let mut upstream: Vec<String> = Vec::new();
+            for name in &cell.reads {
+                for id in definers.get(name.as_str()).into_iter().flatten() {
+                    if *id != cell.cell_id && !upstream.iter().any(|u| u == id) {
+                        upstream.push(id.to_string());
+                    }
+                }
+            }
+            upstream.sort_by_key(|id| position[id.as_str()]);
+            depends_on.insert(cell.cell_id.clone(), upstream);
+        }
+
+        let mut graph = Self {
+            cells,
+            depends_on,
+            multiple_definitions,
+            cycles: Vec::new(),
+        };
+        graph.cycles = graph.find_cycles();
+        graph
+    }
+
+    /// Cells reading a global defined by `cell_id`
+    pub fn dependents(&self, cell_id: &str) -> Vec<&str> {
+        self.cells
+            .iter()
+            .filter(|cell| self.depends_on.get(&cell.cell_id).is_some_and(|up| up.iter().any(|id| id == cell_id)))
+            .map(|cell| cell.cell_id.as_str())
+            .collect()
+    }
+
+    /// The changed cells and every cell downstream of them, in topological order
+    ///
+    /// Cells with no ordering constraint between them keep notebook order. Fails
+    /// when an affected cell is part of a cycle or defines a global that another
+    /// cell also defines, since no execution order is correct in either case.
+    pub fn execution_order(&self, changed: &[String]) -> Result<Vec<String>, String> {
+        for id in changed {
+            if !self.depends_on.contains_key(id) {
+                return Err(format!("Unknown cell: {}", id));
+            }
+        }
+
+        let mut affected: HashSet<&str> = HashSet::new();
+        let mut stack: Vec<&str> = changed.iter().map(String::as_str).collect();
+        while let Some(id) = stack.pop() {
+            if affected.insert(id) {
+                stack.extend(self.dependents(id));
+            }
+        }
+
+        if let Some(cycle) = self.cycles.iter().find(|cycle| cycle.iter().any(|id| affected.contains(id.as_str()))) {
+            return Err(format!("Cells form a dependency cycle: {}", cycle.join(" -> ")));
+        }
+        if let Some(multiple) = self
+            .multiple_definitions
+            .iter()
+            .find(|m| m.cell_ids.iter().any(|id| affected.contains(id.as_str())))
+        {
+            return Err(format!(
+                "`{}` is defined in multiple cells: {}",
+                multiple.name,
+                multiple.cell_ids.join(", ")
+            ));
+        }
+
+        // Kahn's algorithm over the affected cells, always taking the earliest ready cell
+        let mut remaining: Vec<&str> = self
+            .cells
+            .iter()
+            .map(|cell| cell.cell_id.as_str())
+            .filter(|id| affected.contains(id))
+            .collect();
+        let mut order = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let ready = remaining.iter().position(|id| {
+                self.depends_on[*id]
+                    .iter()
+                    .all(|up| !remaining.contains(&up.as_str()))
+            });
+            let Some(index) = ready else {
+                return Err("Cells form a dependency cycle".to_string());
+            };
+            order.push(remaining.remove(index).to_string());
+        }
+        Ok(order)
+    }
+
+    /// Strongly connected components with more than one cell (Tarjan's algorithm)
+    fn find_cycles(&self) -> Vec<Vec<String>> {
+        struct Tarjan<'a> {
+            graph: &'a CellDependencyGraph,
+            index: HashMap<&'a str, usize>,
+            low: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: HashSet<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        impl<'a> Tarjan<'a> {
+            fn visit(&mut self, id: &'a str) {
+                let next = self.index.len();
+                self.index.insert(id, next);
+                self.low.insert(id, next);
+                self.stack.push(id);
+                self.on_stack.insert(id);
+
+                for up in &self.graph.depends_on[id] {
+                    let up = up.as_str();
+                    if !self.index.contains_key(up) {
+                        self.visit(up);
+                        let low = self.low[id].min(self.low[up]);
+                        self.low.insert(id, low);
+                    } else if self.on_stack.contains(up) {
+                        let low = self.low[id].min(self.index[up]);
+                        self.low.insert(id, low);
+                    }
+                }
+
+                if self.low[id] == self.index[id] {
+                    let mut component = Vec::new();
+                    while let Some(member) = self.stack.pop() {
+                        self.on_stack.remove(member);
+                        component.push(member);
+                        if member == id {
+                            break;
+                        }
+                    }
+                    self.components.push(component);
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: HashMap::new(),
+            low: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for cell in &self.cells {
+            if !tarjan.index.contains_key(cell.cell_id.as_str()) {
+                tarjan.visit(&cell.cell_id);
+            }
+        }
+
+        let position: HashMap<&str, usize> =
+            self.cells.iter().enumerate().map(|(i, c)| (c.cell_id.as_str(), i)).collect();
+        let mut cycles: Vec<Vec<String>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|mut component| {
+                component.sort_by_key(|id| position[id]);
+                component.into_iter().map(str::to_string).collect()
+            })
+            .collect();
+        cycles.sort_by_key(|cycle: &Vec<String>| position[cycle[0].as_str()]);
+        cycles
+    }
+}
+
+/// Identifies a method within its function: the positional parameter types, since
+/// parameter names and keyword arguments don't distinguish methods
+fn method_key(sig: &FunctionSignature) -> String {
+    let positional: Vec<String> = sig
+        .parameters
+        .iter()
+        .filter(|p| !p.kind.is_keyword())
+        .map(|p| {
+            let param_type = p.param_type.as_ref().map_or_else(|| "Any".to_string(), |t| t.to_string());
+            match p.kind {
+                ParameterKind::Varargs => format!("{}...", param_type),
+                _ => param_type,
+            }
+        })
+        .collect();
+    format!("({})", positional.join(", "))
+}
+
+/// Cells defining methods of `name` by method signature, or None when a defining cell
+/// assigns `name` other than through a method definition
+fn method_definers<'a>(cells: &'a [CellDependencies], name: &str) -> Option<BTreeMap<&'a str, Vec<&'a str>>> {
+    let mut by_signature: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for cell in cells.iter().filter(|cell| cell.defines.contains(name)) {
+        for key in cell.methods.get(name)? {
+            by_signature.entry(key.as_str()).or_default().push(cell.cell_id.as_str());
+        }
+    }
+    Some(by_signature)
+}
+
+/// Function and module scopes (excluding the file scope) that contain a range
+fn enclosing_scopes<'a>(tree: &'a ScopeTree, range: &Range) -> Vec<&'a ScopeNode> {
+    let mut scopes = Vec::new();
+    let mut children = &tree.root.children;
+    while let Some(scope) = children.iter().find(|scope| range_contains(&scope.range, range)) {
+        scopes.push(scope);
+        children = &scope.children;
+    }
+    scopes
+}
+
+fn range_contains(outer: &Range, inner: &Range) -> bool {
+    let start = (outer.start.line, outer.start.character) <= (inner.start.line, inner.start.character);
+    let end = (inner.end.line, inner.end.character) <= (outer.end.line, outer.end.character);
+    start && end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(id: &str, defines: &[&str], reads: &[&str]) -> CellDependencies {
+        CellDependencies {
+            cell_id: id.to_string(),
+            defines: defines.iter().map(|s| s.to_string()).collect(),
+            reads: reads.iter().map(|s| s.to_string()).collect(),
+            methods: BTreeMap::new(),
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_analyze_cell_globals() {
+        let code = "y = x + 1\nfunction f(a)\n    b = a * scale\n    return b\nend";
+        let deps = CellDependencies::analyze("c1", code).unwrap();
+        assert!(deps.defines.contains("y"));
+        assert!(deps.defines.contains("f"));
+        assert!(!deps.defines.contains("b"));
+        assert!(deps.reads.contains("x"));
+        assert!(deps.reads.contains("scale"));
+        assert!(!deps.reads.contains("a"));
+    }
+
+    #[test]
+    fn test_execution_order_follows_dependencies() {
+        // c3 reads `b` from c4, which comes later in the notebook
+        let graph = CellDependencyGraph::from_cells(vec![
+            cell("c1", &["a"], &[]),
+            cell("c2", &["unrelated"], &[]),
+            cell("c3", &["c"], &["a", "b"]),
+            cell("c4", &["b"], &["a"]),
+        ]);
+        assert_eq!(graph.depends_on["c3"], ids(&["c1", "c4"]));
+        assert_eq!(graph.execution_order(&ids(&["c1"])).unwrap(), ids(&["c1", "c4", "c3"]));
+        assert_eq!(graph.execution_order(&ids(&["c4"])).unwrap(), ids(&["c4", "c3"]));
+        assert_eq!(graph.execution_order(&ids(&["c2"])).unwrap(), ids(&["c2"]));
+    }
+
+    #[test]
+    fn test_multiple_definitions() {
+        let graph = CellDependencyGraph::from_cells(vec![
+            cell("c1", &["x"], &[]),
+            cell("c2", &["x"], &[]),
+            cell("c3", &["y"], &[]),
+        ]);
+        assert_eq!(graph.multiple_definitions.len(), 1);
+        assert_eq!(graph.multiple_definitions[0].name, "x");
+        assert_eq!(graph.multiple_definitions[0].cell_ids, ids(&["c1", "c2"]));
+        assert!(graph.execution_order(&ids(&["c1"])).is_err());
+        assert!(graph.execution_order(&ids(&["c3"])).is_ok());
+    }
+
+    #[test]
+    fn test_methods_across_cells() {
+        let cells = [
+            ("c1", "area(r::Float64) = pi * r^2"),
+            ("c2", "function area(w::Float64, h::Float64)\n    w * h\nend"),
+            ("c3", "total = area(1.0) + area(2.0, 3.0)"),
+        ];
+        let cells: Vec<(String, String)> = cells.iter().map(|(id, code)| (id.to_string(), code.to_string())).collect();
+        let graph = CellDependencyGraph::analyze(&cells).unwrap();
+        assert!(graph.multiple_definitions.is_empty(), "{:?}", graph.multiple_definitions);
+        assert!(graph.cells[0].defines.contains("area"));
+        assert!(!graph.cells[0].reads.contains("r"));
+        assert_eq!(graph.depends_on["c3"], ids(&["c1", "c2"]));
+        assert_eq!(graph.execution_order(&ids(&["c1"])).unwrap(), ids(&["c1", "c3"]));
+
+        // Redefining a method with the same parameter types replaces it
+        let mut cells = cells;
+        cells.push(("c4".to_string(), "area(radius::Float64; digits = 2) = round(pi * radius^2; digits)".to_string()));
+        let graph = CellDependencyGraph::analyze(&cells).unwrap();
+        assert_eq!(graph.multiple_definitions.len(), 1);
+        assert_eq!(graph.multiple_definitions[0].cell_ids, ids(&["c1", "c4"]));
+        assert!(graph.execution_order(&ids(&["c2"])).is_ok());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = CellDependencyGraph::from_cells(vec![
+            cell("c1", &["a"], &["b"]),
+            cell("c2", &["b"], &["a"]),
+            cell("c3", &["c"], &[]),
+        ]);
+        assert_eq!(graph.cycles, vec![ids(&["c1", "c2"])]);
+        assert!(graph.execution_order(&ids(&["c1"])).unwrap_err().contains("cycle"));
+        assert_eq!(graph.execution_order(&ids(&["c3"])).unwrap(), ids(&["c3"]));
+    }
+
+    #[test]
+    fn test_unknown_cell() {
+        let graph = CellDependencyGraph::from_cells(vec![cell("c1", &["a"], &[])]);
+        assert!(graph.execution_order(&ids(&["missing"])).is_err());
+    }
+}
@@ -0,0 +1,545 @@
+use crate::pipeline::sources::ProjectContext;
+use crate::pipeline::storage::Index;
+use crate::types::{
+    builtin_supertype, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, FunctionSignature, Location,
+    ParameterKind, Position, Range, TypeDefinitionKind, TypeExpr,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Base types a package may dispatch on without owning them
+const BASE_TYPES: &[&str] = &[
+    "Any", "Nothing", "Missing", "Bool", "Char", "String", "Symbol", "Number", "Real", "Integer", "Signed",
+    "Unsigned", "AbstractFloat", "Int", "UInt", "Int8", "Int16", "Int32", "Int64", "Int128", "UInt8", "UInt16",
+    "UInt32", "UInt64", "UInt128", "Float16", "Float32", "Float64", "BigInt", "BigFloat", "Complex", "Rational",
+    "AbstractString", "AbstractChar", "AbstractArray", "AbstractVector", "AbstractMatrix", "AbstractVecOrMat",
+    "AbstractRange", "AbstractDict", "AbstractSet", "Array", "Vector", "Matrix", "Dict", "Set", "Tuple",
+    "NamedTuple", "Pair", "Function", "Type", "DataType", "IO", "IOBuffer", "Ref", "Union", "UnitRange",
+];
+
+/// A method as reported to the user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodRef {
+    /// Module owning the function (e.g. "Base" for a `Base.show` method)
+    pub function_module: String,
+    pub name: String,
+    /// Rendered signature, e.g. `show(io::IO, x::Int)`
+    pub signature: String,
+    pub file_uri: String,
+    pub range: Range,
+}
+
+/// A workspace method added to another module's function where no argument type is owned by the workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypePiracy {
+    pub method: MethodRef,
+}
+
+/// A workspace method that replaces a dependency's method with the same signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverwrittenMethod {
+    pub method: MethodRef,
+    pub overwritten: MethodRef,
+}
+
+/// Two workspace methods where neither is more specific and no method covers their intersection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodAmbiguity {
+    pub first: MethodRef,
+    pub second: MethodRef,
+}
+
+/// Method-level problems in the workspace package
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageHealthReport {
+    pub package_name: Option<String>,
+    pub type_piracy: Vec<TypePiracy>,
+    pub overwritten_methods: Vec<OverwrittenMethod>,
+    pub ambiguities: Vec<MethodAmbiguity>,
+}
+
+impl PackageHealthReport {
+    pub fn issue_count(&self) -> usize {
+        self.type_piracy.len() + self.overwritten_methods.len() + self.ambiguities.len()
+    }
+}
+
+/// Package-level analysis of the workspace's methods (type piracy, overwritten methods, ambiguities)
+pub struct PackageHealthProvider;
+
+impl PackageHealthProvider {
+    /// Analyze the methods defined by workspace files against the rest of the index
+    pub fn analyze(context: &ProjectContext, index: &Index) -> PackageHealthReport {
+        let ownership = Ownership::collect(context, index);
+        let mut report = PackageHealthReport {
+            package_name: context.project_name().map(|s| s.to_string()),
+            ..Default::default()
+        };
+
+        // Workspace methods grouped by the function they belong to; BTreeMap keeps the report stable
+        let mut functions: BTreeMap<(String, String), Vec<FunctionSignature>> = BTreeMap::new();
+        for module in index.get_all_modules() {
+            for name in index.get_module_functions(&module) {
+                for sig in index.find_signatures(&module, &name) {
+                    if sig.parameters_known && ownership.is_workspace_file(&sig.file_uri) {
+                        let function_module = function_module(&sig, index);
+                        functions.entry((function_module, sig.name.clone())).or_default().push(sig);
+                    }
+                }
+            }
+        }
+
+        for ((function_module, name), methods) in &functions {
+            if !ownership.owns_module(function_module) {
+                for sig in methods {
+                    if ownership.is_pirated(sig) {
+                        report.type_piracy.push(TypePiracy { method: method_ref(function_module, sig) });
+                    }
+                    let overwritten = index
+                        .find_signatures(function_module, name)
+                        .into_iter()
+                        .find(|dep| {
+                            dep.parameters_known
+                                && !ownership.is_workspace_file(&dep.file_uri)
+                                && same_dispatch_signature(sig, dep)
+                        });
+                    if let Some(dep) = overwritten {
+                        report.overwritten_methods.push(OverwrittenMethod {
+                            method: method_ref(function_module, sig),
+                            overwritten: method_ref(function_module, &dep),
+                        });
+                    }
+                }
+            }
+
+            for (i, first) in methods.iter().enumerate() {
+                for second in &methods[i + 1..] {
+                    if ownership.is_ambiguous(first, second, methods) {
+                        report.ambiguities.push(MethodAmbiguity {
+                            first: method_ref(function_module, first),
+                            second: method_ref(function_module, second),
+                        });
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Diagnostics for one workspace file
+    pub fn to_diagnostics(report: &PackageHealthReport, file_uri: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for piracy in report.type_piracy.iter().filter(|p| p.method.file_uri == file_uri) {
+            let method = &piracy.method;
+            diagnostics.push(make_diagnostic(
+                method,
+                "type_piracy",
+                format!(
+                    "Type piracy: `{}` adds a method to `{}.{}` without any argument type owned by this package",
+                    method.signature, method.function_module, method.name
+                ),
+                Vec::new(),
+            ));
+        }
+
+        for overwrite in report.overwritten_methods.iter().filter(|o| o.method.file_uri == file_uri) {
+            let method = &overwrite.method;
+            diagnostics.push(make_diagnostic(
+                method,
+                "overwritten_method",
+                format!(
+                    "`{}` overwrites the method with the same signature in `{}`",
+                    method.signature, method.function_module
+                ),
+                vec![related(&overwrite.overwritten, "Overwritten method")],
+            ));
+        }
+
+        for ambiguity in &report.ambiguities {
+            for (method, other) in [(&ambiguity.first, &ambiguity.second), (&ambiguity.second, &ambiguity.first)] {
+                if method.file_uri == file_uri {
+                    diagnostics.push(make_diagnostic(
+                        method,
+                        "method_ambiguity",
+                        format!(
+                            "`{}` is ambiguous with `{}`: calls matching both have no most specific method",
+                            method.signature, other.signature
+                        ),
+                        vec![related(other, "Ambiguous method")],
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// What the workspace package owns: its files, modules and types
+struct Ownership {
+    root: PathBuf,
+    modules: HashSet<String>,
+    /// Workspace types by name (for piracy) and whether they are concrete structs (for ambiguity)
+    types: HashMap<String, bool>,
+    /// Type names defined outside the workspace
+    foreign_types: HashSet<String>,
+}
+
+impl Ownership {
+    fn collect(context: &ProjectContext, index: &Index) -> Self {
+        let mut ownership = Ownership {
+            root: context.root_path.clone(),
+            modules: context.project_name().map(|n| n.to_string()).into_iter().collect(),
+            types: HashMap::new(),
+            foreign_types: BASE_TYPES.iter().map(|t| t.to_string()).collect(),
+        };
+
+        for (path, file_module) in index.get_file_modules() {
+            if path.starts_with(&ownership.root) {
+                ownership.modules.insert(file_module.context.clone());
+            }
+        }
+        for module in index.get_all_type_modules() {
+            for name in index.get_module_types(&module) {
+                let Some(type_def) = index.find_type(&module, &name) else { continue };
+                if ownership.is_workspace_file(&type_def.file_uri) {
+                    ownership.types.insert(name, type_def.kind == TypeDefinitionKind::Struct);
+                } else {
+                    ownership.foreign_types.insert(name);
+                }
+            }
+        }
+        ownership
+    }
+
+    fn is_workspace_file(&self, file_uri: &str) -> bool {
+        Path::new(file_uri).starts_with(&self.root)
+    }
+
+    fn owns_module(&self, module: &str) -> bool {
+        self.modules.iter().any(|m| module == m || module.starts_with(&format!("{}.", m)))
+    }
+
+    /// Piracy needs every positional argument type to be known and foreign;
+    /// unknown names (e.g. `where` type variables) are given the benefit of the doubt
+    fn is_pirated(&self, sig: &FunctionSignature) -> bool {
+        sig.parameters
+            .iter()
+            .filter(|p| !p.kind.is_keyword())
+            .flat_map(|p| p.param_type.as_ref().map(type_names).unwrap_or_default())
+            .all(|name| !self.types.contains_key(&name) && self.foreign_types.contains(&name))
+    }
+
+    fn is_ambiguous(&self, a: &FunctionSignature, b: &FunctionSignature, methods: &[FunctionSignature]) -> bool {
+        let (Some(a_types), Some(b_types)) = (fixed_positional_types(a), fixed_positional_types(b)) else {
+            return false;
+        };
+        if a_types.len() != b_types.len() {
+            return false;
+        }
+
+        // The intersection of both methods, position by position
+        let mut intersection = Vec::new();
+        let (mut a_more_specific, mut b_more_specific) = (false, false);
+        for (a_type, b_type) in a_types.iter().zip(&b_types) {
+            match self.relation(a_type, b_type) {
+                Relation::Equal => intersection.push(a_type.clone()),
+                Relation::Sub => {
+                    a_more_specific = true;
+                    intersection.push(a_type.clone());
+                }
+                Relation::Super => {
+                    b_more_specific = true;
+                    intersection.push(b_type.clone());
+                }
+                Relation::Disjoint | Relation::Unknown => return false,
+            }
+        }
+        if !(a_more_specific && b_more_specific) {
+            return false;
+        }
+        // A method for exactly the intersection resolves the ambiguity
+        !methods
+            .iter()
+            .any(|m| fixed_positional_types(m).is_some_and(|types| types == intersection))
+    }
+
+    fn relation(&self, a: &TypeExpr, b: &TypeExpr) -> Relation {
+        if a == b {
+            return Relation::Equal;
+        }
+        match (a, b) {
+            (_, TypeExpr::Any) => Relation::Sub,
+            (TypeExpr::Any, _) => Relation::Super,
+            (TypeExpr::Concrete(a), TypeExpr::Concrete(b)) => {
+                if supertypes(a).contains(&b.as_str()) {
+                    Relation::Sub
+                } else if supertypes(b).contains(&a.as_str()) {
+                    Relation::Super
+                } else if self.is_known_leaf_or_builtin(a) && self.is_known_leaf_or_builtin(b) {
+                    Relation::Disjoint
+                } else {
+                    Relation::Unknown
+                }
+            }
+            _ => Relation::Unknown,
+        }
+    }
+
+    /// Builtin types have a known place in the hierarchy; workspace structs are
+    /// concrete leaves, so they can't be subtypes of another type we know of
+    fn is_known_leaf_or_builtin(&self, name: &str) -> bool {
+        builtin_supertype(name).is_some() || self.types.get(name).copied().unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Relation {
+    Equal,
+    Sub,
+    Super,
+    Disjoint,
+    Unknown,
+}
+
+/// Module owning the function a method belongs to: the qualifying module of
+/// `Base.show(...) = ...`, the module of `import Base: show`, or the defining module
+fn function_module(sig: &FunctionSignature, index: &Index) -> String {
+    if let Some(ref module) = sig.extends_module {
+        return module.clone();
+    }
+    for import in index.get_file_imports(&PathBuf::from(&sig.file_uri)) {
+        if !import.is_qualified_only {
+            continue;
+        }
+        if import.symbols.contains(&sig.name) {
+            return import.module;
+        }
+        // import Base.show
+        if let Some(module) = import.module.strip_suffix(&format!(".{}", sig.name)) {
+            return module.to_string();
+        }
+    }
+    sig.module.clone()
+}
+
+/// Positional argument types, or None for methods with optional or varargs parameters
+fn fixed_positional_types(sig: &FunctionSignature) -> Option<Vec<TypeExpr>> {
+    sig.parameters
+        .iter()
+        .filter(|p| !p.kind.is_keyword())
+        .map(|p| match p.kind {
+            ParameterKind::Required => Some(normalize(p.param_type.clone().unwrap_or(TypeExpr::Any))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Keyword arguments don't take part in dispatch
+fn same_dispatch_signature(a: &FunctionSignature, b: &FunctionSignature) -> bool {
+    let positional = |sig: &FunctionSignature| -> Vec<(ParameterKind, TypeExpr)> {
+        sig.parameters
+            .iter()
+            .filter(|p| !p.kind.is_keyword())
+            .map(|p| (p.kind, normalize(p.param_type.clone().unwrap_or(TypeExpr::Any))))
+            .collect()
+    };
+    positional(a) == positional(b)
+}
+
+fn normalize(type_expr: TypeExpr) -> TypeExpr {
+    match type_expr {
+        TypeExpr::Concrete(name) if name == "Any" => TypeExpr::Any,
+        TypeExpr::Concrete(name) if name == "Int" => TypeExpr::Concrete("Int64".to_string()),
+        other => other,
+    }
+}
+
+/// Type names mentioned in a type expression, without module qualification
+fn type_names(type_expr: &TypeExpr) -> Vec<String> {
+    match type_expr {
+        TypeExpr::Concrete(name) => {
+            let name = name.trim_start_matches("<:").trim();
+            if name.is_empty() || name.chars().all(|c| c.is_ascii_digit()) {
+                Vec::new()
+            } else {
+                vec![name.rsplit('.').next().unwrap_or(name).to_string()]
+            }
+        }
+        TypeExpr::Generic(name, params) => {
+            let mut names = type_names(&TypeExpr::Concrete(name.clone()));
+            names.extend(params.iter().flat_map(type_names));
+            names
+        }
+        TypeExpr::Union(members) => members.iter().flat_map(type_names).collect(),
+        TypeExpr::Any => Vec::new(),
+        TypeExpr::Unknown => vec!["?".to_string()],
+    }
+}
+
+fn supertypes(name: &str) -> Vec<&'static str> {
+    let mut chain = Vec::new();
+    let mut current = builtin_supertype(name);
+    while let Some(parent) = current {
+        chain.push(parent);
+        current = builtin_supertype(parent);
+    }
+    chain
+}
+
+fn method_ref(function_module: &str, sig: &FunctionSignature) -> MethodRef {
+    MethodRef {
+        function_module: function_module.to_string(),
+        name: sig.name.clone(),
+        signature: sig.label(),
+        file_uri: sig.file_uri.clone(),
+        range: sig.range.clone(),
+    }
+}
+
+fn related(method: &MethodRef, label: &str) -> DiagnosticRelatedInformation {
+    DiagnosticRelatedInformation {
+        location: Location { uri: method.file_uri.clone(), range: method.range.clone() },
+        message: format!("{}: {}", label, method.signature),
+    }
+}
+
+fn make_diagnostic(
+    method: &MethodRef,
+    code: &str,
+    message: String,
+    related_information: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    // Long-form definitions span their whole body; mark only the `function` keyword
+    let start = method.range.start;
+    let end = if method.range.end.line == start.line {
+        method.range.end
+    } else {
+        Position { line: start.line, character: start.character + "function".len() as u32 }
+    };
+    Diagnostic {
+        range: Range { start, end },
+        severity: Some(DiagnosticSeverity::Warning),
+        code: Some(code.to_string()),
+        source: Some("package_health".to_string()),
+        message,
+        related_information: if related_information.is_empty() { None } else { Some(related_information) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Parameter, TypeDefinition};
+
+    fn method(module: &str, name: &str, file: &str, params: &[Option<&str>]) -> FunctionSignature {
+        FunctionSignature {
+            module: module.to_string(),
+            name: name.to_string(),
+            parameters: params
+                .iter()
+                .enumerate()
+                .map(|(i, t)| Parameter {
+                    name: format!("x{}", i),
+                    param_type: t.map(TypeExpr::parse),
+                    kind: ParameterKind::Required,
+                })
+                .collect(),
+            return_type: None,
+            doc_comment: None,
+            file_uri: file.to_string(),
+            range: Range {
+                start: Position { line: 0, character: 0 },
+                end: Position { line: 0, character: 10 },
+            },
+            parameters_known: true,
+            extends_module: None,
+        }
+    }
+
+    fn ownership(types: &[(&str, bool)]) -> Ownership {
+        Ownership {
+            root: PathBuf::from("/ws"),
+            modules: ["MyPkg".to_string()].into_iter().collect(),
+            types: types.iter().map(|(n, s)| (n.to_string(), *s)).collect(),
+            foreign_types: BASE_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_piracy_requires_only_foreign_types() {
+        let ownership = ownership(&[("Point", true)]);
+        assert!(ownership.is_pirated(&method("MyPkg", "+", "/ws/src/a.jl", &[Some("Int"), Some("String")])));
+        assert!(ownership.is_pirated(&method("MyPkg", "length", "/ws/src/a.jl", &[None])));
+        assert!(!ownership.is_pirated(&method("MyPkg", "show", "/ws/src/a.jl", &[Some("IO"), Some("Point")])));
+        assert!(!ownership.is_pirated(&method("MyPkg", "f", "/ws/src/a.jl", &[Some("Vector{Point}")])));
+        // `where` type variables aren't resolvable, so they are not reported
+        assert!(!ownership.is_pirated(&method("MyPkg", "g", "/ws/src/a.jl", &[Some("T")])));
+    }
+
+    #[test]
+    fn test_ambiguity_detection() {
+        let ownership = ownership(&[("Point", true)]);
+        let a = method("MyPkg", "f", "/ws/src/a.jl", &[Some("Int"), None]);
+        let b = method("MyPkg", "f", "/ws/src/a.jl", &[None, Some("Int")]);
+        assert!(ownership.is_ambiguous(&a, &b, &[a.clone(), b.clone()]));
+
+        // f(::Int, ::Int) covers the intersection
+        let both = method("MyPkg", "f", "/ws/src/a.jl", &[Some("Int"), Some("Int")]);
+        assert!(!ownership.is_ambiguous(&a, &b, &[a.clone(), b.clone(), both]));
+
+        // One method is strictly more specific
+        let c = method("MyPkg", "f", "/ws/src/a.jl", &[Some("Real"), None]);
+        assert!(!ownership.is_ambiguous(&a, &c, &[a.clone(), c.clone()]));
+
+        // Disjoint argument types never overlap
+        let d = method("MyPkg", "f", "/ws/src/a.jl", &[Some("String"), Some("Point")]);
+        assert!(!ownership.is_ambiguous(&b, &d, &[b.clone(), d.clone()]));
+    }
+
+    #[test]
+    fn test_same_dispatch_signature_ignores_names() {
+        let a = method("Dep", "f", "/depot/Dep/src/Dep.jl", &[Some("Int"), None]);
+        let b = method("MyPkg", "f", "/ws/src/a.jl", &[Some("Int64"), Some("Any")]);
+        let c = method("MyPkg", "f", "/ws/src/a.jl", &[Some("Int"), Some("Float64")]);
+        assert!(same_dispatch_signature(&a, &b));
+        assert!(!same_dispatch_signature(&a, &c));
+    }
+
+    #[test]
+    fn test_report_diagnostics_per_file() {
+        let type_def = TypeDefinition {
+            module: "MyPkg".to_string(),
+            name: "Point".to_string(),
+            kind: TypeDefinitionKind::Struct,
+            doc_comment: None,
+            file_uri: "/ws/src/a.jl".to_string(),
+            range: Range {
+                start: Position { line: 0, character: 0 },
+                end: Position { line: 0, character: 0 },
+            },
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            inner_constructors: Vec::new(),
+        };
+        let first = method_ref("Base", &method("MyPkg", "f", "/ws/src/a.jl", &[Some("Int"), None]));
+        let second = method_ref("Base", &method("MyPkg", "f", "/ws/src/b.jl", &[None, Some("Int")]));
+        let report = PackageHealthReport {
+            package_name: Some(type_def.module),
+            type_piracy: vec![TypePiracy { method: first.clone() }],
+            overwritten_methods: Vec::new(),
+            ambiguities: vec![MethodAmbiguity { first, second }],
+        };
+        assert_eq!(report.issue_count(), 2);
+
+        let a = PackageHealthProvider::to_diagnostics(&report, "/ws/src/a.jl");
+        assert_eq!(a.len(), 2);
+        let b = PackageHealthProvider::to_diagnostics(&report, "/ws/src/b.jl");
+        assert_eq!(b.len(), 1);
+        assert_eq!(b[0].code.as_deref(), Some("method_ambiguity"));
+        assert_eq!(b[0].related_information.as_ref().map(|r| r[0].location.uri.as_str()), Some("/ws/src/a.jl"));
+    }
+}
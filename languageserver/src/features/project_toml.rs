@@ -0,0 +1,430 @@
+use super::dependency_health::{find_key_range, is_julia_stdlib, section_range, table_header, JULIA_STDLIBS};
+use crate::pipeline::sources::project_context::ProjectToml;
+use crate::pipeline::sources::{LocalRegistry, ProjectContext, VersionNumber, VersionSpec};
+use crate::types::{
+    CompletionItem, CompletionItemKind, CompletionList, Diagnostic, DiagnosticSeverity, Position, Range,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Tables mapping package names to UUIDs
+const DEPENDENCY_TABLES: &[&str] = &["deps", "weakdeps", "extras"];
+
+/// Registry completions offered at once; more are found by typing a longer prefix
+const MAX_PACKAGE_COMPLETIONS: usize = 100;
+
+/// Editing support for the root Project.toml: UUID and `[compat]` checks, package name
+/// completion from the local registry, and hover with the resolved versions.
+///
+/// Unparseable compat specifiers and compat bounds excluding the manifest version are
+/// reported by `DependencyHealthProvider`.
+pub struct ProjectTomlProvider;
+
+impl ProjectTomlProvider {
+    /// Invalid UUIDs, UUIDs disagreeing with the manifest or registry, and `[compat]`
+    /// entries for packages that are not dependencies
+    pub fn diagnostics(
+        project: &ProjectToml,
+        text: &str,
+        context: &ProjectContext,
+        registry: Option<&LocalRegistry>,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if let Some(ref uuid) = project.uuid {
+            if !is_valid_uuid(uuid) {
+                diagnostics.push(make_diagnostic(
+                    find_key_range(text, "", "uuid").unwrap_or_else(|| section_range(text, "")),
+                    DiagnosticSeverity::Error,
+                    "invalid_uuid",
+                    format!("\"{}\" is not a valid UUID", uuid),
+                ));
+            }
+        }
+
+        for (table, entries) in dependency_tables(project) {
+            for (name, uuid) in entries {
+                let range = find_key_range(text, table, name).unwrap_or_else(|| section_range(text, table));
+                if let Some((severity, code, message)) = check_dependency_uuid(name, uuid, context, registry) {
+                    diagnostics.push(make_diagnostic(range, severity, code, message));
+                }
+            }
+        }
+
+        let declared: Vec<&String> = dependency_tables(project).flat_map(|(_, entries)| entries.into_keys()).collect();
+        let compat: BTreeMap<&String, &String> = project.compat.iter().flatten().collect();
+        for name in compat.keys() {
+            if name.as_str() != "julia" && !declared.contains(name) {
+                diagnostics.push(make_diagnostic(
+                    find_key_range(text, "compat", name).unwrap_or_else(|| section_range(text, "compat")),
+                    DiagnosticSeverity::Warning,
+                    "compat_without_dependency",
+                    format!("Compat entry for {}, which is not in [deps], [weakdeps] or [extras]", name),
+                ));
+            }
+        }
+
+        diagnostics.sort_by_key(|d| (d.range.start.line, d.range.start.character));
+        diagnostics
+    }
+
+    /// Package names in `[deps]`, `[weakdeps]` and `[extras]` (inserted with their UUID),
+    /// and dependency names in `[compat]` (inserted with a bound from the manifest version)
+    pub fn complete(
+        text: &str,
+        position: Position,
+        context: &ProjectContext,
+        registry: Option<&LocalRegistry>,
+    ) -> Option<CompletionList> {
+        let line = text.lines().nth(position.line as usize).unwrap_or("");
+        let before_cursor = line.get(..(position.character as usize).min(line.len()))?;
+        let prefix = before_cursor.trim_start().trim_start_matches('"');
+        if !prefix.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+        let table = table_at_line(text, position.line as usize)?;
+        let project = context.project_toml.as_ref();
+
+        let mut items = Vec::new();
+        let mut is_incomplete = false;
+        if DEPENDENCY_TABLES.contains(&table) {
+            let declared = project.map(|p| table_entries(p, table)).unwrap_or_default();
+            let mut candidates: BTreeMap<&str, (&str, String)> = BTreeMap::new();
+            for package in registry.into_iter().flat_map(|r| r.packages()) {
+                let detail = format!("registered in {}", package.registry);
+                candidates.entry(package.name.as_str()).or_insert((&package.uuid, detail));
+            }
+            // Stdlibs are not in the registry; their UUIDs are in the manifest
+            for name in JULIA_STDLIBS {
+                let manifest_uuid = context.manifest_toml.as_ref().and_then(|m| m.find(name, None)?.uuid.as_deref());
+                if let Some(uuid) = manifest_uuid {
+                    candidates.entry(*name).or_insert((uuid, "standard library".to_string()));
+                }
+            }
+            let mut matching = candidates
+                .into_iter()
+                .filter(|(name, _)| starts_with_ignore_case(name, prefix) && !declared.contains_key(*name));
+            for (name, (uuid, detail)) in matching.by_ref().take(MAX_PACKAGE_COMPLETIONS) {
+                items.push(CompletionItem {
+                    label: name.to_string(),
+                    kind: CompletionItemKind::Module,
+                    detail: Some(format!("{} ({})", uuid, detail)),
+                    documentation: None,
+                    insert_text: Some(format!("{} = \"{}\"", name, uuid)),
+                });
+            }
+            is_incomplete = matching.next().is_some();
+        } else if table == "compat" {
+            let project = project?;
+            let compat = project.compat.as_ref();
+            let julia = "julia".to_string();
+            let mut names: Vec<&String> = dependency_tables(project).flat_map(|(_, entries)| entries.into_keys()).collect();
+            names.sort();
+            names.push(&julia);
+            for name in names {
+                if !starts_with_ignore_case(name, prefix) || compat.is_some_and(|c| c.contains_key(name)) {
+                    continue;
+                }
+                let version = if name == "julia" {
+                    context.julia_version.as_ref().map(|v| v.to_string())
+                } else {
+                    context.manifest_version(name).map(str::to_string)
+                };
+                let bound = version.and_then(|v| VersionNumber::parse(&v).ok()).map(|v| v.major_minor());
+                items.push(CompletionItem {
+                    label: name.to_string(),
+                    kind: CompletionItemKind::Module,
+                    detail: bound.as_ref().map(|b| format!("compatible with {}", b)),
+                    documentation: None,
+                    insert_text: bound.map(|b| format!("{} = \"{}\"", name, b)),
+                });
+            }
+        }
+        Some(CompletionList { is_incomplete, items })
+    }
+
+    /// UUID, manifest version, latest registered version and compat bound of the
+    /// package on the line under the cursor
+    pub fn hover(
+        text: &str,
+        position: Position,
+        context: &ProjectContext,
+        registry: Option<&LocalRegistry>,
+    ) -> Option<String> {
+        let line = text.lines().nth(position.line as usize)?;
+        let (key, _) = line.split_once('=')?;
+        let name = key.trim().trim_matches('"');
+        let table = table_at_line(text, position.line as usize)?;
+        if !DEPENDENCY_TABLES.contains(&table) && table != "compat" {
+            return None;
+        }
+        let project = context.project_toml.as_ref()?;
+
+        if name == "julia" {
+            let mut content = "**julia**\n\n".to_string();
+            if let Some(ref version) = context.julia_version {
+                content.push_str(&format!("Configured Julia: {}\n\n", version));
+            }
+            if let Some(version) = context.manifest_toml.as_ref().and_then(|m| m.julia_version.as_ref()) {
+                content.push_str(&format!("Manifest resolved with Julia {}\n\n", version));
+            }
+            return Some(content);
+        }
+
+        let uuid = DEPENDENCY_TABLES.iter().find_map(|table| table_entries(project, table).get(name).copied());
+        let mut content = format!("**{}**", name);
+        if let Some(uuid) = uuid {
+            content.push_str(&format!(" `{}`", uuid));
+        }
+        content.push_str("\n\n");
+
+        let manifest_entry = context.manifest_toml.as_ref().and_then(|m| m.find(name, uuid.map(String::as_str)));
+        let manifest_version = manifest_entry.and_then(|e| e.version.as_deref());
+        match manifest_entry {
+            Some(entry) => {
+                let origin = match (&entry.path, &entry.repo) {
+                    (Some(path), _) => format!(" (developed at `{}`)", path),
+                    (None, Some(repo)) => format!(" (tracking `{}`)", repo),
+                    (None, None) if entry.is_stdlib() || is_julia_stdlib(name) => " (standard library)".to_string(),
+                    (None, None) => String::new(),
+                };
+                let version = manifest_version.unwrap_or("unversioned");
+                content.push_str(&format!("Manifest.toml: **{}**{}\n\n", version, origin));
+            }
+            None => content.push_str("Not resolved in Manifest.toml\n\n"),
+        }
+
+        if let (Some(registry), Some(uuid)) = (registry, uuid) {
+            if let Some(latest) = registry.latest_version(uuid) {
+                let registry_name = registry.get(uuid).map(|p| p.registry.as_str()).unwrap_or("registry");
+                content.push_str(&format!("Latest in {}: {}\n\n", registry_name, latest));
+            }
+        }
+
+        if let Some(spec) = project.compat.as_ref().and_then(|c| c.get(name)) {
+            let admits = VersionSpec::parse(spec).ok().zip(manifest_version.and_then(|v| VersionNumber::parse(v).ok()));
+            let note = match admits {
+                Some((spec, version)) if spec.contains(&version) => " (admits the manifest version)",
+                Some(_) => " (excludes the manifest version)",
+                None => "",
+            };
+            content.push_str(&format!("Compat: `{}`{}\n\n", spec, note));
+        }
+
+        Some(content)
+    }
+}
+
+/// Severity, code and message for a dependency UUID that is malformed or disagrees
+/// with Manifest.toml or the registry
+fn check_dependency_uuid(
+    name: &str,
+    uuid: &str,
+    context: &ProjectContext,
+    registry: Option<&LocalRegistry>,
+) -> Option<(DiagnosticSeverity, &'static str, String)> {
+    if !is_valid_uuid(uuid) {
+        let message = format!("\"{}\" is not a valid UUID for {}", uuid, name);
+        return Some((DiagnosticSeverity::Error, "invalid_uuid", message));
+    }
+
+    let manifest_uuids: Vec<&str> = context
+        .manifest_toml
+        .as_ref()
+        .and_then(|m| m.packages.get(name))
+        .map(|entries| entries.iter().filter_map(|e| e.uuid.as_deref()).collect())
+        .unwrap_or_default();
+    if !manifest_uuids.is_empty() && !manifest_uuids.iter().any(|u| u.eq_ignore_ascii_case(uuid)) {
+        let message = format!("UUID of {} does not match Manifest.toml ({})", name, manifest_uuids.join(", "));
+        return Some((DiagnosticSeverity::Error, "uuid_mismatch", message));
+    }
+
+    let registry = registry.filter(|_| !is_julia_stdlib(name))?;
+    match registry.get(&uuid.to_lowercase()) {
+        Some(package) if package.name != name => {
+            let message = format!("UUID {} is registered as {}, not {}", uuid, package.name, name);
+            Some((DiagnosticSeverity::Error, "uuid_mismatch", message))
+        }
+        Some(_) => None,
+        None => {
+            // Unregistered packages are fine; a registered name under another UUID is suspicious
+            let registered: Vec<String> = registry.find_by_name(name).iter().map(|p| p.uuid.clone()).collect();
+            (!registered.is_empty()).then(|| {
+                let message = format!("UUID of {} does not match the registry ({})", name, registered.join(", "));
+                (DiagnosticSeverity::Warning, "uuid_mismatch", message)
+            })
+        }
+    }
+}
+
+/// `[deps]`, `[weakdeps]` and `[extras]` with their entries, in a stable order
+fn dependency_tables<'a>(
+    project: &'a ProjectToml,
+) -> impl Iterator<Item = (&'static str, BTreeMap<&'a String, &'a String>)> + 'a {
+    DEPENDENCY_TABLES.iter().map(move |table| {
+        let entries = match *table {
+            "deps" => project.dependencies.as_ref(),
+            "weakdeps" => project.weakdeps.as_ref(),
+            _ => project.extras.as_ref(),
+        };
+        (*table, entries.iter().flat_map(|e| e.iter()).collect())
+    })
+}
+
+fn table_entries<'a>(project: &'a ProjectToml, table: &str) -> HashMap<&'a str, &'a String> {
+    dependency_tables(project)
+        .find(|(name, _)| *name == table)
+        .map(|(_, entries)| entries.into_iter().map(|(k, v)| (k.as_str(), v)).collect())
+        .unwrap_or_default()
+}
+
+/// Name of the table containing a line ("" for top-level keys)
+fn table_at_line(text: &str, line_number: usize) -> Option<&str> {
+    let mut table = "";
+    for (i, line) in text.lines().enumerate() {
+        if i == line_number && table_header(line).is_some() {
+            return None;
+        }
+        if i >= line_number {
+            break;
+        }
+        if let Some(header) = table_header(line) {
+            table = header;
+        }
+    }
+    Some(table)
+}
+
+/// Canonical 8-4-4-4-12 hexadecimal UUID
+fn is_valid_uuid(uuid: &str) -> bool {
+    let groups: Vec<&str> = uuid.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit())
+        })
+}
+
+fn starts_with_ignore_case(name: &str, prefix: &str) -> bool {
+    name.len() >= prefix.len() && name.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+fn make_diagnostic(range: Range, severity: DiagnosticSeverity, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(code.to_string()),
+        source: Some("dependencies".to_string()),
+        message,
+        related_information: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const DATAFRAMES_UUID: &str = "a93c6f00-e57d-5684-b7b6-d8193f3e46c0";
+    const JSON_UUID: &str = "682c06a0-de6a-54ab-a142-c8b1cf79cde6";
+
+    const PROJECT_TOML: &str = r#"name = "TestProject"
+uuid = "12345678-1234-1234-1234-12345678901"
+
+[deps]
+DataFrames = "a93c6f00-e57d-5684-b7b6-d8193f3e46c1"
+JSON = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
+Broken = "not-a-uuid"
+
+[compat]
+CSV = "0.10"
+DataFrames = "1.5"
+julia = "1.9"
+"#;
+
+    fn create_project(project_toml: &str) -> (TempDir, ProjectContext, LocalRegistry) {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("Project.toml"), project_toml).unwrap();
+        fs::write(
+            root.join("Manifest.toml"),
+            format!(
+                "[[deps.DataFrames]]\nuuid = \"{}\"\nversion = \"1.5.0\"\ngit-tree-sha1 = \"a\"\n\n[[deps.JSON]]\nuuid = \"{}\"\nversion = \"0.21.4\"\ngit-tree-sha1 = \"b\"\n",
+                DATAFRAMES_UUID, JSON_UUID
+            ),
+        )
+        .unwrap();
+
+        let registry_dir = root.join("depot").join("registries").join("General");
+        fs::create_dir_all(registry_dir.join("D").join("DataFrames")).unwrap();
+        fs::write(
+            registry_dir.join("Registry.toml"),
+            format!(
+                "[packages]\n{} = {{ name = \"DataFrames\", path = \"D/DataFrames\" }}\n{} = {{ name = \"JSON\", path = \"J/JSON\" }}\n",
+                DATAFRAMES_UUID, JSON_UUID
+            ),
+        )
+        .unwrap();
+        fs::write(
+            registry_dir.join("D").join("DataFrames").join("Versions.toml"),
+            "[\"1.5.0\"]\ngit-tree-sha1 = \"a\"\n\n[\"1.6.1\"]\ngit-tree-sha1 = \"b\"\n",
+        )
+        .unwrap();
+
+        let context = ProjectContext::new(root.to_path_buf()).unwrap();
+        let mut registry = LocalRegistry::load(&root.join("depot")).unwrap();
+        registry.load_versions(&HashSet::from([DATAFRAMES_UUID.to_string()]));
+        (dir, context, registry)
+    }
+
+    #[test]
+    fn test_uuid_and_compat_diagnostics() {
+        let (_dir, context, registry) = create_project(PROJECT_TOML);
+        let project = ProjectToml::parse(PROJECT_TOML).unwrap();
+        let diagnostics = ProjectTomlProvider::diagnostics(&project, PROJECT_TOML, &context, Some(&registry));
+        let found: Vec<(&str, u32)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_deref().unwrap(), d.range.start.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("invalid_uuid", 1),
+                ("uuid_mismatch", 4),
+                ("invalid_uuid", 6),
+                ("compat_without_dependency", 9),
+            ]
+        );
+        assert!(diagnostics[1].message.contains(DATAFRAMES_UUID));
+    }
+
+    #[test]
+    fn test_deps_completion_fills_uuid() {
+        let saved = format!("name = \"TestProject\"\n\n[deps]\nJSON = \"{}\"\n", JSON_UUID);
+        let text = format!("{}data", saved);
+        let (_dir, context, registry) = create_project(&saved);
+        let completions =
+            ProjectTomlProvider::complete(&text, Position { line: 4, character: 4 }, &context, Some(&registry)).unwrap();
+        assert_eq!(completions.items.len(), 1);
+        assert_eq!(
+            completions.items[0].insert_text.as_deref(),
+            Some(format!("DataFrames = \"{}\"", DATAFRAMES_UUID).as_str())
+        );
+
+        // JSON is already declared
+        let completions =
+            ProjectTomlProvider::complete(&text, Position { line: 4, character: 0 }, &context, Some(&registry)).unwrap();
+        assert!(completions.items.iter().all(|item| item.label != "JSON"));
+    }
+
+    #[test]
+    fn test_hover_shows_resolved_versions() {
+        let text = format!("[deps]\nDataFrames = \"{}\"\n\n[compat]\nDataFrames = \"1.5\"\n", DATAFRAMES_UUID);
+        let (_dir, context, registry) = create_project(&text);
+        let hover =
+            ProjectTomlProvider::hover(&text, Position { line: 1, character: 2 }, &context, Some(&registry)).unwrap();
+        assert!(hover.contains("Manifest.toml: **1.5.0**"), "{}", hover);
+        assert!(hover.contains("Latest in General: 1.6.1"), "{}", hover);
+        assert!(hover.contains("Compat: `1.5` (admits the manifest version)"), "{}", hover);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::pipeline::sources::Document;
+use crate::pipeline::storage::Index;
+use crate::types::{
+    FunctionSignature, ParameterInformation, ParameterKind, Position, SignatureHelp, SignatureInformation,
+    TypeDefinition,
+};
+use std::collections::HashSet;
+
+/// Stateless signature help provider - methods and struct constructors from the Index
+pub struct SignatureHelpProvider;
+
+impl SignatureHelpProvider {
+    /// Signatures for the call whose argument list contains `position`.
+    ///
+    /// The call is found in the text rather than the tree: while an argument list is
+    /// being typed it is usually unclosed, and tree-sitter reports it as an error node.
+    pub fn signature_help(index: &Index, document: &Document, position: Position) -> Option<SignatureHelp> {
+        let text = document.text();
+        let offset = byte_offset(&text, position)?;
+        let (callee, arguments) = enclosing_call(&text[..offset])?;
+        let signatures = candidate_signatures(callee, index);
+        if signatures.is_empty() {
+            return None;
+        }
+
+        let argument = ArgumentPosition::parse(arguments);
+        let active = signatures.iter().position(|sig| argument.fits(sig)).unwrap_or(0);
+        let active_parameter = argument.parameter_index(&signatures[active]);
+
+        Some(SignatureHelp {
+            signatures: signatures.iter().map(signature_information).collect(),
+            active_signature: Some(active as u32),
+            active_parameter: active_parameter.map(|i| i as u32),
+        })
+    }
+}
+
+/// Where the cursor is within the arguments typed so far
+#[derive(Debug, Default, PartialEq)]
+struct ArgumentPosition {
+    /// Positional arguments before the cursor
+    positional: usize,
+    /// Keyword argument being typed (`verbose = |`)
+    keyword: Option<String>,
+    /// After `;`, where only keywords are accepted
+    after_semicolon: bool,
+}
+
+impl ArgumentPosition {
+    fn parse(arguments: &str) -> Self {
+        let mut position = ArgumentPosition::default();
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut segment_start = 0;
+        for (i, c) in arguments.char_indices() {
+            match c {
+                '"' => in_string = !in_string,
+                _ if in_string => {}
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' | ';' if depth == 0 => {
+                    if !position.after_semicolon && keyword_name(&arguments[segment_start..i]).is_none() {
+                        position.positional += 1;
+                    }
+                    position.after_semicolon |= c == ';';
+                    segment_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        position.keyword = keyword_name(&arguments[segment_start..]).map(str::to_string);
+        position
+    }
+
+    /// Whether `sig` accepts the arguments typed so far
+    fn fits(&self, sig: &FunctionSignature) -> bool {
+        if let Some(ref keyword) = self.keyword {
+            return sig.parameters.iter().any(|p| {
+                (p.kind.is_keyword() && p.name == *keyword) || p.kind == ParameterKind::KeywordVarargs
+            });
+        }
+        if self.after_semicolon {
+            return sig.parameters.iter().any(|p| p.kind.is_keyword());
+        }
+        let positional = sig.parameters.iter().filter(|p| !p.kind.is_keyword()).count();
+        self.positional < positional || sig.parameters.iter().any(|p| p.kind == ParameterKind::Varargs)
+    }
+
+    /// Index into `sig.parameters` of the parameter being typed
+    fn parameter_index(&self, sig: &FunctionSignature) -> Option<usize> {
+        let params = &sig.parameters;
+        if let Some(ref keyword) = self.keyword {
+            return params.iter().position(|p| p.kind.is_keyword() && p.name == *keyword);
+        }
+        if self.after_semicolon {
+            return params.iter().position(|p| p.kind.is_keyword());
+        }
+        let positional: Vec<usize> = (0..params.len()).filter(|&i| !params[i].kind.is_keyword()).collect();
+        positional.get(self.positional).copied().or_else(|| {
+            // Extra arguments go to a trailing `args...`
+            positional.last().copied().filter(|&i| params[i].kind == ParameterKind::Varargs)
+        })
+    }
+}
+
+/// `name` of an argument written as `name = value`
+fn keyword_name(argument: &str) -> Option<&str> {
+    let (name, value) = argument.split_once('=')?;
+    let name = name.trim();
+    let is_identifier = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '!')
+        && !name.ends_with('!');
+    // `a == b`, `a => b` are expressions, not keywords
+    (is_identifier && !value.starts_with(['=', '>'])).then_some(name)
+}
+
+/// Callee and argument text of the innermost unclosed call before the cursor
+fn enclosing_call(before_cursor: &str) -> Option<(&str, &str)> {
+    let bytes = before_cursor.as_bytes();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut open = None;
+    for i in (0..bytes.len()).rev() {
+        match bytes[i] {
+            b'"' => in_string = !in_string,
+            _ if in_string => {}
+            b')' | b']' | b'}' => depth += 1,
+            b'[' | b'{' if depth > 0 => depth -= 1,
+            b'(' if depth > 0 => depth -= 1,
+            b'(' => {
+                open = Some(i);
+                break;
+            }
+            // Unclosed index or collection: not inside a call's arguments
+            b'[' | b'{' => return None,
+            _ => {}
+        }
+    }
+    let open = open?;
+    // Skip type parameters of `Point{Float64}(`
+    let mut end = open;
+    if before_cursor[..end].ends_with('}') {
+        end = before_cursor[..end].rfind('{')?;
+    }
+    let start = before_cursor[..end]
+        .rfind(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '!')))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let callee = before_cursor[start..end].trim_start_matches('.');
+    let is_name = callee.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_');
+    is_name.then(|| (callee, &before_cursor[open + 1..]))
+}
+
+/// Methods of `callee` and, for a struct, its constructors (inner or default)
+fn candidate_signatures(callee: &str, index: &Index) -> Vec<FunctionSignature> {
+    let types: Vec<TypeDefinition> = match callee.rsplit_once('.') {
+        Some((module, name)) => index.find_type(module, name).into_iter().collect(),
+        None => index.find_types_by_name(callee),
+    };
+    let methods = if callee.contains('.') {
+        index.find_function_by_qualified_name(callee).unwrap_or_default()
+    } else {
+        index.find_signatures_by_name(callee)
+    };
+
+    let mut signatures: Vec<FunctionSignature> = types.iter().flat_map(TypeDefinition::constructors).collect();
+    signatures.extend(methods.into_iter().filter(|sig| sig.parameters_known));
+    // The default constructor of a `@kwdef` struct is both recorded and derived from its fields
+    let mut seen = HashSet::new();
+    signatures.retain(|sig| {
+        let shape: Vec<String> = sig.parameters.iter().map(|p| format!("{}:{:?}", p.name, p.kind)).collect();
+        seen.insert(shape)
+    });
+    signatures
+}
+
+fn signature_information(sig: &FunctionSignature) -> SignatureInformation {
+    SignatureInformation {
+        label: sig.label(),
+        documentation: sig.doc_comment.clone(),
+        parameters: sig
+            .parameters
+            .iter()
+            .map(|p| ParameterInformation { label: p.label(), documentation: None })
+            .collect(),
+    }
+}
+
+/// Byte offset of an LSP position (columns are byte offsets, as in tree-sitter)
+fn byte_offset(text: &str, position: Position) -> Option<usize> {
+    let mut offset = 0;
+    for (line_number, line) in text.split('\n').enumerate() {
+        if line_number == position.line as usize {
+            let offset = offset + (position.character as usize).min(line.len());
+            return text.is_char_boundary(offset).then_some(offset);
+        }
+        offset += line.len() + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::parser::JuliaParser;
+    use crate::pipeline::sources::file::FileSource;
+    use crate::pipeline::WorkspacePipeline;
+    use std::path::PathBuf;
+
+    /// Signature help at the end of `code`
+    fn help_at_end(code: &str) -> Option<SignatureHelp> {
+        let mut doc = Document::new("test.jl".to_string(), code.to_string());
+        let mut parser = JuliaParser::new().create_parser().unwrap();
+        doc.parse(&mut parser).unwrap();
+        let source = FileSource::from_content(PathBuf::from("test.jl"), code.to_string());
+        let index = WorkspacePipeline::new().run(vec![source]).unwrap();
+        let last_line = code.split('\n').count() as u32 - 1;
+        let character = code.split('\n').next_back().unwrap().len() as u32;
+        SignatureHelpProvider::signature_help(&index, &doc, Position { line: last_line, character })
+    }
+
+    #[test]
+    fn test_struct_default_constructor() {
+        let help = help_at_end("struct Point\n    x::Float64\n    y::Float64\nend\nPoint(1.0, ").expect("help");
+        assert_eq!(help.signatures[0].label, "Point(x::Float64, y::Float64)");
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn test_kwdef_keyword_constructor() {
+        let code = "@kwdef struct Config\n    rate::Float64 = 0.1\n    name::String\nend\nConfig(name = ";
+        let help = help_at_end(code).expect("help");
+        let active = &help.signatures[help.active_signature.unwrap() as usize];
+        assert_eq!(active.parameters[help.active_parameter.unwrap() as usize].label, "name::String");
+    }
+
+    #[test]
+    fn test_argument_position() {
+        let position = ArgumentPosition::parse("f(a, b), [1, 2]; verbose = ");
+        assert_eq!(position.positional, 2);
+        assert!(position.after_semicolon);
+        assert_eq!(position.keyword.as_deref(), Some("verbose"));
+        assert_eq!(enclosing_call("x = g(1, h(2), "), Some(("g", "1, h(2), ")));
+        assert_eq!(enclosing_call("v[f(1, "), Some(("f", "1, ")));
+        assert_eq!(enclosing_call("v[1, "), None);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::features::hover::find_prior_assignment_in_scope;
+use crate::pipeline::storage::Index;
+use crate::types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, HoverResult, Location, Position, Range,
+    TypeDefinition, TypeDefinitionKind, TypeExpr,
+};
+use tree_sitter::{Node, Tree};
+
+/// Methods that give a type properties other than its fields
+const PROPERTY_OVERLOADS: &[&str] = &["getproperty", "setproperty!"];
+
+/// Field lookups for `obj.field`, using the struct definitions in the index and the
+/// declared or constructed type of `obj`
+pub struct StructFieldProvider;
+
+impl StructFieldProvider {
+    /// The struct `object` holds, from a typed parameter (`p::Point`) or a constructor
+    /// assignment (`p = Point(1, 2)`) in scope
+    pub fn infer_struct_type(object: Node, text: &str, index: &Index) -> Option<TypeDefinition> {
+        if object.kind() != "identifier" {
+            return None;
+        }
+        let name = object.utf8_text(text.as_bytes()).ok()?;
+        let type_name = parameter_type(object, name, text).or_else(|| constructed_type(object, name, text))?;
+        resolve_struct(&type_name, index)
+    }
+
+    /// Hover for the field name of `obj.field`, ranged over the field name
+    pub fn field_hover(node: Node, text: &str, index: &Index) -> Option<HoverResult> {
+        let (object, field) = node.parent().and_then(field_access_parts)?;
+        // At the field's first character the position lookup lands on the end of the `.`
+        if field.id() != node.id() && node.kind() != "." {
+            return None;
+        }
+        let type_def = Self::infer_struct_type(object, text, index)?;
+        let struct_field = type_def.field(field.utf8_text(text.as_bytes()).ok()?)?;
+
+        let mut content = format!("```julia\n{}.{}\n```\n\n", type_def.name, struct_field.label());
+        content.push_str(&format!("Field of struct `{}`", type_def.name));
+        if !type_def.type_parameters.is_empty() {
+            content.push_str(&format!(" with parameters `{}`", type_def.type_parameters.join(", ")));
+        }
+        content.push_str("\n\n");
+        Some(HoverResult { contents: content, range: Some(node_range(field)) })
+    }
+
+    /// Report `x.foo` where `x` holds a known struct that has no field `foo` and no
+    /// `getproperty`/`setproperty!` overload
+    pub fn check_field_access(tree: &Tree, text: &str, index: &Index) -> Vec<Diagnostic> {
+        let has_overload = |type_def: &TypeDefinition| {
+            PROPERTY_OVERLOADS.iter().flat_map(|name| index.find_signatures_by_name(name)).any(|sig| {
+                sig.parameters
+                    .first()
+                    .and_then(|p| p.param_type.as_ref())
+                    .is_some_and(|t| names_type(t, &type_def.name))
+            })
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            stack.extend((0..node.child_count()).filter_map(|i| node.child(i)));
+            let Some((object, field)) = field_access_parts(node) else {
+                continue;
+            };
+            let Ok(field_name) = field.utf8_text(text.as_bytes()) else {
+                continue;
+            };
+            // Without recorded fields the struct body could not be read; don't guess
+            let Some(type_def) = Self::infer_struct_type(object, text, index).filter(|t| !t.fields.is_empty()) else {
+                continue;
+            };
+            if type_def.field(field_name).is_some() || has_overload(&type_def) {
+                continue;
+            }
+            let fields: Vec<&str> = type_def.fields.iter().map(|f| f.name.as_str()).collect();
+            diagnostics.push(Diagnostic {
+                range: node_range(field),
+                severity: Some(DiagnosticSeverity::Warning),
+                code: Some("unknown_field".to_string()),
+                source: Some("semantic".to_string()),
+                message: format!(
+                    "`{}` has no field `{}` (fields: {})",
+                    type_def.name,
+                    field_name,
+                    fields.join(", ")
+                ),
+                related_information: Some(vec![DiagnosticRelatedInformation {
+                    location: Location { uri: type_def.file_uri.clone(), range: type_def.range.clone() },
+                    message: format!("`{}` defined here", type_def.name),
+                }]),
+            });
+        }
+        diagnostics.sort_by_key(|d| (d.range.start.line, d.range.start.character));
+        diagnostics
+    }
+}
+
+/// Object and field name of `obj.field`
+fn field_access_parts(node: Node) -> Option<(Node, Node)> {
+    if !matches!(node.kind(), "field_access" | "field_expression") || node.named_child_count() != 2 {
+        return None;
+    }
+    let field = node.named_child(1).filter(|f| f.kind() == "identifier")?;
+    Some((node.named_child(0)?, field))
+}
+
+/// Declared type of parameter `name` of an enclosing method
+fn parameter_type(node: Node, name: &str, text: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        let signature = match parent.kind() {
+            "function_definition" => (0..parent.child_count())
+                .filter_map(|i| parent.child(i))
+                .find(|c| c.kind() == "signature"),
+            "assignment" => parent.child(0).filter(|lhs| lhs.kind() == "call_expression"),
+            _ => None,
+        };
+        if let Some(type_text) = signature.and_then(|sig| typed_parameter(sig, name, text)) {
+            return Some(type_text);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// Type of `name::T` anywhere in a method signature
+fn typed_parameter(node: Node, name: &str, text: &str) -> Option<String> {
+    if matches!(node.kind(), "typed_expression" | "typed_parameter") {
+        let is_param = node
+            .named_child(0)
+            .and_then(|n| n.utf8_text(text.as_bytes()).ok())
+            .is_some_and(|n| n == name);
+        if is_param {
+            let type_node = node.named_child(node.named_child_count().checked_sub(1)?)?;
+            return type_node.utf8_text(text.as_bytes()).ok().map(str::to_string);
+        }
+    }
+    (0..node.named_child_count())
+        .filter_map(|i| node.named_child(i))
+        .find_map(|child| typed_parameter(child, name, text))
+}
+
+/// Callee of the closest earlier `name = T(...)` in scope
+fn constructed_type(node: Node, name: &str, text: &str) -> Option<String> {
+    let value = find_prior_assignment_in_scope(node, text, name)?;
+    let callee = value[..value.find('(')?].trim();
+    let is_type_name = !callee.is_empty()
+        && callee.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '{' | '}' | ',' | ' '));
+    is_type_name.then(|| callee.split('{').next().unwrap_or(callee).to_string())
+}
+
+/// The struct named `type_name` (optionally module-qualified and parametrized)
+fn resolve_struct(type_name: &str, index: &Index) -> Option<TypeDefinition> {
+    let type_name = type_name.split('{').next()?.trim();
+    let candidates: Vec<TypeDefinition> = match type_name.rsplit_once('.') {
+        Some((module, name)) => index.find_type(module, name).into_iter().collect(),
+        None => index.find_types_by_name(type_name),
+    };
+    let mut structs = candidates.into_iter().filter(|t| t.kind == TypeDefinitionKind::Struct);
+    let type_def = structs.next()?;
+    // Same-named structs in several modules: can't tell which one is meant
+    structs.next().is_none().then_some(type_def)
+}
+
+/// Whether `type_expr` is `name`, `Module.name` or `name{...}`
+fn names_type(type_expr: &TypeExpr, name: &str) -> bool {
+    let written = match type_expr {
+        TypeExpr::Concrete(n) | TypeExpr::Generic(n, _) => n.as_str(),
+        _ => return false,
+    };
+    let written = written.split('{').next().unwrap_or(written);
+    written == name || written.rsplit('.').next() == Some(name)
+}
+
+fn node_range(node: Node) -> Range {
+    Range {
+        start: Position::from(node.start_position()),
+        end: Position::from(node.end_position()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::parser::JuliaParser;
+    use crate::pipeline::sources::Document;
+    use crate::pipeline::WorkspacePipeline;
+    use crate::pipeline::sources::file::FileSource;
+    use std::path::PathBuf;
+
+    fn document_and_index(code: &str) -> (Document, Index) {
+        let mut doc = Document::new("test.jl".to_string(), code.to_string());
+        let mut parser = JuliaParser::new().create_parser().unwrap();
+        doc.parse(&mut parser).unwrap();
+        let source = FileSource::from_content(PathBuf::from("test.jl"), code.to_string());
+        let index = WorkspacePipeline::new().run(vec![source]).unwrap();
+        (doc, index)
+    }
+
+    const POINT: &str = "struct Point\n    x::Float64\n    y::Float64\nend\n";
+
+    #[test]
+    fn test_unknown_field_reported() {
+        let code = format!("{}function norm2(p::Point)\n    p.x^2 + p.z^2\nend\nq = Point(1.0, 2.0)\nq.w", POINT);
+        let (doc, index) = document_and_index(&code);
+        let diagnostics = StructFieldProvider::check_field_access(doc.tree().unwrap(), &doc.text(), &index);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(diagnostics.len(), 2, "{:?}", messages);
+        assert!(messages[0].contains("no field `z`"));
+        assert!(messages[1].contains("no field `w`"));
+    }
+
+    #[test]
+    fn test_getproperty_overload_suppresses() {
+        let code = format!(
+            "{}Base.getproperty(p::Point, s::Symbol) = s === :r ? hypot(p.x, p.y) : getfield(p, s)\nq = Point(1.0, 2.0)\nq.r",
+            POINT
+        );
+        let (doc, index) = document_and_index(&code);
+        let diagnostics = StructFieldProvider::check_field_access(doc.tree().unwrap(), &doc.text(), &index);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_field_hover() {
+        let code = format!("{}q = Point(1.0, 2.0)\nq.y", POINT);
+        let (doc, index) = document_and_index(&code);
+        let text = doc.text();
+        let field = crate::pipeline::query::SymbolResolver::new(doc.tree().unwrap(), &text)
+            .node_at_position(5, 2)
+            .unwrap();
+        let hover = StructFieldProvider::field_hover(field, &text, &index).expect("field hover");
+        assert!(hover.contents.contains("Point.y::Float64"));
+        assert_eq!(hover.range.unwrap().start, Position { line: 5, character: 2 });
+    }
+}
@@ -10,8 +10,9 @@ pub fn analyze(parsed: &ParsedItem) -> Result<HashMap<String, HashSet<String>>,
     let root = parsed.tree.root_node();
     let text = parsed.text.as_str();
     
-    // Infer default module name from path (fallback)
-    let default_module = infer_module_name_from_path(&parsed.path);
+    // Module from the include() tree, falling back to a guess from the file path
+    let default_module = parsed.module.clone()
+        .unwrap_or_else(|| infer_module_name_from_path(&parsed.path));
     
     walk_node(&root, text, &default_module, &mut exports_by_module)?;
     
@@ -0,0 +1,222 @@
+use crate::pipeline::types::{ImportStatement, ParsedItem};
+use crate::types::{LspError, Position, Range};
+use tree_sitter::Node;
+
+/// Analyze a parsed item to extract `using`/`import` statements
+pub fn analyze(parsed: &ParsedItem) -> Result<Vec<ImportStatement>, LspError> {
+    let mut imports = Vec::new();
+    let root = parsed.tree.root_node();
+    let text = parsed.text.as_str();
+
+    walk_node(&root, text, &parsed.path.to_string_lossy(), &mut imports);
+
+    Ok(imports)
+}
+
+fn walk_node(node: &Node, text: &str, file_uri: &str, imports: &mut Vec<ImportStatement>) {
+    match node.kind() {
+        "using_statement" => {
+            extract_imports(node, text, file_uri, false, imports);
+            return;
+        }
+        "import_statement" => {
+            extract_imports(node, text, file_uri, true, imports);
+            return;
+        }
+        _ => {}
+    }
+
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            walk_node(&child, text, file_uri, imports);
+        }
+    }
+}
+
+/// Extract the imported modules of a single statement
+///
+/// Works on the statement source rather than on child node kinds, so that
+/// `using A, B.C`, `using A: x, y`, `import A as B` and relative `using ..A`
+/// are all handled the same way regardless of how the grammar nests them.
+fn extract_imports(
+    node: &Node,
+    text: &str,
+    file_uri: &str,
+    is_qualified_only: bool,
+    imports: &mut Vec<ImportStatement>,
+) {
+    let Ok(source) = node.utf8_text(text.as_bytes()) else {
+        return;
+    };
+    // `using A, # note` followed by more modules on the next line
+    let statement = &blank_comments(source);
+    let keyword_len = if is_qualified_only { "import".len() } else { "using".len() };
+    if statement.len() < keyword_len {
+        return;
+    }
+    let body = &statement[keyword_len..];
+    let start = node.start_position();
+
+    // Selective import: only the module before ':' is imported
+    if let Some(colon) = body.find(':') {
+        let (module_offset, module) = trim_with_offset(&body[..colon]);
+        if module.is_empty() {
+            return;
+        }
+        let symbols: Vec<String> = body[colon + 1..]
+            .split(',')
+            .map(|item| strip_alias(item.trim()).to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+        let offset = keyword_len + module_offset;
+        imports.push(ImportStatement {
+            module: module.to_string(),
+            range: offset_range(statement, start, offset, offset + module.len()),
+            file_uri: file_uri.to_string(),
+            is_qualified_only,
+            symbols,
+        });
+        return;
+    }
+
+    let mut item_offset = keyword_len;
+    for item in body.split(',') {
+        let (leading, trimmed) = trim_with_offset(item);
+        let module = strip_alias(trimmed);
+        if !module.is_empty() && is_module_path(module) {
+            let offset = item_offset + leading;
+            imports.push(ImportStatement {
+                module: module.to_string(),
+                range: offset_range(statement, start, offset, offset + module.len()),
+                file_uri: file_uri.to_string(),
+                is_qualified_only,
+                symbols: Vec::new(),
+            });
+        }
+        item_offset += item.len() + 1;
+    }
+}
+
+/// Replace `# ...` and `#= ... =#` comments with spaces, keeping byte offsets and newlines
+fn blank_comments(statement: &str) -> String {
+    let mut blanked = String::with_capacity(statement.len());
+    let mut chars = statement.chars().peekable();
+    let mut block_depth = 0;
+    let mut in_line_comment = false;
+    while let Some(c) = chars.next() {
+        if in_line_comment {
+            if c == '\n' {
+                in_line_comment = false;
+                blanked.push(c);
+            } else {
+                blanked.push_str(&" ".repeat(c.len_utf8()));
+            }
+        } else if c == '#' && chars.peek() == Some(&'=') {
+            block_depth += 1;
+            blanked.push(' ');
+            blanked.push(' ');
+            chars.next();
+        } else if block_depth > 0 {
+            if c == '=' && chars.peek() == Some(&'#') {
+                block_depth -= 1;
+                blanked.push(' ');
+                blanked.push(' ');
+                chars.next();
+            } else if c == '\n' {
+                blanked.push(c);
+            } else {
+                blanked.push_str(&" ".repeat(c.len_utf8()));
+            }
+        } else if c == '#' {
+            in_line_comment = true;
+            blanked.push(' ');
+        } else {
+            blanked.push(c);
+        }
+    }
+    blanked
+}
+
+/// Trim whitespace, returning the byte offset of the trimmed text within the input
+fn trim_with_offset(s: &str) -> (usize, &str) {
+    let trimmed_start = s.trim_start();
+    let leading = s.len() - trimmed_start.len();
+    (leading, trimmed_start.trim_end())
+}
+
+/// Remove a trailing `as Alias` clause
+fn strip_alias(item: &str) -> &str {
+    match item.find(" as ") {
+        Some(pos) => item[..pos].trim_end(),
+        None => item,
+    }
+}
+
+fn is_module_path(s: &str) -> bool {
+    s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '!')
+}
+
+/// Convert a byte span inside the statement text to a document range
+fn offset_range(statement: &str, start: tree_sitter::Point, from: usize, to: usize) -> Range {
+    Range {
+        start: offset_position(statement, start, from),
+        end: offset_position(statement, start, to),
+    }
+}
+
+fn offset_position(statement: &str, start: tree_sitter::Point, offset: usize) -> Position {
+    let before = &statement[..offset.min(statement.len())];
+    match before.rfind('\n') {
+        Some(newline) => Position {
+            line: (start.row + before.matches('\n').count()) as u32,
+            character: (before.len() - newline - 1) as u32,
+        },
+        None => Position {
+            line: start.row as u32,
+            character: (start.column + before.len()) as u32,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::parser;
+    use crate::pipeline::sources::file::FileSource;
+    use std::path::PathBuf;
+
+    fn analyze_code(code: &str) -> Vec<ImportStatement> {
+        let source = FileSource::from_content(PathBuf::from("test.jl"), code.to_string());
+        let parsed = parser::parse(&source).unwrap();
+        analyze(&parsed).unwrap()
+    }
+
+    #[test]
+    fn test_using_multiple_modules() {
+        let imports = analyze_code("using DataFrames, CSV\nimport Base.Threads");
+        let modules: Vec<&str> = imports.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, vec!["DataFrames", "CSV", "Base.Threads"]);
+        assert!(!imports[0].is_qualified_only);
+        assert!(imports[2].is_qualified_only);
+        assert_eq!(imports[2].root_module(), Some("Base"));
+        assert_eq!(imports[1].range.start.character, 18);
+    }
+
+    #[test]
+    fn test_selective_and_relative_imports() {
+        let imports = analyze_code("using Statistics: mean, std\nusing ..Utils");
+        assert_eq!(imports[0].module, "Statistics");
+        assert_eq!(imports[0].symbols, vec!["mean".to_string(), "std".to_string()]);
+        assert_eq!(imports[1].module, "..Utils");
+        assert_eq!(imports[1].root_module(), None);
+    }
+
+    #[test]
+    fn test_comments_inside_statement() {
+        let imports = analyze_code("using DataFrames, # tables\n    CSV, #= files =# JSON");
+        let modules: Vec<&str> = imports.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, vec!["DataFrames", "CSV", "JSON"]);
+        assert_eq!(imports[1].range.start.line, 1);
+        assert_eq!(imports[1].range.start.character, 4);
+    }
+}
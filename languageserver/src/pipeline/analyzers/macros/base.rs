@@ -0,0 +1,197 @@
+//! Rules for macros from Base and the Test standard library
+
+use super::{
+    block_items, named_children, BindingScope, KeywordConstructor, MacroCall, MacroExpansion, MacroRegistry,
+    MacroRule,
+};
+use crate::types::{Parameter, ParameterKind, SymbolKind, TypeExpr};
+use tree_sitter::Node;
+
+/// Macros that return their argument with only code-generation changes
+const PASSTHROUGH_MACROS: &[&str] = &[
+    "inbounds", "views", "view", "simd", "fastmath", "inline", "noinline", "propagate_inbounds", "nospecialize",
+    "specialize", "threads", "spawn", "async", "sync", "assume_effects", "polly", "generated", "time", "timev",
+    "elapsed", "allocated", "allocations", "static", "turbo",
+];
+
+pub(super) fn register(registry: &mut MacroRegistry) {
+    registry.register(Box::new(Passthrough));
+    registry.register(Box::new(Kwdef));
+    registry.register(Box::new(Enum));
+    registry.register(Box::new(Eval));
+    registry.register(Box::new(Testset));
+}
+
+struct Passthrough;
+
+impl MacroRule for Passthrough {
+    fn macro_names(&self) -> &[&str] {
+        PASSTHROUGH_MACROS
+    }
+
+    fn expand(&self, _call: &MacroCall) -> MacroExpansion {
+        MacroExpansion { passthrough: true, ..Default::default() }
+    }
+}
+
+/// `@kwdef struct S; a::Int = 1; b; end` adds `S(; a = 1, b)`
+struct Kwdef;
+
+impl MacroRule for Kwdef {
+    fn macro_names(&self) -> &[&str] {
+        &["kwdef"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let Some(definition) = call.arguments.iter().find(|a| a.kind() == "struct_definition") else {
+            return MacroExpansion { passthrough: true, ..Default::default() };
+        };
+        let type_name = named_children(*definition)
+            .find(|c| c.kind() == "type_head")
+            .and_then(first_identifier)
+            .map(|n| call.node_text(n).to_string());
+
+        let parameters = named_children(*definition)
+            .filter(|c| c.kind() != "type_head")
+            .flat_map(block_items)
+            .filter_map(|field| kwdef_field(call, field))
+            .collect();
+
+        MacroExpansion {
+            passthrough: true,
+            keyword_constructor: type_name.map(|type_name| KeywordConstructor { type_name, parameters }),
+            ..Default::default()
+        }
+    }
+}
+
+/// Keyword parameter for a field: `a`, `a::T`, `a = default` or `a::T = default`
+fn kwdef_field(call: &MacroCall, field: Node) -> Option<Parameter> {
+    let (declaration, kind) = match field.kind() {
+        "assignment" => (field.child(0)?, ParameterKind::Keyword),
+        "identifier" | "typed_expression" => (field, ParameterKind::RequiredKeyword),
+        _ => return None,
+    };
+    let (name, param_type) = match declaration.kind() {
+        "identifier" => (declaration, None),
+        "typed_expression" => {
+            let type_node = declaration.named_child(declaration.named_child_count().checked_sub(1)?)?;
+            (declaration.named_child(0)?, Some(TypeExpr::parse(call.node_text(type_node))))
+        }
+        _ => return None,
+    };
+    if name.kind() != "identifier" {
+        return None;
+    }
+    Some(Parameter { name: call.node_text(name).to_string(), param_type, kind })
+}
+
+/// `@enum Name[::BaseType] a b=2 ...` or with a `begin ... end` block of members
+struct Enum;
+
+impl MacroRule for Enum {
+    fn macro_names(&self) -> &[&str] {
+        &["enum"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let mut bindings = Vec::new();
+        let mut arguments = call.arguments.iter();
+        if let Some(type_name) = arguments.next().and_then(|a| first_identifier(*a)) {
+            bindings.push(call.binding(type_name, SymbolKind::Type, BindingScope::Enclosing));
+        }
+        for member in arguments.flat_map(|a| block_items(*a)) {
+            let name = match member.kind() {
+                "identifier" => Some(member),
+                "assignment" => member.child(0).filter(|lhs| lhs.kind() == "identifier"),
+                _ => None,
+            };
+            if let Some(name) = name {
+                bindings.push(call.binding(name, SymbolKind::Constant, BindingScope::Enclosing));
+            }
+        }
+        MacroExpansion { bindings, ..Default::default() }
+    }
+}
+
+/// `for f in (:a, :b); @eval $f(x) = ...; end` defines `a` and `b`
+struct Eval;
+
+impl MacroRule for Eval {
+    fn macro_names(&self) -> &[&str] {
+        &["eval"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let mut bindings = Vec::new();
+        if let Some((variable, symbols)) = enclosing_symbol_loop(call) {
+            let interpolated = [format!("${}", variable), format!("$({})", variable)];
+            let defines_interpolated = call
+                .arguments
+                .iter()
+                .flat_map(|a| block_items(*a))
+                .filter_map(defined_name)
+                .any(|name| interpolated.iter().any(|i| i == call.node_text(name)));
+            if defines_interpolated {
+                for symbol in symbols {
+                    let mut binding = call.binding(symbol, SymbolKind::Function, BindingScope::Enclosing);
+                    binding.name = binding.name.trim_start_matches(':').to_string();
+                    bindings.push(binding);
+                }
+            }
+        }
+        // Without interpolation, the evaluated code is ordinary top-level code
+        MacroExpansion { passthrough: bindings.is_empty(), bindings, ..Default::default() }
+    }
+}
+
+/// Loop variable and quoted symbols of `for v in (:a, :b)` around the macro call
+fn enclosing_symbol_loop<'t>(call: &MacroCall<'t>) -> Option<(&'t str, Vec<Node<'t>>)> {
+    let mut current = call.node.parent();
+    while let Some(node) = current {
+        match node.kind() {
+            "for_statement" => {
+                let binding = named_children(node).find(|c| c.kind() == "for_binding")?;
+                let variable = binding.named_child(0).filter(|v| v.kind() == "identifier")?;
+                let collection = binding.named_child(binding.named_child_count().checked_sub(1)?)?;
+                let symbols: Vec<Node> = named_children(collection)
+                    .filter(|s| call.node_text(*s).starts_with(':'))
+                    .collect();
+                return (!symbols.is_empty()).then(|| (call.node_text(variable), symbols));
+            }
+            "function_definition" | "module_definition" => return None,
+            _ => current = node.parent(),
+        }
+    }
+    None
+}
+
+/// Callee of `f(x) = ...` or `function f(x) ... end`
+fn defined_name(node: Node) -> Option<Node> {
+    let call = match node.kind() {
+        "assignment" => node.child(0)?,
+        "function_definition" => named_children(node).find(|c| c.kind() == "signature")?.named_child(0)?,
+        _ => return None,
+    };
+    (call.kind() == "call_expression").then(|| call.child(0)).flatten()
+}
+
+/// `@testset "name" begin ... end` runs its body in a local scope
+struct Testset;
+
+impl MacroRule for Testset {
+    fn macro_names(&self) -> &[&str] {
+        &["testset"]
+    }
+
+    fn expand(&self, _call: &MacroCall) -> MacroExpansion {
+        MacroExpansion { introduces_scope: true, ..Default::default() }
+    }
+}
+
+fn first_identifier(node: Node) -> Option<Node> {
+    if node.kind() == "identifier" {
+        return Some(node);
+    }
+    named_children(node).find_map(first_identifier)
+}
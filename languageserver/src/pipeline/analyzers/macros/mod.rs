@@ -0,0 +1,285 @@
+use crate::types::{Parameter, Range, Position, SymbolKind};
+use std::sync::OnceLock;
+use tree_sitter::Node;
+
+mod base;
+mod packages;
+
+/// Where a symbol defined by a macro call is visible
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingScope {
+    /// The scope containing the macro call (e.g. `@enum` members, JuMP variables)
+    Enclosing,
+    /// Only inside the macro call (e.g. JuMP index sets, Turing `~` statements in a model)
+    Local,
+}
+
+/// A symbol the expanded code defines
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroBinding {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Range of the name in the macro call
+    pub range: Range,
+    pub scope: BindingScope,
+}
+
+/// Keyword constructor generated for a struct (`@kwdef`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordConstructor {
+    pub type_name: String,
+    /// One keyword parameter per field; fields with a default are optional
+    pub parameters: Vec<Parameter>,
+}
+
+/// What a macro call expands to, as far as the analyzers are concerned
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MacroExpansion {
+    pub bindings: Vec<MacroBinding>,
+    /// The macro returns its argument unchanged (`@inbounds`, `@views`, ...)
+    pub passthrough: bool,
+    /// The macro body is evaluated in its own local scope (`@testset`)
+    pub introduces_scope: bool,
+    pub keyword_constructor: Option<KeywordConstructor>,
+}
+
+/// A macro call being expanded
+pub struct MacroCall<'t> {
+    pub node: Node<'t>,
+    /// Macro name without `@` or module qualification (`kwdef` for `Base.@kwdef`)
+    pub name: &'t str,
+    /// Arguments, whether space-separated or parenthesized
+    pub arguments: Vec<Node<'t>>,
+    pub text: &'t str,
+}
+
+impl<'t> MacroCall<'t> {
+    /// Read a `macrocall_expression` node
+    pub fn from_node(node: Node<'t>, text: &'t str) -> Option<Self> {
+        if node.kind() != "macrocall_expression" {
+            return None;
+        }
+        let name = macro_name(node, text)?;
+        let mut arguments = Vec::new();
+        // `Base.@kwdef` has the module before the macro identifier
+        let after_name = named_children(node).skip_while(|c| c.kind() != "macro_identifier").skip(1);
+        for child in after_name {
+            match child.kind() {
+                "macro_argument_list" => {
+                    for argument in named_children(child).filter(|c| !is_comment(*c)) {
+                        split_space_separated(argument, &mut arguments);
+                    }
+                }
+                "argument_list" => arguments.extend(named_children(child).filter(|c| !is_comment(*c))),
+                _ if !is_comment(child) => arguments.push(child),
+                _ => {}
+            }
+        }
+        Some(MacroCall { node, name, arguments, text })
+    }
+
+    pub fn node_text(&self, node: Node) -> &'t str {
+        node.utf8_text(self.text.as_bytes()).unwrap_or_default()
+    }
+
+    /// A binding for the identifier `node`
+    pub fn binding(&self, node: Node, kind: SymbolKind, scope: BindingScope) -> MacroBinding {
+        MacroBinding {
+            name: self.node_text(node).to_string(),
+            kind,
+            range: node_range(node),
+            scope,
+        }
+    }
+}
+
+/// Expansion rule for one family of macros
+pub trait MacroRule: Send + Sync {
+    /// Macro names handled by this rule, without the `@`
+    fn macro_names(&self) -> &[&str];
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion;
+}
+
+/// Macro expansion rules by macro name; unknown macros are left opaque
+pub struct MacroRegistry {
+    rules: Vec<Box<dyn MacroRule>>,
+}
+
+impl MacroRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Registry with the built-in rules for Base, Test and popular package DSLs
+    pub fn builtin() -> &'static MacroRegistry {
+        static BUILTIN: OnceLock<MacroRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut registry = MacroRegistry::new();
+            base::register(&mut registry);
+            packages::register(&mut registry);
+            registry
+        })
+    }
+
+    /// Add a rule; rules registered later take precedence for the same macro name
+    pub fn register(&mut self, rule: Box<dyn MacroRule>) {
+        self.rules.insert(0, rule);
+    }
+
+    fn rule_for(&self, name: &str) -> Option<&dyn MacroRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.macro_names().contains(&name))
+            .map(|rule| rule.as_ref())
+    }
+
+    /// Expand a `macrocall_expression` node, if a rule handles the macro
+    pub fn expand(&self, node: Node, text: &str) -> Option<MacroExpansion> {
+        let call = MacroCall::from_node(node, text)?;
+        Some(self.rule_for(call.name)?.expand(&call))
+    }
+
+    /// Whether code inside the macro call is ordinary code (passthrough or scoped block macros)
+    pub fn evaluates_arguments_as_code(&self, node: Node, text: &str) -> bool {
+        self.expand(node, text)
+            .is_some_and(|expansion| expansion.passthrough || expansion.introduces_scope)
+    }
+}
+
+impl Default for MacroRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `kwdef` for `@kwdef`, `Base.@kwdef` and `@kwdef` written as `@Base.kwdef`
+pub fn macro_name<'t>(node: Node, text: &'t str) -> Option<&'t str> {
+    let identifier = named_children(node).find(|c| c.kind() == "macro_identifier")?;
+    let full = identifier.utf8_text(text.as_bytes()).ok()?;
+    let name = full.rsplit(['@', '.']).next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Push a space-separated macro argument, undoing the parse of `a=1 b` as `a = (1 b)`:
+/// the juxtaposed operands after the value are the following arguments
+fn split_space_separated<'t>(argument: Node<'t>, arguments: &mut Vec<Node<'t>>) {
+    arguments.push(argument);
+    if argument.kind() != "assignment" {
+        return;
+    }
+    let Some(value) = argument.named_child(argument.named_child_count().saturating_sub(1)) else {
+        return;
+    };
+    if value.kind() == "juxtaposition_expression" {
+        for next in named_children(value).skip(1) {
+            split_space_separated(next, arguments);
+        }
+    }
+}
+
+pub(crate) fn named_children(node: Node) -> impl Iterator<Item = Node> {
+    (0..node.named_child_count()).filter_map(move |i| node.named_child(i))
+}
+
+fn is_comment(node: Node) -> bool {
+    matches!(node.kind(), "comment" | "line_comment" | "block_comment")
+}
+
+/// Statements of a `begin ... end` block argument, or the argument itself
+pub(crate) fn block_items(node: Node) -> Vec<Node> {
+    match node.kind() {
+        "begin_statement" | "block" => named_children(node)
+            .flat_map(|child| if child.kind() == "block" { block_items(child) } else { vec![child] })
+            .filter(|child| !is_comment(*child))
+            .collect(),
+        _ => vec![node],
+    }
+}
+
+pub(crate) fn node_range(node: Node) -> Range {
+    Range {
+        start: Position::from(node.start_position()),
+        end: Position::from(node.end_position()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::parser;
+    use crate::pipeline::sources::file::FileSource;
+    use crate::types::ParameterKind;
+    use std::path::PathBuf;
+
+    /// Expansion of the first macro call in `code`
+    fn expand_first(code: &str) -> MacroExpansion {
+        let source = FileSource::from_content(PathBuf::from("test.jl"), code.to_string());
+        let parsed = parser::parse(&source).unwrap();
+        fn find(node: Node) -> Option<Node> {
+            if node.kind() == "macrocall_expression" {
+                return Some(node);
+            }
+            (0..node.child_count()).filter_map(|i| node.child(i)).find_map(find)
+        }
+        let node = find(parsed.tree.root_node()).expect("macro call");
+        MacroRegistry::builtin().expand(node, &parsed.text).expect("known macro")
+    }
+
+    fn names(expansion: &MacroExpansion) -> Vec<&str> {
+        expansion.bindings.iter().map(|b| b.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_kwdef_constructor() {
+        let expansion = expand_first("Base.@kwdef struct Config\n    rate::Float64 = 0.1\n    name::String\nend");
+        let constructor = expansion.keyword_constructor.expect("keyword constructor");
+        assert_eq!(constructor.type_name, "Config");
+        let kinds: Vec<(&str, ParameterKind)> = constructor.parameters.iter().map(|p| (p.name.as_str(), p.kind)).collect();
+        assert_eq!(kinds, vec![("rate", ParameterKind::Keyword), ("name", ParameterKind::RequiredKeyword)]);
+    }
+
+    #[test]
+    fn test_enum_members() {
+        let expansion = expand_first("@enum Fruit::UInt8 apple=1 orange");
+        assert_eq!(names(&expansion), vec!["Fruit", "apple", "orange"]);
+        assert_eq!(expansion.bindings[0].kind, SymbolKind::Type);
+        assert_eq!(expansion.bindings[1].kind, SymbolKind::Constant);
+    }
+
+    #[test]
+    fn test_eval_loop_functions() {
+        let expansion = expand_first("for f in (:alpha, :beta)\n    @eval $f(x) = x\nend");
+        assert_eq!(names(&expansion), vec!["alpha", "beta"]);
+        assert_eq!(expansion.bindings[0].kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_passthrough_and_testset() {
+        assert!(expand_first("@inbounds y = x[1]").passthrough);
+        assert!(expand_first("@testset \"math\" begin\n    z = 1\nend").introduces_scope);
+    }
+
+    #[test]
+    fn test_jump_variables() {
+        let expansion = expand_first("@variable(model, 0 <= y[i = 1:3] <= i)");
+        assert_eq!(names(&expansion), vec!["y", "i"]);
+        assert_eq!(expansion.bindings[1].scope, BindingScope::Local);
+        let expansion = expand_first("@constraint(model, budget, x + y <= 10)");
+        assert_eq!(names(&expansion), vec!["budget"]);
+    }
+
+    #[test]
+    fn test_modelingtoolkit_variables() {
+        let expansion = expand_first("@variables t x(t)=1.0 y(t)");
+        assert_eq!(names(&expansion), vec!["t", "x", "y"]);
+    }
+
+    #[test]
+    fn test_turing_model() {
+        let expansion = expand_first("@model function coin(y)\n    p ~ Beta(1, 1)\n    y ~ Bernoulli(p)\nend");
+        assert_eq!(names(&expansion), vec!["p", "y"]);
+        assert!(expansion.bindings.iter().all(|b| b.scope == BindingScope::Local));
+    }
+}
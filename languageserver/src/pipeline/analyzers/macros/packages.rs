@@ -0,0 +1,213 @@
+//! Rules for the modeling DSLs of popular packages (ModelingToolkit, Turing, JuMP)
+
+use super::{block_items, named_children, BindingScope, MacroBinding, MacroCall, MacroExpansion, MacroRegistry, MacroRule};
+use crate::types::SymbolKind;
+use tree_sitter::Node;
+
+/// Comparison operators JuMP accepts in variable bounds
+const BOUND_OPERATORS: &[&str] = &["<=", ">=", "==", "≤", "≥", "in", "∈"];
+
+pub(super) fn register(registry: &mut MacroRegistry) {
+    registry.register(Box::new(SymbolicVariables));
+    registry.register(Box::new(Named));
+    registry.register(Box::new(Model));
+    registry.register(Box::new(JumpVariable));
+    registry.register(Box::new(JumpNamedExpression));
+}
+
+/// ModelingToolkit/Symbolics `@variables t x(t)=1.0 y[1:3]`
+struct SymbolicVariables;
+
+impl MacroRule for SymbolicVariables {
+    fn macro_names(&self) -> &[&str] {
+        &["variables", "parameters", "constants", "independent_variables", "brownian", "syms"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        // `@variables(model, ...)` with a model first is JuMP's block form
+        if call.arguments.len() > 1 && call.arguments[1].kind() == "begin_statement" {
+            return JumpVariable.expand(call);
+        }
+        let bindings = call
+            .arguments
+            .iter()
+            .flat_map(|a| block_items(*a))
+            .filter_map(symbolic_name)
+            .map(|name| call.binding(name, SymbolKind::Variable, BindingScope::Enclosing))
+            .collect();
+        MacroExpansion { bindings, ..Default::default() }
+    }
+}
+
+/// Name declared by `x`, `x(t)`, `x(t) = 1.0`, `x[1:3]`, `x(t)[1:3]` or `x::Real`
+fn symbolic_name(node: Node) -> Option<Node> {
+    match node.kind() {
+        "identifier" => Some(node),
+        "call_expression" | "index_expression" | "typed_expression" | "assignment" => {
+            node.child(0).and_then(symbolic_name)
+        }
+        _ => None,
+    }
+}
+
+/// ModelingToolkit `@named sys = ODESystem(...)`
+struct Named;
+
+impl MacroRule for Named {
+    fn macro_names(&self) -> &[&str] {
+        &["named"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let bindings = call
+            .arguments
+            .iter()
+            .flat_map(|a| block_items(*a))
+            .filter(|a| a.kind() == "assignment")
+            .filter_map(|a| a.child(0).filter(|lhs| lhs.kind() == "identifier"))
+            .map(|name| call.binding(name, SymbolKind::Variable, BindingScope::Enclosing))
+            .collect();
+        MacroExpansion { bindings, ..Default::default() }
+    }
+}
+
+/// Turing `@model function f(y) p ~ Beta(1, 1); y ~ Bernoulli(p) end`: `~` defines its left side
+struct Model;
+
+impl MacroRule for Model {
+    fn macro_names(&self) -> &[&str] {
+        &["model"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let mut bindings = Vec::new();
+        for argument in &call.arguments {
+            collect_tilde_bindings(call, *argument, &mut bindings);
+        }
+        MacroExpansion { passthrough: true, bindings, ..Default::default() }
+    }
+}
+
+fn collect_tilde_bindings(call: &MacroCall, node: Node, bindings: &mut Vec<MacroBinding>) {
+    // `p ~ Beta(1, 1)` parses as a compound assignment, like `p += 1`
+    if matches!(node.kind(), "compound_assignment_expression" | "binary_expression") {
+        let is_tilde = node.child(1).is_some_and(|op| matches!(call.node_text(op), "~" | ".~"));
+        if is_tilde {
+            if let Some(name) = node.child(0).and_then(symbolic_name) {
+                bindings.push(call.binding(name, SymbolKind::Variable, BindingScope::Local));
+            }
+            return;
+        }
+    }
+    for child in named_children(node) {
+        collect_tilde_bindings(call, child, bindings);
+    }
+}
+
+/// JuMP `@variable(model, 0 <= x[i = 1:3] <= 1, Int)`; `@variables(model, begin ... end)` is
+/// dispatched here by [`SymbolicVariables`], which owns the `variables` name
+struct JumpVariable;
+
+impl MacroRule for JumpVariable {
+    fn macro_names(&self) -> &[&str] {
+        &["variable"]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let mut bindings = Vec::new();
+        // The first argument is the model
+        for declaration in call.arguments.iter().skip(1).take(1).flat_map(|a| block_items(*a)) {
+            if let Some(name) = jump_variable_name(call, declaration) {
+                bindings.push(call.binding(name, SymbolKind::Variable, BindingScope::Enclosing));
+                bindings.extend(index_bindings(call, name));
+            }
+        }
+        MacroExpansion { bindings, ..Default::default() }
+    }
+}
+
+/// The variable of a declaration: the middle operand of `lb <= x <= ub`, else the first non-literal one
+fn jump_variable_name<'t>(call: &MacroCall<'t>, declaration: Node<'t>) -> Option<Node<'t>> {
+    let mut operands = Vec::new();
+    flatten_comparison(call, declaration, &mut operands);
+    let candidate = if operands.len() == 3 {
+        operands[1]
+    } else {
+        *operands.iter().find(|o| !o.kind().ends_with("literal") && o.kind() != "unary_expression")?
+    };
+    match candidate.kind() {
+        "identifier" => Some(candidate),
+        "index_expression" => candidate.child(0).filter(|c| c.kind() == "identifier"),
+        _ => None,
+    }
+}
+
+fn flatten_comparison<'t>(call: &MacroCall<'t>, node: Node<'t>, operands: &mut Vec<Node<'t>>) {
+    let operator = node.child(1).map(|op| call.node_text(op));
+    if node.kind() == "binary_expression" && operator.is_some_and(|op| BOUND_OPERATORS.contains(&op)) {
+        for child in named_children(node).filter(|c| c.kind() != "operator") {
+            flatten_comparison(call, child, operands);
+        }
+    } else {
+        operands.push(node);
+    }
+}
+
+/// `i` and `j` of `x[i = 1:3, j = 1:2]` are only visible inside the declaration
+fn index_bindings(call: &MacroCall, name: Node) -> Vec<MacroBinding> {
+    let Some(indexed) = name.parent().filter(|p| p.kind() == "index_expression") else {
+        return Vec::new();
+    };
+    let mut bindings = Vec::new();
+    let mut stack: Vec<Node> = named_children(indexed).skip(1).collect();
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "assignment" | "named_argument") {
+            if let Some(index) = node.child(0).filter(|lhs| lhs.kind() == "identifier") {
+                bindings.push(call.binding(index, SymbolKind::Variable, BindingScope::Local));
+            }
+        } else {
+            stack.extend(named_children(node));
+        }
+    }
+    bindings.reverse();
+    bindings
+}
+
+/// JuMP `@constraint(model, name, expr)`, `@expression(model, name[i = 1:3], expr)`, ...
+struct JumpNamedExpression;
+
+impl MacroRule for JumpNamedExpression {
+    fn macro_names(&self) -> &[&str] {
+        &[
+            "constraint", "constraints", "expression", "expressions", "NLconstraint", "NLconstraints",
+            "NLexpression", "NLexpressions", "NLparameter",
+        ]
+    }
+
+    fn expand(&self, call: &MacroCall) -> MacroExpansion {
+        let mut bindings = Vec::new();
+        let declarations: Vec<Node> = match call.arguments.get(1) {
+            // @constraints(model, begin name, expr; ... end)
+            Some(block) if block.kind() == "begin_statement" => block_items(*block)
+                .into_iter()
+                .filter(|item| matches!(item.kind(), "tuple_expression" | "open_tuple"))
+                .filter_map(|item| item.named_child(0))
+                .collect(),
+            // A name is only given when an expression follows it
+            Some(name) if call.arguments.len() > 2 => vec![*name],
+            _ => Vec::new(),
+        };
+        for declaration in declarations {
+            let name = match declaration.kind() {
+                "identifier" => Some(declaration),
+                "index_expression" => declaration.child(0).filter(|c| c.kind() == "identifier"),
+                _ => None,
+            };
+            if let Some(name) = name {
+                bindings.push(call.binding(name, SymbolKind::Variable, BindingScope::Enclosing));
+                bindings.extend(index_bindings(call, name));
+            }
+        }
+        MacroExpansion { bindings, ..Default::default() }
+    }
+}
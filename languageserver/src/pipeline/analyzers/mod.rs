@@ -5,4 +5,6 @@ pub mod scope;
 pub mod signature;
 pub mod traits;
 pub mod export;
+pub mod import;
 
+pub mod macros;
@@ -1,5 +1,6 @@
 use crate::pipeline::types::{ParsedItem, ScopeTree, ScopeNode};
 use crate::types::{LspError, Range, Position};
+use super::macros::MacroRegistry;
 use tree_sitter::Node;
 
 /// Analyze a parsed item to build scope hierarchy
@@ -26,42 +27,47 @@ pub fn analyze(parsed: &ParsedItem) -> Result<ScopeTree, LspError> {
 
 fn build_scope_tree(
     node: &Node,
-    _text: &str,
+    text: &str,
     file_uri: &str,
     parent_id: u32,
     parent_scope: &mut ScopeNode,
     next_scope_id: &mut u32,
 ) -> Result<(), LspError> {
-    match node.kind() {
-        "function_definition" | "module_definition" => {
-            let scope_id = *next_scope_id;
-            *next_scope_id += 1;
-
-            let mut child_scope = ScopeNode {
-                id: scope_id,
-                parent_id: Some(parent_id),
-                range: node_to_range(*node),
-                file_uri: file_uri.to_string(),
-                children: Vec::new(),
-            };
-
-            // Recursively build children scopes
-            for i in 0..node.child_count() {
-                if let Some(child) = node.child(i) {
-                    build_scope_tree(&child, _text, file_uri, scope_id, &mut child_scope, next_scope_id)?;
-                }
+    let opens_scope = match node.kind() {
+        "function_definition" | "module_definition" => true,
+        // `@testset` and similar block macros run their body in a local scope
+        "macrocall_expression" => MacroRegistry::builtin()
+            .expand(*node, text)
+            .is_some_and(|expansion| expansion.introduces_scope),
+        _ => false,
+    };
+    if opens_scope {
+        let scope_id = *next_scope_id;
+        *next_scope_id += 1;
+
+        let mut child_scope = ScopeNode {
+            id: scope_id,
+            parent_id: Some(parent_id),
+            range: node_to_range(*node),
+            file_uri: file_uri.to_string(),
+            children: Vec::new(),
+        };
+
+        // Recursively build children scopes
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                build_scope_tree(&child, text, file_uri, scope_id, &mut child_scope, next_scope_id)?;
             }
-
-            parent_scope.children.push(child_scope);
-            return Ok(());
         }
-        _ => {}
+
+        parent_scope.children.push(child_scope);
+        return Ok(());
     }
 
     // Continue walking children
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            build_scope_tree(&child, _text, file_uri, parent_id, parent_scope, next_scope_id)?;
+            build_scope_tree(&child, text, file_uri, parent_id, parent_scope, next_scope_id)?;
         }
     }
 
@@ -111,6 +117,15 @@ end
         assert_eq!(scope_tree.root.children.len(), 1); // outer function
         assert_eq!(scope_tree.root.children[0].children.len(), 1); // inner function
     }
+
+    #[test]
+    fn test_testset_opens_scope() {
+        let code = "@testset \"math\" begin\n    x = 1\nend\n@inbounds y = 2";
+        let parsed = parse_code(code);
+        let scope_tree = analyze(&parsed).unwrap();
+
+        assert_eq!(scope_tree.root.children.len(), 1); // @testset only
+    }
 }
 
 
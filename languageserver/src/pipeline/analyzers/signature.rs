@@ -1,7 +1,8 @@
 use crate::pipeline::types::ParsedItem;
-use crate::types::{FunctionSignature, Parameter};
+use crate::types::{FunctionSignature, Parameter, ParameterKind, TypeExpr};
 use crate::types::{LspError, Range, Position};
 use crate::pipeline::sources::indexing::extract_docstrings_with_function_names;
+use super::macros::{KeywordConstructor, MacroRegistry};
 use tree_sitter::Node;
 use std::collections::HashMap;
 
@@ -12,8 +13,9 @@ pub fn analyze(parsed: &ParsedItem) -> Result<Vec<FunctionSignature>, LspError>
     let root = parsed.tree.root_node();
     let text = parsed.text.as_str();
 
-    // Try to infer module name from file path (package name)
-    let default_module = infer_module_name_from_path(&parsed.path);
+    // Module from the include() tree, falling back to a guess from the file path
+    let default_module = parsed.module.clone()
+        .unwrap_or_else(|| infer_module_name_from_path(&parsed.path));
     
     // Step 1: Extract all docstrings and derive function names from them (docstring-first approach)
     let docstring_map = extract_docstrings_with_function_names(root, text);
@@ -142,6 +144,8 @@ pub fn analyze(parsed: &ParsedItem) -> Result<Vec<FunctionSignature>, LspError>
                     start: Position { line: 0, character: 0 },
                     end: Position { line: 0, character: 0 },
                 },
+                parameters_known: false,
+                extends_module: None,
             };
             signatures_from_ast.push(sig);
         }
@@ -277,8 +281,8 @@ fn walk_node(
         }
     }
     
-    if node.kind() == "function_definition" {
-        if let Some(sig) = extract_function_signature(node, text, file_uri, module_name)? {
+    if matches!(node.kind(), "function_definition" | "assignment") {
+        if let Some(sig) = method_signature(node, text, file_uri, module_name)? {
             signatures.push(sig);
         }
     } else if node.kind() == "macro_definition" {
@@ -286,15 +290,13 @@ fn walk_node(
         if let Some(sig) = extract_macro_signature(node, text, file_uri, module_name)? {
             signatures.push(sig);
         }
-    } else if node.kind() == "assignment" {
-        // Handle short-form function definitions: f(x) = y
-        // Also handle operators: (op)(args...) = y
-        if let Some(left_node) = node.child(0) {
-            if left_node.kind() == "call_expression" {
-                if let Some(sig) = extract_short_form_signature(&left_node, node, text, file_uri, module_name)? {
-                    signatures.push(sig);
-                }
-            }
+    } else if node.kind() == "macrocall_expression" {
+        // `@kwdef struct ... end` generates constructors
+        if let Some(constructor) = MacroRegistry::builtin()
+            .expand(*node, text)
+            .and_then(|expansion| expansion.keyword_constructor)
+        {
+            signatures.extend(kwdef_constructor_signatures(node, constructor, file_uri, module_name));
         }
     }
 
@@ -318,7 +320,7 @@ fn extract_function_signature(
     // 1. An identifier (e.g., "describe")
     // 2. A field_access/field_expression (e.g., "DataAPI.describe")
     let (name, qualified_module) = if let Some(signature_node) = find_first_child_of_type(node, "signature") {
-        if let Some(call_node) = find_first_child_of_type(&signature_node, "call_expression") {
+        if let Some(call_node) = find_signature_call(signature_node) {
             // Check if function name is a qualified access (Module.function)
             if let Some(field_node) = find_first_child_of_type(&call_node, "field_access") {
                 // Extract qualified name like "DataAPI.describe"
@@ -369,22 +371,9 @@ fn extract_function_signature(
 
     // Find parameter list - it's in: function_definition -> signature -> call_expression -> argument_list
     if let Some(signature_node) = find_first_child_of_type(node, "signature") {
-        if let Some(call_node) = find_first_child_of_type(&signature_node, "call_expression") {
+        if let Some(call_node) = find_signature_call(signature_node) {
             if let Some(param_list) = find_first_child_of_type(&call_node, "argument_list") {
-                for i in 0..param_list.child_count() {
-                    if let Some(param_node) = param_list.child(i) {
-                        if param_node.kind() == "identifier" {
-                            let param_name = param_node.utf8_text(text.as_bytes())
-                                .map_err(|e| LspError::ParseError(format!("Failed to extract parameter name: {}", e)))?
-                                .to_string();
-
-                            parameters.push(Parameter {
-                                name: param_name,
-                                param_type: None, // Type inference would be done separately
-                            });
-                        }
-                    }
-                }
+                parameters = extract_parameters(&param_list, text);
             }
         }
     }
@@ -423,6 +412,8 @@ fn extract_function_signature(
         doc_comment,
         file_uri: file_uri.to_string(),
         range,
+        parameters_known: true,
+        extends_module: qualified_module.filter(|m| m != module_name),
     };
     
     // Log function signature extraction for debugging
@@ -434,6 +425,73 @@ fn extract_function_signature(
     Ok(Some(sig))
 }
 
+/// Signature of a method defined by `function f(...) ... end` or the short form `f(x) = y`
+pub(super) fn method_signature(
+    node: &Node,
+    text: &str,
+    file_uri: &str,
+    module_name: &str,
+) -> Result<Option<FunctionSignature>, LspError> {
+    match node.kind() {
+        "function_definition" => extract_function_signature(node, text, file_uri, module_name),
+        // Also handle operators: (op)(args...) = y
+        // `f(x)::T = y` and `f(x::T) where T = y` wrap the call
+        "assignment" => match node.child(0).and_then(find_signature_call) {
+            Some(left_node) => extract_short_form_signature(&left_node, node, text, file_uri, module_name),
+            None => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// The keyword constructor `@kwdef` adds, plus the default positional constructor it keeps.
+/// Positional arguments are converted to the field types, so they are left untyped.
+fn kwdef_constructor_signatures(
+    node: &Node,
+    constructor: KeywordConstructor,
+    file_uri: &str,
+    module_name: &str,
+) -> Vec<FunctionSignature> {
+    let module = if module_name.is_empty() { "Main".to_string() } else { module_name.to_string() };
+    let positional: Vec<Parameter> = constructor
+        .parameters
+        .iter()
+        .map(|p| Parameter { name: p.name.clone(), param_type: None, kind: ParameterKind::Required })
+        .collect();
+    let mut variants = vec![constructor.parameters];
+    // A struct without fields has a single zero-argument constructor
+    if !positional.is_empty() {
+        variants.push(positional);
+    }
+    variants
+        .into_iter()
+        .map(|parameters| FunctionSignature {
+            module: module.clone(),
+            name: constructor.type_name.clone(),
+            parameters,
+            return_type: Some(TypeExpr::Concrete(constructor.type_name.clone())),
+            doc_comment: None,
+            file_uri: file_uri.to_string(),
+            range: node_to_range(*node),
+            parameters_known: true,
+            extends_module: None,
+        })
+        .collect()
+}
+
+/// The call_expression of a method signature, looking through a return type
+/// annotation (`f(x)::Int`) and `where` clauses
+fn find_signature_call(node: Node) -> Option<Node> {
+    match node.kind() {
+        "call_expression" => Some(node),
+        "where_expression" | "typed_expression" => node.child(0).and_then(find_signature_call),
+        "signature" => (0..node.child_count())
+            .filter_map(|i| node.child(i))
+            .find_map(find_signature_call),
+        _ => None,
+    }
+}
+
 fn find_first_child_of_type<'a>(node: &'a Node<'a>, kind: &str) -> Option<Node<'a>> {
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
@@ -461,6 +519,61 @@ fn node_to_range(node: Node) -> Range {
     }
 }
 
+/// Extract the parameters of a method definition from its argument_list,
+/// classifying each as positional/keyword, defaulted and varargs.
+/// Parameters after `;` are keywords.
+fn extract_parameters(param_list: &Node, text: &str) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    collect_parameters(param_list, text, false, &mut parameters);
+    parameters
+}
+
+fn collect_parameters(node: &Node, text: &str, mut keyword: bool, parameters: &mut Vec<Parameter>) {
+    for i in 0..node.child_count() {
+        let Some(param_node) = node.child(i) else { continue };
+        let (kind, default_kind, splat_kind) = if keyword {
+            (ParameterKind::RequiredKeyword, ParameterKind::Keyword, ParameterKind::KeywordVarargs)
+        } else {
+            (ParameterKind::Required, ParameterKind::Optional, ParameterKind::Varargs)
+        };
+        match param_node.kind() {
+            ";" => keyword = true,
+            // Older grammars wrap the keyword section in its own node
+            "keyword_parameters" => collect_parameters(&param_node, text, true, parameters),
+            "identifier" | "typed_expression" | "typed_parameter" | "unary_typed_expression" => {
+                parameters.push(parameter_from_node(&param_node, text, kind));
+            }
+            "named_argument" | "assignment" | "keyword_argument" | "optional_parameter" => {
+                if let Some(target) = param_node.child(0) {
+                    parameters.push(parameter_from_node(&target, text, default_kind));
+                }
+            }
+            "splat_expression" | "spread_parameter" => {
+                if let Some(target) = param_node.child(0) {
+                    let mut param = parameter_from_node(&target, text, splat_kind);
+                    param.name = format!("{}...", param.name);
+                    parameters.push(param);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Build a parameter from `x`, `x::T` or `::T`
+fn parameter_from_node(node: &Node, text: &str, kind: ParameterKind) -> Parameter {
+    let source = node.utf8_text(text.as_bytes()).unwrap_or("");
+    let (name, param_type) = match source.split_once("::") {
+        Some((name, type_text)) => (name.trim(), Some(TypeExpr::parse(type_text))),
+        None => (source.trim(), None),
+    };
+    Parameter {
+        name: if name.is_empty() { "_".to_string() } else { name.to_string() },
+        param_type,
+        kind,
+    }
+}
+
 /// Extract function signature from short-form definition: f(x) = y
 fn extract_short_form_signature(
     call_node: &Node,
@@ -516,22 +629,9 @@ fn extract_short_form_signature(
     };
 
     // Extract parameters from argument_list
-    let mut parameters = Vec::new();
-    if let Some(param_list) = find_first_child_of_type(call_node, "argument_list") {
-        for i in 0..param_list.child_count() {
-            if let Some(param_node) = param_list.child(i) {
-                if param_node.kind() == "identifier" {
-                    let param_name = param_node.utf8_text(text.as_bytes())
-                        .map_err(|e| LspError::ParseError(format!("Failed to extract parameter name: {}", e)))?
-                        .to_string();
-                    parameters.push(Parameter {
-                        name: param_name,
-                        param_type: None,
-                    });
-                }
-            }
-        }
-    }
+    let parameters = find_first_child_of_type(call_node, "argument_list")
+        .map(|param_list| extract_parameters(&param_list, text))
+        .unwrap_or_default();
 
     let range = node_to_range(*assignment_node);
     // Docstrings will be matched from docstring-first extraction, not extracted here
@@ -564,6 +664,8 @@ fn extract_short_form_signature(
         doc_comment,
         file_uri: file_uri.to_string(),
         range,
+        parameters_known: true,
+        extends_module: qualified_module.filter(|m| m != module_name),
     };
     
     if module != "Main" && !module.is_empty() {
@@ -638,6 +740,7 @@ fn extract_macro_signature(
                             parameters.push(Parameter {
                                 name: param_name,
                                 param_type: None,
+                                kind: ParameterKind::Required,
                             });
                         } else if param_node.kind() == "splat_expression" {
                             // Handle splat parameters like `args...`
@@ -648,6 +751,7 @@ fn extract_macro_signature(
                                 parameters.push(Parameter {
                                     name: format!("{}...", param_name),
                                     param_type: None,
+                                    kind: ParameterKind::Varargs,
                                 });
                             }
                         }
@@ -695,6 +799,8 @@ fn extract_macro_signature(
         doc_comment,
         file_uri: file_uri.to_string(),
         range,
+        parameters_known: true,
+        extends_module: None,
     };
     
     if module != "Main" && !module.is_empty() {
@@ -740,5 +846,50 @@ mod tests {
         assert_eq!(signatures[0].name, "test");
         assert_eq!(signatures[0].parameters.len(), 0);
     }
+    #[test]
+    fn test_analyze_parameter_kinds() {
+        let code = "function test(x::Int, y=1, rest...; flag=false, required, opts...) end";
+        let parsed = parse_code(code);
+        let signatures = analyze(&parsed).unwrap();
+
+        assert_eq!(signatures.len(), 1);
+        assert!(signatures[0].parameters_known);
+        let kinds: Vec<ParameterKind> = signatures[0].parameters.iter().map(|p| p.kind).collect();
+        assert_eq!(kinds, vec![
+            ParameterKind::Required,
+            ParameterKind::Optional,
+            ParameterKind::Varargs,
+            ParameterKind::Keyword,
+            ParameterKind::RequiredKeyword,
+            ParameterKind::KeywordVarargs,
+        ]);
+        assert_eq!(signatures[0].parameters[0].name, "x");
+        assert_eq!(signatures[0].parameters[0].param_type, Some(TypeExpr::Concrete("Int".to_string())));
+    }
+
+    #[test]
+    fn test_analyze_qualified_method_extends_module() {
+        let code = "Base.show(io::IO, p::Point) = print(io, p.x)\nfunction area(p::Point) end";
+        let parsed = parse_code(code);
+        let signatures = analyze(&parsed).unwrap();
+
+        let show = signatures.iter().find(|s| s.name == "show").expect("show method");
+        assert_eq!(show.extends_module.as_deref(), Some("Base"));
+        let area = signatures.iter().find(|s| s.name == "area").expect("area method");
+        assert_eq!(area.extends_module, None);
+    }
+
+    #[test]
+    fn test_analyze_kwdef_constructors() {
+        let code = "@kwdef struct Config\n    rate::Float64 = 0.1\n    name::String\nend";
+        let parsed = parse_code(code);
+        let signatures = analyze(&parsed).unwrap();
+
+        let constructors: Vec<&FunctionSignature> = signatures.iter().filter(|s| s.name == "Config").collect();
+        assert_eq!(constructors.len(), 2);
+        assert!(constructors[0].parameters.iter().all(|p| p.kind.is_keyword()));
+        let positional: Vec<ParameterKind> = constructors[1].parameters.iter().map(|p| p.kind).collect();
+        assert_eq!(positional, vec![ParameterKind::Required, ParameterKind::Required]);
+    }
 }
 
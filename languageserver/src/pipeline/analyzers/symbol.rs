@@ -1,5 +1,6 @@
 use crate::pipeline::types::ParsedItem;
 use crate::types::{Symbol, SymbolKind, LspError, Range, Position};
+use super::macros::MacroRegistry;
 use tree_sitter::Node;
 
 /// Analyze a parsed item to extract symbols
@@ -50,6 +51,20 @@ fn walk_node(
                 symbols.push(symbol);
             }
         }
+        "macrocall_expression" => {
+            // Names defined by known macros (`@enum` members, `@variables`, JuMP variables, ...)
+            if let Some(expansion) = MacroRegistry::builtin().expand(*node, text) {
+                symbols.extend(expansion.bindings.into_iter().map(|binding| Symbol {
+                    name: binding.name,
+                    kind: binding.kind,
+                    range: binding.range,
+                    scope_id,
+                    doc_comment: None,
+                    signature: None,
+                    file_uri: file_uri.to_string(),
+                }));
+            }
+        }
         _ => {}
     }
 
@@ -455,5 +470,16 @@ y = 2
         assert!(symbols.iter().any(|s| s.name == "x"));
         assert!(symbols.iter().any(|s| s.name == "y"));
     }
+
+    #[test]
+    fn test_analyze_macro_bindings() {
+        let code = "@enum Color red green\n@variables t x(t)";
+        let parsed = parse_code(code);
+        let symbols = analyze(&parsed).unwrap();
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Color", "red", "green", "t", "x"]);
+        assert_eq!(symbols[0].kind, SymbolKind::Type);
+    }
 }
 
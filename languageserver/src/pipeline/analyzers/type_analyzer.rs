@@ -1,6 +1,8 @@
 use crate::pipeline::types::ParsedItem;
-use crate::types::{TypeDefinition, TypeDefinitionKind};
+use crate::types::{FunctionSignature, StructField, TypeDefinition, TypeDefinitionKind, TypeExpr};
 use crate::types::{LspError, Range, Position};
+use super::macros::{block_items, named_children};
+use super::signature;
 use tree_sitter::Node;
 
 /// Analyze a parsed item to extract type definitions
@@ -9,8 +11,9 @@ pub fn analyze(parsed: &ParsedItem) -> Result<Vec<TypeDefinition>, LspError> {
     let root = parsed.tree.root_node();
     let text = parsed.text.as_str();
 
-    // Infer module name from file path (same logic as signature analyzer)
-    let default_module = infer_module_name_from_path(&parsed.path);
+    // Module from the include() tree, falling back to a guess from the file path
+    let default_module = parsed.module.clone()
+        .unwrap_or_else(|| infer_module_name_from_path(&parsed.path));
 
     walk_node(&root, text, &parsed.path.to_string_lossy(), &default_module, &mut types)?;
 
@@ -28,6 +31,7 @@ fn walk_node(
         "struct_definition" => {
             if let Some(mut type_def) = extract_struct_definition(node, text, file_uri)? {
                 type_def.module = module_name.to_string();
+                type_def.inner_constructors = extract_inner_constructors(node, text, file_uri, module_name, &type_def.name)?;
                 types.push(type_def);
             }
         }
@@ -80,7 +84,8 @@ fn extract_struct_definition(
     file_uri: &str,
 ) -> Result<Option<TypeDefinition>, LspError> {
     if let Some(type_head) = find_first_child_of_type(node, "type_head") {
-        if let Some(name_node) = find_first_child_of_type(&type_head, "identifier") {
+        // `Point{T} <: AbstractPoint` nests the name inside the type head
+        if let Some(name_node) = find_first_identifier(type_head) {
             let name = name_node.utf8_text(text.as_bytes())
                 .map_err(|e| LspError::ParseError(format!("Failed to extract struct name: {}", e)))?
                 .to_string();
@@ -95,6 +100,9 @@ fn extract_struct_definition(
                 doc_comment,
                 file_uri: file_uri.to_string(),
                 range,
+                fields: extract_fields(node, text),
+                type_parameters: extract_type_parameters(name_node, text),
+                inner_constructors: Vec::new(), // Set by caller, which knows the module
             }));
         }
     }
@@ -102,6 +110,97 @@ fn extract_struct_definition(
     Ok(None)
 }
 
+/// Fields of a struct body: `x`, `x::T`, `const x::T` and `@kwdef` defaults `x::T = value`
+fn extract_fields(node: &Node, text: &str) -> Vec<StructField> {
+    named_children(*node)
+        .filter(|child| child.kind() != "type_head")
+        .flat_map(block_items)
+        .filter_map(|item| extract_field(item, text))
+        .collect()
+}
+
+fn extract_field(node: Node, text: &str) -> Option<StructField> {
+    let node_text = |n: Node| n.utf8_text(text.as_bytes()).ok().map(str::to_string);
+    match node.kind() {
+        "identifier" => Some(StructField { name: node_text(node)?, field_type: None, default: None }),
+        "typed_expression" => {
+            let name = node.named_child(0).filter(|n| n.kind() == "identifier")?;
+            let type_node = node.named_child(node.named_child_count().checked_sub(1)?)?;
+            Some(StructField {
+                name: node_text(name)?,
+                field_type: Some(TypeExpr::parse(&node_text(type_node)?)),
+                default: None,
+            })
+        }
+        "assignment" => {
+            // `f(x) = ...` in a struct body is an inner constructor, not a field
+            let mut field = extract_field(node.child(0)?, text)?;
+            let value = node.named_child(node.named_child_count().checked_sub(1)?)?;
+            field.default = node_text(value);
+            Some(field)
+        }
+        "const_statement" => named_children(node).find_map(|child| extract_field(child, text)),
+        _ => None,
+    }
+}
+
+/// Names of the type parameters written after the struct name: `T` and `N` of `Grid{T<:Real, N}`
+fn extract_type_parameters(name_node: Node, text: &str) -> Vec<String> {
+    let Some(rest) = text.get(name_node.end_byte()..).filter(|rest| rest.starts_with('{')) else {
+        return Vec::new();
+    };
+    let mut parameters = Vec::new();
+    let mut depth = 0;
+    let mut start = 1;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth -= 1,
+            ',' if depth == 1 => {
+                parameters.push(&rest[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        if depth == 0 {
+            parameters.push(&rest[start..i]);
+            break;
+        }
+    }
+    parameters
+        .into_iter()
+        .filter_map(|p| p.split(['<', '>']).next())
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Methods defined in the struct body whose name is the struct's
+fn extract_inner_constructors(
+    node: &Node,
+    text: &str,
+    file_uri: &str,
+    module_name: &str,
+    type_name: &str,
+) -> Result<Vec<FunctionSignature>, LspError> {
+    let mut constructors = Vec::new();
+    for item in named_children(*node).flat_map(block_items) {
+        if let Some(sig) = signature::method_signature(&item, text, file_uri, module_name)? {
+            if sig.name == type_name {
+                constructors.push(sig);
+            }
+        }
+    }
+    Ok(constructors)
+}
+
+fn find_first_identifier(node: Node) -> Option<Node> {
+    if node.kind() == "identifier" {
+        return Some(node);
+    }
+    named_children(node).find_map(find_first_identifier)
+}
+
 fn extract_abstract_definition(
     node: &Node,
     text: &str,
@@ -123,6 +222,9 @@ fn extract_abstract_definition(
                 doc_comment,
                 file_uri: file_uri.to_string(),
                 range,
+                fields: Vec::new(),
+                type_parameters: Vec::new(),
+                inner_constructors: Vec::new(),
             }));
         }
     }
@@ -152,6 +254,9 @@ fn extract_module_definition(
             doc_comment,
             file_uri: file_uri.to_string(),
             range,
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            inner_constructors: Vec::new(),
         }));
     }
 
@@ -266,6 +371,29 @@ mod tests {
         assert_eq!(types[0].kind, TypeDefinitionKind::Struct);
     }
 
+    #[test]
+    fn test_analyze_struct_fields() {
+        let code = r#"
+Base.@kwdef struct Grid{T<:Real, N} <: AbstractGrid
+    cells::Array{T, N}
+    spacing::T = 1
+    label
+    Grid(cells) = new{eltype(cells), ndims(cells)}(cells, 1, "")
+end
+"#;
+        let parsed = parse_code(code);
+        let types = analyze(&parsed).unwrap();
+
+        let grid = types.iter().find(|t| t.name == "Grid").expect("Grid");
+        assert_eq!(grid.type_parameters, vec!["T", "N"]);
+        let fields: Vec<&str> = grid.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["cells", "spacing", "label"]);
+        assert_eq!(grid.fields[0].field_type, Some(TypeExpr::parse("Array{T, N}")));
+        assert_eq!(grid.fields[1].default.as_deref(), Some("1"));
+        assert_eq!(grid.inner_constructors.len(), 1);
+        assert_eq!(grid.inner_constructors[0].parameters.len(), 1);
+    }
+
     #[test]
     fn test_analyze_abstract() {
         let code = "abstract type MyAbstract end";
@@ -7,6 +7,7 @@ pub mod orchestrator;
 pub mod config;
 pub mod types;
 pub mod indexing;
+pub mod module_tree;
 pub mod workspace_pipeline;
 pub mod package_pipeline;
 pub mod julia_pipeline;
@@ -19,6 +20,7 @@ pub use workspace_pipeline::WorkspacePipeline;
 pub use package_pipeline::{PackagePipeline, PackagePipelineInput};
 pub use julia_pipeline::JuliaPipeline;
 pub use pipeline_trait::Pipeline;
+pub use module_tree::{FileModule, ModuleTree};
 
 
 
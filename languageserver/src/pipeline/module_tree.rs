@@ -0,0 +1,443 @@
+use crate::pipeline::parser::JuliaParser;
+use crate::pipeline::types::SourceItem;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::Node;
+
+/// Module placement of a single file, as determined by following `include()` chains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileModule {
+    /// Module the file's top-level code is evaluated in (e.g. "Main" for a package entry
+    /// file, "MyPkg.Utils" for a file included inside `module Utils`)
+    pub context: String,
+    /// Module the file's definitions mostly belong to: its first top-level `module` block
+    /// if it declares one, otherwise `context`
+    pub primary: String,
+    /// Whether this file is a package entry file (`src/<Package>.jl`)
+    pub is_entry: bool,
+}
+
+/// Module tree of a workspace or package, built by starting from entry files and
+/// following `include(...)` calls through nested `module`/`baremodule` blocks
+#[derive(Debug, Clone, Default)]
+pub struct ModuleTree {
+    files: HashMap<PathBuf, FileModule>,
+    modules: BTreeSet<String>,
+    /// Files that were only reached as roots, not through an `include`
+    roots: HashSet<PathBuf>,
+}
+
+impl ModuleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the module tree of a single package from its entry file `src/<package_name>.jl`
+    pub fn for_package(package_path: &Path, package_name: &str, source_items: &[SourceItem]) -> Self {
+        Self::for_package_with_extensions(package_path, package_name, &[], source_items)
+    }
+
+    /// Build the module tree of a package plus the entry files of its active extensions
+    ///
+    /// Extension modules are top-level modules of their own, loaded from `ext/`.
+    pub fn for_package_with_extensions(
+        package_path: &Path,
+        package_name: &str,
+        extension_entries: &[PathBuf],
+        source_items: &[SourceItem],
+    ) -> Self {
+        let mut tree = Self::new();
+        let entry = package_path.join("src").join(format!("{}.jl", package_name));
+        let contents = contents_by_path(source_items);
+        tree.add_root(&entry, true, &contents);
+        for extension_entry in extension_entries {
+            tree.add_root(extension_entry, true, &contents);
+        }
+        tree
+    }
+
+    /// Build the module tree of a workspace.
+    ///
+    /// Package entry files (`<root>/src/<Name>.jl` next to a `Project.toml`) are followed
+    /// first; every file not reached from them is treated as a script evaluated in `Main`.
+    pub fn for_workspace(source_items: &[SourceItem]) -> Self {
+        let mut tree = Self::new();
+        let contents = contents_by_path(source_items);
+
+        let mut paths: Vec<PathBuf> = contents.keys().cloned().collect();
+        paths.sort();
+
+        for path in paths.iter().filter(|path| is_package_entry(path)) {
+            tree.add_root(path, true, &contents);
+        }
+        for path in &paths {
+            if !tree.files.contains_key(path) {
+                tree.add_root(path, false, &contents);
+            }
+        }
+        tree
+    }
+
+    /// Module placement of `path`, if it is part of the tree
+    pub fn module_of(&self, path: &Path) -> Option<&FileModule> {
+        self.files.get(&normalize(path))
+    }
+
+    /// All files in the tree with their module placement
+    pub fn files(&self) -> impl Iterator<Item = (&PathBuf, &FileModule)> {
+        self.files.iter()
+    }
+
+    /// Fully qualified names of all modules declared in the tree
+    pub fn modules(&self) -> impl Iterator<Item = &String> {
+        self.modules.iter()
+    }
+
+    fn add_root(&mut self, path: &Path, is_entry: bool, contents: &HashMap<PathBuf, &str>) {
+        let path = normalize(path);
+        self.roots.insert(path.clone());
+        let mut visiting = HashSet::new();
+        self.visit(&path, "Main", is_entry, contents, &mut visiting);
+    }
+
+    fn visit(
+        &mut self,
+        path: &Path,
+        context: &str,
+        is_entry: bool,
+        contents: &HashMap<PathBuf, &str>,
+        visiting: &mut HashSet<PathBuf>,
+    ) {
+        // Guard against include cycles within one chain
+        if !visiting.insert(path.to_path_buf()) {
+            return;
+        }
+
+        let owned;
+        let text = match contents.get(path) {
+            Some(text) => *text,
+            None => match std::fs::read_to_string(path) {
+                Ok(text) => {
+                    owned = text;
+                    owned.as_str()
+                }
+                Err(e) => {
+                    log::trace!("ModuleTree: Cannot read included file {:?}: {}", path, e);
+                    return;
+                }
+            },
+        };
+
+        let tree = match JuliaParser::new().parse(text) {
+            Ok(tree) => tree,
+            Err(e) => {
+                log::trace!("ModuleTree: Failed to parse {:?}: {}", path, e);
+                return;
+            }
+        };
+
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let mut walker = FileWalker {
+            text,
+            dir: &dir,
+            primary: None,
+            modules: Vec::new(),
+            includes: Vec::new(),
+        };
+        walker.walk(&tree.root_node(), context, true);
+
+        self.files.insert(
+            path.to_path_buf(),
+            FileModule {
+                context: context.to_string(),
+                primary: walker.primary.unwrap_or_else(|| context.to_string()),
+                is_entry,
+            },
+        );
+        self.modules.extend(walker.modules);
+
+        for (included, module) in walker.includes {
+            // A file reached earlier only as a standalone root gets its real placement now
+            let placed = self.files.contains_key(&included) && !self.roots.contains(&included);
+            if placed {
+                continue;
+            }
+            self.roots.remove(&included);
+            self.visit(&included, &module, false, contents, visiting);
+        }
+
+        visiting.remove(path);
+    }
+}
+
+/// Collects module blocks and include targets of one file
+struct FileWalker<'a> {
+    text: &'a str,
+    dir: &'a Path,
+    primary: Option<String>,
+    modules: Vec<String>,
+    includes: Vec<(PathBuf, String)>,
+}
+
+impl FileWalker<'_> {
+    fn walk(&mut self, node: &Node, module: &str, top_level: bool) {
+        match node.kind() {
+            "module_definition" => {
+                // `baremodule` parses to the same node kind as `module`
+                if let Some(name) = find_first_child_of_type(node, "identifier")
+                    .and_then(|name| name.utf8_text(self.text.as_bytes()).ok())
+                {
+                    let nested = qualify(module, name);
+                    if top_level && self.primary.is_none() {
+                        self.primary = Some(nested.clone());
+                    }
+                    self.modules.push(nested.clone());
+                    for i in 0..node.child_count() {
+                        if let Some(child) = node.child(i) {
+                            self.walk(&child, &nested, false);
+                        }
+                    }
+                    return;
+                }
+            }
+            "call_expression" => {
+                if let Some(target) = include_target(node, self.text, self.dir) {
+                    self.includes.push((target, module.to_string()));
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.walk(&child, module, top_level);
+            }
+        }
+    }
+}
+
+fn contents_by_path(source_items: &[SourceItem]) -> HashMap<PathBuf, &str> {
+    source_items
+        .iter()
+        .map(|item| (normalize(&item.path), item.content.as_str()))
+        .collect()
+}
+
+/// `<root>/src/<Name>.jl` where `<root>` holds a `Project.toml`
+fn is_package_entry(path: &Path) -> bool {
+    let Some(src_dir) = path.parent() else {
+        return false;
+    };
+    if src_dir.file_name().and_then(|n| n.to_str()) != Some("src") {
+        return false;
+    }
+    let Some(root) = src_dir.parent() else {
+        return false;
+    };
+    let has_project = root.join("Project.toml").is_file() || root.join("JuliaProject.toml").is_file();
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let starts_upper = stem.chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
+    has_project && starts_upper && path.extension().and_then(|e| e.to_str()) == Some("jl")
+}
+
+/// Qualified name of module `name` declared inside `parent`
+pub fn qualify(parent: &str, name: &str) -> String {
+    if parent.is_empty() || parent == "Main" {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+/// Resolve the file loaded by an `include(...)` / `Base.include(...)` call
+fn include_target(node: &Node, text: &str, dir: &Path) -> Option<PathBuf> {
+    let callee = node.child(0)?.utf8_text(text.as_bytes()).ok()?;
+    if callee != "include" && callee != "Base.include" {
+        return None;
+    }
+    let args = find_first_child_of_type(node, "argument_list")?
+        .utf8_text(text.as_bytes())
+        .ok()?
+        .trim();
+    let args = args.strip_prefix('(')?.strip_suffix(')')?;
+    // Keyword arguments follow `;`; with two positional arguments
+    // (`include(mapexpr, path)` or `include(Mod, path)`) the path comes last
+    let positional = split_top_level(args, ';').into_iter().next()?;
+    let path_arg = split_top_level(positional, ',').into_iter().last()?;
+    let path = eval_path(path_arg, dir)?;
+    Some(normalize(&dir.join(path)))
+}
+
+/// Evaluate the small subset of path expressions used with `include`: string literals,
+/// `@__DIR__`, and `joinpath`/`normpath`/`abspath` over those
+fn eval_path(expr: &str, dir: &Path) -> Option<PathBuf> {
+    let expr = expr.trim();
+    if let Some(literal) = string_literal(expr) {
+        return Some(PathBuf::from(literal));
+    }
+    if expr == "@__DIR__" || expr == "@__DIR__()" {
+        return Some(dir.to_path_buf());
+    }
+
+    let open = expr.find('(')?;
+    let function = expr[..open].trim();
+    let args = expr[open + 1..].strip_suffix(')')?;
+    match function {
+        "joinpath" => {
+            let mut path = PathBuf::new();
+            for arg in split_top_level(args, ',') {
+                // Like Julia's joinpath, an absolute component replaces what came before
+                path.push(eval_path(arg, dir)?);
+            }
+            Some(path)
+        }
+        "normpath" | "abspath" | "realpath" => eval_path(args, dir),
+        _ => None,
+    }
+}
+
+/// Contents of a plain (non-interpolated) string literal
+fn string_literal(expr: &str) -> Option<String> {
+    let inner = expr.strip_prefix('"')?.strip_suffix('"')?;
+    if inner.is_empty() || inner.starts_with('"') || inner.contains('$') {
+        return None;
+    }
+    Some(inner.replace("\\\\", "\\").replace("\\\"", "\""))
+}
+
+/// Split on `separator` outside of strings and brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, ch) in text.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ if ch == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().map(str::trim).filter(|part| !part.is_empty()).collect()
+}
+
+/// Lexically normalize a path (resolve `.` and `..` without touching the file system)
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+fn find_first_child_of_type<'a>(node: &'a Node<'a>, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::sources::file::FileSource;
+
+    #[test]
+    fn test_eval_include_paths() {
+        let dir = Path::new("/pkg/src");
+        assert_eq!(eval_path("\"utils.jl\"", dir), Some(PathBuf::from("utils.jl")));
+        assert_eq!(
+            eval_path("joinpath(@__DIR__, \"sub\", \"a.jl\")", dir),
+            Some(PathBuf::from("/pkg/src/sub/a.jl"))
+        );
+        assert_eq!(
+            eval_path("normpath(joinpath(@__DIR__(), \"..\", \"ext\", \"b.jl\"))", dir),
+            Some(PathBuf::from("/pkg/src/../ext/b.jl"))
+        );
+        assert_eq!(eval_path("\"$(name).jl\"", dir), None);
+        assert_eq!(eval_path("file", dir), None);
+    }
+
+    #[test]
+    fn test_split_top_level() {
+        assert_eq!(split_top_level("f, joinpath(a, \"b,c\")", ','), vec!["f", "joinpath(a, \"b,c\")"]);
+        assert_eq!(split_top_level("\"a.jl\"; kw = 1", ';'), vec!["\"a.jl\"", "kw = 1"]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(Path::new("/pkg/src/../ext/./b.jl")), PathBuf::from("/pkg/ext/b.jl"));
+        assert_eq!(qualify("Main", "MyPkg"), "MyPkg");
+        assert_eq!(qualify("MyPkg", "Utils"), "MyPkg.Utils");
+    }
+
+    #[test]
+    fn test_follow_includes_into_submodules() {
+        let items = vec![
+            FileSource::from_content(
+                PathBuf::from("/pkg/src/MyPkg.jl"),
+                "module MyPkg\ninclude(\"core.jl\")\nmodule Utils\ninclude(joinpath(@__DIR__, \"utils\", \"strings.jl\"))\nend\nend".to_string(),
+            ),
+            FileSource::from_content(PathBuf::from("/pkg/src/core.jl"), "f(x) = x".to_string()),
+            FileSource::from_content(
+                PathBuf::from("/pkg/src/utils/strings.jl"),
+                "baremodule Inner\nend\ng(x) = x".to_string(),
+            ),
+            FileSource::from_content(PathBuf::from("/pkg/src/unused.jl"), "h() = 1".to_string()),
+        ];
+        let tree = ModuleTree::for_package(Path::new("/pkg"), "MyPkg", &items);
+
+        let entry = tree.module_of(Path::new("/pkg/src/MyPkg.jl")).unwrap();
+        assert_eq!((entry.context.as_str(), entry.primary.as_str(), entry.is_entry), ("Main", "MyPkg", true));
+        assert_eq!(tree.module_of(Path::new("/pkg/src/core.jl")).unwrap().context, "MyPkg");
+        let strings = tree.module_of(Path::new("/pkg/src/utils/strings.jl")).unwrap();
+        assert_eq!(strings.context, "MyPkg.Utils");
+        assert_eq!(strings.primary, "MyPkg.Utils.Inner");
+        assert!(tree.module_of(Path::new("/pkg/src/unused.jl")).is_none());
+        assert!(tree.modules().any(|m| m == "MyPkg.Utils.Inner"));
+    }
+
+    #[test]
+    fn test_workspace_scripts_run_in_main() {
+        let items = vec![
+            FileSource::from_content(PathBuf::from("/ws/test/helpers.jl"), "helper() = 1".to_string()),
+            FileSource::from_content(
+                PathBuf::from("/ws/test/runtests.jl"),
+                "module Tests\ninclude(\"helpers.jl\")\nend".to_string(),
+            ),
+        ];
+        let tree = ModuleTree::for_workspace(&items);
+        assert_eq!(tree.module_of(Path::new("/ws/test/helpers.jl")).unwrap().context, "Tests");
+        assert_eq!(tree.module_of(Path::new("/ws/test/runtests.jl")).unwrap().context, "Main");
+    }
+}
@@ -4,6 +4,7 @@ use crate::pipeline::{
     parser,
     analyzers,
     storage,
+    module_tree::ModuleTree,
 };
 use crate::types::LspError;
 
@@ -28,12 +29,17 @@ impl Pipeline {
         log::trace!("Pipeline: Running with {} files", source_items.len());
         let mut index = existing_index.take().unwrap_or_default();
 
+        // Resolve each file's module by following include() chains from package entry files
+        let module_tree = ModuleTree::for_workspace(&source_items);
+        index.set_module_tree(&module_tree);
+        let module_of = |item: &SourceItem| module_tree.module_of(&item.path).map(|m| m.context.as_str());
+
         // PASS 0: Collect all exports first (needed to filter which symbols to index from dependencies)
         // Sort files so main module files (with exports) are processed first
         let mut sorted_items = source_items.clone();
         sorted_items.sort_by(|a, b| {
-            let a_is_main = Self::is_main_module_file(&a.path);
-            let b_is_main = Self::is_main_module_file(&b.path);
+            let a_is_main = Self::is_main_module_file(&module_tree, &a.path);
+            let b_is_main = Self::is_main_module_file(&module_tree, &b.path);
             match (a_is_main, b_is_main) {
                 (true, false) => std::cmp::Ordering::Less,
                 (false, true) => std::cmp::Ordering::Greater,
@@ -41,15 +47,15 @@ impl Pipeline {
             }
         });
         
-        // First pass: Collect exports only
-        for source_item in &sorted_items {
-            let parsed = parser::parse(source_item)?;
-            let analysis = self.analyze_pass1(&parsed)?;
-            
-            // Only merge exports in first pass
-            if !analysis.exports.is_empty() {
-                let module_name = Self::infer_module_name_from_path(&source_item.path);
-                index.add_exports(module_name.clone(), analysis.exports.clone(), source_item.path.clone());
+        // First pass: Collect exports only, keyed by the module that declares them
+        if self.config.extract_exports {
+            for source_item in &sorted_items {
+                let parsed = parser::parse_in_module(source_item, module_of(source_item))?;
+                for (module_name, exports) in analyzers::export::analyze(&parsed)? {
+                    if !exports.is_empty() {
+                        index.add_exports(module_name, exports, source_item.path.clone());
+                    }
+                }
             }
         }
 
@@ -57,7 +63,7 @@ impl Pipeline {
         // Now we have all exports, so we can filter which symbols to index
         for source_item in &sorted_items {
             // Parse
-            let parsed = parser::parse(source_item)?;
+            let parsed = parser::parse_in_module(source_item, module_of(source_item))?;
 
             // Analyze (without type inference)
             let analysis = self.analyze_pass1(&parsed)?;
@@ -70,47 +76,9 @@ impl Pipeline {
         Ok(index)
     }
     
-    /// Check if a file is a main module file (e.g., DataFrames.jl)
-    fn is_main_module_file(path: &std::path::Path) -> bool {
-        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            // Check if filename matches module name pattern
-            // Main module files are typically named after the package (e.g., DataFrames.jl)
-            if let Some(stem) = file_name.strip_suffix(".jl") {
-                // Check if it's likely a main module file (capitalized, matches package name)
-                let first_char = stem.chars().next();
-                first_char.map(|c| c.is_uppercase()).unwrap_or(false)
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    }
-    
-    /// Infer module name from file path (helper)
-    fn infer_module_name_from_path(path: &std::path::Path) -> String {
-        let path_str = path.to_string_lossy();
-        
-        // Check if path contains "packages/{PackageName}/"
-        if let Some(packages_pos) = path_str.find("packages/") {
-            let after_packages = &path_str[packages_pos + 9..];
-            if let Some(slash_pos) = after_packages.find('/') {
-                let package_name = &after_packages[..slash_pos];
-                return package_name.to_string();
-            }
-        }
-        
-        // Fallback: use filename without extension
-        if let Some(file_stem) = path.file_stem() {
-            if let Some(stem_str) = file_stem.to_str() {
-                let mut chars = stem_str.chars();
-                if let Some(first) = chars.next() {
-                    return format!("{}{}", first.to_uppercase(), chars.as_str());
-                }
-            }
-        }
-        
-        "Main".to_string()
+    /// Check if a file is a package entry file (e.g., src/DataFrames.jl)
+    fn is_main_module_file(module_tree: &ModuleTree, path: &std::path::Path) -> bool {
+        module_tree.module_of(path).map(|m| m.is_entry).unwrap_or(false)
     }
 
     /// Run the pipeline for a single file and return the analysis result
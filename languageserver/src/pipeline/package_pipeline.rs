@@ -1,5 +1,6 @@
 use crate::pipeline::sources::package::PackageSource;
 use crate::pipeline::sources::{ProjectContext, indexing::should_skip_entry};
+use crate::pipeline::sources::project_context::{PackageExtension, PackageOrigin, ResolvedPackage};
 use crate::pipeline::pipeline_trait::Pipeline;
 use crate::pipeline::{
     types::{ParsedItem, AnalysisResult},
@@ -7,6 +8,7 @@ use crate::pipeline::{
     analyzers,
     storage::{self, persistence},
     sources::file::FileSource,
+    module_tree::ModuleTree,
 };
 use crate::types::LspError;
 use std::path::{Path, PathBuf};
@@ -41,6 +43,28 @@ impl PackagePipeline {
         package_path: &Path,
         package_name: &str,
     ) -> Result<storage::Index, LspError> {
+        self.process_package_with_extensions(package_path, package_name, &[])
+    }
+
+    /// Process a package together with its active extensions
+    /// 
+    /// Files under `ext/` are only indexed when they belong to an active extension,
+    /// since Julia never loads the others.
+    pub fn process_package_with_extensions(
+        &self,
+        package_path: &Path,
+        package_name: &str,
+        extensions: &[PackageExtension],
+    ) -> Result<storage::Index, LspError> {
+        let ext_dir = package_path.join("ext");
+        let extension_entries: Vec<PathBuf> = extensions.iter().filter_map(|e| e.entry.clone()).collect();
+        let is_active_extension_file = |path: &Path| {
+            extension_entries.iter().any(|entry| {
+                // `ext/<Name>.jl` is a single file; `ext/<Name>/<Name>.jl` owns its directory
+                path == entry || entry.parent().is_some_and(|dir| dir != ext_dir && path.starts_with(dir))
+            })
+        };
+
         // Discover all Julia files in the package
         let mut source_items = Vec::new();
         for entry in WalkDir::new(package_path)
@@ -49,6 +73,9 @@ impl PackagePipeline {
         {
             let entry = entry.map_err(|e| LspError::InternalError(format!("Failed to walk directory: {}", e)))?;
             let path = entry.path();
+            if path.starts_with(&ext_dir) && !is_active_extension_file(path) {
+                continue;
+            }
             if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("jl") {
                 let file_source = FileSource::new(path.to_path_buf());
                 match file_source.load() {
@@ -63,12 +90,25 @@ impl PackagePipeline {
         
         log::info!("PackagePipeline: Processing {} files from package '{}'", source_items.len(), package_name);
         
+        // Resolve each file's module by following include() chains from src/<Package>.jl
+        // and the entry files of active extensions
+        let module_tree = ModuleTree::for_package_with_extensions(
+            package_path,
+            package_name,
+            &extension_entries,
+            &source_items,
+        );
+        let module_of = |item: &crate::pipeline::types::SourceItem| {
+            module_tree.module_of(&item.path).map(|m| m.context.as_str())
+        };
+
         // Run lightweight analysis pipeline (signatures, types, exports only)
         let mut index = storage::Index::new();
+        index.set_module_tree(&module_tree);
         
         // First pass: Collect exports with module context
         for source_item in &source_items {
-            let parsed = parser::parse(source_item)?;
+            let parsed = parser::parse_in_module(source_item, module_of(source_item))?;
             // Use module-aware export analyzer
             let exports_by_module = analyzers::export::analyze(&parsed)?;
             
@@ -82,7 +122,7 @@ impl PackagePipeline {
         
         // Second pass: Extract signatures and types (filtered by exports)
         for source_item in &source_items {
-            let parsed = parser::parse(source_item)?;
+            let parsed = parser::parse_in_module(source_item, module_of(source_item))?;
             let analysis = self.analyze(&parsed)?;
             
             // Merge into index (will filter based on exports we collected in first pass)
@@ -100,9 +140,11 @@ impl PackagePipeline {
         Ok(index)
     }
 
-    /// Discover and process all packages from project dependencies
+    /// Discover and process all packages loadable from the project
     /// 
-    /// This discovers direct dependencies and extracts metadata from each package.
+    /// This resolves the project's dependencies through the stacked LOAD_PATH and
+    /// extracts metadata from each package, including its active extensions.
+    /// Stdlibs are skipped (they are covered by the Base/stdlib index).
     /// Returns a unified Index containing all package metadata.
     /// Automatically checks and uses cache for each package if available.
     pub fn discover_and_process(
@@ -110,50 +152,24 @@ impl PackagePipeline {
         depot_path: PathBuf,
         project_context: ProjectContext,
     ) -> Result<storage::Index, LspError> {
-        // Discover packages (this handles the discovery logic)
-        let Some(dependencies) = project_context.dependencies() else {
+        let packages: Vec<ResolvedPackage> = project_context
+            .resolve_packages(Some(&depot_path))
+            .into_iter()
+            .filter(|package| package.origin != PackageOrigin::Stdlib)
+            .collect();
+        if packages.is_empty() {
             log::info!("PackagePipeline: No dependencies found in project");
             return Ok(storage::Index::new());
-        };
+        }
         
-        log::info!("PackagePipeline: Found {} direct dependencies to index", dependencies.len());
-        let manifest = project_context.manifest_toml.as_ref();
+        log::info!("PackagePipeline: Found {} loadable packages to index", packages.len());
         
         let mut unified_index = storage::Index::new();
         
         // Process each package with cache checking
-        for package_name in dependencies.keys() {
-            // Try to get UUID and git-tree-sha1 from manifest to compute slug
-            let slug = if let Some(manifest) = manifest {
-                if let Some(entries) = manifest.packages.get(package_name) {
-                    entries.iter().find_map(|entry| {
-                        if let (Some(uuid), Some(git_tree_sha1)) = (&entry.uuid, &entry.git_tree_sha1) {
-                            match crate::pipeline::sources::indexing::compute_package_slug(uuid, git_tree_sha1) {
-                                Ok(s) => Some(s),
-                                Err(e) => {
-                                    log::warn!("PackagePipeline: Failed to compute slug for {}: {}", package_name, e);
-                                    None
-                                }
-                            }
-                        } else {
-                            None
-                        }
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-            
-            // Try to resolve package path
-            let package_path = crate::pipeline::sources::indexing::resolve_package_path(
-                &depot_path,
-                package_name,
-                manifest,
-            );
-            
-            let Some(package_path) = package_path else {
+        for package in &packages {
+            let package_name = &package.name;
+            let Some(ref package_path) = package.path else {
                 log::warn!("PackagePipeline: Could not resolve path for package '{}' from depot {:?}", 
                     package_name, depot_path);
                 log::trace!("PackagePipeline: Package '{}' is in Project.toml but not found in depot. Check if:", package_name);
@@ -162,16 +178,18 @@ impl PackagePipeline {
                 continue;
             };
             
-            log::trace!("PackagePipeline: Resolved package '{}' to path: {:?}", package_name, package_path);
+            log::trace!("PackagePipeline: Resolved package '{}' to path: {:?} ({:?} via {:?})",
+                package_name, package_path, package.origin, package.environment);
             
-            // Check cache if we have a slug
+            // Check cache if we have a slug (keyed by the active extensions as well)
+            let cache_key = package.cache_key();
             let mut loaded_from_cache = false;
-            if let Some(ref slug) = slug {
-                let cache_path = PackageSource::get_package_cache_path(package_name, slug);
+            if let Some(ref cache_key) = cache_key {
+                let cache_path = PackageSource::get_package_cache_path(package_name, cache_key);
                 
                 // Check if cache is valid
                 if cache_path.exists() {
-                    if let Some(cached_index) = self.load_package_cache(&cache_path, &package_path) {
+                    if let Some(cached_index) = self.load_package_cache(&cache_path, package_path) {
                         unified_index.merge(cached_index);
                         loaded_from_cache = true;
                         log::trace!("PackagePipeline: Loaded package '{}' from cache", package_name);
@@ -181,11 +199,11 @@ impl PackagePipeline {
             
             // If not loaded from cache, process the package
             if !loaded_from_cache {
-                match self.process_package(&package_path, package_name) {
+                match self.process_package_with_extensions(package_path, package_name, &package.extensions) {
                     Ok(package_index) => {
                         // Save to cache if slug is available
-                        if let Some(ref slug) = slug {
-                            let cache_path = PackageSource::get_package_cache_path(package_name, slug);
+                        if let Some(ref cache_key) = cache_key {
+                            let cache_path = PackageSource::get_package_cache_path(package_name, cache_key);
                             if let Err(e) = self.save_package_cache(&package_index, &cache_path) {
                                 log::warn!("PackagePipeline: Failed to save cache for package {}: {}", package_name, e);
                             }
@@ -282,57 +300,6 @@ impl PackagePipeline {
         Ok(())
     }
     
-    /// Infer module name from file path (helper)
-    #[allow(dead_code)]
-    fn infer_module_name_from_path(path: &std::path::Path, package_name: &str) -> String {
-        // For packages, try to extract module name from path
-        let path_str = path.to_string_lossy();
-        
-        // Check if path contains package name pattern (e.g., packages/DataFrames/.../src/DataFrames.jl)
-        if let Some(packages_pos) = path_str.find("packages/") {
-            let after_packages = &path_str[packages_pos + 9..];
-            if let Some(slash_pos) = after_packages.find('/') {
-                let extracted_name = &after_packages[..slash_pos];
-                // Check if it matches the package name
-                if extracted_name == package_name {
-                    // Check if file is in a subdirectory (e.g., packages/Flux/.../src/losses/functions.jl -> Flux.Losses)
-                    if let Some(src_pos) = path_str.find("/src/") {
-                        let after_src = &path_str[src_pos + 5..]; // Skip "/src/"
-                        // Get the directory containing the file (if any)
-                        if let Some(file_name_pos) = after_src.rfind('/') {
-                            let dir_path = &after_src[..file_name_pos];
-                            if !dir_path.is_empty() {
-                                // Extract directory name and capitalize first letter
-                                let dir_name = dir_path.split('/').last().unwrap_or(dir_path);
-                                let dir_name = dir_path.split('\\').last().unwrap_or(dir_name);
-                                if !dir_name.is_empty() && dir_name != package_name {
-                                    let mut chars = dir_name.chars();
-                                    if let Some(first) = chars.next() {
-                                        let capitalized = format!("{}{}", first.to_uppercase(), chars.as_str());
-                                        return format!("{}.{}", package_name, capitalized);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    return package_name.to_string();
-                }
-            }
-        }
-        
-        // Check if filename matches package name (e.g., DataFrames.jl)
-        if let Some(file_stem) = path.file_stem() {
-            if let Some(stem_str) = file_stem.to_str() {
-                if stem_str == package_name {
-                    return package_name.to_string();
-                }
-            }
-        }
-        
-        // Fallback to package name
-        package_name.to_string()
-    }
-    
     /// Analyze a parsed item with lightweight analysis (signatures, types, exports only)
     fn analyze(&self, parsed: &ParsedItem) -> Result<AnalysisResult, LspError> {
         let mut result = AnalysisResult::new();
@@ -7,6 +7,11 @@ use crate::types::LspError;
 
 /// Parse a source item into a parsed item
 pub fn parse(source: &SourceItem) -> Result<ParsedItem, LspError> {
+    parse_in_module(source, None)
+}
+
+/// Parse a source item whose top-level code runs in `module` (from the module tree)
+pub fn parse_in_module(source: &SourceItem, module: Option<&str>) -> Result<ParsedItem, LspError> {
     let parser = JuliaParser::new();
     let tree = parser.parse(&source.content)?;
     
@@ -14,6 +19,7 @@ pub fn parse(source: &SourceItem) -> Result<ParsedItem, LspError> {
         path: source.path.clone(),
         tree,
         text: source.content.clone(),
+        module: module.map(str::to_string),
     })
 }
 
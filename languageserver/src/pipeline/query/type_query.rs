@@ -47,6 +47,9 @@ mod tests {
                 start: crate::types::Position { line: 0, character: 0 },
                 end: crate::types::Position { line: 0, character: 10 },
             },
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            inner_constructors: Vec::new(),
         };
         analysis.types.push(type_def);
 
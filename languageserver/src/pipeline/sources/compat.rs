@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A Julia `VersionNumber` (major.minor.patch with optional prerelease/build suffix)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VersionNumber {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub prerelease: Option<String>,
+}
+
+impl VersionNumber {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, prerelease: None }
+    }
+
+    /// Parse a version such as "1.2.3", "v1.10", "0.4.0-DEV" or "1.2.3+0"
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (numbers, prerelease) = split_version_suffix(s.trim().trim_start_matches('v'))?;
+        let parts = parse_numbers(numbers)?;
+        Ok(Self {
+            major: parts[0],
+            minor: parts.get(1).copied().unwrap_or(0),
+            patch: parts.get(2).copied().unwrap_or(0),
+            prerelease,
+        })
+    }
+
+    /// True for prerelease versions like "1.0.0-rc1"
+    pub fn is_prerelease(&self) -> bool {
+        self.prerelease.is_some()
+    }
+
+    /// The "major.minor" prefix used for versioned manifests and environments
+    pub fn major_minor(&self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+}
+
+impl Ord for VersionNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                // A prerelease sorts before the corresponding release
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for VersionNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for VersionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(ref pre) = self.prerelease {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// Half-open version interval [lower, upper); `upper == None` means unbounded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRange {
+    pub lower: VersionNumber,
+    pub upper: Option<VersionNumber>,
+}
+
+impl VersionRange {
+    pub fn contains(&self, version: &VersionNumber) -> bool {
+        // Compare on the release triple so "1.2.0-rc1" is not rejected by a "1.2" lower bound
+        let release = VersionNumber::new(version.major, version.minor, version.patch);
+        release >= self.lower && self.upper.as_ref().map(|u| release < *u).unwrap_or(true)
+    }
+}
+
+/// A parsed `[compat]` entry: the union of all comma-separated specifiers
+///
+/// Follows Pkg's compat semantics: bare and `^` specifiers are caret ranges,
+/// `~` is a tilde range, `=` pins an exact version, `<`, `<=`/`≤`, `>`,
+/// `>=`/`≥` are inequalities and `a - b` is an inclusive hyphen range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionSpec {
+    pub ranges: Vec<VersionRange>,
+}
+
+impl VersionSpec {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                return Err(format!("empty specifier in \"{}\"", spec));
+            }
+            ranges.push(parse_specifier(part)?);
+        }
+        Ok(Self { ranges })
+    }
+
+    pub fn contains(&self, version: &VersionNumber) -> bool {
+        self.ranges.iter().any(|r| r.contains(version))
+    }
+}
+
+fn parse_specifier(part: &str) -> Result<VersionRange, String> {
+    // Hyphen range: "1.2 - 2.3" (spaces around '-' are required by Pkg)
+    if let Some((lo, hi)) = part.split_once(" - ") {
+        let lower = parse_numbers(lo.trim())?;
+        let upper = parse_numbers(hi.trim())?;
+        return Ok(VersionRange {
+            lower: from_parts(&lower),
+            upper: Some(bump(&upper, upper.len())),
+        });
+    }
+
+    for (op, kind) in [(">=", ">="), ("≥", ">="), ("<=", "<="), ("≤", "<="), ("<", "<"), (">", ">"), ("=", "=")] {
+        if let Some(rest) = part.strip_prefix(op) {
+            let parts = parse_numbers(rest.trim())?;
+            let version = from_parts(&parts);
+            return Ok(match kind {
+                ">=" => VersionRange { lower: version, upper: None },
+                ">" => VersionRange { lower: bump(&parts, parts.len()), upper: None },
+                "<=" => VersionRange { lower: VersionNumber::new(0, 0, 0), upper: Some(bump(&parts, parts.len())) },
+                "<" => VersionRange { lower: VersionNumber::new(0, 0, 0), upper: Some(version) },
+                _ => VersionRange { lower: version, upper: Some(bump(&parts, parts.len())) },
+            });
+        }
+    }
+
+    if let Some(rest) = part.strip_prefix('~') {
+        let parts = parse_numbers(rest.trim())?;
+        let position = match parts.len() {
+            1 => 1,
+            2 => 2,
+            _ if parts[0] == 0 && parts[1] == 0 => 3,
+            _ => 2,
+        };
+        return Ok(VersionRange { lower: from_parts(&parts), upper: Some(bump(&parts, position)) });
+    }
+
+    let parts = parse_numbers(part.strip_prefix('^').unwrap_or(part).trim())?;
+    // Caret: the first non-zero component (or the last given one) is the breaking component
+    let position = parts
+        .iter()
+        .position(|&n| n != 0)
+        .map(|p| p + 1)
+        .unwrap_or(parts.len());
+    Ok(VersionRange { lower: from_parts(&parts), upper: Some(bump(&parts, position)) })
+}
+
+fn split_version_suffix(s: &str) -> Result<(&str, Option<String>), String> {
+    let s = s.split('+').next().unwrap_or(s);
+    match s.split_once('-') {
+        Some((numbers, pre)) if !pre.is_empty() => Ok((numbers, Some(pre.to_string()))),
+        Some(_) => Err(format!("invalid version \"{}\"", s)),
+        None => Ok((s, None)),
+    }
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<u64>, String> {
+    if s.is_empty() {
+        return Err("missing version number".to_string());
+    }
+    let parts: Result<Vec<u64>, _> = s.split('.').map(|p| p.parse::<u64>()).collect();
+    match parts {
+        Ok(parts) if (1..=3).contains(&parts.len()) => Ok(parts),
+        _ => Err(format!("invalid version \"{}\"", s)),
+    }
+}
+
+fn from_parts(parts: &[u64]) -> VersionNumber {
+    VersionNumber::new(
+        parts[0],
+        parts.get(1).copied().unwrap_or(0),
+        parts.get(2).copied().unwrap_or(0),
+    )
+}
+
+/// Increment the component at 1-based `position`, zeroing everything after it
+fn bump(parts: &[u64], position: usize) -> VersionNumber {
+    let mut full = [
+        parts[0],
+        parts.get(1).copied().unwrap_or(0),
+        parts.get(2).copied().unwrap_or(0),
+    ];
+    let index = position.clamp(1, 3) - 1;
+    full[index] += 1;
+    for value in full.iter_mut().skip(index + 1) {
+        *value = 0;
+    }
+    VersionNumber::new(full[0], full[1], full[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> VersionNumber {
+        VersionNumber::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(v("1.2.3") < v("1.10.0"));
+        assert!(v("1.0.0-rc1") < v("1.0.0"));
+        assert_eq!(v("v1.6"), VersionNumber::new(1, 6, 0));
+    }
+
+    #[test]
+    fn test_caret_and_tilde_specifiers() {
+        let caret = VersionSpec::parse("1.2").unwrap();
+        assert!(caret.contains(&v("1.9.9")));
+        assert!(!caret.contains(&v("2.0.0")));
+
+        let zero = VersionSpec::parse("0.2.3").unwrap();
+        assert!(zero.contains(&v("0.2.9")));
+        assert!(!zero.contains(&v("0.3.0")));
+
+        let tilde = VersionSpec::parse("~1.2.3").unwrap();
+        assert!(tilde.contains(&v("1.2.9")));
+        assert!(!tilde.contains(&v("1.3.0")));
+    }
+
+    #[test]
+    fn test_union_inequality_and_hyphen_specifiers() {
+        let spec = VersionSpec::parse("0.5, 1").unwrap();
+        assert!(spec.contains(&v("0.5.4")));
+        assert!(spec.contains(&v("1.8.0")));
+        assert!(!spec.contains(&v("0.6.0")));
+
+        assert!(VersionSpec::parse(">= 1.3").unwrap().contains(&v("4.0.0")));
+        assert!(!VersionSpec::parse("< 1.3").unwrap().contains(&v("1.3.0")));
+        assert!(VersionSpec::parse("=1.2.3").unwrap().contains(&v("1.2.3")));
+        assert!(!VersionSpec::parse("=1.2.3").unwrap().contains(&v("1.2.4")));
+
+        let hyphen = VersionSpec::parse("1.2 - 2").unwrap();
+        assert!(hyphen.contains(&v("2.9.0")));
+        assert!(!hyphen.contains(&v("3.0.0")));
+    }
+
+    #[test]
+    fn test_invalid_specifiers() {
+        assert!(VersionSpec::parse("").is_err());
+        assert!(VersionSpec::parse("1.x").is_err());
+        assert!(VersionSpec::parse("1, ").is_err());
+        assert!(VersionSpec::parse("1.2.3.4").is_err());
+    }
+}
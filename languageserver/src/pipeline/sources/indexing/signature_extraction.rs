@@ -1,4 +1,4 @@
-use crate::types::{FunctionSignature, Parameter, TypeExpr, Range, Position};
+use crate::types::{FunctionSignature, Parameter, ParameterKind, TypeExpr, Range, Position};
 use crate::types::LspError;
 use tree_sitter::Node;
 use super::docstring_extraction::extract_docstring;
@@ -199,6 +199,8 @@ pub fn extract_function_signature(
         doc_comment,
         file_uri: file_uri.to_string(),
         range,
+        parameters_known: true,
+        extends_module: None,
     }))
 }
 
@@ -224,6 +226,8 @@ fn handle_non_call_signature(
                 doc_comment: None,
                 file_uri: file_uri.to_string(),
                 range,
+                parameters_known: false,
+                extends_module: None,
             }));
         }
     }
@@ -241,6 +245,8 @@ fn handle_non_call_signature(
             doc_comment: None,
             file_uri: file_uri.to_string(),
             range,
+            parameters_known: true,
+            extends_module: None,
         }));
     }
     
@@ -299,6 +305,8 @@ fn handle_non_call_signature(
             doc_comment: None,
             file_uri: file_uri.to_string(),
             range,
+            parameters_known: true,
+            extends_module: None,
         }));
     }
     
@@ -353,6 +361,7 @@ fn extract_parameters_from_argument_list(
                     parameters.push(Parameter {
                         name,
                         param_type: None,
+                        kind: ParameterKind::Required,
                     });
                 }
                 "typed_expression" => {
@@ -364,6 +373,7 @@ fn extract_parameters_from_argument_list(
                         parameters.push(Parameter {
                             name,
                             param_type,
+                            kind: ParameterKind::Required,
                         });
                     }
                     // Silently skip invalid typed_expression parameters
@@ -399,6 +409,7 @@ fn extract_parameters(
                         parameters.push(Parameter {
                             name,
                             param_type: None,
+                            kind: ParameterKind::Required,
                         });
                     }
                     "typed_expression" => {
@@ -410,6 +421,7 @@ fn extract_parameters(
                             parameters.push(Parameter {
                                 name,
                                 param_type,
+                                kind: ParameterKind::Required,
                             });
                         }
                         // Silently skip invalid typed_expression parameters
@@ -27,6 +27,9 @@ pub fn extract_struct_definition(
             doc_comment,
             file_uri: file_uri.to_string(),
             range,
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            inner_constructors: Vec::new(),
         }))
     } else {
         Ok(None)
@@ -57,6 +60,9 @@ pub fn extract_abstract_definition(
             doc_comment,
             file_uri: file_uri.to_string(),
             range,
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            inner_constructors: Vec::new(),
         }))
     } else {
         Ok(None)
@@ -7,6 +7,8 @@ pub mod project_context;
 pub mod base_docs;
 pub mod base_docs_extraction;
 pub mod indexing;
+pub mod compat;
+pub mod registry;
 
 pub use workspace::WorkspaceSource;
 pub use package::PackageSource;
@@ -15,6 +17,8 @@ pub use base::BaseSource;
 pub use document::Document;
 pub use project_context::ProjectContext;
 pub use base_docs::BaseDocsRegistry;
+pub use compat::{VersionNumber, VersionSpec};
+pub use registry::LocalRegistry;
 // PackageIndexer removed - was using TypeRegistry and is not used anywhere
 
 
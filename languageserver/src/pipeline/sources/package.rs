@@ -1,6 +1,7 @@
 use crate::pipeline::types::{SourceItem, FileMetadata};
 use crate::pipeline::sources::ProjectContext;
-use crate::pipeline::sources::indexing::{resolve_package_path, should_skip_entry, compute_package_slug, extract_package_slug};
+use crate::pipeline::sources::project_context::{PackageOrigin, ResolvedPackage};
+use crate::pipeline::sources::indexing::{should_skip_entry, extract_package_slug};
 use crate::pipeline::sources::base_docs::BaseDocsRegistry;
 use crate::types::LspError;
 use std::path::{Path, PathBuf};
@@ -43,134 +44,62 @@ impl PackageSource {
 
     /// Discover all package files from project dependencies with caching support
     /// 
+    /// Packages are resolved through the project's stacked LOAD_PATH; stdlibs are skipped.
+    /// 
     /// Note: We only index direct dependencies because:
     /// 1. Symbol resolution and type inference only need symbols from packages the user directly imports
     /// 2. Transitive dependencies are typically re-exported by direct dependencies if needed
     /// 3. Scanning transitive dependencies can be very expensive for large dependency trees
     pub fn discover_with_cache(&self) -> Result<DiscoverResult, LspError> {
         let mut cached_registries = HashMap::new();
-        let mut package_stats = Vec::new();
+        let mut packages_to_process = Vec::new();
 
-        let Some(dependencies) = self.project_context.dependencies() else {
+        let packages = self.loadable_packages();
+        if packages.is_empty() {
             log::info!("PackageSource: No dependencies found in project");
             return Ok(DiscoverResult {
                 cached_registries,
-                packages_to_process: Vec::new(),
+                packages_to_process,
             });
-        };
+        }
 
-        log::info!("PackageSource: Found {} direct dependencies to index (skipping transitive dependencies)", dependencies.len());
-        let manifest = self.project_context.manifest_toml.as_ref();
-
-        // Only process direct dependencies (skip transitive dependencies)
-        for package_name in dependencies.keys() {
-            // Try to get UUID and git-tree-sha1 from manifest to compute slug
-            let slug = if let Some(manifest) = manifest {
-                if let Some(entries) = manifest.packages.get(package_name) {
-                    // Find entry with UUID and git-tree-sha1
-                    entries.iter().find_map(|entry| {
-                        if let (Some(uuid), Some(git_tree_sha1)) = (&entry.uuid, &entry.git_tree_sha1) {
-                            match compute_package_slug(uuid, git_tree_sha1) {
-                                Ok(s) => Some(s),
-                                Err(e) => {
-                                    log::warn!("PackageSource: Failed to compute slug for {}: {}", package_name, e);
-                                    None
-                                }
-                            }
-                        } else {
-                            None
-                        }
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
+        log::info!("PackageSource: Found {} direct dependencies to index (skipping transitive dependencies)", packages.len());
+
+        for package in packages {
+            let Some(package_path) = package.path else {
+                log::warn!("PackageSource: Could not resolve path for package '{}'", package.name);
+                continue;
             };
 
             // Check cache if we have a slug
-            let mut cache_hit = false;
-            if let Some(ref slug) = slug {
-                let cache_path = Self::get_package_cache_path(package_name, slug);
-                
-                // Try to resolve package path to validate cache
-                if let Some(package_path) = resolve_package_path(
-                    &self.depot_path,
-                    package_name,
-                    manifest,
-                ) {
-                    if let Some(cached_registry) = Self::load_package_cache(&cache_path, &package_path) {
-                        log::info!("PackageSource: Loaded cached docstrings for package '{}' (slug: {}, {} entries)", 
-                            package_name, slug, cached_registry.len());
-                        cached_registries.insert(package_name.clone(), cached_registry);
-                        cache_hit = true;
-                        package_stats.push((package_name.clone(), 0, package_path.clone(), true));
-                    }
+            if let Some(ref slug) = package.slug {
+                let cache_path = Self::get_package_cache_path(&package.name, slug);
+                if let Some(cached_registry) = Self::load_package_cache(&cache_path, &package_path) {
+                    log::info!("PackageSource: Loaded cached docstrings for package '{}' (slug: {}, {} entries)", 
+                        package.name, slug, cached_registry.len());
+                    cached_registries.insert(package.name, cached_registry);
+                    continue;
                 }
-            }
 
-            // If cache miss, add to packages to process
-            if !cache_hit {
-                if let Some(package_path) = resolve_package_path(
-                    &self.depot_path,
-                    package_name,
-                    manifest,
-                ) {
-                    // Validate slug if we computed one
-                    if let Some(ref computed_slug) = slug {
-                        if let Some(extracted_slug) = extract_package_slug(&package_path) {
-                            if computed_slug != &extracted_slug {
-                                log::warn!("PackageSource: Computed slug '{}' does not match extracted slug '{}' for package '{}'", 
-                                    computed_slug, extracted_slug, package_name);
-                            }
-                        }
+                // Validate slug against the installed directory
+                if let Some(extracted_slug) = extract_package_slug(&package_path) {
+                    if package.origin == PackageOrigin::Depot && slug != &extracted_slug {
+                        log::warn!("PackageSource: Computed slug '{}' does not match extracted slug '{}' for package '{}'", 
+                            slug, extracted_slug, package.name);
                     }
-
-                    log::info!("PackageSource: Will process package '{}' from {:?}", package_name, package_path);
-                    package_stats.push((package_name.clone(), 0, package_path.clone(), false));
-                } else {
-                    log::warn!("PackageSource: Could not resolve path for package '{}'", package_name);
-                    package_stats.push((package_name.clone(), 0, PathBuf::new(), false));
                 }
             }
+
+            log::info!("PackageSource: Will process package '{}' from {:?}", package.name, package_path);
+            packages_to_process.push(PackageMetadata {
+                name: package.name,
+                slug: package.slug,
+                path: package_path,
+            });
         }
 
-        let cached_count = cached_registries.len();
-        let processed_count = package_stats.iter().filter(|(_, _, _, cached)| !cached).count();
         log::info!("PackageSource: {} packages from cache, {} packages to process", 
-            cached_count, processed_count);
-
-        // Collect packages that were processed (not cached)
-        let packages_to_process: Vec<PackageMetadata> = package_stats
-            .iter()
-            .filter(|(_, _, _, cached)| !cached)
-            .filter_map(|(name, _, path, _)| {
-                if path.exists() {
-                    // Try to get slug from manifest
-                    let slug = if let Some(manifest) = manifest {
-                        manifest.packages.get(name).and_then(|entries| {
-                            entries.iter().find_map(|entry| {
-                                if let (Some(uuid), Some(git_tree_sha1)) = (&entry.uuid, &entry.git_tree_sha1) {
-                                    compute_package_slug(uuid, git_tree_sha1).ok()
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    } else {
-                        None
-                    };
-                    
-                    Some(PackageMetadata {
-                        name: name.clone(),
-                        slug,
-                        path: path.clone(),
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+            cached_registries.len(), packages_to_process.len());
 
         Ok(DiscoverResult {
             cached_registries,
@@ -187,33 +116,33 @@ impl PackageSource {
     /// 2. Transitive dependencies are typically re-exported by direct dependencies if needed
     /// 3. Scanning transitive dependencies can be very expensive for large dependency trees
     pub fn discover(&self) -> Result<Vec<SourceItem>, LspError> {
-        // Legacy method - discover files for processing
-        let Some(dependencies) = self.project_context.dependencies() else {
-            return Ok(Vec::new());
-        };
-        
         let mut source_items = Vec::new();
-        let manifest = self.project_context.manifest_toml.as_ref();
         
-        for package_name in dependencies.keys() {
-            if let Some(package_path) = resolve_package_path(
-                &self.depot_path,
-                package_name,
-                manifest,
-            ) {
-                match Self::discover_package_files(&package_path, package_name) {
-                    Ok(mut package_items) => {
-                        source_items.append(&mut package_items);
-                    }
-                    Err(e) => {
-                        log::warn!("PackageSource: Failed to discover files for package {}: {}", package_name, e);
-                    }
+        for package in self.loadable_packages() {
+            let Some(package_path) = package.path else {
+                continue;
+            };
+            match Self::discover_package_files(&package_path, &package.name) {
+                Ok(mut package_items) => {
+                    source_items.append(&mut package_items);
+                }
+                Err(e) => {
+                    log::warn!("PackageSource: Failed to discover files for package {}: {}", package.name, e);
                 }
             }
         }
         
         Ok(source_items)
     }
+
+    /// Packages loadable from the project, excluding stdlibs
+    fn loadable_packages(&self) -> Vec<ResolvedPackage> {
+        self.project_context
+            .resolve_packages(Some(&self.depot_path))
+            .into_iter()
+            .filter(|package| package.origin != PackageOrigin::Stdlib)
+            .collect()
+    }
     
 
     fn discover_package_files(
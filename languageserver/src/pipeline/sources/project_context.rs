@@ -1,9 +1,14 @@
+use crate::pipeline::module_tree::normalize;
+use crate::pipeline::sources::compat::VersionNumber;
 use crate::types::LspError;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Project file names in the order Julia looks for them
+const PROJECT_NAMES: &[&str] = &["JuliaProject.toml", "Project.toml"];
+
 /// Represents a Julia project's context parsed from Project.toml
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProjectToml {
@@ -15,6 +20,65 @@ pub struct ProjectToml {
     pub dependencies: Option<HashMap<String, String>>,
     #[serde(rename = "compat")]
     pub compat: Option<HashMap<String, String>>,
+    pub weakdeps: Option<HashMap<String, String>>,
+    pub extras: Option<HashMap<String, String>>,
+    /// Package extensions: extension module name -> trigger package(s)
+    pub extensions: Option<HashMap<String, ExtensionTriggers>>,
+    /// `[sources]` overrides for where dependencies come from
+    pub sources: Option<HashMap<String, SourceSpec>>,
+    /// `[workspace]` table (Julia 1.12+)
+    pub workspace: Option<WorkspaceSpec>,
+    /// Explicit manifest path, relative to the project file
+    pub manifest: Option<String>,
+}
+
+impl ProjectToml {
+    /// Parse Project.toml content
+    pub fn parse(content: &str) -> Result<Self, LspError> {
+        toml::from_str(content)
+            .map_err(|e| LspError::ParseError(format!("Failed to parse Project.toml: {}", e)))
+    }
+
+    /// Read and parse a project file
+    pub fn read(path: &Path) -> Result<Self, LspError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| LspError::InternalError(format!("Failed to read {:?}: {}", path, e)))?;
+        Self::parse(&content)
+    }
+}
+
+/// Trigger packages of an extension: `Ext = "Dep"` or `Ext = ["DepA", "DepB"]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ExtensionTriggers {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ExtensionTriggers {
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            ExtensionTriggers::One(name) => vec![name.clone()],
+            ExtensionTriggers::Many(names) => names.clone(),
+        }
+    }
+}
+
+/// A `[sources]` entry: either a local path or a repository URL
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceSpec {
+    pub path: Option<String>,
+    pub url: Option<String>,
+    pub rev: Option<String>,
+    pub subdir: Option<String>,
+}
+
+/// The `[workspace]` table of a project
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorkspaceSpec {
+    /// Member project directories, relative to the workspace root
+    #[serde(default)]
+    pub projects: Vec<String>,
 }
 
 /// Represents dependency information from Manifest.toml
@@ -26,31 +90,231 @@ pub struct ManifestEntry {
     pub repo: Option<String>,
     #[serde(rename = "git-tree-sha1")]
     pub git_tree_sha1: Option<String>,
+    /// Names of the package's dependencies
+    #[serde(default)]
+    pub deps: Vec<String>,
+    /// Names of the package's weak dependencies
+    #[serde(default)]
+    pub weakdeps: Vec<String>,
+    /// Extension module name -> trigger packages
+    #[serde(default)]
+    pub extensions: HashMap<String, Vec<String>>,
+}
+
+impl ManifestEntry {
+    /// Stdlibs are recorded without a tree hash, path or repository
+    pub fn is_stdlib(&self) -> bool {
+        self.git_tree_sha1.is_none() && self.path.is_none() && self.repo.is_none()
+    }
+
+    fn from_table(table: &toml::value::Table) -> Self {
+        let string = |key: &str| table.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        Self {
+            uuid: string("uuid"),
+            version: string("version"),
+            path: string("path"),
+            repo: string("repo-url").or_else(|| string("repo")),
+            git_tree_sha1: string("git-tree-sha1"),
+            deps: dependency_names(table.get("deps")),
+            weakdeps: dependency_names(table.get("weakdeps")),
+            extensions: table
+                .get("extensions")
+                .and_then(|v| v.as_table())
+                .map(|extensions| {
+                    extensions
+                        .iter()
+                        .map(|(name, triggers)| (name.clone(), dependency_names(Some(triggers))))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Dependency lists are either `["A", "B"]`, `{A = "uuid", B = "uuid"}` or a single name
+fn dependency_names(value: Option<&toml::Value>) -> Vec<String> {
+    match value {
+        Some(toml::Value::String(name)) => vec![name.clone()],
+        Some(toml::Value::Array(names)) => names.iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+        Some(toml::Value::Table(table)) => {
+            let mut names: Vec<String> = table.keys().cloned().collect();
+            names.sort();
+            names
+        }
+        _ => Vec::new(),
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ManifestToml {
+    /// Julia version the manifest was resolved with
+    pub julia_version: Option<String>,
+    /// "2.0" for current manifests; absent for the legacy format
+    pub manifest_format: Option<String>,
     #[serde(flatten)]
     pub packages: HashMap<String, Vec<ManifestEntry>>,
 }
 
+impl ManifestToml {
+    /// Parse Manifest.toml content in either the current (`[[deps.Name]]`) or
+    /// the legacy pre-1.7 (`[[Name]]`) format
+    pub fn parse(content: &str) -> Result<Self, LspError> {
+        let value: toml::Value = toml::from_str(content)
+            .map_err(|e| LspError::ParseError(format!("Failed to parse Manifest.toml: {}", e)))?;
+        let string = |key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        let manifest_format = string("manifest_format");
+
+        // Current manifests nest packages under a `deps` table of arrays; some writers omit
+        // `manifest_format`, so the layout is detected from `deps` itself. A legacy manifest
+        // with a package called `deps` has `[[deps]]`, an array, there instead.
+        let deps = value
+            .get("deps")
+            .and_then(|v| v.as_table())
+            .filter(|deps| deps.values().all(|v| v.is_array()));
+        let root = deps.or_else(|| value.as_table());
+
+        let mut packages: HashMap<String, Vec<ManifestEntry>> = HashMap::new();
+        for (package_name, package_value) in root.into_iter().flatten() {
+            let Some(entries) = package_value.as_array() else {
+                continue;
+            };
+            let manifest_entries: Vec<ManifestEntry> = entries
+                .iter()
+                .filter_map(|entry| entry.as_table())
+                .map(ManifestEntry::from_table)
+                .collect();
+            if !manifest_entries.is_empty() {
+                packages.insert(package_name.clone(), manifest_entries);
+            }
+        }
+
+        Ok(Self {
+            julia_version: string("julia_version"),
+            manifest_format,
+            packages,
+        })
+    }
+
+    /// Find the entry for a package, preferring the one with a matching UUID
+    pub fn find(&self, package_name: &str, uuid: Option<&str>) -> Option<&ManifestEntry> {
+        let entries = self.packages.get(package_name)?;
+        uuid.and_then(|uuid| entries.iter().find(|e| e.uuid.as_deref() == Some(uuid)))
+            .or_else(|| entries.first())
+    }
+}
+
+/// An entry of Julia's default `LOAD_PATH`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoadPathEntry {
+    /// `@`: the active project (and its workspace)
+    Active,
+    /// `@v#.#`: the depot's shared default environment
+    Default,
+    /// `@stdlib`: the standard libraries of the Julia installation
+    Stdlib,
+}
+
+/// Where a loadable package's source code comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageOrigin {
+    /// Installed into the depot from a registry or repository URL
+    Depot,
+    /// A `develop`ed package or a `[sources]` path
+    Path,
+    /// Shipped with Julia
+    Stdlib,
+    /// Found in the project's local `deps/` folder
+    Local,
+}
+
+/// A package extension whose trigger packages are all present in the environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageExtension {
+    pub name: String,
+    pub triggers: Vec<String>,
+    /// `ext/<Name>.jl` or `ext/<Name>/<Name>.jl`, if it exists
+    pub entry: Option<PathBuf>,
+}
+
+/// A package that `using`/`import` would load from the project
+#[derive(Debug, Clone)]
+pub struct ResolvedPackage {
+    pub name: String,
+    pub uuid: Option<String>,
+    pub version: Option<String>,
+    /// Package root directory, if it could be located
+    pub path: Option<PathBuf>,
+    /// Depot slug computed from UUID and git-tree-sha1
+    pub slug: Option<String>,
+    pub origin: PackageOrigin,
+    /// The `LOAD_PATH` entry the package is loaded from
+    pub environment: LoadPathEntry,
+    /// Extensions of this package that are active in the environment
+    pub extensions: Vec<PackageExtension>,
+}
+
+impl ResolvedPackage {
+    /// Cache key for the package's index: the slug plus its active extensions
+    pub fn cache_key(&self) -> Option<String> {
+        let slug = self.slug.as_ref()?;
+        let mut parts: Vec<&str> = self.extensions.iter().map(|e| e.name.as_str()).collect();
+        parts.sort_unstable();
+        parts.insert(0, slug);
+        Some(parts.join("+"))
+    }
+}
+
+/// A project listed in a `[workspace]`; it shares the workspace root's manifest
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    pub root_path: PathBuf,
+    pub project_toml: ProjectToml,
+}
+
+/// A project environment as seen by code loading: projects sharing one manifest
+struct Environment<'a> {
+    entry: LoadPathEntry,
+    /// Project directories with their parsed project files
+    projects: Vec<(&'a Path, &'a ProjectToml)>,
+    manifest: Option<&'a ManifestToml>,
+    manifest_dir: Option<&'a Path>,
+}
+
 /// Project context that understands Julia project structure
 #[derive(Debug, Clone)]
 pub struct ProjectContext {
     /// Project root directory
     pub root_path: PathBuf,
-    
+
+    /// Path of the project file (`JuliaProject.toml` or `Project.toml`)
+    pub project_file: Option<PathBuf>,
+
     /// Parsed Project.toml
     pub project_toml: Option<ProjectToml>,
-    
+
+    /// Path of the manifest the project resolves against
+    pub manifest_file: Option<PathBuf>,
+
     /// Parsed Manifest.toml
     pub manifest_toml: Option<ManifestToml>,
-    
+
+    /// Root of the enclosing `[workspace]`, when the project is a workspace member
+    pub workspace_root: Option<PathBuf>,
+
+    /// Projects listed in this project's `[workspace]`
+    pub workspace_members: Vec<WorkspaceMember>,
+
     /// Resolved package paths for dependencies
     pub package_paths: HashMap<String, PathBuf>,
-    
+
     /// Optional Julia depot path for resolving packages
     pub depot_path: Option<PathBuf>,
+
+    /// Julia version (major.minor) of the configured installation
+    pub julia_version: Option<VersionNumber>,
+
+    /// Versioned stdlib directory of the Julia installation (`share/julia/stdlib/v#.#`)
+    pub stdlib_path: Option<PathBuf>,
 }
 
 impl ProjectContext {
@@ -58,234 +322,574 @@ impl ProjectContext {
     pub fn new(root_path: PathBuf) -> Result<Self, LspError> {
         Self::with_depot_path(root_path, None)
     }
-    
+
     /// Create a new project context with an optional depot path
     pub fn with_depot_path(root_path: PathBuf, depot_path: Option<PathBuf>) -> Result<Self, LspError> {
+        Self::with_environment(root_path, depot_path, None)
+    }
+
+    /// Create a new project context for a depot and Julia installation
+    ///
+    /// The Julia executable is used to locate the stdlib directory, which also
+    /// determines the Julia version for versioned manifests and `@v#.#`.
+    pub fn with_environment(
+        root_path: PathBuf,
+        depot_path: Option<PathBuf>,
+        julia_executable: Option<&Path>,
+    ) -> Result<Self, LspError> {
         log::trace!("ProjectContext: Creating context for project at {:?}", root_path);
-        
+
         if !root_path.exists() {
             return Err(LspError::InternalError(format!(
                 "Project root does not exist: {:?}",
                 root_path
             )));
         }
-        
+
+        let (julia_version, stdlib_path) = julia_executable
+            .and_then(find_stdlib_dir)
+            .map(|(version, path)| (Some(version), Some(path)))
+            .unwrap_or((None, None));
+
         let mut context = Self {
             root_path: root_path.clone(),
+            project_file: None,
             project_toml: None,
+            manifest_file: None,
             manifest_toml: None,
+            workspace_root: None,
+            workspace_members: Vec::new(),
             package_paths: HashMap::new(),
             depot_path,
+            julia_version,
+            stdlib_path,
         };
-        
+
         // Try to parse Project.toml
         context.load_project_toml()?;
-        
+
+        // Load workspace members and locate the enclosing workspace (Julia 1.12)
+        context.load_workspace();
+
         // Try to parse Manifest.toml (optional)
         let _ = context.load_manifest_toml();
-        
+
         // Resolve package paths
         context.resolve_package_paths();
-        
+
         log::trace!(
             "ProjectContext: Initialized with {} dependencies",
             context.package_paths.len()
         );
-        
+
         Ok(context)
     }
-    
+
     /// Load and parse Project.toml
     fn load_project_toml(&mut self) -> Result<(), LspError> {
-        let project_toml_path = self.root_path.join("Project.toml");
-        
-        if !project_toml_path.exists() {
-            log::trace!("ProjectContext: No Project.toml found at {:?}", project_toml_path);
+        let Some(project_toml_path) = find_project_file(&self.root_path) else {
+            log::trace!("ProjectContext: No Project.toml found at {:?}", self.root_path);
             return Ok(());
-        }
-        
-        let content = fs::read_to_string(&project_toml_path)
-            .map_err(|e| LspError::InternalError(format!("Failed to read Project.toml: {}", e)))?;
-        
-        let project_toml: ProjectToml = toml::from_str(&content)
-            .map_err(|e| LspError::ParseError(format!("Failed to parse Project.toml: {}", e)))?;
-        
+        };
+
+        let project_toml = ProjectToml::read(&project_toml_path)?;
+
         log::trace!(
             "ProjectContext: Loaded Project.toml - name: {:?}, {} dependencies",
             project_toml.name,
             project_toml.dependencies.as_ref().map(|d| d.len()).unwrap_or(0)
         );
-        
+
+        self.project_file = Some(project_toml_path);
         self.project_toml = Some(project_toml);
         Ok(())
     }
-    
-    /// Load and parse Manifest.toml
-    fn load_manifest_toml(&mut self) -> Result<(), LspError> {
-        let manifest_toml_path = self.root_path.join("Manifest.toml");
-        
-        if !manifest_toml_path.exists() {
-            log::trace!("ProjectContext: No Manifest.toml found at {:?}", manifest_toml_path);
-            return Ok(());
+
+    /// Collect `[workspace]` members and find the workspace this project belongs to
+    fn load_workspace(&mut self) {
+        if self.project_toml.is_none() {
+            return;
         }
-        
-        let content = fs::read_to_string(&manifest_toml_path)
-            .map_err(|e| LspError::InternalError(format!("Failed to read Manifest.toml: {}", e)))?;
-        
-        // Parse Manifest.toml manually to handle [[deps.PackageName]] format
-        // The TOML format uses array of tables: [[deps.DataFrames]]
-        let value: toml::Value = toml::from_str(&content)
-            .map_err(|e| LspError::ParseError(format!("Failed to parse Manifest.toml: {}", e)))?;
-        
-        let mut packages: HashMap<String, Vec<ManifestEntry>> = HashMap::new();
-        
-        // Extract all deps.* entries
-        if let Some(deps_value) = value.get("deps") {
-            if let Some(root) = deps_value.as_table() {
-                for (package_name, package_value) in root {
-                    if let Some(entries) = package_value.as_array() {
-                        let mut manifest_entries = Vec::new();
-                        for entry_value in entries {
-                            if let Some(entry_table) = entry_value.as_table() {
-                                let entry = ManifestEntry {
-                                    uuid: entry_table.get("uuid")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string()),
-                                    version: entry_table.get("version")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string()),
-                                    path: entry_table.get("path")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string()),
-                                    repo: entry_table.get("repo")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string()),
-                                    git_tree_sha1: entry_table.get("git-tree-sha1")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string()),
-                                };
-                                manifest_entries.push(entry);
-                            }
-                        }
-                        if !manifest_entries.is_empty() {
-                            packages.insert(package_name.clone(), manifest_entries);
-                        }
+
+        let mut visited = HashSet::from([normalize(&self.root_path)]);
+        let mut pending = vec![(self.root_path.clone(), self.project_toml.clone())];
+        while let Some((dir, project)) = pending.pop() {
+            let Some(workspace) = project.and_then(|p| p.workspace) else {
+                continue;
+            };
+            for member in &workspace.projects {
+                let member_dir = normalize(&dir.join(member));
+                if !visited.insert(member_dir.clone()) {
+                    continue;
+                }
+                let Some(project_file) = find_project_file(&member_dir) else {
+                    log::trace!("ProjectContext: Workspace member {:?} has no project file", member_dir);
+                    continue;
+                };
+                match ProjectToml::read(&project_file) {
+                    Ok(project_toml) => {
+                        pending.push((member_dir.clone(), Some(project_toml.clone())));
+                        self.workspace_members.push(WorkspaceMember { root_path: member_dir, project_toml });
                     }
+                    Err(e) => log::warn!("ProjectContext: Failed to parse workspace member {:?}: {}", project_file, e),
                 }
             }
         }
-        
-        let manifest_toml = ManifestToml { packages };
-        log::trace!("ProjectContext: Loaded Manifest.toml with {} package entries", manifest_toml.packages.len());
-        
+
+        self.workspace_root = find_workspace_root(&self.root_path);
+        if let Some(ref workspace_root) = self.workspace_root {
+            log::trace!("ProjectContext: Project is a member of the workspace at {:?}", workspace_root);
+        }
+    }
+
+    /// Load and parse Manifest.toml
+    ///
+    /// Workspace members use the manifest of the workspace root. Versioned
+    /// manifests (`Manifest-v1.11.toml`) take precedence over `Manifest.toml`.
+    fn load_manifest_toml(&mut self) -> Result<(), LspError> {
+        let manifest_path = match self.workspace_root {
+            Some(ref workspace_root) => {
+                let project = find_project_file(workspace_root).and_then(|p| ProjectToml::read(&p).ok());
+                find_manifest_file(workspace_root, project.as_ref(), self.julia_version.as_ref())
+            }
+            None => find_manifest_file(&self.root_path, self.project_toml.as_ref(), self.julia_version.as_ref()),
+        };
+
+        let Some(manifest_toml_path) = manifest_path else {
+            log::trace!("ProjectContext: No Manifest.toml found for {:?}", self.root_path);
+            return Ok(());
+        };
+
+        let content = fs::read_to_string(&manifest_toml_path)
+            .map_err(|e| LspError::InternalError(format!("Failed to read Manifest.toml: {}", e)))?;
+        let manifest_toml = ManifestToml::parse(&content)?;
+        log::trace!(
+            "ProjectContext: Loaded {:?} with {} package entries",
+            manifest_toml_path,
+            manifest_toml.packages.len()
+        );
+
+        self.manifest_file = Some(manifest_toml_path);
         self.manifest_toml = Some(manifest_toml);
         Ok(())
     }
-    
+
     /// Resolve paths to package dependencies
     fn resolve_package_paths(&mut self) {
-        // Check if we have dependencies in Project.toml
-        let Some(ref project_toml) = self.project_toml else {
-            return;
-        };
-        
-        let Some(ref dependencies) = project_toml.dependencies else {
-            return;
+        let depot_path = self.depot_path.clone();
+        self.package_paths = self
+            .resolve_packages(depot_path.as_deref())
+            .into_iter()
+            .filter(|package| package.environment == LoadPathEntry::Active)
+            .filter_map(|package| Some((package.name, package.path?)))
+            .collect();
+    }
+
+    /// Resolve every package loadable from the project through the stacked
+    /// `LOAD_PATH` (`@`, `@v#.#`, `@stdlib`)
+    ///
+    /// As in Julia, a name is loaded from the first environment that provides
+    /// it. Only direct dependencies of each environment are included; the
+    /// active environment also covers the dependencies of its workspace members.
+    pub fn resolve_packages(&self, depot_path: Option<&Path>) -> Vec<ResolvedPackage> {
+        let mut resolved = Vec::new();
+        let mut seen = HashSet::new();
+
+        // `@`: the active project and its workspace members
+        if let Some(ref project_toml) = self.project_toml {
+            let mut projects = vec![(self.root_path.as_path(), project_toml)];
+            projects.extend(self.workspace_members.iter().map(|m| (m.root_path.as_path(), &m.project_toml)));
+            let environment = Environment {
+                entry: LoadPathEntry::Active,
+                projects,
+                manifest: self.manifest_toml.as_ref(),
+                manifest_dir: self.manifest_file.as_deref().and_then(Path::parent),
+            };
+            self.resolve_environment(&environment, depot_path, &mut seen, &mut resolved);
+        }
+
+        // `@v#.#`: the depot's default environment
+        if let Some(default_dir) = depot_path.and_then(|depot| default_environment_dir(depot, self.julia_version.as_ref())) {
+            if let Some(project_toml) = find_project_file(&default_dir).and_then(|p| ProjectToml::read(&p).ok()) {
+                let manifest_file = find_manifest_file(&default_dir, Some(&project_toml), self.julia_version.as_ref());
+                let manifest = manifest_file
+                    .as_ref()
+                    .and_then(|p| fs::read_to_string(p).ok())
+                    .and_then(|content| ManifestToml::parse(&content).ok());
+                let environment = Environment {
+                    entry: LoadPathEntry::Default,
+                    projects: vec![(default_dir.as_path(), &project_toml)],
+                    manifest: manifest.as_ref(),
+                    manifest_dir: manifest_file.as_deref().and_then(Path::parent),
+                };
+                self.resolve_environment(&environment, depot_path, &mut seen, &mut resolved);
+            }
+        }
+
+        // `@stdlib`: every standard library of the installation
+        if let Some(ref stdlib_path) = self.stdlib_path {
+            let mut names: Vec<String> = fs::read_dir(stdlib_path)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter(|e| e.path().join("src").is_dir())
+                        .map(|e| e.file_name().to_string_lossy().to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            names.sort();
+            for name in names {
+                if seen.insert(name.clone()) {
+                    let path = stdlib_path.join(&name);
+                    resolved.push(ResolvedPackage {
+                        uuid: read_project_field(&path, |p| p.uuid.clone()),
+                        version: read_project_field(&path, |p| p.version.clone()),
+                        name,
+                        path: Some(path),
+                        slug: None,
+                        origin: PackageOrigin::Stdlib,
+                        environment: LoadPathEntry::Stdlib,
+                        extensions: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        resolved
+    }
+
+    fn resolve_environment(
+        &self,
+        environment: &Environment,
+        depot_path: Option<&Path>,
+        seen: &mut HashSet<String>,
+        resolved: &mut Vec<ResolvedPackage>,
+    ) {
+        // Packages present in the environment decide which extensions are active
+        let present: HashSet<&str> = match environment.manifest {
+            Some(manifest) => manifest.packages.keys().map(String::as_str).collect(),
+            None => environment
+                .projects
+                .iter()
+                .flat_map(|(_, project)| project.dependencies.iter().flatten())
+                .map(|(name, _)| name.as_str())
+                .collect(),
         };
-        
-        // Try to resolve paths for each dependency
-        for package_name in dependencies.keys() {
-            if let Some(path) = self.try_resolve_package_path(package_name) {
-                self.package_paths.insert(package_name.clone(), path);
+
+        for (project_dir, project) in &environment.projects {
+            let mut dependencies: Vec<(&String, &String)> = project.dependencies.iter().flatten().collect();
+            dependencies.sort();
+            for (name, uuid) in dependencies {
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+                let source = project.sources.as_ref().and_then(|s| s.get(name));
+                let mut package = self.resolve_dependency(environment, project_dir, name, uuid, source, depot_path);
+                if let Some(ref path) = package.path {
+                    let entry = environment.manifest.and_then(|m| m.find(name, Some(uuid)));
+                    package.extensions = active_extensions(path, entry, &present);
+                }
+                resolved.push(package);
             }
         }
     }
-    
-    /// Try to resolve the path for a specific package
-    fn try_resolve_package_path(&self, package_name: &str) -> Option<PathBuf> {
-        // First check if manifest has path information
-        if let Some(ref manifest) = self.manifest_toml {
-            if let Some(entries) = manifest.packages.get(package_name) {
-                for entry in entries {
-                    if let Some(ref path) = entry.path {
-                        let resolved_path = if Path::new(path).is_absolute() {
-                            PathBuf::from(path)
-                        } else {
-                            self.root_path.join(path)
-                        };
-                        
-                        if resolved_path.exists() {
-                            log::trace!("ProjectContext: Resolved package {} from manifest path: {:?}", package_name, resolved_path);
-                            return Some(resolved_path);
-                        }
-                    }
+
+    /// Locate one dependency following Julia's rules: `[sources]` paths and
+    /// `develop`ed paths first, then stdlibs, then the depot
+    fn resolve_dependency(
+        &self,
+        environment: &Environment,
+        project_dir: &Path,
+        name: &str,
+        uuid: &str,
+        source: Option<&SourceSpec>,
+        depot_path: Option<&Path>,
+    ) -> ResolvedPackage {
+        let entry = environment.manifest.and_then(|m| m.find(name, Some(uuid)));
+        let slug = entry.and_then(|e| {
+            let (uuid, git_tree_sha1) = (e.uuid.as_ref()?, e.git_tree_sha1.as_ref()?);
+            match crate::pipeline::sources::indexing::compute_package_slug(uuid, git_tree_sha1) {
+                Ok(slug) => Some(slug),
+                Err(e) => {
+                    log::warn!("ProjectContext: Failed to compute slug for {}: {}", name, e);
+                    None
                 }
             }
+        });
+        let mut package = ResolvedPackage {
+            name: name.to_string(),
+            uuid: Some(uuid.to_string()),
+            version: entry.and_then(|e| e.version.clone()),
+            path: None,
+            slug: slug.clone(),
+            origin: PackageOrigin::Depot,
+            environment: environment.entry,
+            extensions: Vec::new(),
+        };
+
+        // 1. `[sources]` path entries are relative to the declaring project
+        if let Some(path) = source.and_then(|s| s.path.as_ref()) {
+            package.path = existing(normalize(&project_dir.join(path)));
+            package.origin = PackageOrigin::Path;
+            return package;
         }
-        
-        // Try common Julia package locations
-        // 1. Check local project deps folder
-        let local_path = self.root_path.join("deps").join(package_name);
-        if local_path.exists() {
-            log::trace!("ProjectContext: Resolved package {} from local deps: {:?}", package_name, local_path);
-            return Some(local_path);
+
+        // 2. `develop`ed packages record their path relative to the manifest
+        if let Some(path) = entry.and_then(|e| e.path.as_ref()) {
+            let base = environment.manifest_dir.unwrap_or(project_dir);
+            package.path = existing(normalize(&base.join(path)));
+            package.origin = PackageOrigin::Path;
+            if package.version.is_none() {
+                package.version = package.path.as_deref().and_then(|p| read_project_field(p, |t| t.version.clone()));
+            }
+            return package;
         }
-        
-        // 2. Try Julia depot using package_resolver
-        if let Some(ref depot_path) = self.depot_path {
-            use crate::pipeline::sources::indexing::resolve_package_path;
-            if let Some(package_path) = resolve_package_path(depot_path, package_name, self.manifest_toml.as_ref()) {
-                log::trace!("ProjectContext: Resolved package {} from depot: {:?}", package_name, package_path);
-                return Some(package_path);
+
+        // 3. Stdlibs live in the Julia installation and are versioned with it
+        let stdlib_dir = self.stdlib_path.as_ref().map(|p| p.join(name)).filter(|p| p.is_dir());
+        if entry.map(ManifestEntry::is_stdlib).unwrap_or(entry.is_none() && stdlib_dir.is_some()) {
+            package.origin = PackageOrigin::Stdlib;
+            if let Some(dir) = stdlib_dir {
+                if package.version.is_none() {
+                    package.version = read_project_field(&dir, |p| p.version.clone());
+                }
+                package.path = Some(dir);
             }
+            return package;
+        }
+
+        // 4. Local `deps/` folder
+        let local_path = self.root_path.join("deps").join(name);
+        if local_path.exists() {
+            log::trace!("ProjectContext: Resolved package {} from local deps: {:?}", name, local_path);
+            package.path = Some(local_path);
+            package.origin = PackageOrigin::Local;
+            return package;
         }
-        
-        log::trace!("ProjectContext: Could not resolve path for package: {}", package_name);
-        
-        None
+
+        // 5. The depot: `packages/<Name>/<slug>`, then any installed version
+        if let Some(depot_path) = depot_path {
+            package.path = slug
+                .and_then(|slug| existing(depot_path.join("packages").join(name).join(slug)))
+                .or_else(|| {
+                    crate::pipeline::sources::indexing::resolve_package_path(depot_path, name, environment.manifest)
+                });
+        }
+        if package.path.is_none() {
+            log::trace!("ProjectContext: Could not resolve path for package: {}", name);
+        }
+        package
     }
-    
+
+    /// Extensions of the project itself that are active in its environment
+    pub fn project_extensions(&self) -> Vec<PackageExtension> {
+        let Some(ref project_toml) = self.project_toml else {
+            return Vec::new();
+        };
+        let present: HashSet<&str> = match self.manifest_toml {
+            Some(ref manifest) => manifest.packages.keys().map(String::as_str).collect(),
+            None => project_toml.dependencies.iter().flatten().map(|(name, _)| name.as_str()).collect(),
+        };
+        select_extensions(&self.root_path, project_toml.extensions.iter().flatten().map(|(n, t)| (n.clone(), t.names())), &present)
+    }
+
     /// Get project name
     pub fn project_name(&self) -> Option<&str> {
         self.project_toml.as_ref()?.name.as_deref()
     }
-    
+
     /// Get project version
     pub fn project_version(&self) -> Option<&str> {
         self.project_toml.as_ref()?.version.as_deref()
     }
-    
+
     /// Get all dependencies
     pub fn dependencies(&self) -> Option<&HashMap<String, String>> {
         self.project_toml.as_ref()?.dependencies.as_ref()
     }
-    
+
+    /// Get all `[compat]` entries
+    pub fn compat(&self) -> Option<&HashMap<String, String>> {
+        self.project_toml.as_ref()?.compat.as_ref()
+    }
+
+    /// Get the version of a package recorded in Manifest.toml
+    pub fn manifest_version(&self, package_name: &str) -> Option<&str> {
+        let uuid = self.dependencies().and_then(|deps| deps.get(package_name)).map(String::as_str);
+        self.manifest_toml
+            .as_ref()?
+            .find(package_name, uuid)?
+            .version
+            .as_deref()
+    }
+
     /// Get resolved path for a specific package
     pub fn get_package_path(&self, package_name: &str) -> Option<&PathBuf> {
         self.package_paths.get(package_name)
     }
-    
+
     /// Check if a path is within the project
     pub fn is_project_file(&self, path: &Path) -> bool {
         path.starts_with(&self.root_path)
     }
-    
+
     /// Check if project has a valid Project.toml
     pub fn has_project_toml(&self) -> bool {
         self.project_toml.is_some()
     }
 }
 
+/// `JuliaProject.toml` or `Project.toml` in `dir`
+fn find_project_file(dir: &Path) -> Option<PathBuf> {
+    PROJECT_NAMES.iter().map(|name| dir.join(name)).find(|path| path.is_file())
+}
+
+/// Locate the manifest of the project in `dir`
+///
+/// Mirrors Julia's lookup: an explicit `manifest` entry, then
+/// `JuliaManifest-v#.#.toml`, `Manifest-v#.#.toml`, `JuliaManifest.toml` and
+/// `Manifest.toml`. Without a known Julia version the newest versioned
+/// manifest is used only if no unversioned one exists.
+fn find_manifest_file(dir: &Path, project: Option<&ProjectToml>, julia_version: Option<&VersionNumber>) -> Option<PathBuf> {
+    if let Some(manifest) = project.and_then(|p| p.manifest.as_ref()) {
+        return existing(dir.join(manifest));
+    }
+
+    let mut names = Vec::new();
+    if let Some(version) = julia_version {
+        names.push(format!("JuliaManifest-v{}.toml", version.major_minor()));
+        names.push(format!("Manifest-v{}.toml", version.major_minor()));
+    }
+    names.push("JuliaManifest.toml".to_string());
+    names.push("Manifest.toml".to_string());
+    if let Some(path) = names.into_iter().map(|name| dir.join(name)).find(|path| path.is_file()) {
+        return Some(path);
+    }
+    if julia_version.is_some() {
+        return None;
+    }
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let version = file_name
+                .strip_prefix("JuliaManifest-v")
+                .or_else(|| file_name.strip_prefix("Manifest-v"))?
+                .strip_suffix(".toml")
+                .and_then(|v| VersionNumber::parse(v).ok())?;
+            // JuliaManifest wins over Manifest for the same version
+            Some(((version, file_name.starts_with("Julia")), entry.path()))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, path)| path)
+}
+
+/// The root of the `[workspace]` that lists `project_dir`, following nested workspaces
+fn find_workspace_root(project_dir: &Path) -> Option<PathBuf> {
+    let project_dir = normalize(project_dir);
+    // The nearest ancestor with a project file decides
+    let parent = project_dir.ancestors().skip(1).find(|dir| find_project_file(dir).is_some())?;
+    let project = ProjectToml::read(&find_project_file(parent)?).ok()?;
+    let is_member = project
+        .workspace?
+        .projects
+        .iter()
+        .any(|member| normalize(&parent.join(member)) == project_dir);
+    if !is_member {
+        return None;
+    }
+    find_workspace_root(parent).or_else(|| Some(parent.to_path_buf()))
+}
+
+/// The default environment for the Julia version (`<depot>/environments/v#.#`)
+fn default_environment_dir(depot_path: &Path, julia_version: Option<&VersionNumber>) -> Option<PathBuf> {
+    let environments = depot_path.join("environments");
+    if let Some(version) = julia_version {
+        return existing(environments.join(format!("v{}", version.major_minor())));
+    }
+    newest_versioned_dir(&environments).map(|(_, path)| path)
+}
+
+/// Find `<julia>/share/julia/stdlib/v#.#` for a Julia executable in `<julia>/bin`
+fn find_stdlib_dir(julia_executable: &Path) -> Option<(VersionNumber, PathBuf)> {
+    let julia_dir = julia_executable.parent()?.parent()?;
+    [julia_dir.join("share").join("julia").join("stdlib"), julia_dir.join("stdlib")]
+        .iter()
+        .find_map(|dir| newest_versioned_dir(dir))
+}
+
+/// The `v#.#` subdirectory of `dir` with the highest version
+fn newest_versioned_dir(dir: &Path) -> Option<(VersionNumber, PathBuf)> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version = VersionNumber::parse(name.strip_prefix('v')?).ok()?;
+            Some((version, entry.path()))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+}
+
+/// Extensions of the package at `package_path` whose triggers are all in `present`
+///
+/// The manifest records a package's extensions; older manifests do not, so the
+/// package's own project file is the fallback.
+fn active_extensions(package_path: &Path, entry: Option<&ManifestEntry>, present: &HashSet<&str>) -> Vec<PackageExtension> {
+    match entry.filter(|e| !e.extensions.is_empty()) {
+        Some(entry) => select_extensions(package_path, entry.extensions.clone(), present),
+        None => {
+            let declared = find_project_file(package_path)
+                .and_then(|p| ProjectToml::read(&p).ok())
+                .and_then(|p| p.extensions)
+                .unwrap_or_default();
+            select_extensions(package_path, declared.into_iter().map(|(n, t)| (n, t.names())), present)
+        }
+    }
+}
+
+fn select_extensions(
+    package_path: &Path,
+    declared: impl IntoIterator<Item = (String, Vec<String>)>,
+    present: &HashSet<&str>,
+) -> Vec<PackageExtension> {
+    let mut extensions: Vec<PackageExtension> = declared
+        .into_iter()
+        .filter(|(_, triggers)| triggers.iter().all(|t| present.contains(t.as_str())))
+        .map(|(name, triggers)| {
+            let ext_dir = package_path.join("ext");
+            let entry = existing(ext_dir.join(format!("{}.jl", name)))
+                .or_else(|| existing(ext_dir.join(&name).join(format!("{}.jl", name))));
+            PackageExtension { name, triggers, entry }
+        })
+        .collect();
+    extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    extensions
+}
+
+/// Read a field from the project file of the package at `dir`
+fn read_project_field(dir: &Path, field: impl Fn(&ProjectToml) -> Option<String>) -> Option<String> {
+    let project = ProjectToml::read(&find_project_file(dir)?).ok()?;
+    field(&project)
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+    path.exists().then_some(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
-    
+
     fn create_test_project() -> (TempDir, PathBuf) {
         let temp_dir = TempDir::new().unwrap();
         let project_path = temp_dir.path().to_path_buf();
-        
+
         let project_toml = r#"
 name = "TestProject"
 uuid = "12345678-1234-1234-1234-123456789012"
@@ -295,58 +899,303 @@ version = "0.1.0"
 JSON = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
 DataFrames = "a93c6f00-e57d-5684-b7b6-d8193f3e46c0"
 "#;
-        
+
         fs::write(project_path.join("Project.toml"), project_toml).unwrap();
-        
+
         (temp_dir, project_path)
     }
-    
+
+    fn create_test_project_in(dir: &Path) -> PathBuf {
+        let project_path = dir.join("Project");
+        fs::create_dir_all(&project_path).unwrap();
+        fs::write(
+            project_path.join("Project.toml"),
+            "name = \"TestProject\"\n\n[deps]\nJSON = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\n",
+        )
+        .unwrap();
+        project_path
+    }
+
+    /// A Julia installation with `bin/julia` and a versioned stdlib directory
+    fn create_julia_install(root: &Path, version: &str, stdlibs: &[(&str, &str)]) -> PathBuf {
+        let stdlib = root.join("julia").join("share").join("julia").join("stdlib").join(format!("v{}", version));
+        fs::create_dir_all(&stdlib).unwrap();
+        for (name, stdlib_version) in stdlibs {
+            fs::create_dir_all(stdlib.join(name).join("src")).unwrap();
+            fs::write(
+                stdlib.join(name).join("Project.toml"),
+                format!("name = \"{}\"\nversion = \"{}\"\n", name, stdlib_version),
+            )
+            .unwrap();
+        }
+        let bin = root.join("julia").join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        bin.join("julia")
+    }
+
     #[test]
     fn test_project_context_creation() {
         let (_temp_dir, project_path) = create_test_project();
-        
+
         let context = ProjectContext::new(project_path.clone()).unwrap();
-        
+
         assert!(context.has_project_toml());
         assert_eq!(context.project_name(), Some("TestProject"));
         assert_eq!(context.project_version(), Some("0.1.0"));
     }
-    
+
     #[test]
     fn test_dependencies_parsing() {
         let (_temp_dir, project_path) = create_test_project();
-        
+
         let context = ProjectContext::new(project_path).unwrap();
-        
+
         let deps = context.dependencies().unwrap();
         assert_eq!(deps.len(), 2);
         assert!(deps.contains_key("JSON"));
         assert!(deps.contains_key("DataFrames"));
     }
-    
+
     #[test]
     fn test_project_without_toml() {
         let temp_dir = TempDir::new().unwrap();
         let project_path = temp_dir.path().to_path_buf();
-        
+
         // Create context without Project.toml
         let context = ProjectContext::new(project_path).unwrap();
-        
+
         assert!(!context.has_project_toml());
         assert_eq!(context.project_name(), None);
         assert_eq!(context.dependencies(), None);
     }
-    
+
     #[test]
     fn test_is_project_file() {
         let (_temp_dir, project_path) = create_test_project();
         let context = ProjectContext::new(project_path.clone()).unwrap();
-        
+
         let file_in_project = project_path.join("src").join("main.jl");
         assert!(context.is_project_file(&file_in_project));
-        
+
         let file_outside = PathBuf::from("/tmp/other.jl");
         assert!(!context.is_project_file(&file_outside));
     }
-}
 
+    #[test]
+    fn test_legacy_manifest_format() {
+        let manifest = ManifestToml::parse(
+            r#"
+[[JSON]]
+deps = ["Dates", "Mmap"]
+git-tree-sha1 = "31e996f0a15c7b280ba9f76636b3ff9e2ae58c9a"
+uuid = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
+version = "0.21.4"
+"#,
+        )
+        .unwrap();
+
+        let entry = manifest.find("JSON", None).unwrap();
+        assert_eq!(entry.version.as_deref(), Some("0.21.4"));
+        assert_eq!(entry.deps, vec!["Dates", "Mmap"]);
+        assert!(manifest.manifest_format.is_none());
+    }
+
+    #[test]
+    fn test_deps_table_without_manifest_format() {
+        let manifest = ManifestToml::parse(
+            "julia_version = \"1.10.4\"\n\n[[deps.JSON]]\nuuid = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\nversion = \"0.21.4\"\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.find("JSON", None).unwrap().version.as_deref(), Some("0.21.4"));
+
+        // A legacy manifest may contain a package named `deps`
+        let manifest = ManifestToml::parse("[[deps]]\nuuid = \"00000000-0000-0000-0000-000000000001\"\nversion = \"1.0.0\"\n").unwrap();
+        assert_eq!(manifest.find("deps", None).unwrap().version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_versioned_manifest_preferred() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = create_test_project_in(temp_dir.path());
+        let julia = create_julia_install(temp_dir.path(), "1.11", &[]);
+        let manifest = |version: &str| {
+            format!(
+                "julia_version = \"{}\"\nmanifest_format = \"2.0\"\n\n[[deps.JSON]]\nuuid = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\ngit-tree-sha1 = \"31e996f0a15c7b280ba9f76636b3ff9e2ae58c9a\"\nversion = \"{}\"\n",
+                version, version
+            )
+        };
+        fs::write(project_path.join("Manifest.toml"), manifest("0.21.0")).unwrap();
+        fs::write(project_path.join("Manifest-v1.11.toml"), manifest("0.21.4")).unwrap();
+        fs::write(project_path.join("Manifest-v1.12.toml"), manifest("0.21.5")).unwrap();
+
+        let context = ProjectContext::with_environment(project_path.clone(), None, Some(&julia)).unwrap();
+        assert_eq!(context.julia_version.as_ref().map(|v| v.major_minor()), Some("1.11".to_string()));
+        assert_eq!(context.manifest_file, Some(project_path.join("Manifest-v1.11.toml")));
+        assert_eq!(context.manifest_version("JSON"), Some("0.21.4"));
+
+        // Without a known Julia version the unversioned manifest wins
+        let context = ProjectContext::new(project_path.clone()).unwrap();
+        assert_eq!(context.manifest_version("JSON"), Some("0.21.0"));
+    }
+
+    #[test]
+    fn test_workspace_member_uses_root_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("MyPkg");
+        let test_dir = root.join("test");
+        fs::create_dir_all(&test_dir).unwrap();
+        fs::write(
+            root.join("Project.toml"),
+            "name = \"MyPkg\"\n\n[deps]\nJSON = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\n\n[workspace]\nprojects = [\"test\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.join("Project.toml"),
+            "[deps]\nTest = \"8dfed614-e22c-5e08-85e1-65c5234f0b40\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Manifest.toml"),
+            "manifest_format = \"2.0\"\n\n[[deps.Test]]\nuuid = \"8dfed614-e22c-5e08-85e1-65c5234f0b40\"\nversion = \"1.11.0\"\n",
+        )
+        .unwrap();
+
+        let member = ProjectContext::new(test_dir.clone()).unwrap();
+        assert_eq!(member.workspace_root, Some(normalize(&root)));
+        assert_eq!(member.manifest_file, Some(normalize(&root).join("Manifest.toml")));
+        assert_eq!(member.manifest_version("Test"), Some("1.11.0"));
+
+        let workspace = ProjectContext::new(root.clone()).unwrap();
+        assert_eq!(workspace.workspace_members.len(), 1);
+        let names: Vec<String> = workspace.resolve_packages(None).into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["JSON", "Test"]);
+    }
+
+    #[test]
+    fn test_sources_and_developed_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("App");
+        let local = temp_dir.path().join("LocalDep");
+        let developed = temp_dir.path().join("dev").join("DevDep");
+        for dir in [&root, &local, &developed] {
+            fs::create_dir_all(dir.join("src")).unwrap();
+        }
+        fs::write(developed.join("Project.toml"), "name = \"DevDep\"\nversion = \"0.3.0\"\n").unwrap();
+        fs::write(
+            root.join("Project.toml"),
+            r#"
+[deps]
+LocalDep = "11111111-1111-1111-1111-111111111111"
+DevDep = "22222222-2222-2222-2222-222222222222"
+
+[sources]
+LocalDep = {path = "../LocalDep"}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("Manifest.toml"),
+            r#"
+manifest_format = "2.0"
+
+[[deps.DevDep]]
+path = "../dev/DevDep"
+uuid = "22222222-2222-2222-2222-222222222222"
+"#,
+        )
+        .unwrap();
+
+        let context = ProjectContext::new(root.clone()).unwrap();
+        let packages = context.resolve_packages(None);
+        let dev = packages.iter().find(|p| p.name == "DevDep").unwrap();
+        assert_eq!(dev.origin, PackageOrigin::Path);
+        assert_eq!(dev.path, Some(developed));
+        assert_eq!(dev.version.as_deref(), Some("0.3.0"));
+
+        let local_dep = packages.iter().find(|p| p.name == "LocalDep").unwrap();
+        assert_eq!(local_dep.origin, PackageOrigin::Path);
+        assert_eq!(local_dep.path, Some(local));
+    }
+
+    #[test]
+    fn test_stacked_load_path_and_stdlib_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = create_test_project_in(temp_dir.path());
+        let julia = create_julia_install(temp_dir.path(), "1.10", &[("LinearAlgebra", "1.10.0"), ("Test", "1.10.0")]);
+        fs::write(
+            project_path.join("Manifest.toml"),
+            "manifest_format = \"2.0\"\n\n[[deps.JSON]]\nuuid = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\ngit-tree-sha1 = \"31e996f0a15c7b280ba9f76636b3ff9e2ae58c9a\"\nversion = \"0.21.4\"\n",
+        )
+        .unwrap();
+
+        // The default environment provides Revise and a shadowed JSON
+        let depot = temp_dir.path().join("depot");
+        let default_env = depot.join("environments").join("v1.10");
+        fs::create_dir_all(&default_env).unwrap();
+        fs::write(
+            default_env.join("Project.toml"),
+            "[deps]\nJSON = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\nRevise = \"295af30f-e4ad-537b-8983-00126c2a3abe\"\n",
+        )
+        .unwrap();
+
+        let context = ProjectContext::with_environment(project_path, Some(depot.clone()), Some(&julia)).unwrap();
+        let packages = context.resolve_packages(Some(&depot));
+        let summary: Vec<(&str, LoadPathEntry)> = packages.iter().map(|p| (p.name.as_str(), p.environment)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("JSON", LoadPathEntry::Active),
+                ("Revise", LoadPathEntry::Default),
+                ("LinearAlgebra", LoadPathEntry::Stdlib),
+                ("Test", LoadPathEntry::Stdlib),
+            ]
+        );
+        assert_eq!(packages[0].version.as_deref(), Some("0.21.4"));
+        assert_eq!(packages[2].origin, PackageOrigin::Stdlib);
+        assert_eq!(packages[2].version.as_deref(), Some("1.10.0"));
+    }
+
+    #[test]
+    fn test_extensions_activated_by_triggers() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("App");
+        let plots = temp_dir.path().join("dev").join("PlotsBase");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(plots.join("ext").join("UnitfulExt")).unwrap();
+        fs::write(plots.join("ext").join("JSONExt.jl"), "module JSONExt end").unwrap();
+        fs::write(plots.join("ext").join("UnitfulExt").join("UnitfulExt.jl"), "module UnitfulExt end").unwrap();
+        fs::write(
+            root.join("Project.toml"),
+            "[deps]\nPlotsBase = \"33333333-3333-3333-3333-333333333333\"\nJSON = \"682c06a0-de6a-54ab-a142-c8b1cf79cde6\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Manifest.toml"),
+            r#"
+manifest_format = "2.0"
+
+[[deps.PlotsBase]]
+path = "../dev/PlotsBase"
+uuid = "33333333-3333-3333-3333-333333333333"
+weakdeps = ["JSON", "Unitful"]
+
+    [deps.PlotsBase.extensions]
+    JSONExt = "JSON"
+    UnitfulExt = ["Unitful", "JSON"]
+
+[[deps.JSON]]
+git-tree-sha1 = "31e996f0a15c7b280ba9f76636b3ff9e2ae58c9a"
+uuid = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
+version = "0.21.4"
+"#,
+        )
+        .unwrap();
+
+        let context = ProjectContext::new(root).unwrap();
+        let packages = context.resolve_packages(None);
+        let plots_base = packages.iter().find(|p| p.name == "PlotsBase").unwrap();
+        assert_eq!(plots_base.extensions.len(), 1);
+        assert_eq!(plots_base.extensions[0].name, "JSONExt");
+        assert_eq!(plots_base.extensions[0].entry, Some(plots.join("ext").join("JSONExt.jl")));
+    }
+}
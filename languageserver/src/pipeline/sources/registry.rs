@@ -0,0 +1,344 @@
+use crate::pipeline::sources::compat::VersionNumber;
+use crate::types::LspError;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// A package entry from a Julia package registry
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryPackage {
+    pub name: String,
+    pub uuid: String,
+    /// Name of the registry the package comes from (e.g., "General")
+    pub registry: String,
+    /// Package directory inside the registry (e.g., "D/DataFrames")
+    pub path: String,
+    /// Non-yanked versions, sorted ascending (None until loaded)
+    pub versions: Option<Vec<VersionNumber>>,
+}
+
+/// Where a registry's files live on disk
+#[derive(Debug, Clone)]
+enum RegistryStorage {
+    /// Unpacked registry: {depot}/registries/General/
+    Directory(PathBuf),
+    /// Compressed registry: {depot}/registries/General.tar.gz (described by General.toml)
+    Tarball(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+struct RegistryInfo {
+    name: String,
+    storage: RegistryStorage,
+}
+
+/// Read-only view of the package registries installed in a Julia depot
+///
+/// Loading reads only each registry's `Registry.toml` (names and UUIDs);
+/// per-package `Versions.toml` files are read on demand with `load_versions`.
+#[derive(Debug, Clone, Default)]
+pub struct LocalRegistry {
+    registries: Vec<RegistryInfo>,
+    /// UUID -> package
+    packages: HashMap<String, RegistryPackage>,
+    /// Package name -> UUIDs (a name can be registered in several registries)
+    by_name: HashMap<String, Vec<String>>,
+}
+
+impl LocalRegistry {
+    /// Load all registries found in `{depot}/registries`
+    pub fn load(depot_path: &Path) -> Result<Self, LspError> {
+        let registries_dir = depot_path.join("registries");
+        let mut registry = Self::default();
+
+        if !registries_dir.is_dir() {
+            log::trace!("LocalRegistry: No registries directory at {:?}", registries_dir);
+            return Ok(registry);
+        }
+
+        // `read_dir` order is arbitrary: visit unpacked registries before tarball descriptors
+        // so that the unpacked copy wins, and in name order for a stable result
+        let mut paths: Vec<PathBuf> = fs::read_dir(&registries_dir)?.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        paths.sort_by_key(|path| !path.is_dir());
+
+        let mut seen = HashSet::new();
+        for path in paths {
+            let info = if path.is_dir() && path.join("Registry.toml").exists() {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                RegistryInfo { name, storage: RegistryStorage::Directory(path.clone()) }
+            } else if path.extension().map(|e| e == "toml").unwrap_or(false) {
+                // Compressed registry descriptor: General.toml with `path = "General.tar.gz"`
+                let Some(tarball) = Self::tarball_from_descriptor(&path) else {
+                    continue;
+                };
+                let name = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                RegistryInfo { name, storage: RegistryStorage::Tarball(tarball) }
+            } else {
+                continue;
+            };
+
+            // Prefer the unpacked copy if a registry is present in both forms
+            if !seen.insert(info.name.clone()) {
+                continue;
+            }
+
+            match registry.load_registry_toml(&info) {
+                Ok(count) => log::trace!("LocalRegistry: Loaded {} packages from registry '{}'", count, info.name),
+                Err(e) => log::warn!("LocalRegistry: Failed to load registry '{}': {}", info.name, e),
+            }
+            registry.registries.push(info);
+        }
+
+        Ok(registry)
+    }
+
+    fn tarball_from_descriptor(descriptor: &Path) -> Option<PathBuf> {
+        let content = fs::read_to_string(descriptor).ok()?;
+        let value: toml::Value = toml::from_str(&content).ok()?;
+        let tarball = descriptor.parent()?.join(value.get("path")?.as_str()?);
+        tarball.exists().then_some(tarball)
+    }
+
+    fn load_registry_toml(&mut self, info: &RegistryInfo) -> Result<usize, LspError> {
+        let content = match &info.storage {
+            RegistryStorage::Directory(dir) => fs::read_to_string(dir.join("Registry.toml"))?,
+            RegistryStorage::Tarball(tarball) => {
+                let wanted: HashSet<String> = ["Registry.toml".to_string()].into_iter().collect();
+                Self::read_tarball_files(tarball, &wanted)?
+                    .remove("Registry.toml")
+                    .ok_or_else(|| LspError::InternalError(format!("No Registry.toml in {:?}", tarball)))?
+            }
+        };
+
+        let value: toml::Value = toml::from_str(&content)
+            .map_err(|e| LspError::ParseError(format!("Failed to parse Registry.toml: {}", e)))?;
+        let Some(packages) = value.get("packages").and_then(|p| p.as_table()) else {
+            return Ok(0);
+        };
+
+        let mut count = 0;
+        for (uuid, entry) in packages {
+            let (Some(name), Some(path)) = (
+                entry.get("name").and_then(|v| v.as_str()),
+                entry.get("path").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            self.by_name.entry(name.to_string()).or_default().push(uuid.clone());
+            self.packages.insert(uuid.clone(), RegistryPackage {
+                name: name.to_string(),
+                uuid: uuid.clone(),
+                registry: info.name.clone(),
+                path: path.to_string(),
+                versions: None,
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Read the given files (registry-relative paths) from a registry tarball in a single pass
+    fn read_tarball_files(tarball: &Path, wanted: &HashSet<String>) -> Result<HashMap<String, String>, LspError> {
+        let file = fs::File::open(tarball)?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut found = HashMap::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.to_string_lossy().replace('\\', "/");
+            let entry_path = entry_path.trim_start_matches("./").to_string();
+            if wanted.contains(&entry_path) {
+                let mut content = String::new();
+                entry.read_to_string(&mut content)?;
+                found.insert(entry_path, content);
+                if found.len() == wanted.len() {
+                    break;
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    /// Load `Versions.toml` for the given package UUIDs (no-op for already loaded ones)
+    pub fn load_versions(&mut self, uuids: &HashSet<String>) {
+        for info in self.registries.clone() {
+            let pending: Vec<String> = uuids
+                .iter()
+                .filter(|uuid| {
+                    self.packages
+                        .get(*uuid)
+                        .map(|p| p.registry == info.name && p.versions.is_none())
+                        .unwrap_or(false)
+                })
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
+
+            let contents: HashMap<String, String> = match &info.storage {
+                RegistryStorage::Directory(dir) => pending
+                    .iter()
+                    .filter_map(|uuid| {
+                        let package = self.packages.get(uuid)?;
+                        let content = fs::read_to_string(dir.join(&package.path).join("Versions.toml")).ok()?;
+                        Some((uuid.clone(), content))
+                    })
+                    .collect(),
+                RegistryStorage::Tarball(tarball) => {
+                    let paths: HashMap<String, String> = pending
+                        .iter()
+                        .filter_map(|uuid| {
+                            let package = self.packages.get(uuid)?;
+                            Some((format!("{}/Versions.toml", package.path), uuid.clone()))
+                        })
+                        .collect();
+                    let wanted: HashSet<String> = paths.keys().cloned().collect();
+                    match Self::read_tarball_files(tarball, &wanted) {
+                        Ok(files) => files
+                            .into_iter()
+                            .filter_map(|(path, content)| Some((paths.get(&path)?.clone(), content)))
+                            .collect(),
+                        Err(e) => {
+                            log::warn!("LocalRegistry: Failed to read {:?}: {}", tarball, e);
+                            HashMap::new()
+                        }
+                    }
+                }
+            };
+
+            for uuid in pending {
+                let versions = contents
+                    .get(&uuid)
+                    .map(|content| Self::parse_versions_toml(content))
+                    .unwrap_or_default();
+                if let Some(package) = self.packages.get_mut(&uuid) {
+                    package.versions = Some(versions);
+                }
+            }
+        }
+    }
+
+    /// Parse the non-yanked versions from a `Versions.toml` file
+    fn parse_versions_toml(content: &str) -> Vec<VersionNumber> {
+        let Ok(value) = toml::from_str::<toml::Value>(content) else {
+            return Vec::new();
+        };
+        let Some(table) = value.as_table() else {
+            return Vec::new();
+        };
+        let mut versions: Vec<VersionNumber> = table
+            .iter()
+            .filter(|(_, info)| !info.get("yanked").and_then(|y| y.as_bool()).unwrap_or(false))
+            .filter_map(|(version, _)| VersionNumber::parse(version).ok())
+            .collect();
+        versions.sort();
+        versions
+    }
+
+    /// Get a package by UUID
+    pub fn get(&self, uuid: &str) -> Option<&RegistryPackage> {
+        self.packages.get(uuid)
+    }
+
+    /// Find all registered packages with the given name
+    pub fn find_by_name(&self, name: &str) -> Vec<&RegistryPackage> {
+        self.by_name
+            .get(name)
+            .map(|uuids| uuids.iter().filter_map(|u| self.packages.get(u)).collect())
+            .unwrap_or_default()
+    }
+
+    /// All registered packages
+    pub fn packages(&self) -> impl Iterator<Item = &RegistryPackage> {
+        self.packages.values()
+    }
+
+    /// Latest non-prerelease version of a package (requires `load_versions`)
+    pub fn latest_version(&self, uuid: &str) -> Option<&VersionNumber> {
+        self.packages
+            .get(uuid)?
+            .versions
+            .as_ref()?
+            .iter()
+            .rev()
+            .find(|v| !v.is_prerelease())
+    }
+
+    /// Number of registries loaded
+    pub fn registry_count(&self) -> usize {
+        self.registries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DATAFRAMES_UUID: &str = "a93c6f00-e57d-5684-b7b6-d8193f3e46c0";
+
+    fn create_unpacked_registry() -> TempDir {
+        let depot = TempDir::new().unwrap();
+        let registry = depot.path().join("registries").join("General");
+        fs::create_dir_all(registry.join("D").join("DataFrames")).unwrap();
+        fs::write(
+            registry.join("Registry.toml"),
+            format!(
+                "name = \"General\"\n\n[packages]\n{} = {{ name = \"DataFrames\", path = \"D/DataFrames\" }}\n",
+                DATAFRAMES_UUID
+            ),
+        )
+        .unwrap();
+        fs::write(
+            registry.join("D").join("DataFrames").join("Versions.toml"),
+            "[\"1.5.0\"]\ngit-tree-sha1 = \"a\"\n\n[\"1.6.1\"]\ngit-tree-sha1 = \"b\"\n\n[\"1.7.0\"]\ngit-tree-sha1 = \"c\"\nyanked = true\n",
+        )
+        .unwrap();
+        depot
+    }
+
+    #[test]
+    fn test_load_unpacked_registry() {
+        let depot = create_unpacked_registry();
+        let mut registry = LocalRegistry::load(depot.path()).unwrap();
+
+        assert_eq!(registry.registry_count(), 1);
+        let found = registry.find_by_name("DataFrames");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].uuid, DATAFRAMES_UUID);
+        assert!(registry.latest_version(DATAFRAMES_UUID).is_none());
+
+        let uuids: HashSet<String> = [DATAFRAMES_UUID.to_string()].into_iter().collect();
+        registry.load_versions(&uuids);
+        // 1.7.0 is yanked
+        assert_eq!(registry.latest_version(DATAFRAMES_UUID), Some(&VersionNumber::new(1, 6, 1)));
+    }
+
+    #[test]
+    fn test_unpacked_registry_preferred_over_tarball() {
+        let depot = create_unpacked_registry();
+        let registries = depot.path().join("registries");
+        // A descriptor whose tarball is not a valid archive: loading it would find no packages
+        fs::write(registries.join("General.toml"), "path = \"General.tar.gz\"\n").unwrap();
+        fs::write(registries.join("General.tar.gz"), "not a tarball").unwrap();
+
+        let registry = LocalRegistry::load(depot.path()).unwrap();
+        assert_eq!(registry.registry_count(), 1);
+        assert_eq!(registry.find_by_name("DataFrames").len(), 1);
+    }
+
+    #[test]
+    fn test_missing_registries_directory() {
+        let depot = TempDir::new().unwrap();
+        let registry = LocalRegistry::load(depot.path()).unwrap();
+        assert!(registry.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
-use crate::pipeline::types::{AnalysisResult, Reference, ScopeTree};
+use crate::pipeline::module_tree::{FileModule, ModuleTree};
+use crate::pipeline::types::{AnalysisResult, ImportStatement, Reference, ScopeTree};
 use crate::types::{TypeDefinition, TypeDefinitionKind, FunctionSignature};
 use crate::types::{Symbol, LspError};
 // Legacy types removed - conversion methods no longer needed
@@ -25,6 +26,10 @@ pub struct Index {
     exports: HashMap<String, std::collections::HashSet<String>>,
     /// File path -> Set of exports (for tracking which file exports what)
     file_exports: HashMap<PathBuf, std::collections::HashSet<String>>,
+    /// File path -> `using`/`import` statements (workspace files only)
+    file_imports: HashMap<PathBuf, Vec<ImportStatement>>,
+    /// File path -> module placement resolved from `include()` chains
+    file_modules: HashMap<PathBuf, FileModule>,
 }
 
 impl Index {
@@ -39,9 +44,30 @@ impl Index {
             signatures: HashMap::new(),
             exports: HashMap::new(),
             file_exports: HashMap::new(),
+            file_imports: HashMap::new(),
+            file_modules: HashMap::new(),
         }
     }
 
+    /// Record the module placement of every file in `tree`
+    pub fn set_module_tree(&mut self, tree: &ModuleTree) {
+        for (path, file_module) in tree.files() {
+            self.file_modules.insert(path.clone(), file_module.clone());
+        }
+    }
+
+    /// Module placement of a file, if it was reached through the module tree
+    pub fn get_file_module(&self, file_path: &std::path::Path) -> Option<&FileModule> {
+        self.file_modules
+            .get(file_path)
+            .or_else(|| self.file_modules.get(&crate::pipeline::module_tree::normalize(file_path)))
+    }
+
+    /// All files with a known module placement
+    pub fn get_file_modules(&self) -> &HashMap<PathBuf, FileModule> {
+        &self.file_modules
+    }
+
     /// Merge analysis results for a file into the index
     /// For dependencies: only indexes exported symbols (not internal functions)
     /// For workspace files: indexes all symbols
@@ -56,12 +82,18 @@ impl Index {
         let is_stdlib_file = path_str.contains("/stdlib/") || path_str.contains("\\stdlib\\");
         let is_dependency = path_str.contains("packages/") || is_base_file || is_stdlib_file;
         
-        // Infer module name from file path
-        let module_name = Self::infer_module_name_from_path(file_path);
-        let is_main_module_file = file_path.file_name()
-            .and_then(|n| n.to_str())
-            .map(|n| n == &format!("{}.jl", module_name) || n == &format!("{}.jl", module_name.to_lowercase()))
-            .unwrap_or(false);
+        // Use the module resolved from include() chains, falling back to a guess from the path
+        let (module_name, is_main_module_file) = match self.get_file_module(file_path) {
+            Some(file_module) => (file_module.primary.clone(), file_module.is_entry),
+            None => {
+                let module_name = Self::infer_module_name_from_path(file_path);
+                let is_main_module_file = file_path.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n == format!("{}.jl", module_name) || n == format!("{}.jl", module_name.to_lowercase()))
+                    .unwrap_or(false);
+                (module_name, is_main_module_file)
+            }
+        };
         
         // Add exports first (so we can use them to filter signatures/types)
         if !analysis.exports.is_empty() {
@@ -112,6 +144,11 @@ impl Index {
             }
         }
         
+        // Add imports (only for workspace files, not dependencies)
+        if !is_dependency && !analysis.imports.is_empty() {
+            self.file_imports.insert(file_path.clone(), analysis.imports);
+        }
+        
         // Add types
         // For dependencies: only index if exported (or module definition)
         // For workspace files: index all types
@@ -221,7 +258,7 @@ impl Index {
         Ok(())
     }
     
-    /// Infer module name from file path (helper for exports)
+    /// Infer module name from file path (fallback for files outside any module tree)
     fn infer_module_name_from_path(path: &std::path::Path) -> String {
         // Try to extract from path components
         // Common patterns:
@@ -315,6 +352,9 @@ impl Index {
         // Remove scopes
         self.file_scopes.remove(file_path);
         
+        // Remove imports
+        self.file_imports.remove(file_path);
+        
         // Remove exports for this file (but keep module exports if they exist in other files)
         // We only remove the file's contribution, not the entire module's exports
         // This is important because exports might be collected in PASS 0 before this file is processed
@@ -354,11 +394,39 @@ impl Index {
         self.references.get(name).cloned().unwrap_or_default()
     }
 
+    /// Get the `using`/`import` statements of a file
+    pub fn get_file_imports(&self, file_path: &PathBuf) -> Vec<ImportStatement> {
+        self.file_imports.get(file_path).cloned().unwrap_or_default()
+    }
+
+    /// Get all `using`/`import` statements across workspace files
+    pub fn get_all_imports(&self) -> Vec<ImportStatement> {
+        self.file_imports.values().flatten().cloned().collect()
+    }
+
+    /// Get the top-level packages loaded by `using`/`import` anywhere in the workspace
+    pub fn get_imported_packages(&self) -> std::collections::HashSet<String> {
+        self.file_imports
+            .values()
+            .flatten()
+            .filter_map(|import| import.root_module().map(|m| m.to_string()))
+            .collect()
+    }
+
     /// Find type definition
     pub fn find_type(&self, module: &str, name: &str) -> Option<TypeDefinition> {
         self.types.get(module)?.get(name).cloned()
     }
 
+    /// Find type definitions with this name in any module
+    pub fn find_types_by_name(&self, name: &str) -> Vec<TypeDefinition> {
+        self.types
+            .values()
+            .filter_map(|m| m.get(name))
+            .cloned()
+            .collect()
+    }
+
     /// Find function signatures
     pub fn find_signatures(&self, module: &str, name: &str) -> Vec<FunctionSignature> {
         self.signatures
@@ -368,6 +436,16 @@ impl Index {
             .unwrap_or_default()
     }
 
+    /// Find function signatures with this name in any module
+    pub fn find_signatures_by_name(&self, name: &str) -> Vec<FunctionSignature> {
+        self.signatures
+            .values()
+            .filter_map(|m| m.get(name))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
     /// Get all function names in a module
     pub fn get_module_functions(&self, module: &str) -> Vec<String> {
         self.signatures
@@ -826,6 +904,14 @@ impl Index {
         for (path, exports) in other.file_exports {
             self.file_exports.insert(path, exports);
         }
+        
+        // Merge imports (replace if exists)
+        for (path, imports) in other.file_imports {
+            self.file_imports.insert(path, imports);
+        }
+
+        // Merge module placements (replace if exists)
+        self.file_modules.extend(other.file_modules);
     }
     
     /// Get all exports for a module
@@ -963,6 +1049,12 @@ impl Index {
         index.exports = exports;
         index
     }
+
+    /// Restore file module placements from serialized data
+    pub fn with_file_modules(mut self, file_modules: HashMap<PathBuf, FileModule>) -> Self {
+        self.file_modules = file_modules;
+        self
+    }
 }
 
 impl Default for Index {
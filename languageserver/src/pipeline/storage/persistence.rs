@@ -1,3 +1,4 @@
+use crate::pipeline::module_tree::FileModule;
 use crate::pipeline::storage::Index;
 use crate::types::LspError;
 use std::path::Path;
@@ -26,9 +27,20 @@ pub fn deserialize_from_json(path: &Path) -> Result<Index, LspError> {
     let serializable: SerializableIndex = serde_json::from_str(&json)
         .map_err(|e| LspError::InternalError(format!("Failed to deserialize index: {}", e)))?;
 
+    // Older caches were built with path-based module guesses; rebuild them
+    if serializable.version < INDEX_VERSION {
+        return Err(LspError::InternalError(format!(
+            "Index cache version {} is outdated (current: {})",
+            serializable.version, INDEX_VERSION
+        )));
+    }
+
     Ok(serializable.into())
 }
 
+/// Version of the serialized index format
+const INDEX_VERSION: u32 = 5;
+
 #[derive(Serialize, Deserialize)]
 struct SerializableIndex {
     version: u32,
@@ -41,6 +53,8 @@ struct SerializableIndex {
     signatures: HashMap<String, HashMap<String, Vec<FunctionSignature>>>,
     exports: HashMap<String, Vec<String>>, // HashSet as Vec
     file_exports: HashMap<String, Vec<String>>, // PathBuf as String, HashSet as Vec
+    #[serde(default)]
+    file_modules: HashMap<String, FileModule>, // PathBuf as String
 }
 
 impl From<&Index> for SerializableIndex {
@@ -117,9 +131,14 @@ impl From<&Index> for SerializableIndex {
         // For base index, scopes aren't critical since we're only storing signatures and types
         let file_scopes = HashMap::new();
         let file_exports = HashMap::new();
+
+        let file_modules = index.get_file_modules()
+            .iter()
+            .map(|(path, file_module)| (path.to_string_lossy().to_string(), file_module.clone()))
+            .collect();
         
         Self {
-            version: 1,
+            version: INDEX_VERSION,
             symbols,
             file_symbols,
             references,
@@ -129,6 +148,7 @@ impl From<&Index> for SerializableIndex {
             signatures,
             exports,
             file_exports,
+            file_modules,
         }
     }
 }
@@ -143,7 +163,12 @@ impl Into<Index> for SerializableIndex {
         
         // For base index, we mainly need types, signatures, and exports
         // Symbols, references, and scopes aren't needed for base/stdlib indexing
-        Index::from_serialized(self.types, self.signatures, exports)
+        let file_modules = self.file_modules
+            .into_iter()
+            .map(|(path, file_module)| (std::path::PathBuf::from(path), file_module))
+            .collect();
+
+        Index::from_serialized(self.types, self.signatures, exports).with_file_modules(file_modules)
     }
 }
 
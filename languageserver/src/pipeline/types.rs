@@ -34,6 +34,9 @@ pub struct ParsedItem {
     pub path: PathBuf,
     pub tree: Tree,
     pub text: String,
+    /// Module the file's top-level code runs in, when known from the module tree.
+    /// Analyzers fall back to guessing it from the path when this is `None`.
+    pub module: Option<String>,
 }
 
 /// Result of analysis from analyzers
@@ -45,6 +48,30 @@ pub struct AnalysisResult {
     pub scopes: ScopeTree,
     pub signatures: Vec<FunctionSignature>,
     pub exports: std::collections::HashSet<String>,
+    pub imports: Vec<ImportStatement>,
+}
+
+/// Represents a module brought into scope by a `using` or `import` statement
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ImportStatement {
+    /// Module path as written (e.g., "DataFrames", "Base.Threads", ".Utils")
+    pub module: String,
+    pub range: crate::types::Range,
+    pub file_uri: String,
+    /// `import` (qualified only) rather than `using`
+    pub is_qualified_only: bool,
+    /// Names listed after `:` in selective imports (`using Module: a, b`)
+    pub symbols: Vec<String>,
+}
+
+impl ImportStatement {
+    /// Top-level package name (e.g., "Base" for "Base.Threads"), None for relative imports
+    pub fn root_module(&self) -> Option<&str> {
+        if self.module.starts_with('.') {
+            return None;
+        }
+        self.module.split('.').next().filter(|s| !s.is_empty())
+    }
 }
 
 /// Represents a reference to a symbol (variable usage, function call, etc.)
@@ -99,6 +126,7 @@ impl AnalysisResult {
             },
             signatures: Vec::new(),
             exports: std::collections::HashSet::new(),
+            imports: Vec::new(),
         }
     }
 }
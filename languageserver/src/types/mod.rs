@@ -98,6 +98,27 @@ pub struct CompletionList {
     pub items: Vec<CompletionItem>,
 }
 
+// Signature help types
+#[derive(Debug, Clone)]
+pub struct SignatureHelp {
+    pub signatures: Vec<SignatureInformation>,
+    pub active_signature: Option<u32>,
+    pub active_parameter: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignatureInformation {
+    pub label: String,
+    pub documentation: Option<String>,
+    pub parameters: Vec<ParameterInformation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParameterInformation {
+    pub label: String,
+    pub documentation: Option<String>,
+}
+
 // Location type for definitions and references
 #[derive(Debug, Clone)]
 pub struct Location {
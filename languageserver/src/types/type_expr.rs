@@ -35,11 +35,107 @@ impl TypeExpr {
     }
 }
 
+impl TypeExpr {
+    /// Parse a type annotation such as `Int64`, `Vector{Float64}` or `Union{Int, Nothing}`
+    pub fn parse(text: &str) -> TypeExpr {
+        let text = text.trim();
+        let Some(open) = text.find('{').filter(|_| text.ends_with('}')) else {
+            return match text {
+                "" => TypeExpr::Unknown,
+                "Any" => TypeExpr::Any,
+                _ => TypeExpr::Concrete(text.to_string()),
+            };
+        };
+        let name = text[..open].trim();
+        let mut params = Vec::new();
+        let mut depth = 0;
+        let inner = &text[open + 1..text.len() - 1];
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '{' | '(' => depth += 1,
+                '}' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    params.push(TypeExpr::parse(&inner[start..i]));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if !inner[start..].trim().is_empty() {
+            params.push(TypeExpr::parse(&inner[start..]));
+        }
+        match name {
+            "Union" => TypeExpr::Union(params),
+            _ => TypeExpr::Generic(name.to_string(), params),
+        }
+    }
+}
+
+/// Direct supertype of a builtin number, string or symbol type (`Int64` -> `Signed`)
+pub fn builtin_supertype(name: &str) -> Option<&'static str> {
+    match name {
+        "Int8" | "Int16" | "Int32" | "Int64" | "Int128" => Some("Signed"),
+        "UInt8" | "UInt16" | "UInt32" | "UInt64" | "UInt128" => Some("Unsigned"),
+        "Signed" | "Unsigned" | "Bool" => Some("Integer"),
+        "Float16" | "Float32" | "Float64" => Some("AbstractFloat"),
+        "Integer" | "AbstractFloat" => Some("Real"),
+        "Real" => Some("Number"),
+        "String" => Some("AbstractString"),
+        "Char" => Some("AbstractChar"),
+        "Number" | "AbstractString" | "AbstractChar" | "Symbol" => Some("Any"),
+        _ => None,
+    }
+}
+
+/// How a parameter binds the arguments of a call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ParameterKind {
+    /// Positional parameter without a default value
+    #[default]
+    Required,
+    /// Positional parameter with a default value
+    Optional,
+    /// Trailing positional `args...`
+    Varargs,
+    /// Keyword parameter with a default value
+    Keyword,
+    /// Keyword parameter without a default value
+    RequiredKeyword,
+    /// Trailing keyword `kwargs...`
+    KeywordVarargs,
+}
+
+impl ParameterKind {
+    pub fn is_keyword(&self) -> bool {
+        matches!(self, ParameterKind::Keyword | ParameterKind::RequiredKeyword | ParameterKind::KeywordVarargs)
+    }
+}
+
 /// Represents a function parameter
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
     pub param_type: Option<TypeExpr>,
+    #[serde(default)]
+    pub kind: ParameterKind,
+}
+
+impl Parameter {
+    /// Source-like rendering, e.g. `x::Int`, `y=…`, `args...`
+    pub fn label(&self) -> String {
+        let mut label = self.name.trim_end_matches("...").to_string();
+        if let Some(ref param_type) = self.param_type {
+            label.push_str("::");
+            label.push_str(&param_type.to_string());
+        }
+        match self.kind {
+            ParameterKind::Optional | ParameterKind::Keyword => label.push_str("=…"),
+            ParameterKind::Varargs | ParameterKind::KeywordVarargs => label.push_str("..."),
+            ParameterKind::Required | ParameterKind::RequiredKeyword => {}
+        }
+        label
+    }
 }
 
 /// Represents a function signature with return type
@@ -54,6 +150,29 @@ pub struct FunctionSignature {
     pub file_uri: String,
     /// Range in the source file
     pub range: crate::types::Range,
+    /// Whether `parameters` were read from a method definition; signatures
+    /// known only from a docstring have no parameter list
+    #[serde(default)]
+    pub parameters_known: bool,
+    /// Module whose function this method extends, for qualified definitions such
+    /// as `Base.show(io::IO, x::Point) = ...` outside that module
+    #[serde(default)]
+    pub extends_module: Option<String>,
+}
+
+impl FunctionSignature {
+    /// Method rendering such as `f(x, y=…; verbose=…)`
+    pub fn label(&self) -> String {
+        let (keywords, positional): (Vec<&Parameter>, Vec<&Parameter>) =
+            self.parameters.iter().partition(|p| p.kind.is_keyword());
+        let positional: Vec<String> = positional.iter().map(|p| p.label()).collect();
+        let keywords: Vec<String> = keywords.iter().map(|p| p.label()).collect();
+        if keywords.is_empty() {
+            format!("{}({})", self.name, positional.join(", "))
+        } else {
+            format!("{}({}; {})", self.name, positional.join(", "), keywords.join(", "))
+        }
+    }
 }
 
 /// Represents a type definition (struct, abstract type, etc.)
@@ -67,6 +186,74 @@ pub struct TypeDefinition {
     pub file_uri: String,
     /// Range in the source file
     pub range: crate::types::Range,
+    /// Fields of a struct, in declaration order
+    #[serde(default)]
+    pub fields: Vec<StructField>,
+    /// Names of the type parameters, e.g. `T` for `Point{T<:Real}`
+    #[serde(default)]
+    pub type_parameters: Vec<String>,
+    /// Constructors defined inside the struct body
+    #[serde(default)]
+    pub inner_constructors: Vec<FunctionSignature>,
+}
+
+impl TypeDefinition {
+    pub fn field(&self, name: &str) -> Option<&StructField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Constructors callable as `T(...)`: the inner constructors if there are any,
+    /// otherwise the default constructor taking one argument per field
+    pub fn constructors(&self) -> Vec<FunctionSignature> {
+        if !self.inner_constructors.is_empty() || self.kind != TypeDefinitionKind::Struct {
+            return self.inner_constructors.clone();
+        }
+        let parameters = self
+            .fields
+            .iter()
+            .map(|field| Parameter {
+                name: field.name.clone(),
+                param_type: field.field_type.clone(),
+                kind: ParameterKind::Required,
+            })
+            .collect();
+        vec![FunctionSignature {
+            module: self.module.clone(),
+            name: self.name.clone(),
+            parameters,
+            return_type: Some(TypeExpr::Concrete(self.name.clone())),
+            doc_comment: self.doc_comment.clone(),
+            file_uri: self.file_uri.clone(),
+            range: self.range.clone(),
+            parameters_known: true,
+            extends_module: None,
+        }]
+    }
+}
+
+/// A field of a struct definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructField {
+    pub name: String,
+    pub field_type: Option<TypeExpr>,
+    /// Default value from `@kwdef` (`rate::Float64 = 0.1`), as written
+    pub default: Option<String>,
+}
+
+impl StructField {
+    /// Source-like rendering, e.g. `rate::Float64 = 0.1`
+    pub fn label(&self) -> String {
+        let mut label = self.name.clone();
+        if let Some(ref field_type) = self.field_type {
+            label.push_str("::");
+            label.push_str(&field_type.to_string());
+        }
+        if let Some(ref default) = self.default {
+            label.push_str(" = ");
+            label.push_str(default);
+        }
+        label
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
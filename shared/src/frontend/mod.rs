@@ -2,6 +2,10 @@ pub mod plots;
 pub mod lsp;
 pub mod orchestrator;
 pub mod notebook;
+pub mod pluto;
+pub mod percent;
+pub mod notebook_export;
+pub mod notebook_diff;
 
 // Re-export for stable paths like shared::frontend::PlotData
 pub use plots::*;
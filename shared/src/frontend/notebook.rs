@@ -11,6 +11,34 @@ pub struct Notebook {
     pub cells: Vec<NotebookCell>,
 }
 
+/// On-disk notebook format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+#[serde(rename_all = "snake_case")]
+pub enum NotebookFormat {
+    /// nbformat 4 JSON (`.ipynb`)
+    Jupyter,
+    /// Pluto.jl notebook (`.jl`)
+    Pluto,
+    /// Julia script with `# %%` cell markers (`.jl`)
+    Percent,
+}
+
+impl NotebookFormat {
+    /// Detect the format of a notebook file from its path and content
+    pub fn detect(path: &str, content: &str) -> Option<Self> {
+        if path.to_lowercase().ends_with(".ipynb") {
+            Some(NotebookFormat::Jupyter)
+        } else if super::pluto::is_pluto_notebook(content) {
+            Some(NotebookFormat::Pluto)
+        } else if super::percent::is_percent_script(content) {
+            Some(NotebookFormat::Percent)
+        } else {
+            None
+        }
+    }
+}
+
 /// Notebook metadata
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../app/src/types/bindings/shared/")]
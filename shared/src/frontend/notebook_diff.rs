@@ -0,0 +1,711 @@
+//! Notebook output stripping, cell-level diff and three-way merge
+//!
+//! Cells have no stable ids across nbformat versions and formats, so cells are
+//! matched by content: identical cells first, then cells of the same type
+//! whose sources are similar enough to count as edits of each other.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::notebook::{CellOutput, CellType, Notebook, NotebookCell};
+
+/// Cell metadata written by Jupyter that changes on every run or view
+const VOLATILE_CELL_METADATA: [&str; 4] = ["execution", "ExecuteTime", "collapsed", "scrolled"];
+
+/// Minimum similarity for two different cells to be treated as one edited cell
+const MODIFIED_CELL_SIMILARITY: f64 = 0.5;
+
+/// Sources up to this size are compared character by character, larger ones line by line
+const CHAR_SIMILARITY_MAX_LEN: usize = 500;
+
+const CONFLICT_START: &str = "<<<<<<< local\n";
+const CONFLICT_SEPARATOR: &str = "=======\n";
+const CONFLICT_END: &str = ">>>>>>> remote\n";
+
+/// Options for stripping outputs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct StripOptions {
+    /// Keep stream and plain-text outputs up to this many bytes; None strips every output
+    #[serde(default)]
+    pub keep_text_outputs_up_to: Option<usize>,
+}
+
+/// How a cell differs between two notebooks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+#[serde(rename_all = "snake_case")]
+pub enum CellChange {
+    Unchanged,
+    Added,
+    Removed,
+    Modified,
+}
+
+/// How a source line differs between two versions of a cell
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+#[serde(rename_all = "snake_case")]
+pub enum LineChange {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// One line of a cell source diff
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct DiffLine {
+    pub change: LineChange,
+    pub text: String,
+}
+
+/// Difference of one cell between a base and another notebook
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct CellDiff {
+    pub change: CellChange,
+    pub base_index: Option<usize>,
+    pub other_index: Option<usize>,
+    pub cell_type_changed: bool,
+    pub source_changed: bool,
+    /// Outputs or execution count differ
+    pub outputs_changed: bool,
+    pub metadata_changed: bool,
+    /// Line diff of the source, for modified cells whose source changed
+    pub source_diff: Vec<DiffLine>,
+}
+
+/// Cell-level difference between two notebooks, in the order of the other notebook
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct NotebookDiff {
+    pub cells: Vec<CellDiff>,
+    pub metadata_changed: bool,
+}
+
+impl NotebookDiff {
+    /// Whether any cell or the notebook metadata changed
+    pub fn has_changes(&self) -> bool {
+        self.metadata_changed || self.cells.iter().any(|cell| cell.change != CellChange::Unchanged)
+    }
+}
+
+/// Kind of edit that could not be merged automatically
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+#[serde(rename_all = "snake_case")]
+pub enum MergeConflictKind {
+    /// Both sides changed the same source lines
+    Source,
+    /// One side deleted a cell the other side edited
+    DeletedModified,
+    /// Both sides changed the cell type differently
+    CellType,
+}
+
+/// A conflict left in the merged notebook
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct MergeConflict {
+    /// Index of the cell in the merged notebook
+    pub cell_index: usize,
+    pub kind: MergeConflictKind,
+}
+
+/// Result of a three-way notebook merge
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+pub struct NotebookMergeResult {
+    /// Merged notebook; conflicting source lines are wrapped in conflict markers
+    pub notebook: Notebook,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Remove outputs, execution counts and volatile cell metadata; returns the number of removed outputs
+pub fn strip_outputs(notebook: &mut Notebook, options: &StripOptions) -> usize {
+    let mut removed = 0;
+    for cell in &mut notebook.cells {
+        cell.execution_count = None;
+        if let Some(metadata) = cell.metadata.as_object_mut() {
+            for key in VOLATILE_CELL_METADATA {
+                metadata.remove(key);
+            }
+        }
+
+        let before = cell.outputs.len();
+        cell.outputs.retain(|output| match options.keep_text_outputs_up_to {
+            Some(limit) => text_output_len(output).is_some_and(|len| len <= limit),
+            None => false,
+        });
+        removed += before - cell.outputs.len();
+
+        for output in &mut cell.outputs {
+            if let CellOutput::ExecuteResult { execution_count, .. } = output {
+                *execution_count = None;
+            }
+        }
+    }
+    removed
+}
+
+/// Size of an output that only holds text, None for rich and error outputs
+fn text_output_len(output: &CellOutput) -> Option<usize> {
+    match output {
+        CellOutput::Stream { text, .. } => Some(text.len()),
+        CellOutput::ExecuteResult { data, .. } | CellOutput::DisplayData { data, .. } => {
+            let bundle = data.data.as_object()?;
+            if bundle.len() != 1 {
+                return None;
+            }
+            match bundle.get("text/plain")? {
+                serde_json::Value::String(text) => Some(text.len()),
+                serde_json::Value::Array(lines) => Some(lines.iter().filter_map(|l| l.as_str()).map(str::len).sum()),
+                _ => None,
+            }
+        }
+        CellOutput::Error { .. } => None,
+    }
+}
+
+/// Cell-level diff from `base` to `other`
+pub fn diff_notebooks(base: &Notebook, other: &Notebook) -> NotebookDiff {
+    let matches = match_cells(&base.cells, &other.cells);
+    let mut cells = Vec::new();
+    let mut next_other = 0;
+
+    for (base_index, matched) in matches.iter().enumerate() {
+        if let Some(other_index) = *matched {
+            for added in next_other..other_index {
+                cells.push(added_or_removed(CellChange::Added, None, Some(added)));
+            }
+            next_other = other_index + 1;
+            cells.push(cell_diff(&base.cells[base_index], &other.cells[other_index], base_index, other_index));
+        } else {
+            cells.push(added_or_removed(CellChange::Removed, Some(base_index), None));
+        }
+    }
+    for added in next_other..other.cells.len() {
+        cells.push(added_or_removed(CellChange::Added, None, Some(added)));
+    }
+
+    NotebookDiff {
+        cells,
+        metadata_changed: metadata_value(base) != metadata_value(other),
+    }
+}
+
+fn added_or_removed(change: CellChange, base_index: Option<usize>, other_index: Option<usize>) -> CellDiff {
+    CellDiff {
+        change,
+        base_index,
+        other_index,
+        cell_type_changed: false,
+        source_changed: false,
+        outputs_changed: false,
+        metadata_changed: false,
+        source_diff: Vec::new(),
+    }
+}
+
+fn cell_diff(base: &NotebookCell, other: &NotebookCell, base_index: usize, other_index: usize) -> CellDiff {
+    let cell_type_changed = base.cell_type != other.cell_type;
+    let source_changed = base.source != other.source;
+    let outputs_changed = outputs_value(base) != outputs_value(other);
+    let metadata_changed = base.metadata != other.metadata;
+    let change = if cell_type_changed || source_changed || outputs_changed || metadata_changed {
+        CellChange::Modified
+    } else {
+        CellChange::Unchanged
+    };
+    CellDiff {
+        change,
+        base_index: Some(base_index),
+        other_index: Some(other_index),
+        cell_type_changed,
+        source_changed,
+        outputs_changed,
+        metadata_changed,
+        source_diff: if source_changed { diff_lines(&base.source, &other.source) } else { Vec::new() },
+    }
+}
+
+/// Line diff between two sources
+pub fn diff_lines(base: &str, other: &str) -> Vec<DiffLine> {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let other_lines: Vec<&str> = other.split_inclusive('\n').collect();
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let line = |change, text: &str| DiffLine { change, text: text.to_string() };
+
+    for (bi, oj) in lcs_pairs(&base_lines, &other_lines) {
+        diff.extend(base_lines[i..bi].iter().map(|text| line(LineChange::Delete, text)));
+        diff.extend(other_lines[j..oj].iter().map(|text| line(LineChange::Insert, text)));
+        diff.push(line(LineChange::Equal, base_lines[bi]));
+        i = bi + 1;
+        j = oj + 1;
+    }
+    diff.extend(base_lines[i..].iter().map(|text| line(LineChange::Delete, text)));
+    diff.extend(other_lines[j..].iter().map(|text| line(LineChange::Insert, text)));
+    diff
+}
+
+/// Three-way merge of `local` and `remote`, both derived from `base`
+///
+/// Edits to different cells, or to different lines of the same cell, are
+/// combined. Cells inserted by both sides at the same place are kept in
+/// local-then-remote order. Overlapping source edits and deletions of cells
+/// edited on the other side are reported as conflicts and marked in the cell
+/// source. Conflicting outputs are cleared, since the merged code has to be
+/// re-run anyway; conflicting metadata keeps the local version.
+pub fn merge_notebooks(base: &Notebook, local: &Notebook, remote: &Notebook) -> NotebookMergeResult {
+    let local_matches = match_cells(&base.cells, &local.cells);
+    let remote_matches = match_cells(&base.cells, &remote.cells);
+    let local_inserts = insertions(&local_matches, local.cells.len());
+    let remote_inserts = insertions(&remote_matches, remote.cells.len());
+
+    let mut cells: Vec<NotebookCell> = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for anchor in 0..=base.cells.len() {
+        let local_new: Vec<&NotebookCell> = local_inserts[anchor].iter().map(|&i| &local.cells[i]).collect();
+        cells.extend(local_new.iter().map(|cell| (*cell).clone()));
+        for &i in &remote_inserts[anchor] {
+            let cell = &remote.cells[i];
+            if !local_new.iter().any(|new| same_content(new, cell)) {
+                cells.push(cell.clone());
+            }
+        }
+
+        let Some(base_cell) = base.cells.get(anchor) else {
+            break;
+        };
+        let local_cell = local_matches[anchor].map(|i| &local.cells[i]);
+        let remote_cell = remote_matches[anchor].map(|i| &remote.cells[i]);
+
+        match (local_cell, remote_cell) {
+            (None, None) => {}
+            (Some(edited), None) | (None, Some(edited)) => {
+                if edits_content(base_cell, edited) {
+                    let mut cell = edited.clone();
+                    cell.source = if local_cell.is_some() {
+                        conflict_block(&edited.source, "")
+                    } else {
+                        conflict_block("", &edited.source)
+                    };
+                    conflicts.push(MergeConflict {
+                        cell_index: cells.len(),
+                        kind: MergeConflictKind::DeletedModified,
+                    });
+                    cells.push(cell);
+                }
+            }
+            (Some(local_cell), Some(remote_cell)) => {
+                let (cell, kinds) = merge_cell(base_cell, local_cell, remote_cell);
+                conflicts.extend(kinds.into_iter().map(|kind| MergeConflict { cell_index: cells.len(), kind }));
+                cells.push(cell);
+            }
+        }
+    }
+
+    let mut notebook = local.clone();
+    notebook.cells = cells;
+    if let Some(metadata) = merge3(&metadata_value(base), &metadata_value(local), &metadata_value(remote)) {
+        if let Ok(metadata) = serde_json::from_value(metadata) {
+            notebook.metadata = metadata;
+        }
+    }
+
+    NotebookMergeResult { notebook, conflicts }
+}
+
+/// Merge one cell present on both sides
+fn merge_cell(base: &NotebookCell, local: &NotebookCell, remote: &NotebookCell) -> (NotebookCell, Vec<MergeConflictKind>) {
+    let mut cell = local.clone();
+    let mut conflicts = Vec::new();
+
+    match merge3(&base.cell_type, &local.cell_type, &remote.cell_type) {
+        Some(cell_type) => cell.cell_type = cell_type,
+        None => conflicts.push(MergeConflictKind::CellType),
+    }
+
+    cell.source = match merge3(&base.source, &local.source, &remote.source) {
+        Some(source) => source,
+        None => {
+            let (source, conflicted) = merge_lines(&base.source, &local.source, &remote.source);
+            if conflicted {
+                conflicts.push(MergeConflictKind::Source);
+            }
+            source
+        }
+    };
+
+    let (base_outputs, remote_outputs) = (outputs_value(base), outputs_value(remote));
+    match merge3(&base_outputs, &outputs_value(local), &remote_outputs) {
+        Some(_) if remote_outputs != base_outputs => {
+            cell.outputs = remote.outputs.clone();
+            cell.execution_count = remote.execution_count;
+        }
+        Some(_) => {}
+        None => {
+            cell.outputs.clear();
+            cell.execution_count = None;
+        }
+    }
+
+    if let Some(metadata) = merge3(&base.metadata, &local.metadata, &remote.metadata) {
+        cell.metadata = metadata;
+    }
+
+    (cell, conflicts)
+}
+
+/// Line-level three-way merge (diff3); returns the merged text and whether conflicts were marked
+pub fn merge_lines(base: &str, local: &str, remote: &str) -> (String, bool) {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let local_lines: Vec<&str> = local.split_inclusive('\n').collect();
+    let remote_lines: Vec<&str> = remote.split_inclusive('\n').collect();
+
+    let mut local_map = vec![None; base_lines.len()];
+    for (b, l) in lcs_pairs(&base_lines, &local_lines) {
+        local_map[b] = Some(l);
+    }
+    let mut remote_map = vec![None; base_lines.len()];
+    for (b, r) in lcs_pairs(&base_lines, &remote_lines) {
+        remote_map[b] = Some(r);
+    }
+
+    let mut merged = String::new();
+    let mut conflicted = false;
+    let (mut b, mut l, mut r) = (0, 0, 0);
+    loop {
+        // Next base line kept unchanged by both sides
+        let sync = (b..base_lines.len()).find_map(|i| Some((i, local_map[i]?, remote_map[i]?)));
+        let (sb, sl, sr) = sync.unwrap_or((base_lines.len(), local_lines.len(), remote_lines.len()));
+
+        let base_chunk = &base_lines[b..sb];
+        let local_chunk = &local_lines[l..sl];
+        let remote_chunk = &remote_lines[r..sr];
+        if local_chunk == base_chunk {
+            merged.extend(remote_chunk.iter().copied());
+        } else if remote_chunk == base_chunk || local_chunk == remote_chunk {
+            merged.extend(local_chunk.iter().copied());
+        } else {
+            conflicted = true;
+            merged.push_str(&conflict_block(&local_chunk.concat(), &remote_chunk.concat()));
+        }
+
+        let Some(_) = sync else {
+            break;
+        };
+        merged.push_str(base_lines[sb]);
+        (b, l, r) = (sb + 1, sl + 1, sr + 1);
+    }
+
+    // Conflict markers end with a newline; drop it when no side ended with one
+    if conflicted && !local.ends_with('\n') && !remote.ends_with('\n') && merged.ends_with(CONFLICT_END) {
+        merged.pop();
+    }
+    (merged, conflicted)
+}
+
+/// Conflict markers around the local and remote versions of a region
+fn conflict_block(local: &str, remote: &str) -> String {
+    let with_newline = |text: &str| {
+        if text.is_empty() || text.ends_with('\n') {
+            text.to_string()
+        } else {
+            format!("{}\n", text)
+        }
+    };
+    format!(
+        "{}{}{}{}{}",
+        CONFLICT_START,
+        with_newline(local),
+        CONFLICT_SEPARATOR,
+        with_newline(remote),
+        CONFLICT_END
+    )
+}
+
+/// Three-way merge of a single value; None when both sides changed it differently
+fn merge3<T: PartialEq + Clone>(base: &T, local: &T, remote: &T) -> Option<T> {
+    if local == base || local == remote {
+        Some(remote.clone())
+    } else if remote == base {
+        Some(local.clone())
+    } else {
+        None
+    }
+}
+
+/// Whether an edit changes what a cell is, rather than only its outputs
+fn edits_content(base: &NotebookCell, edited: &NotebookCell) -> bool {
+    base.cell_type != edited.cell_type || base.source != edited.source
+}
+
+fn same_content(a: &NotebookCell, b: &NotebookCell) -> bool {
+    a.cell_type == b.cell_type && a.source == b.source
+}
+
+fn outputs_value(cell: &NotebookCell) -> serde_json::Value {
+    serde_json::json!({
+        "outputs": serde_json::to_value(&cell.outputs).unwrap_or_default(),
+        "execution_count": cell.execution_count,
+    })
+}
+
+fn metadata_value(notebook: &Notebook) -> serde_json::Value {
+    serde_json::to_value(&notebook.metadata).unwrap_or_default()
+}
+
+/// For each base cell, the index of the matching cell in `other`, preserving order
+fn match_cells(base: &[NotebookCell], other: &[NotebookCell]) -> Vec<Option<usize>> {
+    let base_keys: Vec<(&CellType, &str)> = base.iter().map(|c| (&c.cell_type, c.source.as_str())).collect();
+    let other_keys: Vec<(&CellType, &str)> = other.iter().map(|c| (&c.cell_type, c.source.as_str())).collect();
+
+    let mut matches = vec![None; base.len()];
+    let identical = lcs_pairs(&base_keys, &other_keys);
+
+    // Pair the remaining cells between identical ones when they look like edits of each other
+    let mut prev = (0, 0);
+    for (bi, oi) in identical.into_iter().chain(std::iter::once((base.len(), other.len()))) {
+        let mut next_other = prev.1;
+        for (b, base_cell) in base.iter().enumerate().take(bi).skip(prev.0) {
+            let candidate = (next_other..oi).find(|&o| {
+                other[o].cell_type == base_cell.cell_type
+                    && similarity(&base_cell.source, &other[o].source) >= MODIFIED_CELL_SIMILARITY
+            });
+            if let Some(o) = candidate {
+                matches[b] = Some(o);
+                next_other = o + 1;
+            }
+        }
+        if bi < base.len() {
+            matches[bi] = Some(oi);
+        }
+        prev = (bi + 1, oi + 1);
+    }
+    matches
+}
+
+/// Cells of `other` not matched to a base cell, grouped by the base cell they are inserted before
+fn insertions(matches: &[Option<usize>], other_len: usize) -> Vec<Vec<usize>> {
+    let mut inserts = vec![Vec::new(); matches.len() + 1];
+    let mut next_other = 0;
+    for (anchor, matched) in matches.iter().enumerate() {
+        if let Some(o) = *matched {
+            inserts[anchor].extend(next_other..o);
+            next_other = o + 1;
+        }
+    }
+    inserts[matches.len()].extend(next_other..other_len);
+    inserts
+}
+
+/// Similarity in [0, 1] of two sources, from their longest common subsequence
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() <= CHAR_SIMILARITY_MAX_LEN && b.len() <= CHAR_SIMILARITY_MAX_LEN {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        ratio(lcs_pairs(&a, &b).len(), a.len() + b.len())
+    } else {
+        let a: Vec<&str> = a.lines().collect();
+        let b: Vec<&str> = b.lines().collect();
+        ratio(lcs_pairs(&a, &b).len(), a.len() + b.len())
+    }
+}
+
+fn ratio(common: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        2.0 * common as f64 / total as f64
+    }
+}
+
+/// Index pairs of a longest common subsequence of `a` and `b`
+fn lcs_pairs<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // lengths[i][j] = LCS length of a[i..] and b[j..]
+    let mut lengths = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[at(i, j)] = if a[i] == b[j] {
+                lengths[at(i + 1, j + 1)] + 1
+            } else {
+                lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[at(i + 1, j)] >= lengths[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::notebook::{NotebookMetadata, OutputData};
+
+    fn code(source: &str) -> NotebookCell {
+        NotebookCell {
+            cell_type: CellType::Code,
+            source: source.to_string(),
+            metadata: serde_json::json!({}),
+            outputs: Vec::new(),
+            execution_count: None,
+        }
+    }
+
+    fn notebook(cells: Vec<NotebookCell>) -> Notebook {
+        Notebook {
+            nbformat: 4,
+            nbformat_minor: 5,
+            metadata: NotebookMetadata::default(),
+            cells,
+        }
+    }
+
+    fn sources(notebook: &Notebook) -> Vec<&str> {
+        notebook.cells.iter().map(|c| c.source.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strip_outputs() {
+        let mut cell = code("println(1)\n1 + 1");
+        cell.execution_count = Some(3);
+        cell.metadata = serde_json::json!({ "execution": { "iopub.status.idle": "..." }, "tags": ["keep"] });
+        cell.outputs = vec![
+            CellOutput::Stream { name: "stdout".to_string(), text: "1\n".to_string() },
+            CellOutput::ExecuteResult {
+                execution_count: Some(3),
+                data: OutputData { data: serde_json::json!({ "text/plain": "2" }) },
+                metadata: serde_json::json!({}),
+            },
+            CellOutput::DisplayData {
+                data: OutputData { data: serde_json::json!({ "image/png": "iVBOR", "text/plain": "Plot" }) },
+                metadata: serde_json::json!({}),
+            },
+        ];
+
+        let mut kept = notebook(vec![cell.clone()]);
+        assert_eq!(strip_outputs(&mut kept, &StripOptions { keep_text_outputs_up_to: Some(100) }), 1);
+        assert_eq!(kept.cells[0].outputs.len(), 2);
+        assert_eq!(kept.cells[0].execution_count, None);
+        assert!(matches!(kept.cells[0].outputs[1], CellOutput::ExecuteResult { execution_count: None, .. }));
+        assert_eq!(kept.cells[0].metadata, serde_json::json!({ "tags": ["keep"] }));
+
+        let mut stripped = notebook(vec![cell]);
+        assert_eq!(strip_outputs(&mut stripped, &StripOptions::default()), 3);
+        assert!(stripped.cells[0].outputs.is_empty());
+    }
+
+    #[test]
+    fn test_diff_cells() {
+        let base = notebook(vec![code("using CSV"), code("x = load(\"data.csv\")"), code("plot(x)")]);
+        let mut ran = code("plot(x)");
+        ran.execution_count = Some(1);
+        let other = notebook(vec![code("using CSV"), code("x = load(\"data2.csv\")"), code("describe(x)"), ran]);
+
+        let diff = diff_notebooks(&base, &other);
+        let changes: Vec<CellChange> = diff.cells.iter().map(|c| c.change).collect();
+        assert_eq!(
+            changes,
+            vec![CellChange::Unchanged, CellChange::Modified, CellChange::Added, CellChange::Modified]
+        );
+        assert!(diff.cells[1].source_changed && !diff.cells[1].outputs_changed);
+        assert_eq!(diff.cells[1].source_diff[0].change, LineChange::Delete);
+        assert!(!diff.cells[3].source_changed && diff.cells[3].outputs_changed);
+        assert!(diff.has_changes());
+        assert!(!diff_notebooks(&base, &base).has_changes());
+    }
+
+    #[test]
+    fn test_diff_removed_cell() {
+        let base = notebook(vec![code("a = 1"), code("completely different"), code("c = 3")]);
+        let other = notebook(vec![code("a = 1"), code("c = 3")]);
+        let diff = diff_notebooks(&base, &other);
+        assert_eq!(diff.cells[1].change, CellChange::Removed);
+        assert_eq!(diff.cells[1].base_index, Some(1));
+    }
+
+    #[test]
+    fn test_merge_non_overlapping_edits() {
+        let base = notebook(vec![code("a = 1"), code("b = 2"), code("c = 3")]);
+        let local = notebook(vec![code("a = 10"), code("b = 2"), code("c = 3"), code("local_new()")]);
+        let remote = notebook(vec![code("remote_new()"), code("a = 1"), code("b = 2"), code("c = 30")]);
+
+        let result = merge_notebooks(&base, &local, &remote);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(sources(&result.notebook), vec!["remote_new()", "a = 10", "b = 2", "c = 30", "local_new()"]);
+    }
+
+    #[test]
+    fn test_merge_lines_in_same_cell() {
+        let base = "a = 1\nb = 2\nc = 3\n";
+        let (merged, conflicted) = merge_lines(base, "a = 10\nb = 2\nc = 3\n", "a = 1\nb = 2\nc = 30\n");
+        assert!(!conflicted);
+        assert_eq!(merged, "a = 10\nb = 2\nc = 30\n");
+    }
+
+    #[test]
+    fn test_merge_conflict_marked_in_cell() {
+        let base = notebook(vec![code("a = 1\nb = 2")]);
+        let local = notebook(vec![code("a = 1\nb = 3")]);
+        let remote = notebook(vec![code("a = 1\nb = 4")]);
+
+        let result = merge_notebooks(&base, &local, &remote);
+        assert_eq!(result.conflicts, vec![MergeConflict { cell_index: 0, kind: MergeConflictKind::Source }]);
+        assert_eq!(
+            result.notebook.cells[0].source,
+            "a = 1\n<<<<<<< local\nb = 3\n=======\nb = 4\n>>>>>>> remote"
+        );
+    }
+
+    #[test]
+    fn test_merge_deleted_and_modified() {
+        let base = notebook(vec![code("keep()"), code("x = compute(1)")]);
+        let local = notebook(vec![code("keep()")]);
+        let remote = notebook(vec![code("keep()"), code("x = compute(2)")]);
+
+        let result = merge_notebooks(&base, &local, &remote);
+        assert_eq!(result.conflicts[0].kind, MergeConflictKind::DeletedModified);
+        assert!(result.notebook.cells[1].source.contains("x = compute(2)"));
+
+        // A deletion against an unchanged cell merges cleanly
+        let unchanged = notebook(vec![code("keep()"), code("x = compute(1)")]);
+        let result = merge_notebooks(&base, &local, &unchanged);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(sources(&result.notebook), vec!["keep()"]);
+    }
+
+    #[test]
+    fn test_merge_conflicting_outputs_are_cleared() {
+        let base = notebook(vec![code("rand()")]);
+        let mut local = notebook(vec![code("rand()")]);
+        local.cells[0].execution_count = Some(1);
+        let mut remote = notebook(vec![code("rand()")]);
+        remote.cells[0].execution_count = Some(2);
+
+        let result = merge_notebooks(&base, &local, &remote);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.notebook.cells[0].execution_count, None);
+    }
+}
@@ -0,0 +1,360 @@
+//! Notebook export to Julia scripts, Markdown and standalone HTML reports
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::notebook::{CellOutput, CellType, Notebook, OutputData};
+use super::percent::write_percent_script;
+
+/// Notebook export format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../app/src/types/bindings/shared/")]
+#[serde(rename_all = "snake_case")]
+pub enum NotebookExportFormat {
+    /// Percent-format `.jl` script
+    Script,
+    Markdown,
+    Html,
+}
+
+impl NotebookExportFormat {
+    /// File extension for exported files
+    pub fn extension(&self) -> &'static str {
+        match self {
+            NotebookExportFormat::Script => "jl",
+            NotebookExportFormat::Markdown => "md",
+            NotebookExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Rich output MIME types, most preferred first
+const IMAGE_MIME_TYPES: [&str; 3] = ["image/png", "image/jpeg", "image/gif"];
+const SVG_MIME_TYPE: &str = "image/svg+xml";
+
+/// Export a notebook in the given format
+pub fn export_notebook(notebook: &Notebook, format: NotebookExportFormat, title: &str) -> String {
+    match format {
+        NotebookExportFormat::Script => write_percent_script(notebook),
+        NotebookExportFormat::Markdown => export_markdown(notebook),
+        NotebookExportFormat::Html => export_html(notebook, title),
+    }
+}
+
+/// Markdown with fenced code cells and their outputs
+pub fn export_markdown(notebook: &Notebook) -> String {
+    let mut parts = Vec::new();
+    for cell in &notebook.cells {
+        match cell.cell_type {
+            CellType::Markdown => parts.push(cell.source.trim_end().to_string()),
+            CellType::Raw => parts.push(fence("", &cell.source)),
+            CellType::Code => {
+                if !cell.source.trim().is_empty() {
+                    parts.push(fence("julia", &cell.source));
+                }
+                parts.extend(cell.outputs.iter().filter_map(output_markdown));
+            }
+        }
+    }
+    let mut out = parts.join("\n\n");
+    out.push('\n');
+    out
+}
+
+/// Standalone HTML report with outputs and images inlined
+pub fn export_html(notebook: &Notebook, title: &str) -> String {
+    let mut body = String::new();
+    for cell in &notebook.cells {
+        match cell.cell_type {
+            CellType::Markdown => {
+                body.push_str("<section class=\"cell markdown\">\n");
+                body.push_str(&markdown_to_html(&cell.source));
+                body.push_str("</section>\n");
+            }
+            CellType::Raw => {
+                body.push_str("<section class=\"cell raw\"><pre>");
+                body.push_str(&escape_html(&cell.source));
+                body.push_str("</pre></section>\n");
+            }
+            CellType::Code => {
+                body.push_str("<section class=\"cell code\">\n");
+                if !cell.source.trim().is_empty() {
+                    body.push_str("<pre class=\"source\"><code class=\"language-julia\">");
+                    body.push_str(&escape_html(cell.source.trim_end()));
+                    body.push_str("</code></pre>\n");
+                }
+                for output in &cell.outputs {
+                    body.push_str(&output_html(output));
+                }
+                body.push_str("</section>\n");
+            }
+        }
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; color: #1f2328; }}
+.cell {{ margin: 1.25rem 0; }}
+pre {{ overflow-x: auto; padding: 0.75rem; border-radius: 6px; font-size: 0.875rem; }}
+pre.source {{ background: #f6f8fa; border-left: 3px solid #9558b2; }}
+pre.output {{ background: #fff; border: 1px solid #d0d7de; }}
+pre.stderr {{ background: #fff8e6; }}
+pre.error {{ background: #ffebe9; color: #82071e; }}
+.output img, .output svg {{ max-width: 100%; height: auto; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #d0d7de; padding: 0.25rem 0.5rem; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+        body = body
+    )
+}
+
+/// Text of a MIME bundle entry (stored as a string or an array of lines)
+fn mime_text(data: &OutputData, mime: &str) -> Option<String> {
+    match data.data.get(mime)? {
+        serde_json::Value::String(text) => Some(text.clone()),
+        serde_json::Value::Array(lines) => Some(lines.iter().filter_map(|l| l.as_str()).collect()),
+        _ => None,
+    }
+}
+
+/// Base64 image data, without a `data:` URL prefix or line breaks
+fn image_base64(data: &OutputData, mime: &str) -> Option<String> {
+    let text = mime_text(data, mime)?;
+    let text = match text.split_once("base64,") {
+        Some((_, base64)) => base64.to_string(),
+        None => text,
+    };
+    Some(text.split_whitespace().collect())
+}
+
+/// Raw SVG markup from an SVG output
+fn svg_markup(data: &OutputData) -> Option<String> {
+    let text = mime_text(data, SVG_MIME_TYPE)?;
+    let start = text.find("<svg")?;
+    let end = text.rfind("</svg>").map(|i| i + "</svg>".len()).unwrap_or(text.len());
+    Some(text[start..end].to_string())
+}
+
+fn output_markdown(output: &CellOutput) -> Option<String> {
+    match output {
+        CellOutput::Stream { text, .. } => {
+            let text = strip_ansi(text);
+            (!text.trim().is_empty()).then(|| fence("", &text))
+        }
+        CellOutput::Error { ename, evalue, traceback } => Some(fence("", &error_text(ename, evalue, traceback))),
+        CellOutput::ExecuteResult { data, .. } | CellOutput::DisplayData { data, .. } => {
+            if let Some((mime, base64)) = IMAGE_MIME_TYPES
+                .iter()
+                .find_map(|mime| image_base64(data, mime).map(|b| (*mime, b)))
+            {
+                return Some(format!("![output](data:{};base64,{})", mime, base64));
+            }
+            if let Some(svg) = svg_markup(data) {
+                return Some(svg);
+            }
+            if let Some(html) = mime_text(data, "text/html") {
+                return Some(html.trim().to_string());
+            }
+            if let Some(markdown) = mime_text(data, "text/markdown") {
+                return Some(markdown.trim().to_string());
+            }
+            mime_text(data, "text/plain").map(|text| fence("", &strip_ansi(&text)))
+        }
+    }
+}
+
+fn output_html(output: &CellOutput) -> String {
+    match output {
+        CellOutput::Stream { name, text } => {
+            let text = strip_ansi(text);
+            if text.trim().is_empty() {
+                return String::new();
+            }
+            let class = if name == "stderr" { "output stderr" } else { "output" };
+            format!("<pre class=\"{}\">{}</pre>\n", class, escape_html(text.trim_end()))
+        }
+        CellOutput::Error { ename, evalue, traceback } => format!(
+            "<pre class=\"output error\">{}</pre>\n",
+            escape_html(&error_text(ename, evalue, traceback))
+        ),
+        CellOutput::ExecuteResult { data, .. } | CellOutput::DisplayData { data, .. } => {
+            let content = if let Some((mime, base64)) = IMAGE_MIME_TYPES
+                .iter()
+                .find_map(|mime| image_base64(data, mime).map(|b| (*mime, b)))
+            {
+                format!("<img src=\"data:{};base64,{}\" alt=\"output\">", mime, base64)
+            } else if let Some(svg) = svg_markup(data) {
+                svg
+            } else if let Some(html) = mime_text(data, "text/html") {
+                html
+            } else if let Some(markdown) = mime_text(data, "text/markdown") {
+                markdown_to_html(&markdown)
+            } else if let Some(text) = mime_text(data, "text/plain") {
+                format!("<pre class=\"output\">{}</pre>", escape_html(strip_ansi(&text).trim_end()))
+            } else {
+                return String::new();
+            };
+            format!("<div class=\"output\">{}</div>\n", content)
+        }
+    }
+}
+
+fn error_text(ename: &str, evalue: &str, traceback: &[String]) -> String {
+    let mut text = format!("{}: {}", ename, evalue);
+    for line in traceback {
+        text.push('\n');
+        text.push_str(&strip_ansi(line));
+    }
+    text
+}
+
+/// Fenced code block, with a fence longer than any backtick run in the text
+fn fence(info: &str, text: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let ticks = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}", ticks, info, text.trim_end_matches('\n'), ticks)
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let options = pulldown_cmark::Options::ENABLE_TABLES
+        | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+        | pulldown_cmark::Options::ENABLE_TASKLISTS
+        | pulldown_cmark::Options::ENABLE_MATH;
+    let parser = pulldown_cmark::Parser::new_ext(markdown, options);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Remove ANSI escape sequences (colored Julia error output)
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                // CSI sequence: parameters end with a byte in '@'..='~'
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::notebook::{NotebookCell, NotebookMetadata};
+
+    fn notebook() -> Notebook {
+        Notebook {
+            nbformat: 4,
+            nbformat_minor: 5,
+            metadata: NotebookMetadata::default(),
+            cells: vec![
+                NotebookCell {
+                    cell_type: CellType::Markdown,
+                    source: "# Report\n\nSee *below*.".to_string(),
+                    metadata: serde_json::json!({}),
+                    outputs: Vec::new(),
+                    execution_count: None,
+                },
+                NotebookCell {
+                    cell_type: CellType::Code,
+                    source: "println(\"a < b\")\nplot(x)".to_string(),
+                    metadata: serde_json::json!({}),
+                    outputs: vec![
+                        CellOutput::Stream {
+                            name: "stdout".to_string(),
+                            text: "a < b\n".to_string(),
+                        },
+                        CellOutput::DisplayData {
+                            data: OutputData {
+                                data: serde_json::json!({ "image/png": "iVBORw0KGgo=\n", "text/plain": "Plot" }),
+                            },
+                            metadata: serde_json::json!({}),
+                        },
+                        CellOutput::Error {
+                            ename: "UndefVarError".to_string(),
+                            evalue: "y not defined".to_string(),
+                            traceback: vec!["\u{1b}[91mStacktrace:\u{1b}[0m".to_string()],
+                        },
+                    ],
+                    execution_count: Some(1),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        let markdown = export_markdown(&notebook());
+        assert!(markdown.starts_with("# Report\n\nSee *below*.\n\n```julia\nprintln(\"a < b\")\nplot(x)\n```"));
+        assert!(markdown.contains("```\na < b\n```"));
+        assert!(markdown.contains("![output](data:image/png;base64,iVBORw0KGgo=)"));
+        assert!(markdown.contains("UndefVarError: y not defined\nStacktrace:"));
+    }
+
+    #[test]
+    fn test_export_html() {
+        let html = export_html(&notebook(), "Report <draft>");
+        assert!(html.contains("<title>Report &lt;draft&gt;</title>"));
+        assert!(html.contains("<h1>Report</h1>"));
+        assert!(html.contains("println(&quot;a &lt; b&quot;)"));
+        assert!(html.contains("<img src=\"data:image/png;base64,iVBORw0KGgo=\" alt=\"output\">"));
+        assert!(!html.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn test_export_script() {
+        let script = export_notebook(&notebook(), NotebookExportFormat::Script, "Report");
+        assert_eq!(
+            script,
+            "# %% [markdown]\n# # Report\n#\n# See *below*.\n\n# %%\nprintln(\"a < b\")\nplot(x)\n"
+        );
+    }
+
+    #[test]
+    fn test_fence_is_longer_than_backticks_in_text() {
+        assert_eq!(fence("", "a ```b```"), "````\na ```b```\n````");
+    }
+}
@@ -0,0 +1,293 @@
+//! Percent-format script notebooks (Jupytext style)
+//!
+//! A percent script is a plain Julia file where `# %%` lines start code cells
+//! and `# %% [markdown]` lines start markdown cells whose lines are commented
+//! out with `# `. Marker lines and an optional leading `# ---` header are kept
+//! in the notebook metadata under `percent` so unchanged scripts are written
+//! back as they were read.
+
+use serde::{Deserialize, Serialize};
+
+use super::notebook::{CellType, Notebook, NotebookCell, NotebookMetadata};
+
+/// Cell type written in the marker line for non-code cells
+const MARKDOWN_TAGS: [&str; 2] = ["[markdown]", "[md]"];
+const RAW_TAG: &str = "[raw]";
+
+/// Notebook-level percent data, stored under `metadata.percent`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PercentNotebookMetadata {
+    /// Comment header before the first cell (e.g. a Jupytext `# ---` block)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub header: String,
+}
+
+/// Cell-level percent data, stored under `cell.metadata.percent`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PercentCellMetadata {
+    /// Marker line as read, including the cell title and options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// Code before the first marker, written without a marker line
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub implicit: bool,
+    /// Blank lines after the cell when they differ from the standard spacing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Whether a line starts a percent-format cell
+pub fn is_cell_marker(line: &str) -> bool {
+    line.starts_with("# %%") || line.starts_with("#%%")
+}
+
+/// Whether file content has percent-format cell markers
+pub fn is_percent_script(content: &str) -> bool {
+    content.lines().any(is_cell_marker)
+}
+
+/// Cell type given by a marker line
+fn marker_cell_type(marker: &str) -> CellType {
+    let options = marker.trim_start_matches('#').trim_start().trim_start_matches("%%");
+    let tokens: Vec<&str> = options.split_whitespace().collect();
+    if tokens.iter().any(|t| MARKDOWN_TAGS.contains(t)) {
+        CellType::Markdown
+    } else if tokens.contains(&RAW_TAG) {
+        CellType::Raw
+    } else {
+        CellType::Code
+    }
+}
+
+/// Parse a percent-format script into a notebook
+pub fn parse_percent_script(content: &str) -> Result<Notebook, String> {
+    if !is_percent_script(content) {
+        return Err("No `# %%` cell markers found".to_string());
+    }
+
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let (header, mut start) = split_header(&lines);
+
+    let mut blocks: Vec<(Option<&str>, String)> = Vec::new();
+    let mut leading = String::new();
+    while start < lines.len() && !is_cell_marker(lines[start]) {
+        leading.push_str(lines[start]);
+        start += 1;
+    }
+    if !leading.trim().is_empty() {
+        blocks.push((None, leading));
+    }
+    for line in &lines[start..] {
+        if is_cell_marker(line) {
+            blocks.push((Some(line.trim_end_matches(['\n', '\r'])), String::new()));
+        } else if let Some((_, body)) = blocks.last_mut() {
+            body.push_str(line);
+        }
+    }
+
+    let count = blocks.len();
+    let cells = blocks
+        .into_iter()
+        .enumerate()
+        .map(|(i, (marker, block))| {
+            let body = block.trim_end_matches('\n');
+            let trailing = &block[body.len()..];
+            let cell_type = marker.map(marker_cell_type).unwrap_or(CellType::Code);
+            let source = match cell_type {
+                CellType::Code => body.to_string(),
+                _ => uncomment(body),
+            };
+            let meta = PercentCellMetadata {
+                marker: marker.map(str::to_string),
+                implicit: marker.is_none(),
+                separator: (trailing != default_separator(i + 1 == count)).then(|| trailing.to_string()),
+            };
+            NotebookCell {
+                cell_type,
+                source,
+                metadata: serde_json::json!({ "percent": meta }),
+                outputs: Vec::new(),
+                execution_count: None,
+            }
+        })
+        .collect();
+
+    let percent = PercentNotebookMetadata { header };
+    let metadata = NotebookMetadata {
+        extra: serde_json::json!({
+            "percent": serde_json::to_value(&percent).map_err(|e| format!("Failed to store script metadata: {}", e))?,
+        }),
+        ..Default::default()
+    };
+
+    Ok(Notebook {
+        nbformat: 4,
+        nbformat_minor: 5,
+        metadata,
+        cells,
+    })
+}
+
+/// Serialize a notebook as a percent-format script; outputs are dropped
+pub fn write_percent_script(notebook: &Notebook) -> String {
+    let percent: PercentNotebookMetadata = notebook
+        .metadata
+        .extra
+        .get("percent")
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+
+    let mut out = percent.header.clone();
+    let count = notebook.cells.len();
+    for (i, cell) in notebook.cells.iter().enumerate() {
+        let meta: PercentCellMetadata = cell
+            .metadata
+            .get("percent")
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default();
+
+        let implicit = meta.implicit && i == 0 && cell.cell_type == CellType::Code;
+        if !implicit {
+            let marker = match meta.marker {
+                Some(marker) if marker_cell_type(&marker) == cell.cell_type => marker,
+                _ => match cell.cell_type {
+                    CellType::Code => "# %%".to_string(),
+                    CellType::Markdown => "# %% [markdown]".to_string(),
+                    CellType::Raw => "# %% [raw]".to_string(),
+                },
+            };
+            out.push_str(&marker);
+            out.push('\n');
+        }
+
+        let body = match cell.cell_type {
+            CellType::Code => cell.source.trim_end_matches('\n').to_string(),
+            _ => comment(cell.source.trim_end_matches('\n')),
+        };
+        out.push_str(&body);
+        let separator = meta.separator.as_deref().unwrap_or(default_separator(i + 1 == count));
+        // An empty cell still needs a line break after its marker
+        if body.is_empty() && separator.is_empty() {
+            continue;
+        }
+        out.push_str(separator);
+    }
+    out
+}
+
+/// Lines after a cell: a blank line between cells and a final newline after the last
+fn default_separator(is_last: bool) -> &'static str {
+    if is_last {
+        "\n"
+    } else {
+        "\n\n"
+    }
+}
+
+/// Split off a leading `# ---` ... `# ---` comment header and the blank lines after it
+fn split_header(lines: &[&str]) -> (String, usize) {
+    if lines.first().map(|l| l.trim_end()) != Some("# ---") {
+        return (String::new(), 0);
+    }
+    let Some(close) = lines
+        .iter()
+        .skip(1)
+        .position(|l| l.trim_end() == "# ---")
+        .map(|i| i + 1)
+    else {
+        return (String::new(), 0);
+    };
+    let mut end = close + 1;
+    while end < lines.len() && lines[end].trim().is_empty() {
+        end += 1;
+    }
+    (lines[..end].concat(), end)
+}
+
+/// Strip the `# ` comment prefix from markdown and raw cell lines
+fn uncomment(body: &str) -> String {
+    body.split('\n')
+        .map(|line| {
+            line.strip_prefix("# ")
+                .or_else(|| line.strip_prefix('#'))
+                .unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Comment out markdown and raw cell lines
+fn comment(text: &str) -> String {
+    text.split('\n')
+        .map(|line| if line.is_empty() { "#".to_string() } else { format!("# {}", line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"# ---
+# jupyter:
+#   kernelspec:
+#     name: julia-1.10
+# ---
+
+# %% [markdown]
+# # Analysis
+#
+# Some notes.
+
+# %% Load data
+using CSV
+data = CSV.read("data.csv")
+
+# %% tags=["parameters"]
+n = 10
+"#;
+
+    #[test]
+    fn test_parse_cells() {
+        let notebook = parse_percent_script(SCRIPT).unwrap();
+        assert_eq!(notebook.cells.len(), 3);
+        assert_eq!(notebook.cells[0].cell_type, CellType::Markdown);
+        assert_eq!(notebook.cells[0].source, "# Analysis\n\nSome notes.");
+        assert_eq!(notebook.cells[1].cell_type, CellType::Code);
+        assert_eq!(notebook.cells[1].source, "using CSV\ndata = CSV.read(\"data.csv\")");
+        assert_eq!(notebook.cells[2].source, "n = 10");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let notebook = parse_percent_script(SCRIPT).unwrap();
+        assert_eq!(write_percent_script(&notebook), SCRIPT);
+    }
+
+    #[test]
+    fn test_round_trip_with_leading_code_and_spacing() {
+        let script = "using LinearAlgebra\n\n# %%\nx = 1\n\n\n# %%\n";
+        let notebook = parse_percent_script(script).unwrap();
+        assert_eq!(notebook.cells.len(), 3);
+        assert_eq!(notebook.cells[0].source, "using LinearAlgebra");
+        assert_eq!(notebook.cells[2].source, "");
+        assert_eq!(write_percent_script(&notebook), script);
+    }
+
+    #[test]
+    fn test_write_changed_cell_type() {
+        let mut notebook = parse_percent_script(SCRIPT).unwrap();
+        notebook.cells[2].cell_type = CellType::Markdown;
+        notebook.cells[2].source = "Done.".to_string();
+        assert!(write_percent_script(&notebook).ends_with("# %% [markdown]\n# Done.\n"));
+    }
+
+    #[test]
+    fn test_parse_requires_markers() {
+        assert!(parse_percent_script("x = 1\n").is_err());
+    }
+}
@@ -0,0 +1,668 @@
+//! Pluto.jl notebook format
+//!
+//! Pluto notebooks are plain Julia files. Each cell starts with a `# ╔═╡ <uuid>`
+//! line, optionally followed by `# ╠═╡ key = value` metadata lines, and the file
+//! ends with a `# ╔═╡ Cell order:` footer listing the cells in display order.
+//! Cells are stored in the file in execution order, which can differ from the
+//! display order, so both orders are kept in the notebook metadata.
+//!
+//! Everything that is not part of a cell's source (the file header, cell ids,
+//! folding, metadata lines, markdown wrappers) is kept under the `pluto` key of
+//! the notebook and cell metadata so an unchanged notebook is written back
+//! byte for byte.
+
+use serde::{Deserialize, Serialize};
+
+use super::notebook::{CellType, Notebook, NotebookCell, NotebookMetadata};
+
+/// First line of every Pluto notebook
+pub const PLUTO_NOTEBOOK_HEADER: &str = "### A Pluto.jl notebook ###";
+
+const CELL_DELIMITER: &str = "# ╔═╡ ";
+const CELL_ORDER_HEADER: &str = "# ╔═╡ Cell order:";
+const METADATA_PREFIX: &str = "# ╠═╡ ";
+const ORDER_SHOWN: &str = "# ╠═";
+const ORDER_FOLDED: &str = "# ╟─";
+const DISABLED_PREFIX: &str = "#=╠═╡\n";
+const DISABLED_SUFFIX: &str = "\n  ╠═╡ =#";
+const CELL_SUFFIX: &str = "\n\n";
+
+/// Cell ids Pluto uses for the embedded Project.toml and Manifest.toml
+pub const PLUTO_PROJECT_CELL_ID: &str = "00000000-0000-0000-0000-000000000001";
+pub const PLUTO_MANIFEST_CELL_ID: &str = "00000000-0000-0000-0000-000000000002";
+
+const PROJECT_TOML_PREFIX: &str = "PLUTO_PROJECT_TOML_CONTENTS = \"\"\"\n";
+const MANIFEST_TOML_PREFIX: &str = "PLUTO_MANIFEST_TOML_CONTENTS = \"\"\"\n";
+const TOML_SUFFIX: &str = "\"\"\"";
+
+const MARKDOWN_BLOCK_PREFIX: &str = "md\"\"\"\n";
+const MARKDOWN_BLOCK_SUFFIX: &str = "\n\"\"\"";
+
+/// Header written for notebooks that were not read from a Pluto file
+const DEFAULT_HEADER: &str = "### A Pluto.jl notebook ###\n# v0.19.46\n\nusing Markdown\nusing InteractiveUtils\n\n";
+
+/// Notebook-level Pluto data, stored under `metadata.pluto`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PlutoNotebookMetadata {
+    /// Everything before the first cell (version line, imports, `@bind` macro)
+    pub header: String,
+    /// Cell ids in file (execution) order
+    pub file_order: Vec<String>,
+    /// Text after the cell order footer, normally empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub trailer: String,
+}
+
+/// Cell-level Pluto data, stored under `cell.metadata.pluto`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PlutoCellMetadata {
+    pub cell_id: String,
+    /// Code is hidden in the Pluto UI (`# ╟─` in the cell order)
+    #[serde(default)]
+    pub folded: bool,
+    #[serde(default)]
+    pub disabled: bool,
+    /// Cell is commented out in the file because a cell it depends on is disabled
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub disabled_by_dependency: bool,
+    /// Raw `# ╠═╡` metadata lines, without the prefix
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata_lines: Vec<String>,
+    /// Code around the cell source (markdown string or embedded TOML assignment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapper: Option<PlutoWrapper>,
+    /// Separator after the cell when it is not the standard blank line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+}
+
+/// Code stripped from a Pluto cell to get the text shown in the notebook UI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlutoWrapper {
+    pub prefix: String,
+    pub suffix: String,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Whether file content is a Pluto notebook
+pub fn is_pluto_notebook(content: &str) -> bool {
+    content.trim_start_matches('\u{feff}').starts_with(PLUTO_NOTEBOOK_HEADER)
+}
+
+/// A cell as it appears in the file
+struct RawCell {
+    id: String,
+    metadata_lines: Vec<String>,
+    code: String,
+    commented_out: bool,
+    separator: Option<String>,
+}
+
+/// Parse a Pluto notebook into a notebook with cells in display order
+pub fn parse_pluto_notebook(content: &str) -> Result<Notebook, String> {
+    if !is_pluto_notebook(content) {
+        return Err("Not a Pluto notebook: missing Pluto header".to_string());
+    }
+
+    let first_cell = find_line_start(content, CELL_DELIMITER, 0)
+        .ok_or_else(|| "Pluto notebook has no cells".to_string())?;
+    let header = content[..first_cell].to_string();
+
+    let order_start = find_line_start(content, CELL_ORDER_HEADER, first_cell)
+        .ok_or_else(|| "Pluto notebook is missing the cell order footer".to_string())?;
+
+    let raw_cells = parse_cells(&content[first_cell..order_start])?;
+    let (order, trailer) = parse_cell_order(&content[order_start..])?;
+
+    let mut cells_by_id: std::collections::HashMap<&str, &RawCell> =
+        raw_cells.iter().map(|cell| (cell.id.as_str(), cell)).collect();
+    let file_order: Vec<String> = raw_cells.iter().map(|cell| cell.id.clone()).collect();
+
+    let mut cells = Vec::with_capacity(raw_cells.len());
+    for (id, folded) in &order {
+        let raw = cells_by_id
+            .remove(id.as_str())
+            .ok_or_else(|| format!("Cell order lists unknown cell {}", id))?;
+        cells.push(to_notebook_cell(raw, *folded));
+    }
+    // Pluto ignores cells missing from the order; keep them at the end so nothing is lost
+    for raw in &raw_cells {
+        if cells_by_id.remove(raw.id.as_str()).is_some() {
+            cells.push(to_notebook_cell(raw, false));
+        }
+    }
+
+    let pluto = PlutoNotebookMetadata {
+        header,
+        file_order,
+        trailer,
+    };
+    let metadata = NotebookMetadata {
+        extra: serde_json::json!({
+            "pluto": serde_json::to_value(&pluto).map_err(|e| format!("Failed to store Pluto metadata: {}", e))?,
+        }),
+        ..Default::default()
+    };
+
+    Ok(Notebook {
+        nbformat: 4,
+        nbformat_minor: 5,
+        metadata,
+        cells,
+    })
+}
+
+/// Serialize a notebook in Pluto format
+///
+/// Cells keep their Pluto ids and file order; cells added in the notebook UI
+/// get new ids and are placed before the embedded package cells.
+pub fn write_pluto_notebook(notebook: &Notebook) -> Result<String, String> {
+    let pluto: PlutoNotebookMetadata = match notebook.metadata.extra.get("pluto") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid Pluto notebook metadata: {}", e))?,
+        None => PlutoNotebookMetadata {
+            header: DEFAULT_HEADER.to_string(),
+            ..Default::default()
+        },
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    let mut cells = Vec::with_capacity(notebook.cells.len());
+    for cell in &notebook.cells {
+        let mut meta = cell_metadata(cell)?.unwrap_or_default();
+        if meta.cell_id.is_empty() || !seen.insert(meta.cell_id.clone()) {
+            // New cell, or a cell copied in the UI together with its metadata
+            meta = PlutoCellMetadata {
+                cell_id: uuid::Uuid::new_v4().to_string(),
+                folded: cell.cell_type == CellType::Markdown,
+                ..Default::default()
+            };
+            seen.insert(meta.cell_id.clone());
+        }
+        cells.push((cell, meta));
+    }
+
+    // File order: known cells in their stored order, new cells before the package cells
+    let index_of: std::collections::HashMap<&str, usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, (_, meta))| (meta.cell_id.as_str(), i))
+        .collect();
+    let mut file_order: Vec<usize> = pluto
+        .file_order
+        .iter()
+        .filter_map(|id| index_of.get(id.as_str()).copied())
+        .collect();
+    let stored: std::collections::HashSet<usize> = file_order.iter().copied().collect();
+    let insert_at = file_order
+        .iter()
+        .position(|&i| is_package_cell(&cells[i].1.cell_id))
+        .unwrap_or(file_order.len());
+    let new_cells: Vec<usize> = (0..cells.len()).filter(|i| !stored.contains(i)).collect();
+    file_order.splice(insert_at..insert_at, new_cells);
+
+    let mut out = String::with_capacity(pluto.header.len() + 64 * cells.len());
+    out.push_str(&pluto.header);
+    for &i in &file_order {
+        let (cell, meta) = &cells[i];
+        write_cell(&mut out, cell, meta);
+    }
+
+    out.push_str(CELL_ORDER_HEADER);
+    out.push('\n');
+    for (_, meta) in &cells {
+        out.push_str(if meta.folded { ORDER_FOLDED } else { ORDER_SHOWN });
+        out.push_str(&meta.cell_id);
+        out.push('\n');
+    }
+    out.push_str(&pluto.trailer);
+    Ok(out)
+}
+
+/// Pluto metadata of a notebook cell, if it came from a Pluto file
+pub fn cell_metadata(cell: &NotebookCell) -> Result<Option<PlutoCellMetadata>, String> {
+    match cell.metadata.get("pluto") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| format!("Invalid Pluto cell metadata: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Pluto cell ids in file (execution) order, if the notebook came from a Pluto file
+pub fn file_order(notebook: &Notebook) -> Option<Vec<String>> {
+    let pluto: PlutoNotebookMetadata = serde_json::from_value(notebook.metadata.extra.get("pluto")?.clone()).ok()?;
+    Some(pluto.file_order)
+}
+
+fn is_package_cell(cell_id: &str) -> bool {
+    cell_id == PLUTO_PROJECT_CELL_ID || cell_id == PLUTO_MANIFEST_CELL_ID
+}
+
+/// Byte offset of the first line starting with `prefix` at or after `from`
+fn find_line_start(content: &str, prefix: &str, from: usize) -> Option<usize> {
+    let mut offset = from;
+    for line in content[from..].split_inclusive('\n') {
+        if (offset == 0 || content.as_bytes()[offset - 1] == b'\n') && line.starts_with(prefix) {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn parse_cells(section: &str) -> Result<Vec<RawCell>, String> {
+    // Split into chunks that each start with a cell delimiter line
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    for line in section.split_inclusive('\n') {
+        if line.starts_with(CELL_DELIMITER) {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+
+    let mut cells = Vec::with_capacity(starts.len());
+    for (n, &start) in starts.iter().enumerate() {
+        let end = starts.get(n + 1).copied().unwrap_or(section.len());
+        cells.push(parse_cell(&section[start..end])?);
+    }
+    Ok(cells)
+}
+
+fn parse_cell(chunk: &str) -> Result<RawCell, String> {
+    let (id_line, mut rest) = chunk.split_once('\n').unwrap_or((chunk, ""));
+    let id = id_line[CELL_DELIMITER.len()..].trim().to_string();
+    if uuid::Uuid::parse_str(&id).is_err() {
+        return Err(format!("Invalid Pluto cell id: {}", id));
+    }
+
+    let mut metadata_lines = Vec::new();
+    while let Some(line_rest) = rest.strip_prefix(METADATA_PREFIX) {
+        let (line, tail) = line_rest.split_once('\n').unwrap_or((line_rest, ""));
+        metadata_lines.push(line.to_string());
+        rest = tail;
+    }
+
+    let (mut code, separator) = match rest.strip_suffix(CELL_SUFFIX) {
+        Some(code) => (code, None),
+        None => {
+            let code = rest.trim_end_matches('\n');
+            (code, Some(rest[code.len()..].to_string()))
+        }
+    };
+
+    let mut commented_out = false;
+    if let Some(inner) = code
+        .strip_prefix(DISABLED_PREFIX)
+        .and_then(|c| c.strip_suffix(DISABLED_SUFFIX))
+    {
+        code = inner;
+        commented_out = true;
+    }
+
+    Ok(RawCell {
+        id,
+        metadata_lines,
+        code: code.to_string(),
+        commented_out,
+        separator,
+    })
+}
+
+fn parse_cell_order(footer: &str) -> Result<(Vec<(String, bool)>, String), String> {
+    let mut lines = footer.split_inclusive('\n');
+    lines.next(); // "# ╔═╡ Cell order:"
+    let mut consumed = footer.find('\n').map(|i| i + 1).unwrap_or(footer.len());
+
+    let mut order = Vec::new();
+    for line in lines {
+        let entry = line.trim_end_matches('\n');
+        let (id, folded) = if let Some(id) = entry.strip_prefix(ORDER_SHOWN) {
+            (id, false)
+        } else if let Some(id) = entry.strip_prefix(ORDER_FOLDED) {
+            (id, true)
+        } else {
+            break;
+        };
+        order.push((id.trim().to_string(), folded));
+        consumed += line.len();
+    }
+    Ok((order, footer[consumed..].to_string()))
+}
+
+/// Value of a simple `key = value` metadata line
+fn metadata_value<'a>(lines: &'a [String], key: &str) -> Option<&'a str> {
+    lines.iter().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == key).then(|| v.trim())
+    })
+}
+
+fn to_notebook_cell(raw: &RawCell, folded: bool) -> NotebookCell {
+    let disabled = metadata_value(&raw.metadata_lines, "disabled") == Some("true");
+    let skip_as_script = metadata_value(&raw.metadata_lines, "skip_as_script") == Some("true");
+
+    let (cell_type, source, wrapper) = split_wrapper(&raw.id, &raw.code, raw.commented_out);
+
+    let meta = PlutoCellMetadata {
+        cell_id: raw.id.clone(),
+        folded,
+        disabled,
+        disabled_by_dependency: raw.commented_out && !disabled && !skip_as_script,
+        metadata_lines: raw.metadata_lines.clone(),
+        wrapper,
+        separator: raw.separator.clone(),
+    };
+
+    NotebookCell {
+        cell_type,
+        source,
+        metadata: serde_json::json!({ "pluto": meta }),
+        outputs: Vec::new(),
+        execution_count: None,
+    }
+}
+
+/// Split a cell into its notebook cell type and the text shown in the UI
+fn split_wrapper(id: &str, code: &str, commented_out: bool) -> (CellType, String, Option<PlutoWrapper>) {
+    let toml_prefix = match id {
+        PLUTO_PROJECT_CELL_ID => Some(PROJECT_TOML_PREFIX),
+        PLUTO_MANIFEST_CELL_ID => Some(MANIFEST_TOML_PREFIX),
+        _ => None,
+    };
+    if let Some(prefix) = toml_prefix {
+        if let Some(toml) = code.strip_prefix(prefix).and_then(|c| c.strip_suffix(TOML_SUFFIX)) {
+            if !toml.contains(TOML_SUFFIX) && !toml.contains('\\') {
+                let wrapper = PlutoWrapper {
+                    prefix: prefix.to_string(),
+                    suffix: TOML_SUFFIX.to_string(),
+                };
+                return (CellType::Raw, toml.to_string(), Some(wrapper));
+            }
+        }
+        return (CellType::Raw, code.to_string(), None);
+    }
+
+    // Only plain markdown becomes a markdown cell; interpolation needs Julia to render
+    if !commented_out {
+        let markdown = [(MARKDOWN_BLOCK_PREFIX, MARKDOWN_BLOCK_SUFFIX), ("md\"", "\"")]
+            .into_iter()
+            .find_map(|(prefix, suffix)| {
+                let text = code.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                let terminator = if prefix == MARKDOWN_BLOCK_PREFIX { "\"\"\"" } else { "\"" };
+                let plain = !text.contains(terminator) && !text.contains('$') && !text.contains('\\');
+                plain.then_some((text, prefix, suffix))
+            });
+        if let Some((text, prefix, suffix)) = markdown {
+            let wrapper = PlutoWrapper {
+                prefix: prefix.to_string(),
+                suffix: suffix.to_string(),
+            };
+            return (CellType::Markdown, text.to_string(), Some(wrapper));
+        }
+    }
+
+    (CellType::Code, code.to_string(), None)
+}
+
+/// Cell code as stored in the file, without the disabled comment wrapper
+fn cell_code(cell: &NotebookCell, meta: &PlutoCellMetadata) -> String {
+    match (&cell.cell_type, &meta.wrapper) {
+        (CellType::Code, _) => cell.source.clone(),
+        (CellType::Markdown, Some(wrapper)) if wrapper.prefix == MARKDOWN_BLOCK_PREFIX => {
+            format!("{}{}{}", wrapper.prefix, escape_markdown(&cell.source, "\"\"\""), wrapper.suffix)
+        }
+        (CellType::Markdown, Some(wrapper)) if !cell.source.contains('\n') => {
+            format!("{}{}{}", wrapper.prefix, escape_markdown(&cell.source, "\""), wrapper.suffix)
+        }
+        (CellType::Markdown, _) => format!(
+            "{}{}{}",
+            MARKDOWN_BLOCK_PREFIX,
+            escape_markdown(&cell.source, "\"\"\""),
+            MARKDOWN_BLOCK_SUFFIX
+        ),
+        (CellType::Raw, Some(wrapper)) => format!("{}{}{}", wrapper.prefix, cell.source, wrapper.suffix),
+        (CellType::Raw, None) => cell.source.clone(),
+    }
+}
+
+/// Escape markdown so it stays literal inside an `md` string
+fn escape_markdown(text: &str, terminator: &str) -> String {
+    let escaped_terminator: String = terminator.chars().map(|c| format!("\\{}", c)).collect();
+    text.replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace(terminator, &escaped_terminator)
+}
+
+fn write_cell(out: &mut String, cell: &NotebookCell, meta: &PlutoCellMetadata) {
+    out.push_str(CELL_DELIMITER);
+    out.push_str(&meta.cell_id);
+    out.push('\n');
+
+    for line in metadata_lines(meta) {
+        out.push_str(METADATA_PREFIX);
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    let skip_as_script = metadata_value(&meta.metadata_lines, "skip_as_script") == Some("true");
+    let commented_out = meta.disabled || skip_as_script || meta.disabled_by_dependency;
+    let code = cell_code(cell, meta);
+    if commented_out {
+        out.push_str(DISABLED_PREFIX);
+        out.push_str(&code);
+        out.push_str(DISABLED_SUFFIX);
+    } else {
+        out.push_str(&code);
+    }
+    out.push_str(meta.separator.as_deref().unwrap_or(CELL_SUFFIX));
+}
+
+/// Metadata lines with the `disabled` flag brought in line with the cell metadata
+fn metadata_lines(meta: &PlutoCellMetadata) -> Vec<String> {
+    let stored_disabled = metadata_value(&meta.metadata_lines, "disabled") == Some("true");
+    if stored_disabled == meta.disabled {
+        return meta.metadata_lines.clone();
+    }
+
+    let mut lines: Vec<String> = meta
+        .metadata_lines
+        .iter()
+        .filter(|line| line.split_once('=').map(|(k, _)| k.trim()) != Some("disabled"))
+        .cloned()
+        .collect();
+    if meta.disabled {
+        // Pluto writes top-level keys sorted, before any tables
+        let at = lines
+            .iter()
+            .position(|line| line.starts_with('[') || line.as_str() > "disabled")
+            .unwrap_or(lines.len());
+        lines.insert(at, "disabled = true".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r#"### A Pluto.jl notebook ###
+# v0.19.46
+
+using Markdown
+using InteractiveUtils
+
+# ╔═╡ 6b2c1e5a-1f3a-4c8e-9d2b-0a1b2c3d4e5f
+md"""
+# Analysis
+
+Some notes.
+"""
+
+# ╔═╡ 1f4c8d2e-3a5b-4c6d-8e9f-0a1b2c3d4e5f
+y = x + 1
+
+# ╔═╡ 2a3b4c5d-6e7f-4a8b-9c0d-1e2f3a4b5c6d
+x = 41
+
+# ╔═╡ 3c4d5e6f-7a8b-4c9d-8e0f-1a2b3c4d5e6f
+# ╠═╡ disabled = true
+# ╠═╡ show_logs = false
+#=╠═╡
+z = expensive()
+  ╠═╡ =#
+
+# ╔═╡ 00000000-0000-0000-0000-000000000001
+PLUTO_PROJECT_TOML_CONTENTS = """
+[deps]
+"""
+
+# ╔═╡ 00000000-0000-0000-0000-000000000002
+PLUTO_MANIFEST_TOML_CONTENTS = """
+# This file is machine-generated - editing it directly is not advised
+
+julia_version = "1.10.4"
+manifest_format = "2.0"
+"""
+
+# ╔═╡ Cell order:
+# ╟─6b2c1e5a-1f3a-4c8e-9d2b-0a1b2c3d4e5f
+# ╠═2a3b4c5d-6e7f-4a8b-9c0d-1e2f3a4b5c6d
+# ╠═1f4c8d2e-3a5b-4c6d-8e9f-0a1b2c3d4e5f
+# ╠═3c4d5e6f-7a8b-4c9d-8e0f-1a2b3c4d5e6f
+# ╟─00000000-0000-0000-0000-000000000001
+# ╟─00000000-0000-0000-0000-000000000002
+"#;
+
+    fn pluto(cell: &NotebookCell) -> PlutoCellMetadata {
+        cell_metadata(cell).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_parse_uses_display_order() {
+        let notebook = parse_pluto_notebook(NOTEBOOK).unwrap();
+        let ids: Vec<String> = notebook.cells.iter().map(|c| pluto(c).cell_id).collect();
+        assert_eq!(ids[1], "2a3b4c5d-6e7f-4a8b-9c0d-1e2f3a4b5c6d");
+        assert_eq!(ids[2], "1f4c8d2e-3a5b-4c6d-8e9f-0a1b2c3d4e5f");
+        assert_eq!(
+            file_order(&notebook).unwrap()[1],
+            "1f4c8d2e-3a5b-4c6d-8e9f-0a1b2c3d4e5f"
+        );
+    }
+
+    #[test]
+    fn test_parse_cell_kinds_and_metadata() {
+        let notebook = parse_pluto_notebook(NOTEBOOK).unwrap();
+        let cells = &notebook.cells;
+
+        assert_eq!(cells[0].cell_type, CellType::Markdown);
+        assert_eq!(cells[0].source, "# Analysis\n\nSome notes.");
+        assert!(pluto(&cells[0]).folded);
+
+        assert_eq!(cells[1].cell_type, CellType::Code);
+        assert_eq!(cells[1].source, "x = 41");
+        assert!(!pluto(&cells[1]).folded);
+
+        assert_eq!(cells[3].source, "z = expensive()");
+        assert!(pluto(&cells[3]).disabled);
+        assert_eq!(pluto(&cells[3]).metadata_lines, vec!["disabled = true", "show_logs = false"]);
+
+        assert_eq!(cells[4].cell_type, CellType::Raw);
+        assert_eq!(cells[4].source, "[deps]\n");
+        assert_eq!(cells[5].cell_type, CellType::Raw);
+        assert!(cells[5].source.starts_with("# This file is machine-generated"));
+    }
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        let notebook = parse_pluto_notebook(NOTEBOOK).unwrap();
+        assert_eq!(write_pluto_notebook(&notebook).unwrap(), NOTEBOOK);
+
+        // Also through JSON, as the notebook travels to the frontend and back
+        let json = serde_json::to_string(&notebook).unwrap();
+        let notebook: Notebook = serde_json::from_str(&json).unwrap();
+        assert_eq!(write_pluto_notebook(&notebook).unwrap(), NOTEBOOK);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_nonstandard_spacing() {
+        let content = NOTEBOOK.replace("x = 41\n\n", "x = 41\n\n\n");
+        let notebook = parse_pluto_notebook(&content).unwrap();
+        assert_eq!(write_pluto_notebook(&notebook).unwrap(), content);
+    }
+
+    #[test]
+    fn test_write_toggles_disabled() {
+        let mut notebook = parse_pluto_notebook(NOTEBOOK).unwrap();
+        let mut meta = pluto(&notebook.cells[3]);
+        meta.disabled = false;
+        notebook.cells[3].metadata = serde_json::json!({ "pluto": meta });
+
+        let mut meta = pluto(&notebook.cells[1]);
+        meta.disabled = true;
+        notebook.cells[1].metadata = serde_json::json!({ "pluto": meta });
+
+        let written = write_pluto_notebook(&notebook).unwrap();
+        assert!(written.contains(
+            "# ╔═╡ 3c4d5e6f-7a8b-4c9d-8e0f-1a2b3c4d5e6f\n# ╠═╡ show_logs = false\nz = expensive()\n\n"
+        ));
+        assert!(written.contains(
+            "# ╔═╡ 2a3b4c5d-6e7f-4a8b-9c0d-1e2f3a4b5c6d\n# ╠═╡ disabled = true\n#=╠═╡\nx = 41\n  ╠═╡ =#\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_write_places_new_cells_before_package_cells() {
+        let mut notebook = parse_pluto_notebook(NOTEBOOK).unwrap();
+        notebook.cells.insert(
+            1,
+            NotebookCell {
+                cell_type: CellType::Markdown,
+                source: "Costs $5".to_string(),
+                metadata: serde_json::json!({}),
+                outputs: Vec::new(),
+                execution_count: None,
+            },
+        );
+
+        let written = write_pluto_notebook(&notebook).unwrap();
+        let new_cell = written.find("md\"\"\"\nCosts \\$5\n\"\"\"").unwrap();
+        assert!(new_cell > written.find("z = expensive()").unwrap());
+        assert!(new_cell < written.find("PLUTO_PROJECT_TOML_CONTENTS").unwrap());
+
+        let reparsed = parse_pluto_notebook(&written).unwrap();
+        assert_eq!(reparsed.cells.len(), 7);
+        assert!(pluto(&reparsed.cells[1]).folded);
+    }
+
+    #[test]
+    fn test_write_notebook_without_pluto_metadata() {
+        let notebook = Notebook {
+            nbformat: 4,
+            nbformat_minor: 5,
+            metadata: NotebookMetadata::default(),
+            cells: vec![NotebookCell {
+                cell_type: CellType::Code,
+                source: "1 + 1".to_string(),
+                metadata: serde_json::json!({}),
+                outputs: Vec::new(),
+                execution_count: None,
+            }],
+        };
+
+        let written = write_pluto_notebook(&notebook).unwrap();
+        assert!(is_pluto_notebook(&written));
+        let reparsed = parse_pluto_notebook(&written).unwrap();
+        assert_eq!(reparsed.cells[0].source, "1 + 1");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_pluto_files() {
+        assert!(parse_pluto_notebook("x = 1\n").is_err());
+        assert!(parse_pluto_notebook("### A Pluto.jl notebook ###\n# v0.19.46\n").is_err());
+    }
+}